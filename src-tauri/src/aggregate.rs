@@ -0,0 +1,339 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{
+    budget, event_cache, export, operations, pacing, send_filter_with_retry, sliced_fetch,
+    AppState, LogEvent,
+};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, State};
+
+/// Bucket widths picked from when no interval is given, smallest first
+const NICE_INTERVALS_MS: [i64; 14] = [
+    1_000,
+    5_000,
+    10_000,
+    30_000,
+    60_000,
+    5 * 60_000,
+    10 * 60_000,
+    15 * 60_000,
+    30 * 60_000,
+    3_600_000,
+    3 * 3_600_000,
+    6 * 3_600_000,
+    12 * 3_600_000,
+    24 * 3_600_000,
+];
+
+/// Buckets aimed for when picking an interval
+const TARGET_BUCKETS: i64 = 120;
+
+/// Most buckets an explicit interval may produce
+const MAX_BUCKETS: i64 = 10_000;
+
+/// Events scanned from CloudWatch before the aggregation stops short
+const DEFAULT_MAX_SCANNED: usize = 1_000_000;
+
+/// Counts of one time bucket
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AggregateBucket {
+    pub start: i64,
+    pub count: usize,
+    /// Error level events, fatal ones included
+    pub error: usize,
+    pub warn: usize,
+    /// Error level events mentioning fatal, critical or panic
+    pub fatal: usize,
+}
+
+/// Event and severity counts of a log group over time, for the timeline histogram
+#[derive(Debug, Clone, Serialize)]
+pub struct LogAggregation {
+    pub log_group_name: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub interval_ms: i64,
+    pub buckets: Vec<AggregateBucket>,
+    pub total: usize,
+    pub error: usize,
+    pub warn: usize,
+    pub fatal: usize,
+    /// Where the events came from: "session", "cache" or "cloudwatch"
+    pub source: &'static str,
+    /// Scanning stopped at the event limit; counts after `scanned_until` are missing
+    pub truncated: bool,
+    pub scanned_until: Option<i64>,
+    pub cancelled: bool,
+}
+
+/// Progress of a paginated aggregation
+#[derive(Clone, Serialize)]
+struct AggregateProgress {
+    fetch_id: u32,
+    scanned: usize,
+    /// Newest timestamp reached so far
+    scanned_until: i64,
+    /// Share of the time range scanned, 0.0-1.0
+    fraction: f64,
+}
+
+fn fatal_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)\b(fatal|critical|crit|panic|emerg|emergency)\b").expect("valid regex")
+    })
+}
+
+/// Interval of `span`: the given one (validated) or the smallest nice interval
+/// giving at most TARGET_BUCKETS buckets
+fn bucket_interval(span: i64, interval_ms: Option<i64>) -> Result<i64, String> {
+    match interval_ms {
+        Some(ms) if ms < 1_000 => Err("Aggregation interval must be at least 1 second".to_string()),
+        Some(ms) if span / ms + 1 > MAX_BUCKETS => Err(format!(
+            "An interval of {} ms splits the range into more than {} buckets",
+            ms, MAX_BUCKETS
+        )),
+        Some(ms) => Ok(ms),
+        None => Ok(NICE_INTERVALS_MS
+            .into_iter()
+            .find(|ms| span / ms < TARGET_BUCKETS)
+            .unwrap_or(NICE_INTERVALS_MS[NICE_INTERVALS_MS.len() - 1])),
+    }
+}
+
+/// Buckets of `start..=end`, aligned to multiples of the interval
+struct Histogram {
+    first: i64,
+    interval: i64,
+    buckets: Vec<AggregateBucket>,
+}
+
+impl Histogram {
+    fn new(start: i64, end: i64, interval: i64) -> Self {
+        let first = start - start.rem_euclid(interval);
+        let buckets = (first..=end)
+            .step_by(interval as usize)
+            .map(|start| AggregateBucket {
+                start,
+                ..AggregateBucket::default()
+            })
+            .collect();
+        Self {
+            first,
+            interval,
+            buckets,
+        }
+    }
+
+    /// Count `events`, classifying their severity the way exports do
+    fn add(&mut self, events: &[LogEvent]) {
+        for (event, processed) in events.iter().zip(export::classify(events)) {
+            let index = (event.timestamp - self.first).div_euclid(self.interval);
+            let Some(bucket) = usize::try_from(index)
+                .ok()
+                .and_then(|i| self.buckets.get_mut(i))
+            else {
+                continue;
+            };
+            bucket.count += 1;
+            match processed.level.as_deref() {
+                Some("error") => {
+                    bucket.error += 1;
+                    if fatal_regex().is_match(&event.message) {
+                        bucket.fatal += 1;
+                    }
+                }
+                Some("warn") => bucket.warn += 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(
+        self,
+        log_group_name: String,
+        start_time: i64,
+        end_time: i64,
+        source: &'static str,
+    ) -> LogAggregation {
+        let sum = |f: fn(&AggregateBucket) -> usize| self.buckets.iter().map(f).sum();
+        LogAggregation {
+            log_group_name,
+            start_time,
+            end_time,
+            interval_ms: self.interval,
+            total: sum(|b| b.count),
+            error: sum(|b| b.error),
+            warn: sum(|b| b.warn),
+            fatal: sum(|b| b.fatal),
+            buckets: self.buckets,
+            source,
+            truncated: false,
+            scanned_until: None,
+            cancelled: false,
+        }
+    }
+}
+
+/// Events of the group already held by the session, when its fetch of the group
+/// used the same pattern and holds everything in `start..=end`
+async fn session_events(
+    state: &AppState,
+    session_id: &str,
+    log_group_name: &str,
+    start: i64,
+    end: i64,
+    filter_pattern: Option<&str>,
+) -> Option<Vec<LogEvent>> {
+    let sessions = state.sessions.lock().await;
+    let session = sessions.get(session_id)?;
+    let context = session.fetches.get(log_group_name)?;
+    let covered = context.filter_pattern.as_deref().filter(|p| !p.is_empty()) == filter_pattern
+        && context.start_time.is_some_and(|s| s <= start)
+        && context.end_time.is_some_and(|e| e >= end)
+        && context.covers(start, end);
+    if !covered {
+        return None;
+    }
+    let mut events = session.range(start, end);
+    events.retain(|e| e.source.as_deref() == Some(log_group_name));
+    Some(events)
+}
+
+/// Count events of a log group per time bucket, with how many of them are
+/// errors, warnings and fatal errors, for the timeline histogram. Events already
+/// fetched into `session_id` or held by the local cache are counted without
+/// calling CloudWatch; otherwise FilterLogEvents is paged through without keeping
+/// the events, emitting `aggregate-progress` along the way. `cancel_fetch` with
+/// the fetch_id stops it, returning the counts so far.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn aggregate_logs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    fetch_id: u32,
+    log_group_name: String,
+    start_time: i64,
+    end_time: Option<i64>,
+    interval_ms: Option<i64>,
+    filter_pattern: Option<String>,
+    session_id: Option<String>,
+    max_events: Option<usize>,
+) -> Result<LogAggregation, String> {
+    let end = end_time.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    if end < start_time {
+        return Err("The aggregation range ends before it starts".to_string());
+    }
+    let interval = bucket_interval(end - start_time, interval_ms)?;
+    let pattern = filter_pattern
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let mut histogram = Histogram::new(start_time, end, interval);
+
+    if let Some(session_id) = &session_id {
+        if let Some(events) = session_events(
+            &state,
+            session_id,
+            &log_group_name,
+            start_time,
+            end,
+            pattern,
+        )
+        .await
+        {
+            histogram.add(&events);
+            return Ok(histogram.finish(log_group_name, start_time, end, "session"));
+        }
+    }
+    let key = event_cache::CacheKey::current(&state, &log_group_name).await;
+    if let Some(events) = event_cache::lookup(&app, &key, Some(start_time), end_time, pattern) {
+        histogram.add(&events);
+        return Ok(histogram.finish(log_group_name, start_time, end, "cache"));
+    }
+
+    let client = state.logs_client().await?;
+    let registration = sliced_fetch::register(&state, fetch_id);
+    let op = operations::Operation::begin(
+        "aggregate",
+        log_group_name.clone(),
+        operations::CancelBy::Flag(Arc::new(AtomicBool::new(false))),
+    );
+    let max_events = max_events.unwrap_or(DEFAULT_MAX_SCANNED);
+    let mut tuner = pacing::PageTuner::new();
+    let mut next_token: Option<String> = None;
+    let (mut scanned, mut scanned_bytes, mut scanned_until) = (0usize, 0usize, start_time);
+    let (mut truncated, mut cancelled) = (false, false);
+    loop {
+        if registration.token.is_cancelled() || op.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        let request = client
+            .filter_log_events()
+            .log_group_name(&log_group_name)
+            .start_time(start_time)
+            .end_time(end)
+            .set_filter_pattern(pattern.map(str::to_string))
+            .set_next_token(next_token);
+        let response = tokio::select! {
+            _ = registration.token.cancelled() => {
+                cancelled = true;
+                break;
+            }
+            response = send_filter_with_retry(request, &op, &mut tuner) => match response {
+                Ok(response) => response,
+                Err(error) => {
+                    budget::record(&app, &state, scanned_bytes as u64, 0).await;
+                    return Err(error.humanized());
+                }
+            },
+        };
+        let events: Vec<LogEvent> = response
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .map(LogEvent::from)
+            .collect();
+        op.page(events.len());
+        scanned += events.len();
+        scanned_bytes += events.iter().map(|e| e.message.len()).sum::<usize>();
+        scanned_until = events
+            .iter()
+            .map(|e| e.timestamp)
+            .fold(scanned_until, i64::max);
+        histogram.add(&events);
+        let progress = AggregateProgress {
+            fetch_id,
+            scanned,
+            scanned_until,
+            fraction: if end > start_time {
+                ((scanned_until - start_time) as f64 / (end - start_time) as f64).clamp(0.0, 1.0)
+            } else {
+                1.0
+            },
+        };
+        app.emit_tagged("aggregate-progress", progress).ok();
+
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+        if scanned >= max_events {
+            truncated = true;
+            break;
+        }
+    }
+    budget::record(&app, &state, scanned_bytes as u64, 0).await;
+    if cancelled {
+        log::info!("Aggregation of {} cancelled", log_group_name);
+    }
+
+    let mut aggregation = histogram.finish(log_group_name, start_time, end, "cloudwatch");
+    aggregation.truncated = truncated;
+    aggregation.scanned_until = (truncated || cancelled).then_some(scanned_until);
+    aggregation.cancelled = cancelled;
+    Ok(aggregation)
+}
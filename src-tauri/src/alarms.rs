@@ -0,0 +1,311 @@
+use crate::{filter_all_events, humanize_aws_error, AppState};
+use aws_sdk_cloudwatch::primitives::DateTime;
+use aws_sdk_cloudwatch::types::HistoryItemType;
+use aws_sdk_cloudwatch::Client as MetricsClient;
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::Serialize;
+use tauri::State;
+
+/// Number of recent state transitions returned per alarm
+const DEFAULT_HISTORY_LIMIT: i32 = 5;
+
+/// Cap on SNS delivery log events read per topic log group
+const MAX_DELIVERY_EVENTS_PER_TOPIC: usize = 500;
+
+/// One alarm state transition from the alarm history
+#[derive(Debug, Clone, Serialize)]
+pub struct AlarmTransition {
+    pub timestamp: Option<i64>,
+    pub summary: Option<String>,
+}
+
+/// A CloudWatch alarm watching a metric produced by one of the group's metric filters
+#[derive(Debug, Clone, Serialize)]
+pub struct LogGroupAlarm {
+    pub alarm_name: String,
+    pub alarm_arn: Option<String>,
+    /// "OK", "ALARM", or "INSUFFICIENT_DATA"
+    pub state: Option<String>,
+    pub state_reason: Option<String>,
+    pub state_updated: Option<i64>,
+    pub actions_enabled: bool,
+    /// SNS topics / actions notified on ALARM
+    pub alarm_actions: Vec<String>,
+    pub metric_filter_name: String,
+    pub metric_namespace: String,
+    pub metric_name: String,
+    pub recent_transitions: Vec<AlarmTransition>,
+}
+
+/// Most recent state transitions of an alarm, newest first
+async fn recent_transitions(
+    client: &MetricsClient,
+    alarm_name: &str,
+    limit: i32,
+) -> Result<Vec<AlarmTransition>, String> {
+    let response = client
+        .describe_alarm_history()
+        .alarm_name(alarm_name)
+        .history_item_type(HistoryItemType::StateUpdate)
+        .max_records(limit)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    Ok(response
+        .alarm_history_items()
+        .iter()
+        .map(|item| AlarmTransition {
+            timestamp: item.timestamp().and_then(|t| t.to_millis().ok()),
+            summary: item.history_summary().map(str::to_string),
+        })
+        .collect())
+}
+
+/// Alarms on metrics derived from the log group's metric filters. Recent transitions
+/// are only looked up when `history_limit` is set.
+async fn group_alarms(
+    logs_client: &CloudWatchClient,
+    metrics_client: &MetricsClient,
+    log_group_name: &str,
+    history_limit: Option<i32>,
+) -> Result<Vec<LogGroupAlarm>, String> {
+    // (filter name, namespace, metric name) for every metric the group produces
+    let mut metrics: Vec<(String, String, String)> = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = logs_client
+            .describe_metric_filters()
+            .log_group_name(log_group_name)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for filter in response.metric_filters() {
+            let filter_name = filter.filter_name().unwrap_or_default().to_string();
+            for transformation in filter.metric_transformations() {
+                let metric = (
+                    filter_name.clone(),
+                    transformation.metric_namespace().to_string(),
+                    transformation.metric_name().to_string(),
+                );
+                if !metrics.contains(&metric) {
+                    metrics.push(metric);
+                }
+            }
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut alarms: Vec<LogGroupAlarm> = Vec::new();
+    for (filter_name, namespace, metric_name) in metrics {
+        let response = metrics_client
+            .describe_alarms_for_metric()
+            .namespace(&namespace)
+            .metric_name(&metric_name)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        for alarm in response.metric_alarms() {
+            let Some(alarm_name) = alarm.alarm_name() else {
+                continue;
+            };
+            // The same metric can be alarmed with several dimension/statistic combos,
+            // but the same alarm shouldn't show up twice
+            if alarms.iter().any(|a| a.alarm_name == alarm_name) {
+                continue;
+            }
+            let transitions = match history_limit {
+                Some(limit) => recent_transitions(metrics_client, alarm_name, limit)
+                    .await
+                    .unwrap_or_else(|e| {
+                        log::warn!("Could not read history for alarm {}: {}", alarm_name, e);
+                        Vec::new()
+                    }),
+                None => Vec::new(),
+            };
+            alarms.push(LogGroupAlarm {
+                alarm_name: alarm_name.to_string(),
+                alarm_arn: alarm.alarm_arn().map(str::to_string),
+                state: alarm.state_value().map(|s| s.as_str().to_string()),
+                state_reason: alarm.state_reason().map(str::to_string),
+                state_updated: alarm
+                    .state_updated_timestamp()
+                    .and_then(|t| t.to_millis().ok()),
+                actions_enabled: alarm.actions_enabled().unwrap_or(false),
+                alarm_actions: alarm.alarm_actions().to_vec(),
+                metric_filter_name: filter_name.clone(),
+                metric_namespace: namespace.clone(),
+                metric_name: metric_name.clone(),
+                recent_transitions: transitions,
+            });
+        }
+    }
+
+    // Alarms currently firing first
+    alarms.sort_by(|a, b| {
+        let not_firing = |alarm: &LogGroupAlarm| alarm.state.as_deref() != Some("ALARM");
+        not_firing(a)
+            .cmp(&not_firing(b))
+            .then_with(|| a.alarm_name.cmp(&b.alarm_name))
+    });
+    Ok(alarms)
+}
+
+/// List alarms on metrics derived from the log group's metric filters, with their
+/// current state and recent transitions
+#[tauri::command]
+pub async fn list_alarms_for_group(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    history_limit: Option<i32>,
+) -> Result<Vec<LogGroupAlarm>, String> {
+    let logs_client = state.logs_client().await?;
+    let metrics_client = MetricsClient::new(&state.sdk_config().await?);
+    let history_limit = history_limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, 100);
+    group_alarms(
+        &logs_client,
+        &metrics_client,
+        &log_group_name,
+        Some(history_limit),
+    )
+    .await
+}
+
+/// An alarm state change or SNS notification delivery, for incident timelines
+#[derive(Debug, Clone, Serialize)]
+pub struct AlarmNotification {
+    pub timestamp: i64,
+    /// "alarm_state" or "sns_delivery"
+    pub kind: String,
+    pub alarm_name: Option<String>,
+    pub topic_arn: Option<String>,
+    /// e.g. "Alarm updated from OK to ALARM" or "Delivered to email (SUCCESS)"
+    pub summary: String,
+}
+
+/// Delivery status log group SNS writes for a topic when delivery logging is enabled:
+/// sns/<region>/<account>/<topic>[/Failure]
+fn sns_delivery_log_groups(topic_arn: &str) -> Option<[String; 2]> {
+    let parts: Vec<&str> = topic_arn.split(':').collect();
+    if parts.len() < 6 || parts[2] != "sns" {
+        return None;
+    }
+    let base = format!("sns/{}/{}/{}", parts[3], parts[4], parts[5]);
+    Some([base.clone(), format!("{}/Failure", base)])
+}
+
+/// Summarize an SNS delivery status log line
+fn delivery_summary(message: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(message) else {
+        return message.to_string();
+    };
+    let status = value
+        .get("status")
+        .and_then(|s| s.as_str())
+        .unwrap_or("UNKNOWN");
+    let destination = value
+        .pointer("/delivery/destination")
+        .and_then(|d| d.as_str())
+        .unwrap_or("subscriber");
+    match value
+        .pointer("/delivery/providerResponse")
+        .and_then(|r| r.as_str())
+    {
+        Some(response) if status != "SUCCESS" => {
+            format!("Delivery to {} {}: {}", destination, status, response)
+        }
+        _ => format!("Delivered to {} ({})", destination, status),
+    }
+}
+
+/// Alarm state changes and SNS notification deliveries for the alarms watching a
+/// log group's metrics within a time range, oldest first. SNS deliveries are only
+/// available for topics with delivery status logging enabled.
+#[tauri::command]
+pub async fn list_alarm_notifications(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<AlarmNotification>, String> {
+    let logs_client = state.logs_client().await?;
+    let metrics_client = MetricsClient::new(&state.sdk_config().await?);
+    let alarms = group_alarms(&logs_client, &metrics_client, &log_group_name, None).await?;
+
+    let mut notifications: Vec<AlarmNotification> = Vec::new();
+    let mut topics: Vec<String> = Vec::new();
+    for alarm in &alarms {
+        let mut next_token: Option<String> = None;
+        loop {
+            let response = metrics_client
+                .describe_alarm_history()
+                .alarm_name(&alarm.alarm_name)
+                .history_item_type(HistoryItemType::StateUpdate)
+                .start_date(DateTime::from_millis(start_time))
+                .end_date(DateTime::from_millis(end_time))
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+            for item in response.alarm_history_items() {
+                notifications.push(AlarmNotification {
+                    timestamp: item
+                        .timestamp()
+                        .and_then(|t| t.to_millis().ok())
+                        .unwrap_or(0),
+                    kind: "alarm_state".to_string(),
+                    alarm_name: Some(alarm.alarm_name.clone()),
+                    topic_arn: None,
+                    summary: item.history_summary().unwrap_or_default().to_string(),
+                });
+            }
+            next_token = response.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        for action in &alarm.alarm_actions {
+            if action.starts_with("arn:aws:sns:") && !topics.contains(action) {
+                topics.push(action.clone());
+            }
+        }
+    }
+
+    for topic_arn in &topics {
+        let Some(log_groups) = sns_delivery_log_groups(topic_arn) else {
+            continue;
+        };
+        for group in log_groups {
+            match filter_all_events(
+                &logs_client,
+                &group,
+                Some(start_time),
+                Some(end_time),
+                None,
+                MAX_DELIVERY_EVENTS_PER_TOPIC,
+            )
+            .await
+            {
+                Ok((events, _)) => {
+                    notifications.extend(events.into_iter().map(|e| AlarmNotification {
+                        timestamp: e.timestamp,
+                        kind: "sns_delivery".to_string(),
+                        alarm_name: None,
+                        topic_arn: Some(topic_arn.clone()),
+                        summary: delivery_summary(&e.message),
+                    }))
+                }
+                // Delivery status logging is off for most topics; nothing to correlate
+                Err(e) => log::debug!("No SNS delivery logs in {}: {}", group, e),
+            }
+        }
+    }
+
+    notifications.sort_by_key(|n| n.timestamp);
+    Ok(notifications)
+}
@@ -0,0 +1,426 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{filter_all_events, schema, storage, workspace, AppState};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+/// File in the app config directory holding the last evaluated alert states
+const ALERT_STATE_FILE: &str = "alert-state.json";
+
+/// How often the background poller evaluates thresholds
+const POLL_INTERVAL_SECS: u64 = 60;
+
+/// Wait before the first evaluation so startup isn't slowed down
+const INITIAL_DELAY_SECS: u64 = 20;
+
+/// Filter pattern used when a threshold doesn't specify one
+pub const DEFAULT_ERROR_PATTERN: &str = "?ERROR ?Error ?error ?FATAL ?Fatal ?fatal";
+
+/// Events a numeric condition reads per window
+const MAX_NUMERIC_EVENTS: usize = 10_000;
+
+fn default_window_minutes() -> u32 {
+    5
+}
+
+/// Statistic of a numeric field over an alert window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Statistic {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Mean,
+    P50,
+    P90,
+    P95,
+    P99,
+}
+
+impl Statistic {
+    /// The statistic of sorted values (None when there are none)
+    fn compute(self, sorted: &[f64]) -> Option<f64> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let sum: f64 = sorted.iter().sum();
+        match self {
+            Statistic::Count => Some(sorted.len() as f64),
+            Statistic::Sum => Some(sum),
+            Statistic::Min => sorted.first().copied(),
+            Statistic::Max => sorted.last().copied(),
+            Statistic::Mean => Some(sum / sorted.len() as f64),
+            Statistic::P50 => schema::percentile(sorted, 50.0),
+            Statistic::P90 => schema::percentile(sorted, 90.0),
+            Statistic::P95 => schema::percentile(sorted, 95.0),
+            Statistic::P99 => schema::percentile(sorted, 99.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+}
+
+impl Comparison {
+    fn holds(self, observed: f64, value: f64) -> bool {
+        match self {
+            Comparison::Above => observed > value,
+            Comparison::AtLeast => observed >= value,
+            Comparison::Below => observed < value,
+            Comparison::AtMost => observed <= value,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AtLeast => ">=",
+            Comparison::Below => "<",
+            Comparison::AtMost => "<=",
+        }
+    }
+}
+
+/// "p95 of duration_ms over the window > 2000" on an extracted JSON field, in the
+/// field's base unit (milliseconds for durations, bytes for sizes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumericCondition {
+    /// JSON field path, e.g. "duration_ms" or "http.latency"
+    pub field: String,
+    pub statistic: Statistic,
+    pub comparison: Comparison,
+    pub value: f64,
+    /// Compare the percent change of the statistic from the previous window of the
+    /// same length instead of the statistic itself ("mean latency up > 50%")
+    #[serde(default)]
+    pub change_percent: bool,
+}
+
+impl NumericCondition {
+    fn describe(&self) -> String {
+        format!(
+            "{:?} {}{} {} {}{}",
+            self.statistic,
+            self.field,
+            if self.change_percent { " change" } else { "" },
+            self.comparison.symbol(),
+            self.value,
+            if self.change_percent { "%" } else { "" }
+        )
+        .to_lowercase()
+    }
+}
+
+/// "More than max_count matching events in the last window_minutes" for one log
+/// group, or a numeric condition on a field of the window's events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertThreshold {
+    pub log_group: String,
+    /// CloudWatch filter pattern counted against the threshold (defaults to error
+    /// keywords); for numeric conditions it narrows the events read (all by default)
+    #[serde(default)]
+    pub filter_pattern: Option<String>,
+    #[serde(default)]
+    pub max_count: u32,
+    #[serde(default = "default_window_minutes")]
+    pub window_minutes: u32,
+    /// Evaluate this instead of the event count
+    #[serde(default)]
+    pub numeric: Option<NumericCondition>,
+}
+
+impl AlertThreshold {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.log_group.is_empty() {
+            return Err("Alert threshold needs a log group".to_string());
+        }
+        if self.window_minutes == 0 || self.window_minutes > 24 * 60 {
+            return Err("Alert window must be between 1 minute and 24 hours".to_string());
+        }
+        if let Some(condition) = &self.numeric {
+            if condition.field.trim().is_empty() {
+                return Err("Numeric alert condition needs a field".to_string());
+            }
+            if !condition.value.is_finite() {
+                return Err("Numeric alert condition needs a finite value".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Key of the threshold's state; thresholds are keyed by pattern, and numeric
+    /// ones by field and statistic too, so a group can have several
+    fn state_key(&self, workspace: &str) -> String {
+        match &self.numeric {
+            Some(condition) => format!(
+                "{}::{}::{}::{:?}::{}",
+                workspace,
+                self.log_group,
+                condition.field,
+                condition.statistic,
+                self.filter_pattern.as_deref().unwrap_or_default()
+            ),
+            None => format!("{}::{}::{}", workspace, self.log_group, self.pattern()),
+        }
+    }
+
+    fn pattern(&self) -> &str {
+        self.filter_pattern
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .unwrap_or(DEFAULT_ERROR_PATTERN)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Ok,
+    Alerting,
+}
+
+/// Last evaluated state of one threshold, persisted across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertState {
+    pub workspace: String,
+    pub log_group: String,
+    pub status: AlertStatus,
+    /// Matching events counted in the last evaluation (capped at max_count + 1), or
+    /// the values a numeric condition was computed from
+    pub count: u32,
+    pub max_count: u32,
+    pub window_minutes: u32,
+    pub last_evaluated: i64,
+    /// When the current status began
+    pub since: i64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// The numeric condition, e.g. "p95 duration_ms > 2000"
+    #[serde(default)]
+    pub condition: Option<String>,
+    /// Statistic (or percent change) observed in the last evaluation
+    #[serde(default)]
+    pub value: Option<f64>,
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Load persisted alert states into AppState (called once at startup)
+pub async fn restore_alert_states(app: &AppHandle) {
+    let states: HashMap<String, AlertState> = storage::read_json(app, ALERT_STATE_FILE);
+    if let Some(state) = app.try_state::<AppState>() {
+        *state.alert_states.lock().await = states;
+    }
+}
+
+/// Count matching events in the window: (count, None, count > max_count)
+async fn evaluate_count(
+    client: &CloudWatchClient,
+    threshold: &AlertThreshold,
+    now: i64,
+) -> Result<(u32, Option<f64>, bool), String> {
+    let start = now - i64::from(threshold.window_minutes) * 60_000;
+    let (events, _) = filter_all_events(
+        client,
+        &threshold.log_group,
+        Some(start),
+        Some(now),
+        Some(threshold.pattern()),
+        threshold.max_count as usize + 1,
+    )
+    .await?;
+    let count = events.len() as u32;
+    Ok((count, None, count > threshold.max_count))
+}
+
+/// The condition's statistic over the window ending at `end`, and how many values
+/// it was computed from
+async fn window_statistic(
+    app: &AppHandle,
+    client: &CloudWatchClient,
+    threshold: &AlertThreshold,
+    condition: &NumericCondition,
+    end: i64,
+) -> Result<(Option<f64>, usize), String> {
+    let start = end - i64::from(threshold.window_minutes) * 60_000;
+    let (events, truncated) = filter_all_events(
+        client,
+        &threshold.log_group,
+        Some(start),
+        Some(end),
+        threshold
+            .filter_pattern
+            .as_deref()
+            .filter(|p| !p.is_empty()),
+        MAX_NUMERIC_EVENTS,
+    )
+    .await?;
+    if truncated {
+        log::debug!(
+            "Numeric alert on {} read the first {} events of the window",
+            threshold.log_group,
+            MAX_NUMERIC_EVENTS
+        );
+    }
+    let values = schema::numeric_field_values(app, &threshold.log_group, &events, &condition.field);
+    Ok((condition.statistic.compute(&values), values.len()))
+}
+
+/// Evaluate a numeric condition: (values read, observed statistic or percent
+/// change, breached). A window without values never breaches.
+async fn evaluate_numeric(
+    app: &AppHandle,
+    client: &CloudWatchClient,
+    threshold: &AlertThreshold,
+    condition: &NumericCondition,
+    now: i64,
+) -> Result<(u32, Option<f64>, bool), String> {
+    let (current, count) = window_statistic(app, client, threshold, condition, now).await?;
+    let observed = if condition.change_percent {
+        let window_ms = i64::from(threshold.window_minutes) * 60_000;
+        let (previous, _) =
+            window_statistic(app, client, threshold, condition, now - window_ms).await?;
+        match (current, previous) {
+            (Some(current), Some(previous)) if previous != 0.0 => {
+                Some((current - previous) / previous.abs() * 100.0)
+            }
+            _ => None,
+        }
+    } else {
+        current
+    };
+    let breached = observed.is_some_and(|o| condition.comparison.holds(o, condition.value));
+    Ok((count as u32, observed, breached))
+}
+
+/// Evaluate every threshold of every workspace bound to the connected profile once
+async fn evaluate_thresholds(app: &AppHandle, state: &AppState) {
+    let Ok(client) = state.logs_client().await else {
+        // Not connected yet
+        return;
+    };
+    let current_profile = state.current_profile.lock().await.clone();
+
+    for ws in workspace::load_workspaces(app) {
+        // Only evaluate workspaces that belong to the connection we have credentials for
+        if ws.profile.is_some() && ws.profile != current_profile {
+            continue;
+        }
+
+        for threshold in &ws.alert_thresholds {
+            let now = now_millis();
+            let key = threshold.state_key(&ws.name);
+            let previous = state.alert_states.lock().await.get(&key).cloned();
+            let result = match &threshold.numeric {
+                Some(condition) => evaluate_numeric(app, &client, threshold, condition, now).await,
+                None => evaluate_count(&client, threshold, now).await,
+            };
+
+            let (count, value, breached, last_error) = match result {
+                Ok((count, value, breached)) => (count, value, breached, None),
+                Err(e) => {
+                    log::warn!(
+                        "Alert evaluation failed for {} in {}: {}",
+                        threshold.log_group,
+                        ws.name,
+                        e
+                    );
+                    // Keep the previous status when the check itself failed
+                    let previous_count = previous.as_ref().map(|p| p.count).unwrap_or(0);
+                    let previous_value = previous.as_ref().and_then(|p| p.value);
+                    (previous_count, previous_value, false, Some(e))
+                }
+            };
+            let status = if last_error.is_some() {
+                previous
+                    .as_ref()
+                    .map(|p| p.status)
+                    .unwrap_or(AlertStatus::Ok)
+            } else if breached {
+                AlertStatus::Alerting
+            } else {
+                AlertStatus::Ok
+            };
+            let since = match &previous {
+                Some(p) if p.status == status => p.since,
+                _ => now,
+            };
+
+            let new_state = AlertState {
+                workspace: ws.name.clone(),
+                log_group: threshold.log_group.clone(),
+                status,
+                count,
+                max_count: threshold.max_count,
+                window_minutes: threshold.window_minutes,
+                last_evaluated: now,
+                since,
+                last_error,
+                condition: threshold.numeric.as_ref().map(NumericCondition::describe),
+                value,
+            };
+
+            let changed = previous.map(|p| p.status) != Some(status);
+            state
+                .alert_states
+                .lock()
+                .await
+                .insert(key, new_state.clone());
+
+            if changed {
+                log::info!(
+                    "Alert {} / {} is now {:?} ({} events{})",
+                    ws.name,
+                    threshold.log_group,
+                    status,
+                    count,
+                    value.map(|v| format!(", value {}", v)).unwrap_or_default()
+                );
+                app.emit_tagged("alert-state-changed", &new_state).ok();
+            }
+        }
+    }
+
+    let states = state.alert_states.lock().await.clone();
+    if let Err(e) = storage::write_json(app, ALERT_STATE_FILE, &states) {
+        log::warn!("Failed to persist alert state: {}", e);
+    }
+}
+
+/// Background poller evaluating workspace alert thresholds every minute
+pub async fn run_alert_poller(app: AppHandle) {
+    restore_alert_states(&app).await;
+    tokio::time::sleep(Duration::from_secs(INITIAL_DELAY_SECS)).await;
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            evaluate_thresholds(&app, &state).await;
+        }
+        tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+    }
+}
+
+/// Current state of every workspace alert threshold
+#[tauri::command]
+pub async fn get_alert_states(state: State<'_, AppState>) -> Result<Vec<AlertState>, String> {
+    let mut states: Vec<AlertState> = state.alert_states.lock().await.values().cloned().collect();
+    states.sort_by(|a, b| {
+        a.workspace
+            .cmp(&b.workspace)
+            .then_with(|| a.log_group.cmp(&b.log_group))
+    });
+    Ok(states)
+}
@@ -0,0 +1,246 @@
+use crate::{columns, export, preview, redaction, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use tauri::{AppHandle, State};
+
+/// Marker identifying a Loggy investigation archive
+const ARCHIVE_FORMAT: &str = "loggy-archive";
+
+/// Current archive layout version; bumped on incompatible changes
+const ARCHIVE_VERSION: u32 = 1;
+
+/// zstd level for archives: written once, kept long
+const ARCHIVE_COMPRESSION_LEVEL: i32 = 9;
+
+/// Events returned with an opened archive; the rest are read with get_session_window
+const ARCHIVE_WINDOW: usize = 500;
+
+/// How one log group of the session was fetched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedQuery {
+    pub log_group_name: String,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub filter_pattern: Option<String>,
+    pub truncated: bool,
+}
+
+/// Summary figures of the archived events
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveStats {
+    pub event_count: usize,
+    pub sources: Vec<String>,
+    pub first_timestamp: Option<i64>,
+    pub last_timestamp: Option<i64>,
+    /// Severity ("error", "warn", ...) -> events
+    pub severity_counts: BTreeMap<String, usize>,
+}
+
+/// Everything about a session needed to review the investigation later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveBundle {
+    pub format: String,
+    pub version: u32,
+    pub archived_at: i64,
+    pub title: String,
+    #[serde(default)]
+    pub app_version: Option<String>,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub queries: Vec<ArchivedQuery>,
+    /// Frontend-owned annotations and notes, stored as given
+    #[serde(default)]
+    pub annotations: serde_json::Value,
+    pub stats: ArchiveStats,
+    #[serde(default)]
+    pub selected: Vec<String>,
+    /// Events oldest-first, masked as they were displayed when archived
+    pub events: Vec<LogEvent>,
+}
+
+/// An archive without its events
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveInfo {
+    pub path: String,
+    pub archived_at: i64,
+    pub title: String,
+    pub app_version: Option<String>,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub queries: Vec<ArchivedQuery>,
+    pub annotations: serde_json::Value,
+    pub stats: ArchiveStats,
+    pub compressed_bytes: u64,
+}
+
+/// An archive loaded into a read-only session
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenedArchive {
+    pub session_id: String,
+    pub info: ArchiveInfo,
+    /// The first window of the session
+    pub events: Vec<LogEvent>,
+}
+
+fn stats(events: &[LogEvent]) -> ArchiveStats {
+    let mut sources: Vec<String> = events.iter().filter_map(|e| e.source.clone()).collect();
+    sources.sort();
+    sources.dedup();
+    let mut severity_counts = BTreeMap::new();
+    for level in export::classify(events).into_iter().filter_map(|e| e.level) {
+        *severity_counts.entry(level).or_default() += 1;
+    }
+    ArchiveStats {
+        event_count: events.len(),
+        sources,
+        first_timestamp: events.first().map(|e| e.timestamp),
+        last_timestamp: events.last().map(|e| e.timestamp),
+        severity_counts,
+    }
+}
+
+fn read_bundle(path: &str) -> Result<(ArchiveBundle, u64), String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let compressed_bytes = file.metadata().map(|m| m.len()).unwrap_or_default();
+    let mut json = Vec::new();
+    zstd::Decoder::new(file)
+        .and_then(|mut decoder| decoder.read_to_end(&mut json))
+        .map_err(|e| format!("{} is not a Loggy archive: {}", path, e))?;
+    let bundle: ArchiveBundle = serde_json::from_slice(&json)
+        .map_err(|e| format!("{} is not a Loggy archive: {}", path, e))?;
+    if bundle.format != ARCHIVE_FORMAT {
+        return Err(format!("{} is not a Loggy archive", path));
+    }
+    if bundle.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "{} was archived by a newer version of Loggy (archive version {})",
+            path, bundle.version
+        ));
+    }
+    Ok((bundle, compressed_bytes))
+}
+
+fn info(path: &str, bundle: &ArchiveBundle, compressed_bytes: u64) -> ArchiveInfo {
+    ArchiveInfo {
+        path: path.to_string(),
+        archived_at: bundle.archived_at,
+        title: bundle.title.clone(),
+        app_version: bundle.app_version.clone(),
+        profile: bundle.profile.clone(),
+        region: bundle.region.clone(),
+        queries: bundle.queries.clone(),
+        annotations: bundle.annotations.clone(),
+        stats: bundle.stats.clone(),
+        compressed_bytes,
+    }
+}
+
+/// Freeze a session into one compressed file at `path`: its events (masked as
+/// displayed), the queries that produced them, the frontend's `annotations` and
+/// summary stats, so a finished incident investigation can be reviewed later
+#[tauri::command]
+pub async fn archive_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    path: String,
+    title: String,
+    annotations: Option<serde_json::Value>,
+) -> Result<ArchiveInfo, String> {
+    let (mut events, queries, selected) = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let mut queries: Vec<ArchivedQuery> = session
+            .fetches
+            .iter()
+            .map(|(group, context)| ArchivedQuery {
+                log_group_name: group.clone(),
+                start_time: context.start_time,
+                end_time: context.end_time,
+                filter_pattern: context.filter_pattern.clone(),
+                truncated: context.truncated,
+            })
+            .collect();
+        queries.sort_by(|a, b| a.log_group_name.cmp(&b.log_group_name));
+        (session.events(), queries, session.selection().event_ids)
+    };
+    if events.is_empty() {
+        return Err("The session has no events to archive".to_string());
+    }
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+
+    let region = state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+    let bundle = ArchiveBundle {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        archived_at: chrono::Utc::now().timestamp_millis(),
+        title,
+        app_version: Some(app.package_info().version.to_string()),
+        profile: state.current_profile.lock().await.clone(),
+        region,
+        queries,
+        annotations: annotations.unwrap_or_default(),
+        stats: stats(&events),
+        selected,
+        events,
+    };
+
+    let json = serde_json::to_vec(&bundle).map_err(|e| e.to_string())?;
+    let file =
+        std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut encoder = zstd::Encoder::new(file, ARCHIVE_COMPRESSION_LEVEL)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    let compressed_bytes = std::fs::metadata(&path)
+        .map(|m| m.len())
+        .unwrap_or_default();
+    log::info!(
+        "Archived session {} ({} events) to {}",
+        session_id,
+        bundle.stats.event_count,
+        path
+    );
+    Ok(info(&path, &bundle, compressed_bytes))
+}
+
+/// Load an archive into `session_id` as a read-only session: windows, search,
+/// goto and exports work, but fetches and imports never change its events
+#[tauri::command]
+pub async fn open_archive(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    session_id: String,
+) -> Result<OpenedArchive, String> {
+    let (bundle, compressed_bytes) = read_bundle(&path)?;
+    let info = info(&path, &bundle, compressed_bytes);
+    let mut window = {
+        let mut sessions = state.sessions.lock().await;
+        sessions.remove(&session_id);
+        let session = sessions.get_or_create(&session_id);
+        session.selected = bundle.selected.into_iter().collect();
+        session.load_frozen(bundle.events);
+        session.window(0, ARCHIVE_WINDOW)
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(OpenedArchive {
+        session_id,
+        info,
+        events: window,
+    })
+}
@@ -0,0 +1,234 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{connectivity, humanize_aws_error, regions, AppState};
+use aws_credential_types::provider::SharedCredentialsProvider;
+use aws_credential_types::Credentials;
+use serde::Serialize;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, State};
+
+/// Session length unless the caller asks for another
+const DEFAULT_DURATION_SECS: i32 = 3600;
+
+/// STS accepts 15 minutes up to the role's maximum (at most 12 hours)
+const MIN_DURATION_SECS: i32 = 900;
+const MAX_DURATION_SECS: i32 = 43_200;
+
+/// `assumed-role-expiring` is emitted this long before the credentials lapse
+const EXPIRY_WARNING_SECS: i64 = 5 * 60;
+
+/// A role assumed on top of the connected profile
+#[derive(Debug, Clone, Serialize)]
+pub struct AssumedRoleInfo {
+    pub role_arn: String,
+    pub session_name: String,
+    /// ARN of the assumed-role session (arn:aws:sts::ACCOUNT:assumed-role/ROLE/SESSION)
+    pub assumed_role_arn: Option<String>,
+    pub account_id: Option<String>,
+    /// Profile whose credentials assumed the role
+    pub source_profile: Option<String>,
+    pub mfa: bool,
+    pub assumed_at: i64,
+    pub expires_at: i64,
+}
+
+/// The assumed role's temporary credentials, held in AppState while in use
+#[derive(Debug, Clone)]
+pub struct AssumedRole {
+    pub info: AssumedRoleInfo,
+    credentials: Credentials,
+}
+
+impl AssumedRole {
+    /// `config` with the assumed role's credentials in place of the profile's
+    pub fn apply(&self, config: &aws_config::SdkConfig) -> aws_config::SdkConfig {
+        config
+            .to_builder()
+            .credentials_provider(SharedCredentialsProvider::new(self.credentials.clone()))
+            .build()
+    }
+}
+
+/// Drop any assumed role, along with the regional clients built from its
+/// credentials. Connecting or reconnecting calls this, returning to the profile.
+pub async fn release(state: &AppState) -> Option<AssumedRoleInfo> {
+    let role = state.assumed_role.lock().await.take()?;
+    state
+        .clients
+        .lock()
+        .await
+        .retain(|(p, _), _| *p != role.info.source_profile);
+    log::info!("Released assumed role {}", role.info.role_arn);
+    Some(role.info)
+}
+
+/// Install `config` (and a client built from it) as the active connection
+async fn install(state: &AppState, config: aws_config::SdkConfig) {
+    let signing_region = state.signing_region.lock().await.clone();
+    *state.client.lock().await = Some(regions::logs_client(&config, signing_region.as_deref()));
+    *state.config.lock().await = Some(config);
+}
+
+/// Warn shortly before the assumed session lapses and again when it has, unless
+/// it was released or replaced in the meantime
+fn watch_expiry(app: AppHandle, info: AssumedRoleInfo) {
+    tauri::async_runtime::spawn(async move {
+        let still_assumed = |app: &AppHandle| {
+            let app = app.clone();
+            let assumed_at = info.assumed_at;
+            async move {
+                let state = app.state::<AppState>();
+                let role = state.assumed_role.lock().await;
+                role.as_ref()
+                    .is_some_and(|r| r.info.assumed_at == assumed_at)
+            }
+        };
+        let until = |at: i64| {
+            let ms = (at - chrono::Utc::now().timestamp_millis()).max(0);
+            Duration::from_millis(ms as u64)
+        };
+
+        tokio::time::sleep(until(info.expires_at - EXPIRY_WARNING_SECS * 1000)).await;
+        if !still_assumed(&app).await {
+            return;
+        }
+        log::info!(
+            "Assumed role {} expires in {} minutes",
+            info.role_arn,
+            until(info.expires_at).as_secs() / 60
+        );
+        app.emit_tagged("assumed-role-expiring", &info).ok();
+
+        tokio::time::sleep(until(info.expires_at)).await;
+        if !still_assumed(&app).await {
+            return;
+        }
+        log::warn!("Assumed role {} has expired", info.role_arn);
+        app.emit_tagged("assumed-role-expired", &info).ok();
+    });
+}
+
+/// Assume `role_arn` with the connected profile's credentials (with MFA when the
+/// role requires it) and use the temporary credentials for every AWS call until
+/// released, re-assumed or the connection changes. `assumed-role-expiring` is
+/// emitted five minutes before they lapse so the role can be re-assumed in time.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn assume_role(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    role_arn: String,
+    session_name: Option<String>,
+    mfa_serial: Option<String>,
+    mfa_token: Option<String>,
+    duration: Option<i32>,
+) -> Result<AssumedRoleInfo, String> {
+    let role_arn = role_arn.trim().to_string();
+    if !role_arn.starts_with("arn:") || !role_arn.contains(":role/") {
+        return Err(format!("{} is not an IAM role ARN", role_arn));
+    }
+    let mfa_serial = mfa_serial.filter(|s| !s.trim().is_empty());
+    let mfa_token = mfa_token.filter(|t| !t.trim().is_empty());
+    if mfa_serial.is_some() != mfa_token.is_some() {
+        return Err("MFA needs both the device serial (or ARN) and a current code".to_string());
+    }
+    let session_name = session_name
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| format!("loggy-{}", chrono::Utc::now().timestamp()));
+    if !(2..=64).contains(&session_name.len()) {
+        return Err("Session name must be 2 to 64 characters".to_string());
+    }
+    let duration = duration
+        .unwrap_or(DEFAULT_DURATION_SECS)
+        .clamp(MIN_DURATION_SECS, MAX_DURATION_SECS);
+
+    // Assume from the profile's own credentials, not a previously assumed role
+    let base = connectivity::load_profile_config(&state).await;
+    let response = aws_sdk_sts::Client::new(&base)
+        .assume_role()
+        .role_arn(&role_arn)
+        .role_session_name(&session_name)
+        .duration_seconds(duration)
+        .set_serial_number(mfa_serial.clone())
+        .set_token_code(mfa_token.map(|t| t.trim().to_string()))
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let issued = response
+        .credentials()
+        .ok_or("STS returned no credentials for the role")?;
+    let expiration = *issued.expiration();
+    let credentials = Credentials::new(
+        issued.access_key_id(),
+        issued.secret_access_key(),
+        Some(issued.session_token().to_string()),
+        SystemTime::try_from(expiration).ok(),
+        "AssumeRole",
+    );
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let info = AssumedRoleInfo {
+        account_id: role_arn
+            .split(':')
+            .nth(4)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string),
+        assumed_role_arn: response.assumed_role_user().map(|u| u.arn().to_string()),
+        role_arn,
+        session_name,
+        source_profile: state.current_profile.lock().await.clone(),
+        mfa: mfa_serial.is_some(),
+        assumed_at: now,
+        expires_at: expiration
+            .to_millis()
+            .unwrap_or(now + i64::from(duration) * 1000),
+    };
+    let role = AssumedRole {
+        info: info.clone(),
+        credentials,
+    };
+    release(&state).await;
+    // Regional clients built with the profile's own credentials would keep calling
+    // other regions as the profile
+    state
+        .clients
+        .lock()
+        .await
+        .retain(|(p, _), _| *p != info.source_profile);
+    install(&state, role.apply(&base)).await;
+    *state.assumed_role.lock().await = Some(role);
+    log::info!(
+        "Assumed role {} until {}",
+        info.role_arn,
+        chrono::DateTime::from_timestamp_millis(info.expires_at)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default()
+    );
+    watch_expiry(app, info.clone());
+    Ok(info)
+}
+
+/// The role currently assumed on top of the connected profile, if any
+#[tauri::command]
+pub async fn get_assumed_role(
+    state: State<'_, AppState>,
+) -> Result<Option<AssumedRoleInfo>, String> {
+    Ok(state
+        .assumed_role
+        .lock()
+        .await
+        .as_ref()
+        .map(|r| r.info.clone()))
+}
+
+/// Stop using the assumed role and return to the profile's own credentials
+#[tauri::command]
+pub async fn release_assumed_role(
+    state: State<'_, AppState>,
+) -> Result<Option<AssumedRoleInfo>, String> {
+    let released = release(&state).await;
+    if released.is_some() {
+        install(&state, connectivity::load_config(&state).await).await;
+    }
+    Ok(released)
+}
@@ -0,0 +1,121 @@
+use crate::{storage, AppState};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use tauri::AppHandle;
+
+/// Append-only JSON-lines file in the app config directory
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Serializes appends from concurrent commands
+static AUDIT_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// One mutating operation performed through Loggy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Epoch milliseconds
+    pub timestamp: i64,
+    /// RFC 3339 UTC time, for humans reading the exported file
+    pub time: String,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    /// e.g. "put_retention_policy", "delete_log_group"
+    pub action: String,
+    pub parameters: serde_json::Value,
+    /// "success" or "error"
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Append an entry for a mutating operation. Failures to write the audit log are
+/// logged but never fail the operation itself.
+pub async fn record<T>(
+    app: &AppHandle,
+    state: &AppState,
+    action: &str,
+    parameters: serde_json::Value,
+    result: &Result<T, String>,
+) {
+    let now = chrono::Utc::now();
+    let region = state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+    let entry = AuditEntry {
+        timestamp: now.timestamp_millis(),
+        time: now.to_rfc3339(),
+        profile: state.current_profile.lock().await.clone(),
+        region,
+        action: action.to_string(),
+        parameters,
+        outcome: if result.is_ok() { "success" } else { "error" }.to_string(),
+        error: result.as_ref().err().cloned(),
+    };
+    if let Err(e) = append(app, &entry) {
+        log::error!("Failed to write audit entry for {}: {}", action, e);
+    }
+}
+
+fn append(app: &AppHandle, entry: &AuditEntry) -> Result<(), String> {
+    let path = storage::config_dir(app)?.join(AUDIT_FILE);
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let _guard = AUDIT_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn read_entries(app: &AppHandle) -> Result<Vec<AuditEntry>, String> {
+    let path = storage::config_dir(app)?.join(AUDIT_FILE);
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(_) => return Ok(Vec::new()),
+    };
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+/// Audit entries, newest first, optionally only those since a timestamp
+#[tauri::command]
+pub fn list_audit_entries(
+    app: AppHandle,
+    since: Option<i64>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    let mut entries = read_entries(&app)?;
+    entries.retain(|e| since.is_none_or(|since| e.timestamp >= since));
+    entries.reverse();
+    entries.truncate(limit.unwrap_or(usize::MAX));
+    Ok(entries)
+}
+
+/// Export audit entries in a time range as JSON lines (change-management evidence).
+/// Returns the number of entries written.
+#[tauri::command]
+pub fn export_audit_log(
+    app: AppHandle,
+    path: String,
+    since: Option<i64>,
+    until: Option<i64>,
+) -> Result<usize, String> {
+    let entries: Vec<AuditEntry> = read_entries(&app)?
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+        .filter(|e| until.is_none_or(|until| e.timestamp <= until))
+        .collect();
+
+    let mut contents = String::new();
+    for entry in &entries {
+        contents.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        contents.push('\n');
+    }
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(entries.len())
+}
@@ -0,0 +1,244 @@
+use crate::pipeline::{self, ProcessedEvent};
+use crate::session::{self, ResultSession};
+use crate::LogEvent;
+use aws_sdk_cloudwatchlogs::types::FilteredLogEvent;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Events per synthesized page (FilterLogEvents returns at most 10,000)
+const PAGE_SIZE: usize = 10_000;
+
+const DEFAULT_EVENT_COUNT: usize = 1_000_000;
+const MAX_EVENT_COUNT: usize = 20_000_000;
+const DEFAULT_MESSAGE_BYTES: usize = 300;
+
+/// Timing and memory for one stage of the benchmark
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseReport {
+    pub phase: String,
+    pub duration_ms: u64,
+    pub events_per_sec: f64,
+    pub mb_per_sec: f64,
+    /// Resident memory after the phase, if the platform reports it
+    pub rss_after_bytes: Option<usize>,
+}
+
+/// Result of a bench_fetch_pipeline run
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub event_count: usize,
+    pub message_bytes: usize,
+    pub total_ms: u64,
+    pub events_per_sec: f64,
+    pub rss_before_bytes: Option<usize>,
+    pub peak_rss_bytes: Option<usize>,
+    /// Bytes that would have crossed the IPC bridge
+    pub emitted_bytes: usize,
+    /// Estimated size of the indexed session (after message compression, if applied)
+    pub session_bytes: usize,
+    pub session_compressed: bool,
+    pub phases: Vec<PhaseReport>,
+}
+
+fn rss() -> Option<usize> {
+    memory_stats::memory_stats().map(|m| m.physical_mem)
+}
+
+/// Deterministic mix of the shapes seen in real groups: JSON (60%), plain text with
+/// a level (30%), and ANSI-colored stack traces (10%), padded to `message_bytes`
+fn synth_message(i: usize, message_bytes: usize) -> String {
+    let mut message = match i % 10 {
+        0..=5 => format!(
+            r#"{{"level":"{}","requestId":"req-{:08x}","http":{{"method":"GET","status":{},"path":"/api/items/{}"}},"durationMs":{},"msg":""#,
+            ["info", "debug", "warn", "error"][i % 4],
+            i,
+            [200, 201, 404, 500][i % 4],
+            i % 997,
+            i % 1500
+        ),
+        6..=8 => format!(
+            "{} [req-{:08x}] processed item {} in {}ms ",
+            ["INFO", "DEBUG", "WARN", "ERROR"][i % 4],
+            i,
+            i % 997,
+            i % 1500
+        ),
+        _ => format!(
+            "\x1b[31mERROR\x1b[0m unhandled exception in worker {}\n    at handler (index.js:{}:13)\n    at process ",
+            i % 16,
+            i % 400
+        ),
+    };
+    while message.len() < message_bytes {
+        message.push_str("lorem ipsum dolor sit amet ");
+    }
+    if i % 10 <= 5 {
+        message.push_str("\"}");
+    }
+    message
+}
+
+fn synth_page(start: usize, len: usize, message_bytes: usize) -> Vec<FilteredLogEvent> {
+    let base_ts = 1_700_000_000_000i64;
+    (start..start + len)
+        .map(|i| {
+            FilteredLogEvent::builder()
+                .timestamp(base_ts + i as i64)
+                .message(synth_message(i, message_bytes))
+                .log_stream_name(format!("stream-{}", i % 32))
+                .event_id(i.to_string())
+                .build()
+        })
+        .collect()
+}
+
+/// Accumulates wall time for a phase that runs once per page
+#[derive(Default)]
+struct PhaseTimer {
+    elapsed: std::time::Duration,
+}
+
+impl PhaseTimer {
+    fn time<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.elapsed += started.elapsed();
+        result
+    }
+
+    fn report(
+        &self,
+        phase: &str,
+        events: usize,
+        bytes: usize,
+        rss_after: Option<usize>,
+    ) -> PhaseReport {
+        let secs = self.elapsed.as_secs_f64().max(f64::EPSILON);
+        PhaseReport {
+            phase: phase.to_string(),
+            duration_ms: self.elapsed.as_millis() as u64,
+            events_per_sec: events as f64 / secs,
+            mb_per_sec: bytes as f64 / (1024.0 * 1024.0) / secs,
+            rss_after_bytes: rss_after,
+        }
+    }
+}
+
+fn run(event_count: usize, message_bytes: usize) -> Result<BenchReport, String> {
+    let stages = pipeline::default_stages();
+    let rss_before = rss();
+    let mut peak = rss_before;
+    let mut track_peak = || {
+        if let Some(now) = rss() {
+            peak = Some(peak.map_or(now, |p| p.max(now)));
+        }
+    };
+
+    let mut ingest = PhaseTimer::default();
+    let mut parse = PhaseTimer::default();
+    let mut emit = PhaseTimer::default();
+    let mut index = PhaseTimer::default();
+    let mut all_events: Vec<LogEvent> = Vec::with_capacity(event_count);
+    let mut result_session = ResultSession::default();
+    let mut message_total = 0usize;
+    let mut emitted_bytes = 0usize;
+    let started = Instant::now();
+
+    let mut offset = 0;
+    while offset < event_count {
+        let len = PAGE_SIZE.min(event_count - offset);
+        let page = synth_page(offset, len, message_bytes);
+
+        // Same conversion and size accounting as fetch_logs
+        let events: Vec<LogEvent> = ingest.time(|| page.into_iter().map(LogEvent::from).collect());
+        message_total += events.iter().map(|e| e.message.len()).sum::<usize>();
+
+        parse.time(|| -> Result<(), String> {
+            let mut processed: Vec<ProcessedEvent> =
+                events.iter().map(ProcessedEvent::from).collect();
+            for stage in &stages {
+                processed = pipeline::apply_stage(stage, processed)?;
+            }
+            Ok(())
+        })?;
+
+        // Serializing the batch is the backend's share of an emit
+        emitted_bytes += emit
+            .time(|| serde_json::to_vec(&events).map(|b| b.len()))
+            .map_err(|e| e.to_string())?;
+
+        all_events.extend(events);
+        offset += len;
+        track_peak();
+    }
+    let ingest_rss = rss();
+
+    index.time(|| {
+        let mut tagged = std::mem::take(&mut all_events);
+        session::tag_source(&mut tagged, "bench");
+        result_session.replace_source("bench", tagged);
+    });
+    track_peak();
+    let index_rss = rss();
+
+    let phases = vec![
+        ingest.report("ingest", event_count, message_total, ingest_rss),
+        parse.report("parse", event_count, message_total, ingest_rss),
+        emit.report("emit", event_count, emitted_bytes, ingest_rss),
+        index.report("index", event_count, message_total, index_rss),
+    ];
+    let session_bytes = result_session.memory_bytes();
+    let session_compressed = result_session.is_compressed();
+    drop(result_session);
+
+    let total = started.elapsed();
+    Ok(BenchReport {
+        event_count,
+        message_bytes,
+        total_ms: total.as_millis() as u64,
+        events_per_sec: event_count as f64 / total.as_secs_f64().max(f64::EPSILON),
+        rss_before_bytes: rss_before,
+        peak_rss_bytes: peak,
+        emitted_bytes,
+        session_bytes,
+        session_compressed,
+        phases,
+    })
+}
+
+/// Internal benchmark: push synthesized events through conversion, the default
+/// processing pipeline, emit serialization and session indexing, and report
+/// throughput and memory per phase. Available in debug builds, or in release builds
+/// when LOGGY_BENCH is set.
+#[tauri::command]
+pub async fn bench_fetch_pipeline(
+    event_count: Option<usize>,
+    message_bytes: Option<usize>,
+) -> Result<BenchReport, String> {
+    if !cfg!(debug_assertions) && std::env::var_os("LOGGY_BENCH").is_none() {
+        return Err("Benchmark mode is disabled; set LOGGY_BENCH=1 to enable it".to_string());
+    }
+    let event_count = event_count.unwrap_or(DEFAULT_EVENT_COUNT);
+    if event_count == 0 || event_count > MAX_EVENT_COUNT {
+        return Err(format!(
+            "event_count must be between 1 and {}",
+            MAX_EVENT_COUNT
+        ));
+    }
+    let message_bytes = message_bytes.unwrap_or(DEFAULT_MESSAGE_BYTES);
+
+    log::info!(
+        "Starting fetch pipeline benchmark: {} events of ~{} bytes",
+        event_count,
+        message_bytes
+    );
+    let report = tauri::async_runtime::spawn_blocking(move || run(event_count, message_bytes))
+        .await
+        .map_err(|e| format!("Benchmark failed: {}", e))??;
+    log::info!(
+        "Benchmark finished: {:.0} events/sec, peak RSS {:?} bytes",
+        report.events_per_sec,
+        report.peak_rss_bytes
+    );
+    Ok(report)
+}
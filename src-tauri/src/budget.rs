@@ -0,0 +1,226 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{storage, AppState};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding each profile's daily volume budget
+const BUDGETS_FILE: &str = "volume-budgets.json";
+
+/// File in the app config directory holding daily volume usage per profile
+const USAGE_FILE: &str = "volume-usage.json";
+
+/// Days of usage kept per profile
+const USAGE_HISTORY_DAYS: usize = 31;
+
+/// Budget key of connections without a profile
+const DEFAULT_PROFILE_KEY: &str = "default";
+
+/// Serializes read-modify-write of the usage file
+static USAGE_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn default_warn_percent() -> u8 {
+    80
+}
+
+/// Soft daily limit on the log data one profile downloads and scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeBudget {
+    /// Bytes per UTC day (downloaded by fetches plus scanned by Insights queries)
+    pub daily_bytes: u64,
+    /// Usage percentage at which the first warning is emitted
+    #[serde(default = "default_warn_percent")]
+    pub warn_at_percent: u8,
+}
+
+/// Budgets keyed by profile name ("default" when no profile is set)
+pub type VolumeBudgets = HashMap<String, VolumeBudget>;
+
+/// Volume used by one profile on one UTC day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DayUsage {
+    /// Message bytes returned by FilterLogEvents fetches
+    pub downloaded_bytes: u64,
+    /// Bytes scanned by Logs Insights queries
+    pub scanned_bytes: u64,
+    pub fetches: u32,
+    pub queries: u32,
+}
+
+impl DayUsage {
+    fn total(&self) -> u64 {
+        self.downloaded_bytes + self.scanned_bytes
+    }
+}
+
+/// Profile -> "YYYY-MM-DD" -> usage
+type VolumeUsage = HashMap<String, BTreeMap<String, DayUsage>>;
+
+/// Today's usage of a profile against its budget
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub profile: String,
+    pub day: String,
+    pub usage: DayUsage,
+    pub budget: Option<VolumeBudget>,
+    /// Percent of the budget used (None without a budget)
+    pub percent_used: Option<f64>,
+    /// "ok", "approaching" (past warn_at_percent) or "exceeded"
+    pub level: &'static str,
+    /// Usage would pass the budget with the bytes passed to get_volume_budget_status
+    pub would_exceed: bool,
+}
+
+fn profile_key(profile: Option<&str>) -> String {
+    profile.unwrap_or(DEFAULT_PROFILE_KEY).to_string()
+}
+
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+fn level(usage: u64, budget: Option<&VolumeBudget>) -> &'static str {
+    match budget {
+        Some(b) if usage >= b.daily_bytes => "exceeded",
+        Some(b) if usage * 100 >= b.daily_bytes * u64::from(b.warn_at_percent) => "approaching",
+        _ => "ok",
+    }
+}
+
+fn status(
+    profile: String,
+    day: String,
+    usage: DayUsage,
+    budget: Option<VolumeBudget>,
+    planned_bytes: u64,
+) -> BudgetStatus {
+    let total = usage.total();
+    BudgetStatus {
+        level: level(total, budget.as_ref()),
+        percent_used: budget
+            .as_ref()
+            .filter(|b| b.daily_bytes > 0)
+            .map(|b| total as f64 * 100.0 / b.daily_bytes as f64),
+        would_exceed: budget
+            .as_ref()
+            .is_some_and(|b| total + planned_bytes > b.daily_bytes),
+        profile,
+        day,
+        usage,
+        budget,
+    }
+}
+
+/// Add a fetch's downloaded bytes or a query's scanned bytes to the current
+/// profile's usage for today, emitting `volume-budget-warning` when this pushes
+/// it past the warning threshold or the budget
+pub async fn record(app: &AppHandle, state: &AppState, downloaded_bytes: u64, scanned_bytes: u64) {
+    let profile = profile_key(state.current_profile.lock().await.as_deref());
+    let day = today();
+    let (before, after) = {
+        let _guard = USAGE_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut usage: VolumeUsage = storage::read_json(app, USAGE_FILE);
+        let days = usage.entry(profile.clone()).or_default();
+        let entry = days.entry(day.clone()).or_default();
+        let before = entry.total();
+        entry.downloaded_bytes += downloaded_bytes;
+        entry.scanned_bytes += scanned_bytes;
+        if scanned_bytes > 0 {
+            entry.queries += 1;
+        } else {
+            entry.fetches += 1;
+        }
+        let after = entry.clone();
+        while days.len() > USAGE_HISTORY_DAYS {
+            days.pop_first();
+        }
+        if let Err(e) = storage::write_json(app, USAGE_FILE, &usage) {
+            log::warn!("Failed to record volume usage: {}", e);
+        }
+        (before, after)
+    };
+
+    let budgets: VolumeBudgets = storage::read_json(app, BUDGETS_FILE);
+    let budget = budgets.get(&profile).cloned();
+    if level(before, budget.as_ref()) != level(after.total(), budget.as_ref()) {
+        let status = status(profile, day, after, budget, 0);
+        log::warn!(
+            "Volume budget of {} {}: {} bytes used today",
+            status.profile,
+            status.level,
+            status.usage.total()
+        );
+        app.emit_tagged("volume-budget-warning", status).ok();
+    }
+}
+
+/// Today's volume usage and budget of a profile (the current one when None).
+/// `planned_bytes` (e.g. a group's stored bytes before a big fetch) sets
+/// `would_exceed` so the UI can warn before running it.
+#[tauri::command]
+pub async fn get_volume_budget_status(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile: Option<String>,
+    planned_bytes: Option<u64>,
+) -> Result<BudgetStatus, String> {
+    let profile = match profile {
+        Some(p) => p,
+        None => profile_key(state.current_profile.lock().await.as_deref()),
+    };
+    let day = today();
+    let mut usage: VolumeUsage = storage::read_json(&app, USAGE_FILE);
+    let today_usage = usage
+        .get_mut(&profile)
+        .and_then(|days| days.remove(&day))
+        .unwrap_or_default();
+    let mut budgets: VolumeBudgets = storage::read_json(&app, BUDGETS_FILE);
+    Ok(status(
+        profile.clone(),
+        day,
+        today_usage,
+        budgets.remove(&profile),
+        planned_bytes.unwrap_or(0),
+    ))
+}
+
+/// Daily usage of every profile for the last month, oldest day first
+#[tauri::command]
+pub fn get_volume_usage_history(app: AppHandle) -> HashMap<String, BTreeMap<String, DayUsage>> {
+    storage::read_json(&app, USAGE_FILE)
+}
+
+/// Budgets of every profile
+#[tauri::command]
+pub fn get_volume_budgets(app: AppHandle) -> VolumeBudgets {
+    storage::read_json(&app, BUDGETS_FILE)
+}
+
+/// Set a profile's daily budget (the current profile when None), or remove it
+/// with `budget` None
+#[tauri::command]
+pub async fn set_volume_budget(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile: Option<String>,
+    budget: Option<VolumeBudget>,
+) -> Result<(), String> {
+    if let Some(b) = &budget {
+        if b.daily_bytes == 0 {
+            return Err("The daily budget must be more than 0 bytes".to_string());
+        }
+        if b.warn_at_percent == 0 || b.warn_at_percent > 100 {
+            return Err("The warning threshold must be between 1 and 100 percent".to_string());
+        }
+    }
+    let profile = match profile {
+        Some(p) => p,
+        None => profile_key(state.current_profile.lock().await.as_deref()),
+    };
+    let mut budgets: VolumeBudgets = storage::read_json(&app, BUDGETS_FILE);
+    match budget {
+        Some(b) => budgets.insert(profile, b),
+        None => budgets.remove(&profile),
+    };
+    storage::write_json(&app, BUDGETS_FILE, &budgets)
+}
@@ -0,0 +1,316 @@
+use crate::{
+    columns, filter_stream_events, humanize_aws_error, preview, redaction, session, AppState,
+    LogEvent,
+};
+use aws_sdk_synthetics::types::CanaryRun;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tauri::{AppHandle, State};
+
+/// Canary runs listed when no limit is given
+const DEFAULT_RUN_LIMIT: i32 = 20;
+
+/// Cap on events fetched for one run
+const MAX_RUN_EVENTS: usize = 20_000;
+
+/// Events returned with the run log; the rest are read with get_session_window
+const RUN_WINDOW: usize = 500;
+
+/// Slack around a run's timeline when matching its log events, since the Lambda
+/// invocation starts a little before and logs a little after the recorded run
+const RUN_SLACK_MS: i64 = 10_000;
+
+fn canary_group_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "/aws/lambda/cwsyn-checkout-flow-0123abcd-4567-89ef-0123-456789abcdef"
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^/aws/lambda/cwsyn-(.+)-[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$",
+        )
+        .expect("valid regex")
+    })
+}
+
+/// The canary a log group belongs to, for groups of Synthetics canary functions
+pub fn canary_name_from_group(log_group_name: &str) -> Option<String> {
+    Some(canary_group_regex().captures(log_group_name)?[1].to_string())
+}
+
+/// One run of a canary
+#[derive(Debug, Clone, Serialize)]
+pub struct CanaryRunSummary {
+    pub id: String,
+    /// "RUNNING", "PASSED" or "FAILED"
+    pub state: Option<String>,
+    pub state_reason: Option<String>,
+    pub started_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    /// Screenshots and HAR files of the run
+    pub artifact_s3_location: Option<String>,
+}
+
+/// A Synthetics canary and its latest run
+#[derive(Debug, Clone, Serialize)]
+pub struct CanarySummary {
+    pub name: String,
+    /// "RUNNING", "STOPPED", "ERROR", ...
+    pub state: Option<String>,
+    pub runtime_version: Option<String>,
+    /// Log group of the canary's function
+    pub log_group_name: Option<String>,
+    pub last_run: Option<CanaryRunSummary>,
+}
+
+/// Everything one canary run logged
+#[derive(Debug, Clone, Serialize)]
+pub struct CanaryRunLog {
+    pub canary: String,
+    pub run: CanaryRunSummary,
+    pub log_group_name: String,
+    pub log_stream_name: String,
+    /// The first window of the session, oldest first
+    pub events: Vec<LogEvent>,
+    pub event_count: usize,
+    pub truncated: bool,
+}
+
+fn run_summary(run: &CanaryRun) -> CanaryRunSummary {
+    let status = run.status();
+    let timeline = run.timeline();
+    CanaryRunSummary {
+        id: run.id().unwrap_or_default().to_string(),
+        state: status
+            .and_then(|s| s.state())
+            .map(|s| s.as_str().to_string()),
+        state_reason: status.and_then(|s| s.state_reason()).map(str::to_string),
+        started_at: timeline
+            .and_then(|t| t.started())
+            .and_then(|t| t.to_millis().ok()),
+        completed_at: timeline
+            .and_then(|t| t.completed())
+            .and_then(|t| t.to_millis().ok()),
+        artifact_s3_location: run.artifact_s3_location().map(str::to_string),
+    }
+}
+
+/// Log group of a canary's function, from its engine ARN
+/// ("arn:aws:lambda:region:account:function:cwsyn-name-uuid:3")
+fn log_group_from_engine_arn(engine_arn: &str) -> Option<String> {
+    let function = engine_arn.split(':').nth(6)?;
+    Some(format!("/aws/lambda/{}", function))
+}
+
+async fn synthetics_client(state: &AppState) -> Result<aws_sdk_synthetics::Client, String> {
+    Ok(aws_sdk_synthetics::Client::new(&state.sdk_config().await?))
+}
+
+/// Canaries of the account/region with their latest run status, failing
+/// canaries first
+#[tauri::command]
+pub async fn list_canaries(state: State<'_, AppState>) -> Result<Vec<CanarySummary>, String> {
+    let client = synthetics_client(&state).await?;
+    let mut canaries = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_canaries()
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        canaries.extend(response.canaries().iter().map(|canary| {
+            CanarySummary {
+                name: canary.name().unwrap_or_default().to_string(),
+                state: canary
+                    .status()
+                    .and_then(|s| s.state())
+                    .map(|s| s.as_str().to_string()),
+                runtime_version: canary.runtime_version().map(str::to_string),
+                log_group_name: canary.engine_arn().and_then(log_group_from_engine_arn),
+                last_run: None,
+            }
+        }));
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut last_runs: HashMap<String, CanaryRunSummary> = HashMap::new();
+    loop {
+        let response = client
+            .describe_canaries_last_run()
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for last in response.canaries_last_run() {
+            if let (Some(name), Some(run)) = (last.canary_name(), last.last_run()) {
+                last_runs.insert(name.to_string(), run_summary(run));
+            }
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+    for canary in &mut canaries {
+        canary.last_run = last_runs.remove(&canary.name);
+    }
+
+    let failing = |c: &CanarySummary| {
+        c.last_run
+            .as_ref()
+            .is_some_and(|r| r.state.as_deref() == Some("FAILED"))
+    };
+    canaries.sort_by(|a, b| {
+        failing(b)
+            .cmp(&failing(a))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(canaries)
+}
+
+/// Recent runs of a canary, newest first
+#[tauri::command]
+pub async fn list_canary_runs(
+    state: State<'_, AppState>,
+    name: String,
+    limit: Option<i32>,
+) -> Result<Vec<CanaryRunSummary>, String> {
+    let client = synthetics_client(&state).await?;
+    let response = client
+        .get_canary_runs()
+        .name(&name)
+        .max_results(limit.unwrap_or(DEFAULT_RUN_LIMIT).clamp(1, 100))
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    Ok(response.canary_runs().iter().map(run_summary).collect())
+}
+
+/// The canary of a log group, if it is a Synthetics canary's group
+#[tauri::command]
+pub fn canary_for_log_group(log_group_name: String) -> Option<String> {
+    canary_name_from_group(&log_group_name)
+}
+
+/// Jump to one canary run: find the log stream of the function invocation that
+/// ran it and fetch that run's events into a result session
+#[tauri::command]
+pub async fn fetch_canary_run(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    name: String,
+    run_id: String,
+) -> Result<CanaryRunLog, String> {
+    let client = synthetics_client(&state).await?;
+    let canary = client
+        .get_canary()
+        .name(&name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let log_group_name = canary
+        .canary()
+        .and_then(|c| c.engine_arn())
+        .and_then(log_group_from_engine_arn)
+        .ok_or_else(|| format!("Canary {} has no function log group", name))?;
+
+    let mut next_token: Option<String> = None;
+    let run = loop {
+        let response = client
+            .get_canary_runs()
+            .name(&name)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        let found = response
+            .canary_runs()
+            .iter()
+            .find(|r| r.id() == Some(run_id.as_str()))
+            .map(run_summary);
+        next_token = response.next_token;
+        if found.is_some() || next_token.is_none() {
+            break found;
+        }
+    };
+    let run = run.ok_or_else(|| format!("Run {} of canary {} not found", run_id, name))?;
+    let started = run
+        .started_at
+        .ok_or_else(|| format!("Run {} has no start time", run_id))?;
+    let start_time = started - RUN_SLACK_MS;
+    let end_time = run.completed_at.unwrap_or(started) + RUN_SLACK_MS;
+
+    // The run's invocation is the stream that logged inside its timeline
+    let logs = state.logs_client().await?;
+    let response = logs
+        .filter_log_events()
+        .log_group_name(&log_group_name)
+        .start_time(start_time)
+        .end_time(end_time)
+        .limit(1)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let log_stream_name = response
+        .events()
+        .first()
+        .and_then(|e| e.log_stream_name())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            format!(
+                "No log events for run {} in {} (they may have expired)",
+                run_id, log_group_name
+            )
+        })?;
+
+    let (mut events, truncated) = filter_stream_events(
+        &logs,
+        &log_group_name,
+        std::slice::from_ref(&log_stream_name),
+        Some(start_time),
+        Some(end_time),
+        None,
+        MAX_RUN_EVENTS,
+    )
+    .await?;
+    events.sort_by_key(|e| e.timestamp);
+    session::tag_source(&mut events, &log_group_name);
+    let event_count = events.len();
+    let mut window = {
+        let mut sessions = state.sessions.lock().await;
+        let result = sessions.get_or_create(&session_id);
+        result.clear();
+        result.fetches.clear();
+        result.replace_source(&log_group_name, events);
+        result.fetches.insert(
+            log_group_name.clone(),
+            session::FetchContext {
+                start_time: Some(start_time),
+                end_time: Some(end_time),
+                filter_pattern: None,
+                truncated,
+                complete_ranges: Vec::new(),
+            },
+        );
+        result.window(0, RUN_WINDOW)
+    };
+
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(CanaryRunLog {
+        canary: name,
+        run,
+        log_group_name,
+        log_stream_name,
+        events: window,
+        event_count,
+        truncated,
+    })
+}
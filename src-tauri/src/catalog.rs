@@ -0,0 +1,486 @@
+use crate::{humanize_aws_error, AppState, LogGroup};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use aws_sdk_resourcegroupstagging::types::TagFilter;
+use aws_sdk_resourcegroupstagging::Client as TaggingClient;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+
+/// Tag CloudFormation puts on every resource it creates
+pub const CLOUDFORMATION_STACK_TAG: &str = "aws:cloudformation:stack-name";
+
+/// Tag keys commonly used to identify the owning service/application
+const SERVICE_TAG_KEYS: [&str; 6] = [
+    "service",
+    "Service",
+    "app",
+    "App",
+    "application",
+    "Application",
+];
+
+/// Default number of related groups suggested
+const DEFAULT_SUGGESTION_LIMIT: usize = 20;
+
+/// A log group as seen through the tagging API
+#[derive(Debug, Clone, Serialize)]
+pub struct TaggedLogGroup {
+    pub name: String,
+    pub arn: String,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// A suggested related log group with the reasons it was suggested
+#[derive(Debug, Clone, Serialize)]
+pub struct RelatedGroupSuggestion {
+    pub name: String,
+    pub score: u32,
+    pub reasons: Vec<String>,
+}
+
+/// Log group name from a log group ARN (arn:aws:logs:region:account:log-group:NAME[:*])
+pub fn log_group_name_from_arn(arn: &str) -> Option<String> {
+    let (_, name) = arn.split_once(":log-group:")?;
+    Some(name.strip_suffix(":*").unwrap_or(name).to_string())
+}
+
+/// List every log group in the account/region
+pub async fn describe_all_log_groups(client: &CloudWatchClient) -> Result<Vec<LogGroup>, String> {
+    let mut log_groups = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_log_groups()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for group in response.log_groups.unwrap_or_default() {
+            log_groups.push(LogGroup {
+                name: group.log_group_name.unwrap_or_default(),
+                arn: group.arn,
+                stored_bytes: group.stored_bytes,
+            });
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            return Ok(log_groups);
+        }
+    }
+}
+
+/// Find log groups carrying a tag (optionally with a specific value) via the
+/// Resource Groups Tagging API, which answers in one paginated query instead of
+/// one ListTagsForResource call per group
+pub async fn tagged_log_groups(
+    config: &aws_config::SdkConfig,
+    key: &str,
+    value: Option<&str>,
+) -> Result<Vec<TaggedLogGroup>, String> {
+    let client = TaggingClient::new(config);
+    let filter = TagFilter::builder()
+        .key(key)
+        .set_values(value.map(|v| vec![v.to_string()]))
+        .build();
+
+    let mut groups = Vec::new();
+    let mut pagination_token: Option<String> = None;
+    loop {
+        let response = client
+            .get_resources()
+            .resource_type_filters("logs:log-group")
+            .tag_filters(filter.clone())
+            .set_pagination_token(pagination_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        for mapping in response.resource_tag_mapping_list() {
+            let Some(arn) = mapping.resource_arn() else {
+                continue;
+            };
+            let Some(name) = log_group_name_from_arn(arn) else {
+                continue;
+            };
+            groups.push(TaggedLogGroup {
+                name,
+                arn: arn.to_string(),
+                tags: mapping
+                    .tags()
+                    .iter()
+                    .map(|t| (t.key().to_string(), t.value().to_string()))
+                    .collect(),
+            });
+        }
+
+        pagination_token = response.pagination_token.filter(|t| !t.is_empty());
+        if pagination_token.is_none() {
+            return Ok(groups);
+        }
+    }
+}
+
+/// Tags of a single log group
+pub async fn log_group_tags(
+    client: &CloudWatchClient,
+    log_group_arn: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let arn = log_group_arn.strip_suffix(":*").unwrap_or(log_group_arn);
+    let response = client
+        .list_tags_for_resource()
+        .resource_arn(arn)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    Ok(response.tags.unwrap_or_default().into_iter().collect())
+}
+
+/// The service part of a log group name, split into tokens.
+/// "/aws/lambda/checkout-prod-orders" -> ["checkout", "prod", "orders"]
+fn name_tokens(name: &str) -> Vec<String> {
+    let service_part = name.rsplit('/').find(|p| !p.is_empty()).unwrap_or(name);
+    service_part
+        .split(['-', '_', '.'])
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Number of leading name tokens two groups share ("checkout-prod-*" share 2)
+fn shared_prefix_len(a: &[String], b: &[String]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Suggest log groups related to `log_group_name`: same CloudFormation stack,
+/// same service/app tag, or the same service/stack name prefix
+#[tauri::command]
+pub async fn suggest_related_groups(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    limit: Option<usize>,
+) -> Result<Vec<RelatedGroupSuggestion>, String> {
+    let client = state.logs_client().await?;
+    let config = state.sdk_config().await?;
+
+    let all_groups = describe_all_log_groups(&client).await?;
+    let target = all_groups
+        .iter()
+        .find(|g| g.name == log_group_name)
+        .ok_or_else(|| format!("Log group {} not found", log_group_name))?;
+
+    let mut scores: HashMap<String, (u32, Vec<String>)> = HashMap::new();
+    let mut add = |name: &str, score: u32, reason: String| {
+        if name == log_group_name {
+            return;
+        }
+        let entry = scores.entry(name.to_string()).or_default();
+        entry.0 += score;
+        entry.1.push(reason);
+    };
+
+    // Tag-based relations (same stack, same service tag)
+    let tags = match &target.arn {
+        Some(arn) => log_group_tags(&client, arn).await.unwrap_or_else(|e| {
+            log::warn!("Could not read tags for {}: {}", log_group_name, e);
+            BTreeMap::new()
+        }),
+        None => BTreeMap::new(),
+    };
+    if let Some(stack) = tags.get(CLOUDFORMATION_STACK_TAG) {
+        for group in tagged_log_groups(&config, CLOUDFORMATION_STACK_TAG, Some(stack)).await? {
+            add(
+                &group.name,
+                100,
+                format!("Same CloudFormation stack ({})", stack),
+            );
+        }
+    }
+    for key in SERVICE_TAG_KEYS {
+        if let Some(value) = tags.get(key) {
+            for group in tagged_log_groups(&config, key, Some(value)).await? {
+                add(&group.name, 50, format!("Same {} tag ({})", key, value));
+            }
+        }
+    }
+
+    // Naming conventions: groups sharing at least the first two name tokens
+    let target_tokens = name_tokens(&log_group_name);
+    for group in &all_groups {
+        let shared = shared_prefix_len(&target_tokens, &name_tokens(&group.name));
+        if shared >= 2 || (shared == 1 && target_tokens.len() == 1) {
+            let prefix = target_tokens[..shared].join("-");
+            add(
+                &group.name,
+                10 * shared as u32,
+                format!("Same name prefix ({})", prefix),
+            );
+        }
+    }
+
+    let mut suggestions: Vec<RelatedGroupSuggestion> = scores
+        .into_iter()
+        .map(|(name, (score, reasons))| RelatedGroupSuggestion {
+            name,
+            score,
+            reasons,
+        })
+        .collect();
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    suggestions.truncate(limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT));
+    Ok(suggestions)
+}
+
+/// Log groups belonging to one CloudFormation stack
+#[derive(Debug, Clone, Serialize)]
+pub struct StackLogGroups {
+    pub stack_name: String,
+    pub log_groups: Vec<String>,
+}
+
+/// All log groups grouped by the CloudFormation stack that created them
+#[derive(Debug, Clone, Serialize)]
+pub struct StackGroupedListing {
+    pub stacks: Vec<StackLogGroups>,
+    /// Log groups not created by CloudFormation (or whose tags aren't visible)
+    pub ungrouped: Vec<String>,
+}
+
+/// List log groups grouped by their `aws:cloudformation:stack-name` tag
+#[tauri::command]
+pub async fn list_log_groups_by_stack(
+    state: State<'_, AppState>,
+) -> Result<StackGroupedListing, String> {
+    let client = state.logs_client().await?;
+    let config = state.sdk_config().await?;
+
+    let tagged = tagged_log_groups(&config, CLOUDFORMATION_STACK_TAG, None).await?;
+    let mut stacks: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for group in tagged {
+        if let Some(stack) = group.tags.get(CLOUDFORMATION_STACK_TAG) {
+            stacks.entry(stack.clone()).or_default().push(group.name);
+        }
+    }
+
+    let mut ungrouped: Vec<String> = describe_all_log_groups(&client)
+        .await?
+        .into_iter()
+        .map(|g| g.name)
+        .filter(|name| !stacks.values().any(|groups| groups.contains(name)))
+        .collect();
+    ungrouped.sort();
+
+    Ok(StackGroupedListing {
+        stacks: stacks
+            .into_iter()
+            .map(|(stack_name, mut log_groups)| {
+                log_groups.sort();
+                StackLogGroups {
+                    stack_name,
+                    log_groups,
+                }
+            })
+            .collect(),
+        ungrouped,
+    })
+}
+
+/// Every log group belonging to a single CloudFormation stack, for opening them together
+#[tauri::command]
+pub async fn list_stack_log_groups(
+    state: State<'_, AppState>,
+    stack_name: String,
+) -> Result<Vec<String>, String> {
+    let config = state.sdk_config().await?;
+    let mut names: Vec<String> =
+        tagged_log_groups(&config, CLOUDFORMATION_STACK_TAG, Some(&stack_name))
+            .await?
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Tags CloudFormation adds alongside the stack name
+const CLOUDFORMATION_LOGICAL_ID_TAG: &str = "aws:cloudformation:logical-id";
+const CLOUDFORMATION_STACK_ID_TAG: &str = "aws:cloudformation:stack-id";
+
+/// Log group name prefixes AWS services create on first write, and the service
+const AUTO_CREATED_PREFIXES: [(&str, &str); 10] = [
+    ("/aws/lambda/", "Lambda"),
+    ("/aws/codebuild/", "CodeBuild"),
+    ("/aws/ecs/containerinsights/", "Container Insights"),
+    ("/aws/containerinsights/", "Container Insights"),
+    ("/aws/rds/", "RDS"),
+    ("/aws/apigateway/", "API Gateway"),
+    ("API-Gateway-Execution-Logs_", "API Gateway"),
+    ("/aws/vendedlogs/", "Vended logs delivery"),
+    ("/aws/sagemaker/", "SageMaker"),
+    ("/aws/glue/", "Glue"),
+];
+
+/// Which infrastructure-as-code (if any) created a log group
+#[derive(Debug, Clone, Serialize)]
+pub struct LogGroupOrigin {
+    pub log_group_name: String,
+    pub arn: Option<String>,
+    pub creation_time: Option<i64>,
+    /// "cdk", "cloudformation", "terraform", "auto-created" or "unknown"
+    pub managed_by: String,
+    pub stack_name: Option<String>,
+    pub stack_id: Option<String>,
+    pub logical_id: Option<String>,
+    /// CDK construct path (aws:cdk:path resource metadata)
+    pub cdk_path: Option<String>,
+    /// Service that auto-creates groups with this name, when no IaC owns it
+    pub auto_created_by: Option<String>,
+    /// How the conclusion was reached, one line per clue
+    pub evidence: Vec<String>,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Tag clues that Terraform manages a resource (it adds none by default, but
+/// default_tags conventions commonly mark it)
+fn terraform_tag(tags: &BTreeMap<String, String>) -> Option<String> {
+    tags.iter()
+        .find(|(key, value)| {
+            let key = key.to_lowercase();
+            key.contains("terraform")
+                || key.starts_with("tf_")
+                || key.starts_with("tf:")
+                || ([
+                    "managedby",
+                    "managed_by",
+                    "managed-by",
+                    "provisioner",
+                    "iac",
+                ]
+                .contains(&key.as_str())
+                    && value.to_lowercase().contains("terraform"))
+        })
+        .map(|(key, value)| format!("{}={}", key, value))
+}
+
+/// Trace a log group back to the CloudFormation stack / CDK construct or Terraform
+/// configuration that created it, or report that a service auto-created it
+#[tauri::command]
+pub async fn describe_log_group_origin(
+    state: State<'_, AppState>,
+    log_group_name: String,
+) -> Result<LogGroupOrigin, String> {
+    let client = state.logs_client().await?;
+    let config = state.sdk_config().await?;
+
+    let group = client
+        .describe_log_groups()
+        .log_group_name_prefix(&log_group_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+        .log_groups
+        .unwrap_or_default()
+        .into_iter()
+        .find(|g| g.log_group_name() == Some(log_group_name.as_str()))
+        .ok_or_else(|| format!("Log group {} not found", log_group_name))?;
+
+    let tags = match group.arn() {
+        Some(arn) => log_group_tags(&client, arn).await?,
+        None => BTreeMap::new(),
+    };
+    let mut origin = LogGroupOrigin {
+        log_group_name: log_group_name.clone(),
+        arn: group.arn().map(str::to_string),
+        creation_time: group.creation_time(),
+        managed_by: "unknown".to_string(),
+        stack_name: tags.get(CLOUDFORMATION_STACK_TAG).cloned(),
+        stack_id: tags.get(CLOUDFORMATION_STACK_ID_TAG).cloned(),
+        logical_id: tags.get(CLOUDFORMATION_LOGICAL_ID_TAG).cloned(),
+        cdk_path: None,
+        auto_created_by: None,
+        evidence: Vec::new(),
+        tags,
+    };
+    if let Some(stack) = &origin.stack_name {
+        origin
+            .evidence
+            .push(format!("Tagged {}={}", CLOUDFORMATION_STACK_TAG, stack));
+    }
+
+    // Resource metadata holds the CDK construct path; the physical id lookup also
+    // finds stacks whose tags didn't propagate to the group
+    let cloudformation = aws_sdk_cloudformation::Client::new(&config);
+    let resource = match (&origin.stack_name, &origin.logical_id) {
+        (Some(stack), Some(logical_id)) => cloudformation
+            .describe_stack_resource()
+            .stack_name(stack)
+            .logical_resource_id(logical_id)
+            .send()
+            .await
+            .map(|r| {
+                r.stack_resource_detail
+                    .map(|d| (d.metadata, d.stack_name, d.stack_id, d.logical_resource_id))
+            })
+            .map_err(|e| format!("{:?}", e)),
+        _ => cloudformation
+            .describe_stack_resources()
+            .physical_resource_id(&log_group_name)
+            .send()
+            .await
+            .map(|r| {
+                r.stack_resources
+                    .unwrap_or_default()
+                    .into_iter()
+                    .next()
+                    .map(|d| (None, d.stack_name, d.stack_id, d.logical_resource_id))
+            })
+            .map_err(|e| format!("{:?}", e)),
+    };
+    match resource {
+        Ok(Some((metadata, stack_name, stack_id, logical_id))) => {
+            if origin.stack_name.is_none() {
+                origin.evidence.push(format!(
+                    "CloudFormation stack {} lists it as a resource",
+                    stack_name.as_deref().unwrap_or_default()
+                ));
+            }
+            origin.stack_name = stack_name.or(origin.stack_name);
+            origin.stack_id = stack_id.or(origin.stack_id);
+            origin.logical_id = logical_id.or(origin.logical_id);
+            origin.cdk_path = metadata
+                .and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok())
+                .and_then(|m| m["aws:cdk:path"].as_str().map(str::to_string));
+            if let Some(path) = &origin.cdk_path {
+                origin.evidence.push(format!("CDK construct path {}", path));
+            }
+        }
+        Ok(None) => {}
+        // Stacks may be in another account or the caller may lack CloudFormation read
+        Err(e) => log::debug!("CloudFormation lookup for {} failed: {}", log_group_name, e),
+    }
+
+    let terraform = terraform_tag(&origin.tags);
+    if let Some(tag) = &terraform {
+        origin.evidence.push(format!("Tagged {}", tag));
+    }
+    origin.managed_by = if origin.cdk_path.is_some() {
+        "cdk"
+    } else if origin.stack_name.is_some() {
+        "cloudformation"
+    } else if terraform.is_some() {
+        "terraform"
+    } else if let Some((prefix, service)) = AUTO_CREATED_PREFIXES
+        .iter()
+        .find(|(prefix, _)| log_group_name.starts_with(prefix))
+    {
+        origin.auto_created_by = Some(service.to_string());
+        origin.evidence.push(format!(
+            "No IaC tags; {} creates {}* groups on first write",
+            service, prefix
+        ));
+        "auto-created"
+    } else {
+        "unknown"
+    }
+    .to_string();
+    Ok(origin)
+}
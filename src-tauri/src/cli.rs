@@ -0,0 +1,169 @@
+use crate::AppState;
+use serde::Serialize;
+use tauri::State;
+
+/// AWS CLI invocations reproducing a view
+#[derive(Debug, Clone, Serialize)]
+pub struct CliReproduction {
+    /// One command per log group for filter-log-events; a single start-query otherwise
+    pub commands: Vec<String>,
+    /// For Logs Insights: the command fetching results once the query has run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow_up: Option<String>,
+}
+
+/// Quote an argument for a POSIX shell
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/_-.:=@,+".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+fn connection_args(profile: Option<&str>, region: Option<&str>) -> String {
+    let mut args = String::new();
+    if let Some(profile) = profile {
+        args.push_str(&format!(" --profile {}", shell_quote(profile)));
+    }
+    if let Some(region) = region {
+        args.push_str(&format!(" --region {}", shell_quote(region)));
+    }
+    args
+}
+
+/// One log group of the view with its time range and filter
+struct ViewSource {
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+}
+
+fn filter_log_events_command(
+    source: &ViewSource,
+    log_stream_names: &[String],
+    connection: &str,
+) -> String {
+    let mut command = format!(
+        "aws logs filter-log-events --log-group-name {}",
+        shell_quote(&source.log_group_name)
+    );
+    if !log_stream_names.is_empty() {
+        command.push_str(" --log-stream-names");
+        for stream in log_stream_names {
+            command.push(' ');
+            command.push_str(&shell_quote(stream));
+        }
+    }
+    // filter-log-events takes epoch milliseconds
+    if let Some(start) = source.start_time {
+        command.push_str(&format!(" --start-time {}", start));
+    }
+    if let Some(end) = source.end_time {
+        command.push_str(&format!(" --end-time {}", end));
+    }
+    if let Some(pattern) = source.filter_pattern.as_deref().filter(|p| !p.is_empty()) {
+        command.push_str(&format!(" --filter-pattern {}", shell_quote(pattern)));
+    }
+    command.push_str(connection);
+    command
+}
+
+/// Produce the `aws logs filter-log-events` (or, with `query`, Logs Insights
+/// `start-query`) invocation equivalent to the current view, using the current
+/// profile and region. With `session_id`, log groups, filters and time ranges not
+/// given explicitly are taken from the session's fetches.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_cli_command(
+    state: State<'_, AppState>,
+    log_group_names: Option<Vec<String>>,
+    log_stream_names: Option<Vec<String>>,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+    query: Option<String>,
+    session_id: Option<String>,
+) -> Result<CliReproduction, String> {
+    let profile = state.current_profile.lock().await.clone();
+    let region = match state.sdk_config().await {
+        Ok(config) => config.region().map(|r| r.to_string()),
+        Err(_) => None,
+    };
+    let connection = connection_args(profile.as_deref(), region.as_deref());
+
+    let mut views: Vec<ViewSource> = Vec::new();
+    if let Some(names) = log_group_names.filter(|n| !n.is_empty()) {
+        for log_group_name in names {
+            views.push(ViewSource {
+                log_group_name,
+                start_time,
+                end_time,
+                filter_pattern: filter_pattern.clone(),
+            });
+        }
+    } else if let Some(session_id) = &session_id {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let mut fetches: Vec<_> = session.fetches.iter().collect();
+        fetches.sort_by(|a, b| a.0.cmp(b.0));
+        for (source, context) in fetches {
+            views.push(ViewSource {
+                log_group_name: source.clone(),
+                start_time: start_time.or(context.start_time),
+                end_time: end_time.or(context.end_time),
+                filter_pattern: filter_pattern
+                    .clone()
+                    .or_else(|| context.filter_pattern.clone()),
+            });
+        }
+    }
+    if views.is_empty() {
+        return Err("No log group to build a command for".to_string());
+    }
+
+    if let Some(query) = query.filter(|q| !q.trim().is_empty()) {
+        // start-query takes epoch seconds and requires both ends of the range
+        let now = chrono::Utc::now().timestamp_millis();
+        let start = views
+            .iter()
+            .filter_map(|v| v.start_time)
+            .min()
+            .unwrap_or(now - 3_600_000);
+        let end = views.iter().filter_map(|v| v.end_time).max().unwrap_or(now);
+        let mut command = "aws logs start-query --log-group-names".to_string();
+        for view in &views {
+            command.push(' ');
+            command.push_str(&shell_quote(&view.log_group_name));
+        }
+        command.push_str(&format!(
+            " --start-time {} --end-time {} --query-string {}{}",
+            start / 1000,
+            end / 1000,
+            shell_quote(&query),
+            connection
+        ));
+        return Ok(CliReproduction {
+            commands: vec![command],
+            follow_up: Some(format!(
+                "aws logs get-query-results --query-id <queryId>{}",
+                connection
+            )),
+        });
+    }
+
+    let streams = log_stream_names.unwrap_or_default();
+    Ok(CliReproduction {
+        commands: views
+            .iter()
+            .map(|view| filter_log_events_command(view, &streams, &connection))
+            .collect(),
+        follow_up: None,
+    })
+}
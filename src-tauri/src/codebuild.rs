@@ -0,0 +1,371 @@
+use crate::{
+    columns, filter_stream_events, humanize_aws_error, preview, redaction, session, AppState,
+    LogEvent,
+};
+use aws_sdk_cloudwatchlogs::types::OrderBy;
+use aws_sdk_codebuild::types::{Build, SortOrderType};
+use aws_sdk_codebuild::Client as CodeBuildClient;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+use tauri::{AppHandle, State};
+
+/// Log group prefix CodeBuild writes project logs under by default
+const CODEBUILD_GROUP_PREFIX: &str = "/aws/codebuild/";
+
+/// Cap on events fetched for one build
+const DEFAULT_BUILD_MAX_EVENTS: usize = 50_000;
+
+/// Events returned with the build log; the rest are read with get_session_window
+const DEFAULT_BUILD_WINDOW: usize = 500;
+
+/// Builds listed per project when no limit is given
+const DEFAULT_BUILD_LIMIT: usize = 50;
+
+/// Build ids scanned when looking up a build number
+const MAX_BUILD_IDS_SCANNED: usize = 1_000;
+
+/// BatchGetBuilds accepts at most this many ids
+const BATCH_GET_BUILDS_MAX: usize = 100;
+
+/// A CodeBuild build whose log stream is in CloudWatch
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildStream {
+    pub project: String,
+    /// "project:uuid"
+    pub build_id: String,
+    /// Only known when the build was looked up through the CodeBuild API
+    pub build_number: Option<i64>,
+    pub log_group_name: String,
+    pub log_stream_name: String,
+    pub first_event_at: Option<i64>,
+    pub last_event_at: Option<i64>,
+}
+
+/// One buildspec phase, from the `[Container]` lines of the build log
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildPhase {
+    pub name: String,
+    pub started_at: i64,
+    /// "SUCCEEDED", "FAILED", ... (None while running or when the log ends early)
+    pub state: Option<String>,
+    pub completed_at: Option<i64>,
+}
+
+/// Everything one build logged
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildLog {
+    pub build: BuildStream,
+    /// The first window of the session, oldest first
+    pub events: Vec<LogEvent>,
+    pub event_count: usize,
+    pub truncated: bool,
+    pub phases: Vec<BuildPhase>,
+    /// First phase that didn't succeed
+    pub failed_phase: Option<String>,
+}
+
+fn phase_regexes() -> &'static (Regex, Regex) {
+    static RE: OnceLock<(Regex, Regex)> = OnceLock::new();
+    // "[Container] 2024/01/15 10:00:00.123 Entering phase BUILD"
+    // "[Container] 2024/01/15 10:02:00.456 Phase complete: BUILD State: FAILED"
+    RE.get_or_init(|| {
+        (
+            Regex::new(r"^\[Container\] .*Entering phase (\w+)").expect("valid regex"),
+            Regex::new(r"^\[Container\] .*Phase complete: (\w+) State: (\w+)")
+                .expect("valid regex"),
+        )
+    })
+}
+
+/// Buildspec phases of a build log, in order
+pub fn parse_phases(events: &[LogEvent]) -> Vec<BuildPhase> {
+    let (entering, complete) = phase_regexes();
+    let mut phases: Vec<BuildPhase> = Vec::new();
+    for event in events {
+        if let Some(captures) = entering.captures(&event.message) {
+            phases.push(BuildPhase {
+                name: captures[1].to_string(),
+                started_at: event.timestamp,
+                state: None,
+                completed_at: None,
+            });
+        } else if let Some(captures) = complete.captures(&event.message) {
+            if let Some(phase) = phases
+                .iter_mut()
+                .rev()
+                .find(|p| p.name == captures[1] && p.state.is_none())
+            {
+                phase.state = Some(captures[2].to_string());
+                phase.completed_at = Some(event.timestamp);
+            }
+        }
+    }
+    phases
+}
+
+/// Project and build uuid of "project:uuid" or a build ARN
+/// ("arn:aws:codebuild:region:account:build/project:uuid")
+fn split_build_id(build: &str) -> Option<(&str, &str)> {
+    let id = build.rsplit_once(":build/").map_or(build, |(_, id)| id);
+    id.split_once(':')
+        .filter(|(project, uuid)| !project.is_empty() && !uuid.is_empty())
+}
+
+/// Find build `number` of a project through the CodeBuild API, newest builds first
+async fn lookup_build_number(
+    state: &AppState,
+    project: &str,
+    number: i64,
+) -> Result<Build, String> {
+    let client = CodeBuildClient::new(&state.sdk_config().await?);
+    let mut ids: Vec<String> = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .list_builds_for_project()
+            .project_name(project)
+            .sort_order(SortOrderType::Descending)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        ids.extend(response.ids.unwrap_or_default());
+        next_token = response.next_token;
+        if next_token.is_none() || ids.len() >= MAX_BUILD_IDS_SCANNED {
+            break;
+        }
+    }
+    ids.truncate(MAX_BUILD_IDS_SCANNED);
+
+    for chunk in ids.chunks(BATCH_GET_BUILDS_MAX) {
+        let found = client
+            .batch_get_builds()
+            .set_ids(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+            .builds
+            .unwrap_or_default();
+        // Ids are newest first, so once a chunk reaches below the number it's gone
+        let passed = found
+            .iter()
+            .any(|b| b.build_number.is_some_and(|n| n < number));
+        if let Some(build) = found.into_iter().find(|b| b.build_number == Some(number)) {
+            return Ok(build);
+        }
+        if passed {
+            break;
+        }
+    }
+    Err(format!(
+        "Build #{} of {} not found in its last {} builds",
+        number, project, MAX_BUILD_IDS_SCANNED
+    ))
+}
+
+/// The log group and stream of a build given as a build number ("1482" or
+/// "#1482"), a build id ("project:uuid"), a build ARN or a bare uuid. Numbers are
+/// resolved through the CodeBuild API (which also knows custom log locations);
+/// ids follow CodeBuild's convention of one stream per build named by its uuid in
+/// `/aws/codebuild/<project>`, or in `log_group_name` when given.
+async fn resolve_build(
+    state: &AppState,
+    project: &str,
+    build: &str,
+    log_group_name: Option<String>,
+) -> Result<BuildStream, String> {
+    let build = build.trim();
+    let default_group = || {
+        log_group_name
+            .clone()
+            .unwrap_or_else(|| format!("{}{}", CODEBUILD_GROUP_PREFIX, project))
+    };
+    if let Ok(number) = build.trim_start_matches('#').parse::<i64>() {
+        let found = lookup_build_number(state, project, number).await?;
+        let build_id = found.id.unwrap_or_default();
+        let logs = found.logs;
+        let uuid = split_build_id(&build_id).map(|(_, uuid)| uuid.to_string());
+        return Ok(BuildStream {
+            project: project.to_string(),
+            build_number: found.build_number,
+            log_group_name: logs
+                .as_ref()
+                .and_then(|l| l.group_name.clone())
+                .unwrap_or_else(default_group),
+            log_stream_name: logs
+                .and_then(|l| l.stream_name)
+                .or(uuid)
+                .ok_or_else(|| format!("Build #{} has no CloudWatch log stream", number))?,
+            build_id,
+            first_event_at: None,
+            last_event_at: None,
+        });
+    }
+    let uuid = match split_build_id(build) {
+        Some((id_project, _)) if id_project != project => {
+            return Err(format!(
+                "Build {} belongs to project {}, not {}",
+                build, id_project, project
+            ))
+        }
+        Some((_, uuid)) => uuid,
+        None => build,
+    };
+    Ok(BuildStream {
+        project: project.to_string(),
+        build_id: format!("{}:{}", project, uuid),
+        build_number: None,
+        log_group_name: default_group(),
+        log_stream_name: uuid.to_string(),
+        first_event_at: None,
+        last_event_at: None,
+    })
+}
+
+/// CodeBuild projects with logs in CloudWatch, from the `/aws/codebuild/` groups
+#[tauri::command]
+pub async fn list_codebuild_projects(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let client = state.logs_client().await?;
+    let mut projects = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_log_groups()
+            .log_group_name_prefix(CODEBUILD_GROUP_PREFIX)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        projects.extend(response.log_groups().iter().filter_map(|g| {
+            g.log_group_name()?
+                .strip_prefix(CODEBUILD_GROUP_PREFIX)
+                .map(str::to_string)
+        }));
+        next_token = response.next_token;
+        if next_token.is_none() {
+            return Ok(projects);
+        }
+    }
+}
+
+/// A project's builds with logs in CloudWatch (one stream per build), most recent
+/// first. Build numbers aren't in stream names; fetch_codebuild_log resolves them.
+#[tauri::command]
+pub async fn list_codebuild_builds(
+    state: State<'_, AppState>,
+    project: String,
+    log_group_name: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<BuildStream>, String> {
+    let client = state.logs_client().await?;
+    let log_group_name =
+        log_group_name.unwrap_or_else(|| format!("{}{}", CODEBUILD_GROUP_PREFIX, project));
+    let limit = limit.unwrap_or(DEFAULT_BUILD_LIMIT);
+    let mut builds = Vec::new();
+    let mut next_token: Option<String> = None;
+    while builds.len() < limit {
+        let response = client
+            .describe_log_streams()
+            .log_group_name(&log_group_name)
+            .order_by(OrderBy::LastEventTime)
+            .descending(true)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        builds.extend(response.log_streams().iter().filter_map(|stream| {
+            let name = stream.log_stream_name()?;
+            Some(BuildStream {
+                project: project.clone(),
+                build_id: format!("{}:{}", project, name),
+                build_number: None,
+                log_group_name: log_group_name.clone(),
+                log_stream_name: name.to_string(),
+                first_event_at: stream.first_event_timestamp(),
+                last_event_at: stream.last_event_timestamp(),
+            })
+        }));
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+    builds.truncate(limit);
+    Ok(builds)
+}
+
+/// Fetch the whole log of one build into a result session, resolving its stream
+/// from a build number, build id, ARN or uuid, with the buildspec phases parsed
+/// from the `[Container]` lines
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_codebuild_log(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    project: String,
+    build: String,
+    log_group_name: Option<String>,
+    max_count: Option<usize>,
+) -> Result<BuildLog, String> {
+    let mut build = resolve_build(&state, &project, &build, log_group_name).await?;
+    let client = state.logs_client().await?;
+    let (mut events, truncated) = filter_stream_events(
+        &client,
+        &build.log_group_name,
+        std::slice::from_ref(&build.log_stream_name),
+        None,
+        None,
+        None,
+        max_count.unwrap_or(DEFAULT_BUILD_MAX_EVENTS),
+    )
+    .await?;
+    if events.is_empty() {
+        return Err(format!(
+            "No log events for build {} in {} (the stream may have expired)",
+            build.build_id, build.log_group_name
+        ));
+    }
+    events.sort_by_key(|e| e.timestamp);
+    build.first_event_at = events.first().map(|e| e.timestamp);
+    build.last_event_at = events.last().map(|e| e.timestamp);
+    let phases = parse_phases(&events);
+    let failed_phase = phases
+        .iter()
+        .find(|p| p.state.as_deref().is_some_and(|s| s != "SUCCEEDED"))
+        .map(|p| p.name.clone());
+
+    session::tag_source(&mut events, &build.log_group_name);
+    let event_count = events.len();
+    let mut window = {
+        let mut sessions = state.sessions.lock().await;
+        let result = sessions.get_or_create(&session_id);
+        result.clear();
+        result.fetches.clear();
+        result.replace_source(&build.log_group_name, events);
+        result.fetches.insert(
+            build.log_group_name.clone(),
+            session::FetchContext {
+                start_time: build.first_event_at,
+                end_time: build.last_event_at,
+                filter_pattern: None,
+                truncated,
+                complete_ranges: Vec::new(),
+            },
+        );
+        result.window(0, DEFAULT_BUILD_WINDOW)
+    };
+
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(BuildLog {
+        build,
+        events: window,
+        event_count,
+        truncated,
+        phases,
+        failed_phase,
+    })
+}
@@ -0,0 +1,291 @@
+use crate::pipeline::{self, PipelineStage, ProcessedEvent};
+use crate::{storage, LogEvent};
+use rhai::{Dynamic, Engine, Scope, AST, FLOAT, INT};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+/// File in the app config directory holding computed column definitions
+const COLUMNS_FILE: &str = "computed-columns.json";
+
+/// Serializes read-modify-write of the columns file
+static COLUMNS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Operations one evaluation may take; expressions are meant to be tiny
+const MAX_OPERATIONS: u64 = 10_000;
+
+/// A column whose value is an expression over an event's extracted fields, e.g.
+/// `duration_ms > 1000 ? "SLOW" : ""`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComputedColumn {
+    pub name: String,
+    /// Rhai expression; `cond ? a : b` is accepted as shorthand for `if cond { a } else { b }`
+    pub expression: String,
+    /// Only computed for log groups starting with this prefix (all when None)
+    #[serde(default)]
+    pub log_group_prefix: Option<String>,
+}
+
+fn load_columns(app: &AppHandle) -> Vec<ComputedColumn> {
+    storage::read_json(app, COLUMNS_FILE)
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_expr_depths(32, 16);
+    engine.set_max_string_size(64 * 1024);
+    engine.disable_symbol("eval");
+    engine
+}
+
+/// Offset of the first occurrence of `target` at nesting depth 0 outside string
+/// literals, skipping Rhai's `?.`, `?[`, `??` and `::` operators
+fn top_level(expression: &str, target: char) -> Option<usize> {
+    let chars: Vec<(usize, char)> = expression.char_indices().collect();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, n)| *n);
+        if let Some(q) = quote {
+            if c == '\\' {
+                i += 1;
+            } else if c == q {
+                quote = None;
+            }
+        } else {
+            match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                '?' if matches!(next, Some('.') | Some('[') | Some('?')) => i += 1,
+                ':' if next == Some(':') => i += 1,
+                _ if c == target && depth == 0 => return Some(offset),
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Rewrite `cond ? a : b` (right-associative) into Rhai's `if cond { a } else { b }`
+fn desugar_ternary(expression: &str) -> String {
+    let Some(question) = top_level(expression, '?') else {
+        return expression.to_string();
+    };
+    let rest = &expression[question + 1..];
+    let Some(colon) = top_level(rest, ':') else {
+        return expression.to_string();
+    };
+    format!(
+        "if {} {{ {} }} else {{ {} }}",
+        expression[..question].trim(),
+        desugar_ternary(rest[..colon].trim()),
+        desugar_ternary(rest[colon + 1..].trim())
+    )
+}
+
+fn compile(engine: &Engine, column: &ComputedColumn) -> Result<AST, String> {
+    engine
+        .compile_expression(desugar_ternary(&column.expression))
+        .map_err(|e| format!("Column {} does not compile: {}", column.name, e))
+}
+
+/// A field value as a Rhai value: numbers and booleans keep their type so they can
+/// be compared
+fn field_value(value: &str) -> Dynamic {
+    if let Ok(int) = value.parse::<INT>() {
+        Dynamic::from(int)
+    } else if let Ok(float) = value.parse::<FLOAT>() {
+        Dynamic::from(float)
+    } else if let Ok(boolean) = value.parse::<bool>() {
+        Dynamic::from(boolean)
+    } else {
+        Dynamic::from(value.to_string())
+    }
+}
+
+fn json_value(value: &serde_json::Value) -> Dynamic {
+    match value {
+        serde_json::Value::Bool(b) => Dynamic::from(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(int) => Dynamic::from(int),
+            None => Dynamic::from(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Dynamic::from(s.clone()),
+        serde_json::Value::Null => Dynamic::UNIT,
+        other => Dynamic::from(other.to_string()),
+    }
+}
+
+/// Variables an expression sees: `message`, `timestamp`, `level`, `log_stream`,
+/// `source`, a `fields` map, and every top-level JSON key and extracted field whose
+/// name is a valid identifier
+fn scope_for<'a>(event: &LogEvent, processed: &ProcessedEvent) -> Scope<'a> {
+    let mut fields = rhai::Map::new();
+    if let Some(serde_json::Value::Object(map)) = &processed.json {
+        for (key, value) in map {
+            fields.insert(key.as_str().into(), json_value(value));
+        }
+    }
+    for (key, value) in &processed.fields {
+        fields.insert(key.as_str().into(), field_value(value));
+    }
+
+    let mut scope = Scope::new();
+    for (key, value) in &fields {
+        let identifier = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if identifier {
+            scope.push_dynamic(key.to_string(), value.clone());
+        }
+    }
+    scope.push("message", processed.message.clone());
+    scope.push("timestamp", event.timestamp as INT);
+    scope.push(
+        "level",
+        processed.level.clone().map_or(Dynamic::UNIT, Dynamic::from),
+    );
+    scope.push(
+        "log_stream",
+        event
+            .log_stream_name
+            .clone()
+            .map_or(Dynamic::UNIT, Dynamic::from),
+    );
+    scope.push(
+        "source",
+        event.source.clone().map_or(Dynamic::UNIT, Dynamic::from),
+    );
+    scope.push("fields", fields);
+    scope
+}
+
+/// Compute the configured columns into each event's `columns`. Fields come from the
+/// event's log group pipeline (without multiline stitching, which would merge events).
+/// An expression failing on an event (e.g. a field it lacks) yields an empty value.
+pub fn apply(app: &AppHandle, events: &mut [LogEvent]) {
+    let columns = load_columns(app);
+    if columns.is_empty() || events.is_empty() {
+        return;
+    }
+    let engine = engine();
+    let compiled: Vec<(&ComputedColumn, AST)> = columns
+        .iter()
+        .filter_map(|column| match compile(&engine, column) {
+            Ok(ast) => Some((column, ast)),
+            Err(e) => {
+                log::warn!("{}", e);
+                None
+            }
+        })
+        .collect();
+
+    // Run each source's pipeline once over all its events
+    let mut by_source: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, event) in events.iter().enumerate() {
+        by_source
+            .entry(event.source.clone().unwrap_or_default())
+            .or_default()
+            .push(i);
+    }
+    for (source, indexes) in by_source {
+        let applicable: Vec<&(&ComputedColumn, AST)> = compiled
+            .iter()
+            .filter(|(c, _)| {
+                c.log_group_prefix
+                    .as_deref()
+                    .is_none_or(|prefix| source.starts_with(prefix))
+            })
+            .collect();
+        if applicable.is_empty() {
+            continue;
+        }
+        let mut processed: Vec<ProcessedEvent> = indexes
+            .iter()
+            .map(|&i| ProcessedEvent::from(&events[i]))
+            .collect();
+        for stage in pipeline::pipeline_for_group(app, &source) {
+            if matches!(stage, PipelineStage::StitchMultiline { .. }) {
+                continue;
+            }
+            match pipeline::apply_stage(&stage, processed.clone()) {
+                Ok(next) => processed = next,
+                Err(e) => log::debug!("Pipeline stage skipped for computed columns: {}", e),
+            }
+        }
+
+        for (&i, processed) in indexes.iter().zip(&processed) {
+            let event = &mut events[i];
+            let mut values = BTreeMap::new();
+            let mut scope = scope_for(event, processed);
+            for (column, ast) in &applicable {
+                let value = engine
+                    .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                    .ok()
+                    .filter(|v| !v.is_unit())
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                values.insert(column.name.clone(), value);
+            }
+            event.columns = Some(values);
+        }
+    }
+}
+
+/// Names of the computed columns present on any of the events, sorted
+pub fn names(events: &[LogEvent]) -> Vec<String> {
+    let names: std::collections::BTreeSet<&String> = events
+        .iter()
+        .filter_map(|e| e.columns.as_ref())
+        .flat_map(|columns| columns.keys())
+        .collect();
+    names.into_iter().cloned().collect()
+}
+
+/// The event's value of a computed column (empty when not computed)
+pub fn value<'a>(event: &'a LogEvent, name: &str) -> &'a str {
+    event
+        .columns
+        .as_ref()
+        .and_then(|columns| columns.get(name))
+        .map_or("", String::as_str)
+}
+
+/// List computed columns
+#[tauri::command]
+pub fn list_computed_columns(app: AppHandle) -> Vec<ComputedColumn> {
+    load_columns(&app)
+}
+
+/// Save a computed column, replacing any existing column with the same name. The
+/// expression is compiled first so syntax errors are reported on save.
+#[tauri::command]
+pub fn save_computed_column(app: AppHandle, column: ComputedColumn) -> Result<(), String> {
+    if column.name.trim().is_empty() {
+        return Err("Column name cannot be empty".to_string());
+    }
+    compile(&engine(), &column)?;
+    let _guard = COLUMNS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut columns = load_columns(&app);
+    match columns.iter_mut().find(|c| c.name == column.name) {
+        Some(existing) => *existing = column,
+        None => columns.push(column),
+    }
+    storage::write_json(&app, COLUMNS_FILE, &columns)
+}
+
+/// Remove a computed column by name
+#[tauri::command]
+pub fn delete_computed_column(app: AppHandle, name: String) -> Result<(), String> {
+    let _guard = COLUMNS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut columns = load_columns(&app);
+    columns.retain(|c| c.name != name);
+    storage::write_json(&app, COLUMNS_FILE, &columns)
+}
@@ -0,0 +1,219 @@
+use crate::{storage, AppState, AwsConnectionInfo};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, State};
+
+/// File in the app config directory holding named connections
+const CONNECTIONS_FILE: &str = "connections.json";
+
+/// Serializes read-modify-write of the connections file
+static CONNECTIONS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Environment of the active connection, mirrored for synchronous event emission
+static CURRENT_ENVIRONMENT: RwLock<Option<String>> = RwLock::new(None);
+
+/// A named AWS profile/region pair, e.g. "EU Prod (read-only)"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    pub name: String,
+    /// AWS profile; None uses the default credential chain
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Region; None uses the profile's region
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Accent color shown while connected ("#rrggbb")
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Refuse mutating operations (retention, deletes, tags, metric filters)
+    #[serde(default)]
+    pub read_only: bool,
+    /// Environment label such as "production" or "staging"; production connections
+    /// require confirmation for mutating commands
+    #[serde(default)]
+    pub environment: Option<String>,
+}
+
+impl Connection {
+    pub fn is_production(&self) -> bool {
+        self.environment
+            .as_deref()
+            .is_some_and(|e| e.eq_ignore_ascii_case("production") || e.eq_ignore_ascii_case("prod"))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Connection name cannot be empty".to_string());
+        }
+        if let Some(profile) = &self.profile {
+            crate::validate_profile_name(profile)?;
+        }
+        if let Some(color) = &self.color {
+            let hex = color.strip_prefix('#').unwrap_or_default();
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("{} is not a #rrggbb color", color));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn load_connections(app: &AppHandle) -> Vec<Connection> {
+    let mut connections: Vec<Connection> = storage::read_json(app, CONNECTIONS_FILE);
+    connections.sort_by(|a, b| a.name.cmp(&b.name));
+    connections
+}
+
+/// Make `connection` the active one (None when connecting by raw profile)
+pub async fn set_active(state: &AppState, connection: Option<Connection>) {
+    *CURRENT_ENVIRONMENT
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) =
+        connection.as_ref().and_then(|c| c.environment.clone());
+    *state.active_connection.lock().await = connection;
+}
+
+/// Fail if the active connection is read-only, or is a production connection and
+/// `confirmation` does not repeat its name; called by every mutating command
+pub async fn ensure_writable(state: &AppState, confirmation: Option<&str>) -> Result<(), String> {
+    match &*state.active_connection.lock().await {
+        Some(connection) if connection.read_only => Err(format!(
+            "Connection {} is read-only; switch to a writable connection to make changes",
+            connection.name
+        )),
+        Some(connection)
+            if connection.is_production() && confirmation != Some(&connection.name) =>
+        {
+            Err(format!(
+                "Connection {} is production; confirm the change by entering the connection name",
+                connection.name
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Emitting with the active connection's `environment` added to the payload, so the
+/// UI can keep a production banner up whatever produced the event
+pub trait EnvironmentEmitter {
+    fn emit_tagged<S: Serialize>(&self, event: &str, payload: S) -> tauri::Result<()>;
+}
+
+impl EnvironmentEmitter for AppHandle {
+    fn emit_tagged<S: Serialize>(&self, event: &str, payload: S) -> tauri::Result<()> {
+        let environment = CURRENT_ENVIRONMENT
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let mut value = serde_json::to_value(payload)?;
+        match &mut value {
+            serde_json::Value::Object(map) => {
+                map.insert("environment".to_string(), environment.into());
+            }
+            serde_json::Value::Null => value = serde_json::json!({ "environment": environment }),
+            // Scalar payloads (theme names, flags, log lines) keep their shape
+            _ => {}
+        }
+        self.emit(event, value)
+    }
+}
+
+/// List named connections, sorted by name
+#[tauri::command]
+pub fn list_connections(app: AppHandle) -> Vec<Connection> {
+    load_connections(&app)
+}
+
+/// Save a named connection, replacing one with the same name (or `previous_name`
+/// when renaming)
+#[tauri::command]
+pub async fn save_connection(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection: Connection,
+    previous_name: Option<String>,
+) -> Result<(), String> {
+    connection.validate()?;
+    {
+        let _guard = CONNECTIONS_FILE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut connections = load_connections(&app);
+        let replaced = previous_name.as_deref().unwrap_or(&connection.name);
+        connections.retain(|c| c.name != replaced && c.name != connection.name);
+        connections.push(connection.clone());
+        storage::write_json(&app, CONNECTIONS_FILE, &connections)?;
+    }
+
+    // Editing the active connection (e.g. toggling read-only) applies immediately;
+    // a changed profile or region takes effect on the next use_connection
+    let mut active = state.active_connection.lock().await;
+    if let Some(current) = active.as_mut() {
+        if Some(current.name.as_str()) == previous_name.as_deref()
+            || current.name == connection.name
+        {
+            current.name = connection.name;
+            current.color = connection.color;
+            current.read_only = connection.read_only;
+            current.environment = connection.environment;
+            *CURRENT_ENVIRONMENT
+                .write()
+                .unwrap_or_else(|e| e.into_inner()) = current.environment.clone();
+        }
+    }
+    Ok(())
+}
+
+/// Delete a named connection
+#[tauri::command]
+pub fn delete_connection(app: AppHandle, name: String) -> Result<(), String> {
+    let _guard = CONNECTIONS_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut connections = load_connections(&app);
+    let before = connections.len();
+    connections.retain(|c| c.name != name);
+    if connections.len() == before {
+        return Err(format!("Connection {} not found", name));
+    }
+    storage::write_json(&app, CONNECTIONS_FILE, &connections)
+}
+
+/// Connect using a named connection's profile and region
+#[tauri::command]
+pub async fn use_connection(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<AwsConnectionInfo, String> {
+    let connection = load_connections(&app)
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| format!("Connection {} not found", name))?;
+    let info = crate::init_aws_client(
+        app,
+        state.clone(),
+        connection.profile.clone(),
+        connection.region.clone(),
+    )
+    .await?;
+    log::info!(
+        "Using connection {}{}",
+        connection.name,
+        if connection.read_only {
+            " (read-only)"
+        } else {
+            ""
+        }
+    );
+    set_active(&state, Some(connection)).await;
+    Ok(info)
+}
+
+/// The named connection in use, or None when connected by raw profile
+#[tauri::command]
+pub async fn get_active_connection(
+    state: State<'_, AppState>,
+) -> Result<Option<Connection>, String> {
+    Ok(state.active_connection.lock().await.clone())
+}
@@ -0,0 +1,315 @@
+use crate::connections::EnvironmentEmitter;
+use crate::i18n::{self, Message};
+use crate::{regions, tail, AppState};
+use aws_config::BehaviorVersion;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::net::{IpAddr, UdpSocket};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+
+/// Idle time after which the AWS client is rebuilt before its next use; pooled
+/// connections and cached endpoints rarely survive hours of inactivity or a sleep
+const IDLE_REVALIDATE_MS: i64 = 15 * 60 * 1000;
+
+/// Epoch milliseconds the AWS client was last handed out (0 before first use)
+static LAST_AWS_USE: AtomicI64 = AtomicI64::new(0);
+
+/// Stable prefix of the error every AWS-calling command returns while offline, so
+/// the frontend can tell it from other failures
+pub const OFFLINE_ERROR_PREFIX: &str = "OFFLINE:";
+
+/// Set while AWS is unreachable: when it went offline (epoch ms) and why
+static OFFLINE: Mutex<Option<(i64, &'static str)>> = Mutex::new(None);
+
+/// Whether the backend is in offline mode
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityStatus {
+    pub offline: bool,
+    /// Epoch milliseconds offline mode began
+    pub since: Option<i64>,
+    /// What triggered offline mode ("wake", "network-change", ...)
+    pub reason: Option<String>,
+}
+
+fn set_offline(reason: Option<&'static str>) {
+    *OFFLINE.lock().unwrap_or_else(|e| e.into_inner()) =
+        reason.map(|r| (chrono::Utc::now().timestamp_millis(), r));
+}
+
+/// Fail fast with the offline error instead of letting an AWS call time out;
+/// called before handing out the client or SDK config
+pub fn ensure_online() -> Result<(), String> {
+    if OFFLINE.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+        return Err(format!(
+            "{} {}",
+            OFFLINE_ERROR_PREFIX,
+            i18n::tr(Message::Offline)
+        ));
+    }
+    Ok(())
+}
+
+/// Whether AWS-calling commands are currently refused. Cached results, imported
+/// files and saved views keep working offline.
+#[tauri::command]
+pub fn get_connectivity_status() -> ConnectivityStatus {
+    let offline = *OFFLINE.lock().unwrap_or_else(|e| e.into_inner());
+    ConnectivityStatus {
+        offline: offline.is_some(),
+        since: offline.map(|(since, _)| since),
+        reason: offline.map(|(_, reason)| reason.to_string()),
+    }
+}
+
+/// Load a fresh SDK config for the connected profile and region, with the
+/// credentials of the assumed role while one is in use
+pub async fn load_config(state: &AppState) -> aws_config::SdkConfig {
+    let config = load_profile_config(state).await;
+    match state.assumed_role.lock().await.as_ref() {
+        Some(role) => role.apply(&config),
+        None => config,
+    }
+}
+
+/// Load a fresh SDK config for the connected profile and region, ignoring any
+/// assumed role
+pub async fn load_profile_config(state: &AppState) -> aws_config::SdkConfig {
+    let profile = state.current_profile.lock().await.clone();
+    let region = state.region_override.lock().await.clone();
+    let mut config_loader = aws_config::defaults(BehaviorVersion::latest());
+    if let Some(p) = profile {
+        config_loader = config_loader.profile_name(p);
+    }
+    if let Some(r) = region {
+        config_loader = config_loader.region(aws_config::Region::new(r));
+    }
+    config_loader.load().await
+}
+
+/// Rebuild the client and config in place when the connection has been idle long
+/// enough to have gone stale, so the command about to run doesn't fail on it.
+/// Called by AppState::logs_client and AppState::sdk_config.
+pub async fn revalidate_if_idle(state: &AppState) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let last = LAST_AWS_USE.swap(now, Ordering::SeqCst);
+    if last == 0 || now - last < IDLE_REVALIDATE_MS {
+        return;
+    }
+    // Holding the client lock makes concurrent callers wait for the new client
+    let mut client = state.client.lock().await;
+    if client.is_none() {
+        return;
+    }
+    let config = load_config(state).await;
+    let signing_region = state.signing_region.lock().await.clone();
+    *client = Some(regions::logs_client(&config, signing_region.as_deref()));
+    *state.config.lock().await = Some(config);
+    log::info!(
+        "Rebuilt AWS client after {} minutes idle",
+        (now - last) / 60_000
+    );
+}
+
+/// How often the watcher samples the default route and the clocks
+const WATCH_INTERVAL_SECS: u64 = 5;
+
+/// Wall-clock time passing beyond the monotonic clock that counts as a sleep
+const WAKE_GAP_MS: i64 = 30_000;
+
+/// Delay between connection probes while AWS is unreachable
+const PROBE_INTERVAL_SECS: u64 = 10;
+
+/// Documentation address (TEST-NET-1) used to find the default route's local
+/// address; connecting a UDP socket sends nothing
+const ROUTE_PROBE_ADDR: &str = "192.0.2.1:9";
+
+/// Tails stopped by a network failure, waiting for the connection to come back
+static DISCONNECTED_TAILS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Wakes the watcher early when a tail lost its connection
+static RECHECK: Notify = Notify::const_new();
+
+/// Payload of the connectivity-changed event
+#[derive(Debug, Clone, Serialize)]
+struct ConnectivityPayload {
+    /// "revalidating", "offline" or "online"
+    status: &'static str,
+    /// "wake", "network-change" or "connection-lost"
+    reason: &'static str,
+    /// Tails paused (revalidating/offline) or resumed (online)
+    tail_ids: Vec<String>,
+}
+
+/// Whether an SDK error means the network (not AWS or the credentials) failed
+pub fn is_network_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    [
+        "dispatchfailure",
+        "dispatch failure",
+        "connectorerror",
+        "dns error",
+        "failed to lookup address",
+        "connection refused",
+        "connection reset",
+        "network is unreachable",
+        "no route to host",
+        "broken pipe",
+        "timeouterror",
+        "timed out",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+/// Called by a tail that stopped on a network error; the watcher resumes it once
+/// the connection is back
+pub fn tail_disconnected(app: &AppHandle, tail_id: &str) {
+    DISCONNECTED_TAILS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(tail_id.to_string());
+    app.emit_tagged(
+        &format!("tail-paused:{}", tail_id),
+        serde_json::json!({ "tail_id": tail_id }),
+    )
+    .ok();
+    RECHECK.notify_one();
+}
+
+/// Ask the watcher to re-validate the connection now (e.g. after the heartbeat
+/// hit a network error)
+pub fn connection_lost() {
+    RECHECK.notify_one();
+}
+
+/// Local address of the default route, None without one
+fn default_route() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect(ROUTE_PROBE_ADDR).ok()?;
+    socket.local_addr().ok().map(|a| a.ip())
+}
+
+/// Rebuild the client and make a cheap call; only network failures count as down
+/// (expired credentials are handled by the credential check)
+async fn probe(state: &AppState) -> Result<(), String> {
+    let config = load_config(state).await;
+    let signing_region = state.signing_region.lock().await.clone();
+    let client = regions::logs_client(&config, signing_region.as_deref());
+    let result = client.describe_log_groups().limit(1).send().await;
+    *state.client.lock().await = Some(client);
+    *state.config.lock().await = Some(config);
+    LAST_AWS_USE.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+    match result {
+        Err(e) if is_network_error(&format!("{:?}", e)) => Err(format!("{}", e)),
+        _ => Ok(()),
+    }
+}
+
+fn emit_connectivity(
+    app: &AppHandle,
+    status: &'static str,
+    reason: &'static str,
+    tail_ids: &[String],
+) {
+    app.emit_tagged(
+        "connectivity-changed",
+        ConnectivityPayload {
+            status,
+            reason,
+            tail_ids: tail_ids.to_vec(),
+        },
+    )
+    .ok();
+}
+
+/// Pause tails, wait until AWS answers again, then resume them with catch-up
+async fn revalidate(app: &AppHandle, state: &AppState, reason: &'static str) {
+    let paused = tail::pause_all(app, state).await;
+    for tail_id in &paused {
+        app.emit_tagged(
+            &format!("tail-paused:{}", tail_id),
+            serde_json::json!({ "tail_id": tail_id }),
+        )
+        .ok();
+    }
+    emit_connectivity(app, "revalidating", reason, &paused);
+    log::info!(
+        "Revalidating AWS connection ({}), {} tails paused",
+        reason,
+        paused.len()
+    );
+
+    let mut offline = false;
+    while let Err(e) = probe(state).await {
+        if !offline {
+            log::warn!("AWS unreachable after {}: {}", reason, e);
+            set_offline(Some(reason));
+            emit_connectivity(app, "offline", reason, &paused);
+            offline = true;
+        }
+        tokio::time::sleep(Duration::from_secs(PROBE_INTERVAL_SECS)).await;
+    }
+    set_offline(None);
+
+    let mut tail_ids: BTreeSet<String> =
+        std::mem::take(&mut *DISCONNECTED_TAILS.lock().unwrap_or_else(|e| e.into_inner()));
+    tail_ids.extend(paused);
+    let mut resumed = Vec::with_capacity(tail_ids.len());
+    for tail_id in tail_ids {
+        match tail::resume(app.clone(), state, &tail_id).await {
+            Ok(summary) => {
+                app.emit_tagged(&format!("tail-resumed:{}", tail_id), summary)
+                    .ok();
+                resumed.push(tail_id);
+            }
+            Err(e) => log::warn!("Failed to resume tail {}: {}", tail_id, e),
+        }
+    }
+    emit_connectivity(app, "online", reason, &resumed);
+    log::info!("AWS connection back, {} tails resumed", resumed.len());
+}
+
+/// Watch for wake-from-sleep and network changes (and tails losing their
+/// connection), re-validating the AWS connection instead of letting every request
+/// and tail fail with connector errors. Emits connectivity-changed; while AWS is
+/// unreachable the backend stays in offline mode (see ensure_online).
+pub async fn run_network_watcher(app: AppHandle) {
+    let mut route = default_route();
+    let mut wall = chrono::Utc::now().timestamp_millis();
+    let mut monotonic = Instant::now();
+    loop {
+        let lost = tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(WATCH_INTERVAL_SECS)) => false,
+            _ = RECHECK.notified() => true,
+        };
+        let now_wall = chrono::Utc::now().timestamp_millis();
+        // The monotonic clock stands still while the machine sleeps
+        let slept = (now_wall - wall) - monotonic.elapsed().as_millis() as i64 > WAKE_GAP_MS;
+        let now_route = default_route();
+        let reason = if slept {
+            "wake"
+        } else if now_route != route {
+            "network-change"
+        } else if lost {
+            "connection-lost"
+        } else {
+            wall = now_wall;
+            monotonic = Instant::now();
+            continue;
+        };
+        route = now_route;
+
+        if let Some(state) = app.try_state::<AppState>() {
+            if state.client.lock().await.is_some() {
+                revalidate(&app, &state, reason).await;
+            }
+        }
+        route = default_route().or(route);
+        wall = chrono::Utc::now().timestamp_millis();
+        monotonic = Instant::now();
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{redaction, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// What kind of EKS/Container Insights log group a log group name refers to
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerLogGroup {
+    pub cluster: Option<String>,
+    /// "application", "dataplane", "host", "performance", "control_plane", or "fluent_bit"
+    pub kind: String,
+    /// Whether events carry a Fluent Bit `kubernetes` metadata block
+    pub has_kubernetes_metadata: bool,
+}
+
+/// Recognize Container Insights and Fluent Bit log group naming conventions
+pub fn classify_log_group(name: &str) -> Option<ContainerLogGroup> {
+    if let Some(rest) = name.strip_prefix("/aws/containerinsights/") {
+        let (cluster, kind) = rest.split_once('/')?;
+        return Some(ContainerLogGroup {
+            cluster: Some(cluster.to_string()),
+            kind: kind.to_string(),
+            has_kubernetes_metadata: matches!(kind, "application" | "dataplane"),
+        });
+    }
+
+    if let Some(rest) = name.strip_prefix("/aws/eks/") {
+        if rest.starts_with("fluentbit-cloudwatch") {
+            return Some(ContainerLogGroup {
+                cluster: None,
+                kind: "fluent_bit".to_string(),
+                has_kubernetes_metadata: true,
+            });
+        }
+        if let Some(cluster) = rest.strip_suffix("/cluster") {
+            return Some(ContainerLogGroup {
+                cluster: Some(cluster.to_string()),
+                kind: "control_plane".to_string(),
+                has_kubernetes_metadata: false,
+            });
+        }
+    }
+
+    None
+}
+
+/// Kubernetes metadata block added by Fluent Bit / Fluentd
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KubernetesMetadata {
+    #[serde(rename(deserialize = "namespace_name"))]
+    pub namespace: Option<String>,
+    #[serde(rename(deserialize = "pod_name"))]
+    pub pod: Option<String>,
+    #[serde(rename(deserialize = "container_name"))]
+    pub container: Option<String>,
+    pub host: Option<String>,
+    pub container_image: Option<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, serde_json::Value>,
+}
+
+/// A container log event split into its workload metadata and application log line
+#[derive(Debug, Clone, Serialize)]
+pub struct KubernetesLogFields {
+    pub kubernetes: KubernetesMetadata,
+    /// The application's own log line (the `log` field), if present
+    pub log: Option<String>,
+    /// "stdout" or "stderr"
+    pub stream: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FluentBitRecord {
+    kubernetes: KubernetesMetadata,
+    log: Option<String>,
+    stream: Option<String>,
+}
+
+/// Parse the kubernetes metadata block out of a Fluent Bit JSON event
+pub fn parse_kubernetes_fields(message: &str) -> Option<KubernetesLogFields> {
+    if !message.trim_start().starts_with('{') {
+        return None;
+    }
+    let record: FluentBitRecord = serde_json::from_str(message).ok()?;
+    Some(KubernetesLogFields {
+        kubernetes: record.kubernetes,
+        log: record.log.map(|l| l.trim_end_matches('\n').to_string()),
+        stream: record.stream,
+    })
+}
+
+/// A facet value and how many events carry it
+#[derive(Debug, Clone, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Namespace/pod/container facets for a set of container log events
+#[derive(Debug, Clone, Serialize)]
+pub struct KubernetesFacets {
+    pub namespaces: Vec<FacetCount>,
+    /// Pods as "namespace/pod"
+    pub pods: Vec<FacetCount>,
+    pub containers: Vec<FacetCount>,
+    /// Events without a kubernetes metadata block
+    pub unmatched: usize,
+}
+
+fn sorted_facets(counts: HashMap<String, usize>) -> Vec<FacetCount> {
+    let mut facets: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount { value, count })
+        .collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    facets
+}
+
+fn compute_facets(events: &[LogEvent]) -> KubernetesFacets {
+    let mut namespaces: HashMap<String, usize> = HashMap::new();
+    let mut pods: HashMap<String, usize> = HashMap::new();
+    let mut containers: HashMap<String, usize> = HashMap::new();
+    let mut unmatched = 0;
+
+    for event in events {
+        let Some(fields) = parse_kubernetes_fields(&event.message) else {
+            unmatched += 1;
+            continue;
+        };
+        let k8s = fields.kubernetes;
+        let namespace = k8s.namespace.unwrap_or_default();
+        if let Some(pod) = k8s.pod {
+            *pods.entry(format!("{}/{}", namespace, pod)).or_default() += 1;
+        }
+        if let Some(container) = k8s.container {
+            *containers.entry(container).or_default() += 1;
+        }
+        *namespaces.entry(namespace).or_default() += 1;
+    }
+
+    KubernetesFacets {
+        namespaces: sorted_facets(namespaces),
+        pods: sorted_facets(pods),
+        containers: sorted_facets(containers),
+        unmatched,
+    }
+}
+
+/// Build a CloudWatch JSON filter pattern selecting a workload, so the filter can be
+/// pushed down into fetch_logs / live tail instead of filtering client-side
+pub fn workload_filter_pattern(
+    namespace: Option<&str>,
+    pod: Option<&str>,
+    container: Option<&str>,
+) -> Option<String> {
+    let clauses: Vec<String> = [
+        ("namespace_name", namespace),
+        ("pod_name", pod),
+        ("container_name", container),
+    ]
+    .iter()
+    .filter_map(|(field, value)| {
+        value
+            .filter(|v| !v.is_empty())
+            .map(|v| format!("$.kubernetes.{} = \"{}\"", field, v.replace('"', "\\\"")))
+    })
+    .collect();
+
+    if clauses.is_empty() {
+        None
+    } else {
+        Some(format!("{{ {} }}", clauses.join(" && ")))
+    }
+}
+
+/// Detect whether a log group follows Container Insights / Fluent Bit conventions
+#[tauri::command]
+pub fn detect_container_log_group(log_group_name: String) -> Option<ContainerLogGroup> {
+    classify_log_group(&log_group_name)
+}
+
+/// Parse the kubernetes metadata of a single event message into fields
+#[tauri::command]
+pub fn parse_kubernetes_event(message: String) -> Option<KubernetesLogFields> {
+    parse_kubernetes_fields(&message)
+}
+
+/// Namespace/pod/container facet counts for the events in a session
+#[tauri::command]
+pub async fn get_kubernetes_facets(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<KubernetesFacets, String> {
+    let sessions = state.sessions.lock().await;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    Ok(compute_facets(&session.events()))
+}
+
+/// Events in a session belonging to a workload (any combination of namespace/pod/container)
+#[tauri::command]
+pub async fn filter_session_by_workload(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    namespace: Option<String>,
+    pod: Option<String>,
+    container: Option<String>,
+) -> Result<Vec<LogEvent>, String> {
+    let events = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?
+        .display_events();
+
+    let matches = |expected: &Option<String>, actual: &Option<String>| {
+        expected.is_none() || expected == actual
+    };
+    let mut matched: Vec<LogEvent> = events
+        .into_iter()
+        .filter(|e| {
+            parse_kubernetes_fields(&e.message).is_some_and(|f| {
+                matches(&namespace, &f.kubernetes.namespace)
+                    && matches(&pod, &f.kubernetes.pod)
+                    && matches(&container, &f.kubernetes.container)
+            })
+        })
+        .collect();
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut matched).await;
+    Ok(matched)
+}
+
+/// CloudWatch filter pattern for a workload, for server-side filtering
+#[tauri::command]
+pub fn build_workload_filter_pattern(
+    namespace: Option<String>,
+    pod: Option<String>,
+    container: Option<String>,
+) -> Option<String> {
+    workload_filter_pattern(namespace.as_deref(), pod.as_deref(), container.as_deref())
+}
@@ -0,0 +1,151 @@
+use crate::{diff, redaction, AppState, LogEvent};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+use tauri::{AppHandle, State};
+
+/// JSON keys that carry a request or correlation id, most specific first.
+/// Matched case-insensitively with `-` and `_` ignored.
+const CORRELATION_KEYS: [&str; 7] = [
+    "correlationid",
+    "requestid",
+    "awsrequestid",
+    "xrequestid",
+    "traceid",
+    "xamzntraceid",
+    "transactionid",
+];
+
+fn lambda_request_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "START RequestId: <id>" lines, and "<time>\t<id>\t<LEVEL>\t..." lines
+    RE.get_or_init(|| {
+        Regex::new(r"^(?:(?:START|END|REPORT) RequestId: ([\w-]+)|\S+\t([0-9a-fA-F-]{36})\t)")
+            .expect("valid regex")
+    })
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Find `keys` (normalized) in a JSON object, top level first, then one level down
+fn find_key(json: &serde_json::Value, keys: &[String]) -> Option<(String, String)> {
+    let map = json.as_object()?;
+    for key in keys {
+        if let Some((name, value)) = map
+            .iter()
+            .find(|(name, value)| normalize_key(name) == *key && scalar(value).is_some())
+        {
+            return scalar(value).map(|v| (name.clone(), v));
+        }
+    }
+    map.values()
+        .filter(|v| v.is_object())
+        .find_map(|nested| find_key(nested, keys))
+}
+
+/// The correlation field and id an event carries: `field` (or any of
+/// CORRELATION_KEYS) in its JSON, else a Lambda request id from the line prefix
+pub fn correlation_id(event: &LogEvent, field: Option<&str>) -> Option<(String, String)> {
+    let keys: Vec<String> = match field {
+        Some(field) => vec![normalize_key(field)],
+        None => CORRELATION_KEYS.iter().map(|k| k.to_string()).collect(),
+    };
+    if let Some(found) = diff::embedded_json(&event.message).and_then(|json| find_key(&json, &keys))
+    {
+        return Some(found);
+    }
+    if field.is_some() {
+        return None;
+    }
+    let captures = lambda_request_regex().captures(&event.message)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| ("RequestId".to_string(), m.as_str().to_string()))
+}
+
+/// An error event and the first event of the request that produced it
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestOrigin {
+    /// Field the id was read from, e.g. "requestId"
+    pub field: String,
+    pub correlation_id: String,
+    pub event: LogEvent,
+    /// Earliest event carrying the same id in any source of the session (None when
+    /// `event` is itself the earliest)
+    pub origin: Option<LogEvent>,
+    /// Events carrying the id across the session, including both above
+    pub related_count: usize,
+}
+
+/// For an (error) event, find the earliest event with the same correlation id across
+/// every log group and file merged into the session, usually the request's input,
+/// so "what was the input that caused this" is one command. `field` names the
+/// correlation field when the defaults (requestId, correlationId, traceId, ...)
+/// don't apply.
+#[tauri::command]
+pub async fn find_request_origin(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_id: String,
+    field: Option<String>,
+) -> Result<RequestOrigin, String> {
+    let events = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|s| s.events())
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let event = events
+        .iter()
+        .find(|e| e.event_id.as_deref() == Some(event_id.as_str()))
+        .cloned()
+        .ok_or_else(|| format!("Event {} is not in session {}", event_id, session_id))?;
+    let (name, id) = correlation_id(&event, field.as_deref())
+        .ok_or_else(|| "The event has no correlation or request id".to_string())?;
+
+    // Any correlation field naming the same id counts, since groups often spell it
+    // differently
+    let carries_id = |e: &LogEvent| {
+        [field.as_deref(), None]
+            .into_iter()
+            .filter_map(|f| correlation_id(e, f))
+            .any(|(_, other)| other == id)
+    };
+    let related: Vec<&LogEvent> = events
+        .iter()
+        .filter(|e| e.message.contains(id.as_str()) && carries_id(e))
+        .collect();
+    let related_count = related.len().max(1);
+    // Stored oldest-first, so the first related event is the earliest
+    let origin = related
+        .first()
+        .filter(|e| e.event_id != event.event_id)
+        .map(|e| (*e).clone());
+
+    let mut found: Vec<LogEvent> = std::iter::once(event).chain(origin).collect();
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut found).await;
+    let origin = found.get(1).cloned();
+    Ok(RequestOrigin {
+        field: name,
+        correlation_id: id,
+        event: found.swap_remove(0),
+        origin,
+        related_count,
+    })
+}
@@ -0,0 +1,92 @@
+use crate::LogEvent;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Issued next_tokens remembered for recovery; older ones are forgotten first
+const MAX_TRACKED_CURSORS: usize = 512;
+
+/// Where a page handed out with a next_token ended
+#[derive(Debug, Clone, Default)]
+struct CursorPosition {
+    /// Newest timestamp returned so far (None when nothing was returned yet)
+    last_timestamp: Option<i64>,
+    /// Event ids already returned at `last_timestamp`
+    seen_ids: Vec<String>,
+}
+
+#[derive(Default)]
+struct CursorMap {
+    positions: HashMap<String, CursorPosition>,
+    order: VecDeque<String>,
+}
+
+static CURSORS: Mutex<Option<CursorMap>> = Mutex::new(None);
+
+fn with_cursors<T>(f: impl FnOnce(&mut CursorMap) -> T) -> T {
+    let mut guard = CURSORS.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(CursorMap::default))
+}
+
+/// Whether FilterLogEvents rejected the request's next_token (expired or unknown)
+pub fn is_invalid_token<R>(
+    error: &aws_sdk_cloudwatchlogs::error::SdkError<
+        aws_sdk_cloudwatchlogs::operation::filter_log_events::FilterLogEventsError,
+        R,
+    >,
+) -> bool {
+    error.as_service_error().is_some_and(|e| {
+        e.is_invalid_parameter_exception()
+            && e.meta()
+                .message()
+                .is_some_and(|m| m.to_lowercase().contains("token"))
+    })
+}
+
+/// Remember where the page issued with `next_token` ended: the position of the
+/// previous token (if any) advanced past `events`
+pub fn track(previous_token: Option<&str>, next_token: &str, events: &[LogEvent]) {
+    with_cursors(|cursors| {
+        let mut position = previous_token
+            .and_then(|t| cursors.positions.get(t).cloned())
+            .unwrap_or_default();
+        for event in events {
+            match position.last_timestamp {
+                Some(last) if event.timestamp < last => {}
+                Some(last) if event.timestamp == last => {
+                    position.seen_ids.extend(event.event_id.clone());
+                }
+                _ => {
+                    position.last_timestamp = Some(event.timestamp);
+                    position.seen_ids = event.event_id.clone().into_iter().collect();
+                }
+            }
+        }
+        if cursors
+            .positions
+            .insert(next_token.to_string(), position)
+            .is_none()
+        {
+            cursors.order.push_back(next_token.to_string());
+        }
+        while cursors.order.len() > MAX_TRACKED_CURSORS {
+            if let Some(oldest) = cursors.order.pop_front() {
+                cursors.positions.remove(&oldest);
+            }
+        }
+    });
+}
+
+/// Start time to restart an expired cursor's query from, and the event ids to drop
+/// at that timestamp because they were already returned. None when the token was
+/// not issued by this run of the app.
+pub fn restart_point(token: &str, start_time: Option<i64>) -> Option<(Option<i64>, Vec<String>)> {
+    with_cursors(|cursors| {
+        cursors
+            .positions
+            .get(token)
+            .map(|position| match position.last_timestamp {
+                Some(last) => (Some(last), position.seen_ids.clone()),
+                None => (start_time, Vec::new()),
+            })
+    })
+}
@@ -0,0 +1,442 @@
+use crate::insights::{self, QueryTargets};
+use crate::{connectivity, regions, storage};
+use aws_config::BehaviorVersion;
+use chrono::{Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// File in the app config directory holding the job settings
+const SETTINGS_FILE: &str = "daily-summary-settings.json";
+
+/// File in the app config directory holding the stored summaries
+const SUMMARIES_FILE: &str = "daily-summaries.json";
+
+/// Serializes read-modify-write of the settings file
+static SETTINGS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Serializes read-modify-write of the summaries file
+static SUMMARIES_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// How often the scheduler checks whether today's run is due
+const SCHEDULER_INTERVAL_SECS: u64 = 600;
+
+/// Days summarized per group when none are stored yet, enough for a
+/// week-over-week comparison
+const BACKFILL_DAYS: u64 = 14;
+
+/// Days of summaries kept
+const RETENTION_DAYS: u64 = 90;
+
+/// Templates kept per summary
+const MAX_TEMPLATES: usize = 10;
+
+/// Error-classified messages, as an Insights regex
+const ERROR_REGEX: &str = r"/(?i)\b(error|exception|fatal|panic|traceback)\b/";
+
+/// A log group whose days are summarized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedGroup {
+    /// AWS profile to query with; the default credentials when absent
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub log_group_name: String,
+}
+
+/// Opt-in daily summary job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailySummarySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub groups: Vec<PinnedGroup>,
+    /// Local date (YYYY-MM-DD) of the last scheduled run
+    #[serde(default)]
+    pub last_run_date: Option<String>,
+}
+
+/// A frequent error template of a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCount {
+    pub template: String,
+    pub count: u64,
+}
+
+/// One group's summary of one local day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailySummary {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub log_group_name: String,
+    pub event_count: u64,
+    pub error_count: u64,
+    /// Bytes the day's events amount to (as scanned by Insights)
+    pub bytes: u64,
+    /// Most frequent error templates, most frequent first
+    pub top_templates: Vec<TemplateCount>,
+    pub computed_at: i64,
+}
+
+/// Totals of one week of summaries
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WeekTotals {
+    /// Days with a stored summary
+    pub days: usize,
+    pub event_count: u64,
+    pub error_count: u64,
+    pub bytes: u64,
+}
+
+/// This week against last week for one group
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupTrend {
+    pub profile: Option<String>,
+    pub log_group_name: String,
+    /// The 7 days up to yesterday
+    pub this_week: WeekTotals,
+    /// The 7 days before those
+    pub last_week: WeekTotals,
+    /// Percent change of errors per day; None without last-week data
+    pub error_change_percent: Option<f64>,
+    pub volume_change_percent: Option<f64>,
+    /// More errors per day than last week
+    pub worse: bool,
+    /// Stored summaries of both weeks, oldest first
+    pub days: Vec<DailySummary>,
+}
+
+fn day_bounds(date: NaiveDate) -> Option<(i64, i64)> {
+    let start = date
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()?;
+    let end = date
+        .checked_add_days(Days::new(1))?
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()?;
+    Some((start.timestamp_millis(), end.timestamp_millis() - 1))
+}
+
+fn load_summaries(app: &AppHandle) -> Vec<DailySummary> {
+    storage::read_json(app, SUMMARIES_FILE)
+}
+
+fn same_group(summary: &DailySummary, group: &PinnedGroup) -> bool {
+    summary.profile == group.profile && summary.log_group_name == group.log_group_name
+}
+
+fn parse_count(row: &BTreeMap<String, String>, field: &str) -> u64 {
+    row.get(field)
+        .and_then(|v| v.parse::<f64>().ok())
+        .map_or(0, |v| v as u64)
+}
+
+/// Summarize one group's day with two stats-only Insights queries: the event
+/// count (whose scan size is the day's volume) and the error templates
+async fn summarize_day(
+    app: &AppHandle,
+    client: &aws_sdk_cloudwatchlogs::Client,
+    group: &PinnedGroup,
+    date: NaiveDate,
+) -> Result<DailySummary, String> {
+    let (start, end) = day_bounds(date).ok_or("Invalid day")?;
+    let targets = QueryTargets::new(vec![group.log_group_name.clone()], Vec::new())?;
+    let counts = insights::run_background_query(
+        app,
+        client,
+        &targets,
+        "stats count(*) as events",
+        start,
+        end,
+        None,
+    )
+    .await?;
+    let errors = insights::run_background_query(
+        app,
+        client,
+        &targets,
+        &format!(
+            "filter @message like {} | pattern @message | sort @sampleCount desc",
+            ERROR_REGEX
+        ),
+        start,
+        end,
+        Some(10_000),
+    )
+    .await?;
+    if counts.status != "Complete" || errors.status != "Complete" {
+        return Err(format!(
+            "Insights queries ended {} / {}",
+            counts.status, errors.status
+        ));
+    }
+
+    let mut top_templates: Vec<TemplateCount> = errors
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(TemplateCount {
+                template: row.get("@pattern")?.clone(),
+                count: parse_count(row, "@sampleCount"),
+            })
+        })
+        .collect();
+    let error_count = top_templates.iter().map(|t| t.count).sum();
+    top_templates.sort_by_key(|t| std::cmp::Reverse(t.count));
+    top_templates.truncate(MAX_TEMPLATES);
+    Ok(DailySummary {
+        date: date.to_string(),
+        profile: group.profile.clone(),
+        log_group_name: group.log_group_name.clone(),
+        event_count: counts
+            .rows
+            .first()
+            .map_or(0, |row| parse_count(row, "events")),
+        error_count,
+        bytes: counts.bytes_scanned as u64,
+        top_templates,
+        computed_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Store summaries, replacing those of the same group and day and dropping
+/// days past retention
+fn store(app: &AppHandle, fresh: Vec<DailySummary>) -> Result<(), String> {
+    let cutoff = Local::now()
+        .date_naive()
+        .checked_sub_days(Days::new(RETENTION_DAYS))
+        .map(|d| d.to_string())
+        .unwrap_or_default();
+    let _guard = SUMMARIES_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut summaries = load_summaries(app);
+    summaries.retain(|s| {
+        s.date >= cutoff
+            && !fresh.iter().any(|f| {
+                f.date == s.date && f.profile == s.profile && f.log_group_name == s.log_group_name
+            })
+    });
+    summaries.extend(fresh);
+    summaries.sort_by(|a, b| {
+        (&a.log_group_name, &a.profile, &a.date).cmp(&(&b.log_group_name, &b.profile, &b.date))
+    });
+    storage::write_json(app, SUMMARIES_FILE, &summaries)
+}
+
+/// Summarize the days missing for each group: yesterday, or the last
+/// BACKFILL_DAYS for a group without summaries. Returns the summaries added.
+async fn run_summaries(app: &AppHandle, groups: &[PinnedGroup]) -> Vec<DailySummary> {
+    let yesterday = Local::now().date_naive() - Days::new(1);
+    let stored = load_summaries(app);
+    let mut added = Vec::new();
+    for group in groups {
+        let dates: Vec<NaiveDate> = (0..BACKFILL_DAYS)
+            .map(|i| yesterday - Days::new(i))
+            .filter(|date| {
+                let date = date.to_string();
+                !stored
+                    .iter()
+                    .any(|s| s.date == date && same_group(s, group))
+            })
+            .collect();
+        if dates.is_empty() {
+            continue;
+        }
+        let config = match &group.profile {
+            Some(profile) => {
+                aws_config::defaults(BehaviorVersion::latest())
+                    .profile_name(profile)
+                    .load()
+                    .await
+            }
+            None => aws_config::defaults(BehaviorVersion::latest()).load().await,
+        };
+        let signing_region = regions::signing_region_for(app, group.profile.as_deref());
+        let client = regions::logs_client(&config, signing_region.as_deref());
+        for date in dates {
+            match summarize_day(app, &client, group, date).await {
+                Ok(summary) => added.push(summary),
+                Err(e) => {
+                    log::info!(
+                        "Daily summary of {} for {} failed: {}",
+                        group.log_group_name,
+                        date,
+                        e
+                    );
+                    // Credentials or permissions won't get better for the older days
+                    break;
+                }
+            }
+        }
+    }
+    if !added.is_empty() {
+        if let Err(e) = store(app, added.clone()) {
+            log::warn!("Failed to store daily summaries: {}", e);
+        }
+    }
+    added
+}
+
+/// Background scheduler summarizing the pinned groups once a day, when enabled
+pub async fn run_summary_scheduler(app: AppHandle) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(SCHEDULER_INTERVAL_SECS)).await;
+        let settings: DailySummarySettings = storage::read_json(&app, SETTINGS_FILE);
+        let today = Local::now().date_naive().to_string();
+        if !settings.enabled
+            || settings.groups.is_empty()
+            || settings.last_run_date.as_deref() == Some(today.as_str())
+            || connectivity::ensure_online().is_err()
+        {
+            continue;
+        }
+        {
+            let _guard = SETTINGS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut current: DailySummarySettings = storage::read_json(&app, SETTINGS_FILE);
+            current.last_run_date = Some(today);
+            if let Err(e) = storage::write_json(&app, SETTINGS_FILE, &current) {
+                log::warn!("Failed to record daily summary run: {}", e);
+            }
+        }
+        let added = run_summaries(&app, &settings.groups).await;
+        log::info!(
+            "Stored {} daily summaries for {} pinned groups",
+            added.len(),
+            settings.groups.len()
+        );
+    }
+}
+
+/// Groups pinned for daily summaries
+pub fn pinned_groups(app: &AppHandle) -> Vec<PinnedGroup> {
+    storage::read_json::<DailySummarySettings>(app, SETTINGS_FILE).groups
+}
+
+/// The daily summary job settings
+#[tauri::command]
+pub fn get_daily_summary_settings(app: AppHandle) -> DailySummarySettings {
+    storage::read_json(&app, SETTINGS_FILE)
+}
+
+/// Configure the daily summary job (enabled, pinned groups)
+#[tauri::command]
+pub fn set_daily_summary_settings(
+    app: AppHandle,
+    settings: DailySummarySettings,
+) -> Result<(), String> {
+    for group in &settings.groups {
+        if let Some(profile) = &group.profile {
+            crate::validate_profile_name(profile)?;
+        }
+        if group.log_group_name.trim().is_empty() {
+            return Err("Pinned groups need a log group name".to_string());
+        }
+    }
+    let _guard = SETTINGS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let previous: DailySummarySettings = storage::read_json(&app, SETTINGS_FILE);
+    let settings = DailySummarySettings {
+        last_run_date: previous.last_run_date,
+        ..settings
+    };
+    storage::write_json(&app, SETTINGS_FILE, &settings)
+}
+
+/// Summarize the missing days of the pinned groups now
+#[tauri::command]
+pub async fn run_daily_summaries(app: AppHandle) -> Result<Vec<DailySummary>, String> {
+    connectivity::ensure_online()?;
+    let settings: DailySummarySettings = storage::read_json(&app, SETTINGS_FILE);
+    if settings.groups.is_empty() {
+        return Err("Pin a log group to summarize first".to_string());
+    }
+    Ok(run_summaries(&app, &settings.groups).await)
+}
+
+/// Stored summaries, optionally of one group, oldest first
+#[tauri::command]
+pub fn list_daily_summaries(app: AppHandle, log_group_name: Option<String>) -> Vec<DailySummary> {
+    let mut summaries = load_summaries(&app);
+    if let Some(name) = log_group_name {
+        summaries.retain(|s| s.log_group_name == name);
+    }
+    summaries.sort_by(|a, b| a.date.cmp(&b.date));
+    summaries
+}
+
+fn week_totals(days: &[&DailySummary]) -> WeekTotals {
+    WeekTotals {
+        days: days.len(),
+        event_count: days.iter().map(|d| d.event_count).sum(),
+        error_count: days.iter().map(|d| d.error_count).sum(),
+        bytes: days.iter().map(|d| d.bytes).sum(),
+    }
+}
+
+/// Percent change of a per-day average, this week against last
+fn change_percent(this: u64, this_days: usize, last: u64, last_days: usize) -> Option<f64> {
+    if this_days == 0 || last_days == 0 || last == 0 {
+        return None;
+    }
+    let this = this as f64 / this_days as f64;
+    let last = last as f64 / last_days as f64;
+    Some((this - last) / last * 100.0)
+}
+
+/// Whether this week is worse than last week for each pinned group: the last 7
+/// stored days against the 7 before, compared per day so missing days don't
+/// skew the result
+#[tauri::command]
+pub fn get_daily_summary_trends(app: AppHandle) -> Vec<GroupTrend> {
+    let settings: DailySummarySettings = storage::read_json(&app, SETTINGS_FILE);
+    let summaries = load_summaries(&app);
+    let yesterday = Local::now().date_naive() - Days::new(1);
+    let this_start = (yesterday - Days::new(6)).to_string();
+    let last_start = (yesterday - Days::new(13)).to_string();
+    let yesterday = yesterday.to_string();
+
+    settings
+        .groups
+        .iter()
+        .map(|group| {
+            let days: Vec<&DailySummary> = summaries
+                .iter()
+                .filter(|s| same_group(s, group) && s.date >= last_start && s.date <= yesterday)
+                .collect();
+            let (this_week, last_week): (Vec<&DailySummary>, Vec<&DailySummary>) =
+                days.iter().copied().partition(|s| s.date >= this_start);
+            let this_week = week_totals(&this_week);
+            let last_week = week_totals(&last_week);
+            let error_change_percent = change_percent(
+                this_week.error_count,
+                this_week.days,
+                last_week.error_count,
+                last_week.days,
+            );
+            let volume_change_percent = change_percent(
+                this_week.bytes,
+                this_week.days,
+                last_week.bytes,
+                last_week.days,
+            );
+            let worse = this_week.days > 0
+                && last_week.days > 0
+                && this_week.error_count as f64 / this_week.days as f64
+                    > last_week.error_count as f64 / last_week.days as f64;
+            GroupTrend {
+                profile: group.profile.clone(),
+                log_group_name: group.log_group_name.clone(),
+                this_week,
+                last_week,
+                error_change_percent,
+                volume_change_percent,
+                worse,
+                days: days.into_iter().cloned().collect(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,212 @@
+use crate::{humanize_aws_error, AppState};
+use aws_sdk_cloudwatchlogs::types::{DataProtectionStatus, InheritedProperty, PolicyType};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::State;
+
+/// Prefix of managed data identifier ARNs (arn:aws:dataprotection::aws:data-identifier/Name)
+const MANAGED_IDENTIFIER_MARKER: &str = ":data-identifier/";
+
+/// What a data protection policy audits and masks
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DataProtectionPolicySummary {
+    pub policy_name: Option<String>,
+    /// Identifiers whose findings are reported (Audit statements)
+    pub audited_identifiers: Vec<String>,
+    /// Identifiers masked in query results (Deidentify statements)
+    pub masked_identifiers: Vec<String>,
+    /// Where audit findings are sent (log group / Firehose / S3 bucket)
+    pub findings_destinations: Vec<String>,
+    pub last_updated_time: Option<i64>,
+    /// For account policies: the selection criteria, if scoped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_criteria: Option<String>,
+}
+
+/// Data protection state of one log group
+#[derive(Debug, Clone, Serialize)]
+pub struct LogGroupProtection {
+    pub log_group_name: String,
+    /// "ACTIVATED", "DISABLED", "ARCHIVED", "DELETED", or None when never configured
+    pub status: Option<String>,
+    /// Covered by the account-level data protection policy
+    pub inherits_account_policy: bool,
+    /// The group's own policy, if attached
+    pub policy: Option<DataProtectionPolicySummary>,
+    /// Set when the group's policy could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Inventory of data protection across all log groups
+#[derive(Debug, Clone, Serialize)]
+pub struct DataProtectionReport {
+    pub scanned_groups: usize,
+    /// Groups with their own active policy or covered by the account policy
+    pub protected_groups: usize,
+    pub unprotected_groups: Vec<String>,
+    pub account_policies: Vec<DataProtectionPolicySummary>,
+    pub groups: Vec<LogGroupProtection>,
+    /// Identifier -> log groups whose own policy configures it
+    pub identifiers: BTreeMap<String, Vec<String>>,
+}
+
+/// Short name of a data identifier ("EmailAddress" for the managed identifier ARN);
+/// custom identifiers are already plain names
+fn identifier_name(identifier: &str) -> String {
+    match identifier.split_once(MANAGED_IDENTIFIER_MARKER) {
+        Some((_, name)) => name.to_string(),
+        None => identifier.to_string(),
+    }
+}
+
+/// Summarize a data protection policy document. Statements carry `DataIdentifier`
+/// and an `Operation` of either `Audit` (with `FindingsDestination`) or `Deidentify`.
+fn summarize_policy(document: &str) -> DataProtectionPolicySummary {
+    let mut summary = DataProtectionPolicySummary::default();
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(document) else {
+        return summary;
+    };
+    summary.policy_name = doc["Name"].as_str().map(str::to_string);
+    for statement in doc["Statement"].as_array().into_iter().flatten() {
+        let identifiers: Vec<String> = statement["DataIdentifier"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|i| i.as_str())
+            .map(identifier_name)
+            .collect();
+        let operation = &statement["Operation"];
+        let target = if operation.get("Deidentify").is_some() {
+            &mut summary.masked_identifiers
+        } else if let Some(audit) = operation.get("Audit") {
+            if let Some(destination) = audit["FindingsDestination"].as_object() {
+                for (kind, config) in destination {
+                    let name = ["LogGroup", "DeliveryStream", "Bucket"]
+                        .iter()
+                        .find_map(|k| config[*k].as_str())
+                        .unwrap_or_default();
+                    summary
+                        .findings_destinations
+                        .push(format!("{}: {}", kind, name));
+                }
+            }
+            &mut summary.audited_identifiers
+        } else {
+            continue;
+        };
+        for identifier in identifiers {
+            if !target.contains(&identifier) {
+                target.push(identifier);
+            }
+        }
+    }
+    summary.audited_identifiers.sort();
+    summary.masked_identifiers.sort();
+    summary
+}
+
+/// Scan every log group for attached data protection policies and summarize which
+/// sensitive-data identifiers are audited or masked where, including the
+/// account-level policy. Only groups reporting an active policy are fetched.
+#[tauri::command]
+pub async fn audit_data_protection(
+    state: State<'_, AppState>,
+    prefix: Option<String>,
+) -> Result<DataProtectionReport, String> {
+    let client = state.logs_client().await?;
+
+    let account_policies: Vec<DataProtectionPolicySummary> = client
+        .describe_account_policies()
+        .policy_type(PolicyType::DataProtectionPolicy)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+        .account_policies()
+        .iter()
+        .map(|p| {
+            let mut summary = summarize_policy(p.policy_document().unwrap_or_default());
+            summary.policy_name = p.policy_name().map(str::to_string).or(summary.policy_name);
+            summary.last_updated_time = p.last_updated_time();
+            summary.selection_criteria = p.selection_criteria().map(str::to_string);
+            summary
+        })
+        .collect();
+
+    let mut groups = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_log_groups()
+            .set_log_group_name_prefix(prefix.clone().filter(|p| !p.is_empty()))
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for group in response.log_groups() {
+            groups.push(LogGroupProtection {
+                log_group_name: group.log_group_name().unwrap_or_default().to_string(),
+                status: group
+                    .data_protection_status()
+                    .map(|s| s.as_str().to_string()),
+                inherits_account_policy: group
+                    .inherited_properties()
+                    .contains(&InheritedProperty::AccountDataProtection),
+                policy: None,
+                error: None,
+            });
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let activated = DataProtectionStatus::Activated.as_str();
+    let mut identifiers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for group in groups
+        .iter_mut()
+        .filter(|g| g.status.as_deref() == Some(activated))
+    {
+        match client
+            .get_data_protection_policy()
+            .log_group_identifier(&group.log_group_name)
+            .send()
+            .await
+        {
+            Ok(response) => {
+                let Some(document) = response.policy_document() else {
+                    continue;
+                };
+                let mut summary = summarize_policy(document);
+                summary.last_updated_time = response.last_updated_time();
+                for identifier in summary
+                    .audited_identifiers
+                    .iter()
+                    .chain(&summary.masked_identifiers)
+                {
+                    let names = identifiers.entry(identifier.clone()).or_default();
+                    if !names.contains(&group.log_group_name) {
+                        names.push(group.log_group_name.clone());
+                    }
+                }
+                group.policy = Some(summary);
+            }
+            Err(e) => group.error = Some(humanize_aws_error(&format!("{}", e))),
+        }
+    }
+
+    let unprotected_groups: Vec<String> = groups
+        .iter()
+        .filter(|g| g.policy.is_none() && g.error.is_none() && !g.inherits_account_policy)
+        .map(|g| g.log_group_name.clone())
+        .collect();
+    Ok(DataProtectionReport {
+        scanned_groups: groups.len(),
+        protected_groups: groups.len() - unprotected_groups.len(),
+        unprotected_groups,
+        account_policies,
+        groups,
+        identifiers,
+    })
+}
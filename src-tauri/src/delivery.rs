@@ -0,0 +1,766 @@
+use crate::{audit, connections, humanize_aws_error, AppState};
+use aws_sdk_cloudwatchlogs::types::InputLogEvent;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+/// A CloudWatch Logs destination (the receiving end of cross-account subscriptions)
+#[derive(Debug, Clone, Serialize)]
+pub struct LogDestination {
+    pub name: String,
+    pub arn: Option<String>,
+    /// Kinesis stream / Firehose / Lambda the destination forwards to
+    pub target_arn: Option<String>,
+    pub role_arn: Option<String>,
+    /// Account ids (or "*") allowed to subscribe by the destination's access policy
+    pub allowed_accounts: Vec<String>,
+    /// True when the target lives in a different account than the destination
+    pub cross_account_target: bool,
+    pub creation_time: Option<i64>,
+}
+
+/// A vended-log delivery (delivery source -> delivery destination)
+#[derive(Debug, Clone, Serialize)]
+pub struct LogDelivery {
+    pub id: Option<String>,
+    pub delivery_source_name: Option<String>,
+    /// Service and resources emitting the logs (from the delivery source)
+    pub source_service: Option<String>,
+    pub source_log_type: Option<String>,
+    pub source_resource_arns: Vec<String>,
+    pub delivery_destination_arn: Option<String>,
+    /// "S3", "CWL", "FH", or "XRAY"
+    pub destination_type: Option<String>,
+    /// The bucket / log group / delivery stream receiving the logs
+    pub destination_resource_arn: Option<String>,
+    pub output_format: Option<String>,
+    pub cross_account: bool,
+}
+
+/// Account id field of an ARN (arn:partition:service:region:account:resource)
+pub fn arn_account(arn: &str) -> Option<&str> {
+    arn.split(':').nth(4).filter(|a| !a.is_empty())
+}
+
+/// Pull principal account ids out of a destination access policy document
+fn policy_principal_accounts(policy: &str) -> Vec<String> {
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(policy) else {
+        return Vec::new();
+    };
+    let statements = match doc.get("Statement") {
+        Some(serde_json::Value::Array(items)) => items.clone(),
+        Some(single) => vec![single.clone()],
+        None => Vec::new(),
+    };
+
+    let mut accounts: Vec<String> = Vec::new();
+    for statement in statements {
+        let principal = match statement.get("Principal") {
+            Some(serde_json::Value::String(p)) => vec![p.clone()],
+            Some(serde_json::Value::Object(map)) => map
+                .get("AWS")
+                .map(|v| match v {
+                    serde_json::Value::Array(items) => items
+                        .iter()
+                        .filter_map(|i| i.as_str().map(str::to_string))
+                        .collect(),
+                    other => other.as_str().map(str::to_string).into_iter().collect(),
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        for p in principal {
+            // Principals can be bare account ids or full ARNs
+            let account = arn_account(&p).map(str::to_string).unwrap_or(p);
+            if !accounts.contains(&account) {
+                accounts.push(account);
+            }
+        }
+    }
+    accounts
+}
+
+/// List CloudWatch Logs destinations and the accounts allowed to send to them
+#[tauri::command]
+pub async fn list_log_destinations(
+    state: State<'_, AppState>,
+) -> Result<Vec<LogDestination>, String> {
+    let client = state.logs_client().await?;
+
+    let mut destinations = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_destinations()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        for d in response.destinations() {
+            let arn = d.arn().map(str::to_string);
+            let target_arn = d.target_arn().map(str::to_string);
+            let cross_account_target = match (arn.as_deref(), target_arn.as_deref()) {
+                (Some(a), Some(t)) => arn_account(a) != arn_account(t),
+                _ => false,
+            };
+            destinations.push(LogDestination {
+                name: d.destination_name().unwrap_or_default().to_string(),
+                arn,
+                target_arn,
+                role_arn: d.role_arn().map(str::to_string),
+                allowed_accounts: d
+                    .access_policy()
+                    .map(policy_principal_accounts)
+                    .unwrap_or_default(),
+                cross_account_target,
+                creation_time: d.creation_time(),
+            });
+        }
+
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(destinations)
+}
+
+/// List vended-log deliveries, joined with their delivery sources and destinations,
+/// so it's visible which resources forward logs where (including other accounts)
+#[tauri::command]
+pub async fn list_log_deliveries(state: State<'_, AppState>) -> Result<Vec<LogDelivery>, String> {
+    let client = state.logs_client().await?;
+
+    let mut sources = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_delivery_sources()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        sources.extend(response.delivery_sources().iter().cloned());
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut destinations = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_delivery_destinations()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        destinations.extend(response.delivery_destinations().iter().cloned());
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut deliveries = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .describe_deliveries()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        for d in response.deliveries() {
+            let source = sources
+                .iter()
+                .find(|s| s.name() == d.delivery_source_name());
+            let destination = destinations
+                .iter()
+                .find(|dest| dest.arn() == d.delivery_destination_arn());
+            let destination_resource_arn = destination
+                .and_then(|dest| dest.delivery_destination_configuration())
+                .map(|c| c.destination_resource_arn().to_string());
+
+            let source_resource_arns: Vec<String> = source
+                .map(|s| s.resource_arns().to_vec())
+                .unwrap_or_default();
+            let cross_account = destination_resource_arn
+                .as_deref()
+                .and_then(arn_account)
+                .is_some_and(|dest_account| {
+                    source_resource_arns
+                        .iter()
+                        .filter_map(|a| arn_account(a))
+                        .any(|a| a != dest_account)
+                });
+
+            deliveries.push(LogDelivery {
+                id: d.id().map(str::to_string),
+                delivery_source_name: d.delivery_source_name().map(str::to_string),
+                source_service: source.and_then(|s| s.service()).map(str::to_string),
+                source_log_type: source.and_then(|s| s.log_type()).map(str::to_string),
+                source_resource_arns,
+                delivery_destination_arn: d.delivery_destination_arn().map(str::to_string),
+                destination_type: d
+                    .delivery_destination_type()
+                    .map(|t| t.as_str().to_string()),
+                destination_resource_arn,
+                output_format: destination
+                    .and_then(|dest| dest.output_format())
+                    .map(|f| f.as_str().to_string()),
+                cross_account,
+            });
+        }
+
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(deliveries)
+}
+
+/// Service principal EventBridge writes to log groups as
+const EVENTS_PRINCIPAL: &str = "events.amazonaws.com";
+
+/// An EventBridge rule target delivering events into a log group
+#[derive(Debug, Clone, Serialize)]
+pub struct EventBridgeFeed {
+    pub event_bus_name: String,
+    pub rule_name: String,
+    pub rule_arn: Option<String>,
+    /// "ENABLED", "DISABLED", ...
+    pub state: Option<String>,
+    pub description: Option<String>,
+    pub event_pattern: Option<String>,
+    pub schedule_expression: Option<String>,
+    /// Set when the rule is owned by another AWS service
+    pub managed_by: Option<String>,
+    pub target_id: String,
+    /// Events are reshaped before delivery (input, input path or transformer)
+    pub transformed: bool,
+    pub dead_letter_arn: Option<String>,
+}
+
+/// What feeds a log group through EventBridge, and whether delivery can work
+#[derive(Debug, Clone, Serialize)]
+pub struct EventBridgeFeedReport {
+    pub log_group_name: String,
+    pub feeds: Vec<EventBridgeFeed>,
+    /// Whether a CloudWatch Logs resource policy lets EventBridge write to the group
+    pub resource_policy_allows_events: bool,
+    /// Resource policies granting EventBridge access to the group
+    pub allowing_policies: Vec<String>,
+    /// Likely reasons nothing arrives
+    pub warnings: Vec<String>,
+}
+
+/// Match an IAM resource pattern with `*`/`?` wildcards
+fn resource_matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    // Classic wildcard DP over (pattern, value) prefixes
+    let mut matches = vec![vec![false; value.len() + 1]; pattern.len() + 1];
+    matches[0][0] = true;
+    for i in 1..=pattern.len() {
+        if pattern[i - 1] == '*' {
+            matches[i][0] = matches[i - 1][0];
+        }
+        for j in 1..=value.len() {
+            matches[i][j] = match pattern[i - 1] {
+                '*' => matches[i - 1][j] || matches[i][j - 1],
+                '?' => matches[i - 1][j - 1],
+                c => matches[i - 1][j - 1] && c == value[j - 1],
+            };
+        }
+    }
+    matches[pattern.len()][value.len()]
+}
+
+/// Values of a policy field that may be a string or a list of strings
+fn policy_strings(value: &serde_json::Value) -> Vec<&str> {
+    match value {
+        serde_json::Value::String(s) => vec![s.as_str()],
+        serde_json::Value::Array(items) => items.iter().filter_map(|i| i.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a resource policy document allows EventBridge to put events into the group
+fn policy_allows_events(policy: &str, log_group_arn: &str) -> bool {
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(policy) else {
+        return false;
+    };
+    let statements = match &doc["Statement"] {
+        serde_json::Value::Array(items) => items.clone(),
+        single => vec![single.clone()],
+    };
+    let group_arn = log_group_arn.strip_suffix(":*").unwrap_or(log_group_arn);
+    let stream_arn = format!("{}:log-stream:x", group_arn);
+    statements.iter().any(|statement| {
+        statement["Effect"].as_str() == Some("Allow")
+            && policy_strings(&statement["Principal"]["Service"]).contains(&EVENTS_PRINCIPAL)
+            && policy_strings(&statement["Action"]).iter().any(|action| {
+                ["logs:PutLogEvents", "logs:*", "*"]
+                    .iter()
+                    .any(|a| action.eq_ignore_ascii_case(a))
+            })
+            && policy_strings(&statement["Resource"])
+                .iter()
+                .any(|resource| {
+                    resource_matches(resource, group_arn)
+                        || resource_matches(resource, &format!("{}:*", group_arn))
+                        || resource_matches(resource, &stream_arn)
+                })
+    })
+}
+
+/// List the EventBridge rules/targets (on every event bus) delivering into a log
+/// group and check that a resource policy lets EventBridge write to it, to answer
+/// "why is nothing arriving in this log group"
+#[tauri::command]
+pub async fn list_eventbridge_feeds(
+    state: State<'_, AppState>,
+    log_group_name: String,
+) -> Result<EventBridgeFeedReport, String> {
+    let logs = state.logs_client().await?;
+    let config = state.sdk_config().await?;
+    let events = aws_sdk_eventbridge::Client::new(&config);
+
+    let log_group_arn = crate::catalog::describe_all_log_groups(&logs)
+        .await?
+        .into_iter()
+        .find(|g| g.name == log_group_name)
+        .and_then(|g| g.arn)
+        .ok_or_else(|| format!("Log group {} not found", log_group_name))?;
+    let target_arn = log_group_arn
+        .strip_suffix(":*")
+        .unwrap_or(&log_group_arn)
+        .to_string();
+
+    let mut buses = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = events
+            .list_event_buses()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        buses.extend(
+            response
+                .event_buses()
+                .iter()
+                .filter_map(|b| b.name().map(str::to_string)),
+        );
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut feeds = Vec::new();
+    for bus in &buses {
+        let mut rule_names = Vec::new();
+        // Targets are registered with or without the trailing ":*"
+        for arn in [target_arn.clone(), format!("{}:*", target_arn)] {
+            let mut next_token: Option<String> = None;
+            loop {
+                let response = events
+                    .list_rule_names_by_target()
+                    .target_arn(&arn)
+                    .event_bus_name(bus)
+                    .set_next_token(next_token)
+                    .send()
+                    .await
+                    .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+                for name in response.rule_names() {
+                    if !rule_names.contains(name) {
+                        rule_names.push(name.clone());
+                    }
+                }
+                next_token = response.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        for rule_name in rule_names {
+            let rule = events
+                .describe_rule()
+                .name(&rule_name)
+                .event_bus_name(bus)
+                .send()
+                .await
+                .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+            let targets = events
+                .list_targets_by_rule()
+                .rule(&rule_name)
+                .event_bus_name(bus)
+                .send()
+                .await
+                .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+            for target in targets
+                .targets()
+                .iter()
+                .filter(|t| t.arn().strip_suffix(":*").unwrap_or(t.arn()) == target_arn)
+            {
+                feeds.push(EventBridgeFeed {
+                    event_bus_name: bus.clone(),
+                    rule_name: rule_name.clone(),
+                    rule_arn: rule.arn().map(str::to_string),
+                    state: rule.state().map(|s| s.as_str().to_string()),
+                    description: rule.description().map(str::to_string),
+                    event_pattern: rule.event_pattern().map(str::to_string),
+                    schedule_expression: rule.schedule_expression().map(str::to_string),
+                    managed_by: rule.managed_by().map(str::to_string),
+                    target_id: target.id().to_string(),
+                    transformed: target.input().is_some()
+                        || target.input_path().is_some()
+                        || target.input_transformer().is_some(),
+                    dead_letter_arn: target
+                        .dead_letter_config()
+                        .and_then(|d| d.arn())
+                        .map(str::to_string),
+                });
+            }
+        }
+    }
+
+    let mut allowing_policies = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = logs
+            .describe_resource_policies()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for policy in response.resource_policies() {
+            if policy
+                .policy_document()
+                .is_some_and(|doc| policy_allows_events(doc, &target_arn))
+            {
+                allowing_policies.push(policy.policy_name().unwrap_or_default().to_string());
+            }
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if feeds.is_empty() {
+        warnings.push("No EventBridge rule targets this log group".to_string());
+    }
+    for feed in feeds
+        .iter()
+        .filter(|f| f.state.as_deref() != Some("ENABLED"))
+    {
+        warnings.push(format!(
+            "Rule {} on bus {} is {}",
+            feed.rule_name,
+            feed.event_bus_name,
+            feed.state.as_deref().unwrap_or("in an unknown state")
+        ));
+    }
+    if !feeds.is_empty() && allowing_policies.is_empty() {
+        warnings.push(format!(
+            "No CloudWatch Logs resource policy allows {} to write to this log group",
+            EVENTS_PRINCIPAL
+        ));
+    }
+
+    Ok(EventBridgeFeedReport {
+        log_group_name,
+        feeds,
+        resource_policy_allows_events: !allowing_policies.is_empty(),
+        allowing_policies,
+        warnings,
+    })
+}
+
+/// Stream the delivery test writes to when none is given
+const DEFAULT_TEST_STREAM: &str = "loggy-delivery-test";
+
+const DEFAULT_DELIVERY_TIMEOUT_SECS: u64 = 60;
+const MAX_DELIVERY_TIMEOUT_SECS: u64 = 300;
+
+/// Minimum time given to subscription destinations after the search check
+const MIN_DESTINATION_WAIT_SECS: u64 = 15;
+
+/// Delay between checks for the marker
+const DELIVERY_POLL_SECS: u64 = 2;
+
+/// Whether the test marker reached one subscription destination
+#[derive(Debug, Clone, Serialize)]
+pub struct DestinationCheck {
+    pub filter_name: Option<String>,
+    pub destination_arn: Option<String>,
+    /// "kinesis", "firehose", "lambda", "logs-destination" or "unknown"
+    pub kind: String,
+    /// Whether the marker arrived; None when Loggy can't read the destination back
+    pub verified: Option<bool>,
+    /// Milliseconds from the write until the marker was seen
+    pub latency_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Outcome of test_log_delivery
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryTestResult {
+    pub log_group_name: String,
+    pub log_stream_name: String,
+    pub marker: String,
+    pub written_at: i64,
+    /// Whether FilterLogEvents returned the marker before the timeout
+    pub searchable: bool,
+    pub search_latency_ms: Option<i64>,
+    pub destinations: Vec<DestinationCheck>,
+}
+
+fn destination_kind(arn: &str) -> &'static str {
+    match arn.split(':').nth(2) {
+        Some("kinesis") => "kinesis",
+        Some("firehose") => "firehose",
+        Some("lambda") => "lambda",
+        Some("logs") => "logs-destination",
+        _ => "unknown",
+    }
+}
+
+/// Read a Kinesis stream from just before the write until the marker shows up in
+/// a decoded subscription payload; returns the latency, or None at the deadline
+async fn wait_in_kinesis(
+    config: &aws_config::SdkConfig,
+    stream_arn: &str,
+    marker: &str,
+    written_at: i64,
+    deadline: std::time::Instant,
+) -> Result<Option<i64>, String> {
+    let stream_name = stream_arn
+        .split_once(":stream/")
+        .map(|(_, name)| name)
+        .ok_or_else(|| format!("{} is not a Kinesis stream ARN", stream_arn))?;
+    let mut kinesis_config = aws_sdk_kinesis::config::Builder::from(config);
+    if let Some(region) = stream_arn.split(':').nth(3).filter(|r| !r.is_empty()) {
+        kinesis_config = kinesis_config.region(aws_config::Region::new(region.to_string()));
+    }
+    let client = aws_sdk_kinesis::Client::from_conf(kinesis_config.build());
+
+    let mut iterators = Vec::new();
+    for shard_id in crate::kinesis::list_shard_ids(&client, stream_name).await? {
+        let iterator = client
+            .get_shard_iterator()
+            .stream_name(stream_name)
+            .shard_id(&shard_id)
+            .shard_iterator_type(aws_sdk_kinesis::types::ShardIteratorType::AtTimestamp)
+            .timestamp(aws_sdk_kinesis::primitives::DateTime::from_millis(
+                written_at - 5_000,
+            ))
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+            .shard_iterator;
+        iterators.extend(iterator);
+    }
+
+    while std::time::Instant::now() < deadline && !iterators.is_empty() {
+        let mut next = Vec::with_capacity(iterators.len());
+        for iterator in iterators {
+            let response = client
+                .get_records()
+                .shard_iterator(iterator)
+                .send()
+                .await
+                .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+            for record in response.records() {
+                let payload = crate::kinesis::decode_subscription_data(
+                    record.data().as_ref(),
+                    0,
+                    record.sequence_number(),
+                );
+                if payload.events.iter().any(|e| e.message.contains(marker)) {
+                    return Ok(Some(chrono::Utc::now().timestamp_millis() - written_at));
+                }
+            }
+            next.extend(response.next_shard_iterator);
+        }
+        iterators = next;
+        tokio::time::sleep(std::time::Duration::from_secs(DELIVERY_POLL_SECS)).await;
+    }
+    Ok(None)
+}
+
+/// Smoke-test a log group's ingestion pipeline: write a unique marker event to a
+/// test stream with PutLogEvents, wait for FilterLogEvents to return it, and (unless
+/// `check_subscriptions` is false) look for it in each subscription destination.
+/// Kinesis destinations are read back; Firehose, Lambda and cross-account
+/// destinations are listed unverified. The write is audited.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn test_log_delivery(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    log_stream_name: Option<String>,
+    check_subscriptions: Option<bool>,
+    timeout_secs: Option<u64>,
+    confirm_environment: Option<String>,
+) -> Result<DeliveryTestResult, String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let log_stream_name = log_stream_name
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_TEST_STREAM.to_string());
+    let timeout = std::time::Duration::from_secs(
+        timeout_secs
+            .unwrap_or(DEFAULT_DELIVERY_TIMEOUT_SECS)
+            .clamp(1, MAX_DELIVERY_TIMEOUT_SECS),
+    );
+    let now = chrono::Utc::now();
+    let written_at = now.timestamp_millis();
+    let marker = format!(
+        "LOGGY-DELIVERY-TEST-{:x}",
+        now.timestamp_nanos_opt().unwrap_or(written_at)
+    );
+
+    let write = async {
+        if let Err(e) = client
+            .create_log_stream()
+            .log_group_name(&log_group_name)
+            .log_stream_name(&log_stream_name)
+            .send()
+            .await
+        {
+            let exists = e
+                .as_service_error()
+                .is_some_and(|se| se.is_resource_already_exists_exception());
+            if !exists {
+                return Err(humanize_aws_error(&format!("{}", e)));
+            }
+        }
+        let event = InputLogEvent::builder()
+            .timestamp(written_at)
+            .message(format!("{} Loggy delivery test", marker))
+            .build()
+            .map_err(|e| e.to_string())?;
+        client
+            .put_log_events()
+            .log_group_name(&log_group_name)
+            .log_stream_name(&log_stream_name)
+            .log_events(event)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))
+    }
+    .await;
+    audit::record(
+        &app,
+        &state,
+        "test_log_delivery",
+        serde_json::json!({
+            "log_group_name": log_group_name,
+            "log_stream_name": log_stream_name,
+            "marker": marker,
+        }),
+        &write,
+    )
+    .await;
+    write?;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut search_latency_ms = None;
+    loop {
+        let found = client
+            .filter_log_events()
+            .log_group_name(&log_group_name)
+            .log_stream_names(&log_stream_name)
+            .start_time(written_at - 1_000)
+            .filter_pattern(format!("\"{}\"", marker))
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+            .events()
+            .iter()
+            .any(|e| e.message().is_some_and(|m| m.contains(&marker)));
+        if found {
+            search_latency_ms = Some(chrono::Utc::now().timestamp_millis() - written_at);
+            break;
+        }
+        if std::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(DELIVERY_POLL_SECS)).await;
+    }
+
+    let mut destinations = Vec::new();
+    if check_subscriptions.unwrap_or(true) {
+        let filters = client
+            .describe_subscription_filters()
+            .log_group_name(&log_group_name)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        let config = state.sdk_config().await?;
+        let deadline = deadline.max(
+            std::time::Instant::now() + std::time::Duration::from_secs(MIN_DESTINATION_WAIT_SECS),
+        );
+        for filter in filters.subscription_filters() {
+            let arn = filter.destination_arn().map(str::to_string);
+            let kind = arn.as_deref().map_or("unknown", destination_kind);
+            let mut check = DestinationCheck {
+                filter_name: filter.filter_name().map(str::to_string),
+                destination_arn: arn.clone(),
+                kind: kind.to_string(),
+                verified: None,
+                latency_ms: None,
+                note: None,
+            };
+            match (kind, arn.as_deref()) {
+                ("kinesis", Some(arn)) => {
+                    match wait_in_kinesis(&config, arn, &marker, written_at, deadline).await {
+                        Ok(latency) => {
+                            check.verified = Some(latency.is_some());
+                            check.latency_ms = latency;
+                        }
+                        Err(e) => check.note = Some(format!("Could not read the stream: {}", e)),
+                    }
+                }
+                _ => {
+                    check.note = Some(format!(
+                        "Delivery to {} destinations can't be read back; check the target for {}",
+                        kind, marker
+                    ))
+                }
+            }
+            destinations.push(check);
+        }
+    }
+
+    log::info!(
+        "Delivery test {} on {}: searchable={}, {} destinations",
+        marker,
+        log_group_name,
+        search_latency_ms.is_some(),
+        destinations.len()
+    );
+    Ok(DeliveryTestResult {
+        log_group_name,
+        log_stream_name,
+        marker,
+        written_at,
+        searchable: search_latency_ms.is_some(),
+        search_latency_ms,
+        destinations,
+    })
+}
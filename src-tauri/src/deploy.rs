@@ -0,0 +1,219 @@
+use crate::alerts::DEFAULT_ERROR_PATTERN;
+use crate::{diff, humanize_aws_error, redaction, tail, workspace, AppState, LogEvent};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+/// Time after the deploy searched when no window is given
+const DEFAULT_WINDOW_MINUTES: i64 = 60;
+
+/// Matching events fetched per group and side of the deploy
+const MAX_EVENTS_PER_GROUP: usize = 5_000;
+
+/// Groups searched at once
+const MAX_CONCURRENT_GROUPS: usize = 4;
+
+/// The first error a log group logged after a deploy
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupFirstError {
+    pub log_group_name: String,
+    /// Earliest error-classified event after the deploy, masked for display
+    pub first_error: Option<LogEvent>,
+    /// Milliseconds from the deploy to the first error
+    pub delay_ms: Option<i64>,
+    /// Errors in the window after the deploy
+    pub error_count: usize,
+    pub signature: Option<String>,
+    /// The first error's signature never appeared in the same span before the deploy
+    pub new_since_deploy: bool,
+    /// More events matched than were fetched
+    pub truncated: bool,
+    /// The group couldn't be searched
+    pub error: Option<String>,
+}
+
+/// First errors after a deploy across groups, most suspicious first: errors new
+/// since the deploy, then those closest to it
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployErrorReport {
+    pub deploy_time: i64,
+    /// "manual" or "stack:<name>" (the stack's last update)
+    pub deploy_source: String,
+    pub window_end: i64,
+    pub groups: Vec<GroupFirstError>,
+}
+
+/// Time of a CloudFormation stack's last update (or creation, if never updated)
+async fn stack_deploy_time(state: &AppState, stack_name: &str) -> Result<i64, String> {
+    let config = state.sdk_config().await?;
+    let client = aws_sdk_cloudformation::Client::new(&config);
+    let response = client
+        .describe_stacks()
+        .stack_name(stack_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let stack = response
+        .stacks()
+        .first()
+        .ok_or_else(|| format!("Stack {} not found", stack_name))?;
+    stack
+        .last_updated_time()
+        .or(stack.creation_time())
+        .and_then(|t| t.to_millis().ok())
+        .ok_or_else(|| format!("Stack {} has no deploy time", stack_name))
+}
+
+/// Error-classified events of a group in a time range, oldest first
+async fn errors_in(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    start: i64,
+    end: i64,
+) -> Result<(Vec<LogEvent>, bool), String> {
+    let (mut events, truncated) = crate::filter_all_events(
+        client,
+        log_group_name,
+        Some(start),
+        Some(end),
+        Some(DEFAULT_ERROR_PATTERN),
+        MAX_EVENTS_PER_GROUP,
+    )
+    .await?;
+    events.retain(|e| tail::is_error_event(&e.message));
+    events.sort_by_key(|e| e.timestamp);
+    Ok((events, truncated))
+}
+
+/// Find the first error-classified event after a deploy in each log group of a
+/// workspace (or `log_group_names`), answering "what broke right after we
+/// shipped". The deploy time is `deploy_time` (epoch ms) or the last update of
+/// CloudFormation stack `stack_name`. Each first error is compared against the
+/// errors of the same span before the deploy, and errors new since the deploy
+/// rank first.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn find_first_errors_after_deploy(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    workspace_name: Option<String>,
+    log_group_names: Option<Vec<String>>,
+    deploy_time: Option<i64>,
+    stack_name: Option<String>,
+    window_minutes: Option<i64>,
+) -> Result<DeployErrorReport, String> {
+    let groups = match (workspace_name, log_group_names) {
+        (_, Some(names)) if !names.is_empty() => names,
+        (Some(name), _) => {
+            workspace::load_workspaces(&app)
+                .into_iter()
+                .find(|w| w.name == name)
+                .ok_or_else(|| format!("Workspace {} not found", name))?
+                .log_groups
+        }
+        _ => return Err("Choose a workspace or log groups to search".to_string()),
+    };
+    if groups.is_empty() {
+        return Err("The workspace has no log groups".to_string());
+    }
+    let (deploy_time, deploy_source) = match (deploy_time, stack_name) {
+        (Some(time), _) => (time, "manual".to_string()),
+        (None, Some(stack)) => (
+            stack_deploy_time(&state, &stack).await?,
+            format!("stack:{}", stack),
+        ),
+        (None, None) => return Err("Enter a deploy time or a stack to look it up".to_string()),
+    };
+    let window_ms = window_minutes.unwrap_or(DEFAULT_WINDOW_MINUTES).max(1) * 60_000;
+    let window_end = (deploy_time + window_ms).min(chrono::Utc::now().timestamp_millis());
+    if window_end <= deploy_time {
+        return Err("The deploy time is in the future".to_string());
+    }
+
+    let client = state.logs_client().await?;
+    let permits = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_GROUPS));
+    let mut tasks = tokio::task::JoinSet::new();
+    for group in groups {
+        let client = client.clone();
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let span = window_end - deploy_time;
+            let (after, before) = tokio::join!(
+                errors_in(&client, &group, deploy_time, window_end),
+                errors_in(&client, &group, deploy_time - span, deploy_time),
+            );
+            (group, after, before)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((log_group_name, after, before)) = joined else {
+            continue;
+        };
+        let (mut errors, truncated) = match after {
+            Ok(found) => found,
+            Err(e) => {
+                results.push(GroupFirstError {
+                    log_group_name,
+                    first_error: None,
+                    delay_ms: None,
+                    error_count: 0,
+                    signature: None,
+                    new_since_deploy: false,
+                    truncated: false,
+                    error: Some(e),
+                });
+                continue;
+            }
+        };
+        // Signatures quote message text, so they are built from masked messages
+        redaction::mask_for_display(&app, &state, None, &mut errors).await;
+        let mut baseline = before.map(|(events, _)| events).unwrap_or_default();
+        redaction::mask_for_display(&app, &state, None, &mut baseline).await;
+        let known: HashSet<String> = baseline
+            .iter()
+            .map(|e| diff::signature(&e.message))
+            .collect();
+
+        let error_count = errors.len();
+        let first_error = errors.into_iter().next();
+        let signature = first_error.as_ref().map(|e| diff::signature(&e.message));
+        results.push(GroupFirstError {
+            log_group_name,
+            delay_ms: first_error.as_ref().map(|e| e.timestamp - deploy_time),
+            new_since_deploy: signature.as_ref().is_some_and(|s| !known.contains(s)),
+            first_error,
+            error_count,
+            signature,
+            truncated,
+            error: None,
+        });
+    }
+
+    // New errors first, then by how soon they followed the deploy; quiet groups last
+    results.sort_by(|a, b| {
+        b.new_since_deploy
+            .cmp(&a.new_since_deploy)
+            .then_with(|| match (a.delay_ms, b.delay_ms) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.log_group_name.cmp(&b.log_group_name),
+            })
+    });
+    log::info!(
+        "Searched {} groups for first errors after the {} deploy at {}",
+        results.len(),
+        deploy_source,
+        deploy_time
+    );
+    Ok(DeployErrorReport {
+        deploy_time,
+        deploy_source,
+        window_end,
+        groups: results,
+    })
+}
@@ -0,0 +1,634 @@
+use crate::connections::EnvironmentEmitter;
+use crate::pipeline::{self, PipelineStage, ProcessedEvent};
+use crate::{redaction, storage, tail, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding the last run of each saved query
+const QUERY_RUNS_FILE: &str = "query-runs.json";
+
+/// Longest error signature kept
+const MAX_SIGNATURE_CHARS: usize = 160;
+
+/// Error signatures remembered per run
+const MAX_SIGNATURES: usize = 200;
+
+/// Prefix of the runs file keys holding the last fetch of each log group, saved
+/// query or not
+const GROUP_RUN_PREFIX: &str = "log-group:";
+
+/// Serializes read-modify-write of the runs file
+static QUERY_RUNS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// What is remembered about one run of a saved query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub ran_at: i64,
+    pub log_group_name: String,
+    pub filter_pattern: Option<String>,
+    pub event_count: usize,
+    pub error_count: usize,
+    /// Newest event timestamp in the result
+    pub latest_timestamp: Option<i64>,
+    /// Normalized error message -> occurrences
+    pub error_signatures: BTreeMap<String, usize>,
+    /// Signatures absent from the previous run, most frequent first
+    #[serde(default)]
+    pub new_errors: Vec<SignatureChange>,
+}
+
+/// An error signature whose count changed between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureChange {
+    pub signature: String,
+    pub previous: usize,
+    pub current: usize,
+}
+
+/// Delta between a saved query's previous run and this one
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryDiff {
+    pub query_id: String,
+    pub previous_ran_at: i64,
+    pub current_ran_at: i64,
+    pub previous_count: usize,
+    pub current_count: usize,
+    /// Percent change in result volume (None when the previous run was empty)
+    pub volume_change_percent: Option<f64>,
+    pub previous_error_count: usize,
+    pub current_error_count: usize,
+    /// Events newer than anything in the previous result
+    pub new_events: usize,
+    /// Errors that appear now but did not before
+    pub new_errors: Vec<SignatureChange>,
+    /// Errors seen before that no longer appear
+    pub resolved_errors: Vec<SignatureChange>,
+    /// Errors present in both runs with a different count
+    pub changed_errors: Vec<SignatureChange>,
+}
+
+/// Payload for query-diff
+#[derive(Debug, Clone, Serialize)]
+struct QueryDiffPayload {
+    fetch_id: u32,
+    diff: QueryDiff,
+}
+
+/// Collapse the variable parts of an error message (ids, numbers, timestamps) so
+/// repeats of the same error share a signature
+pub(crate) fn signature(message: &str) -> String {
+    let trimmed = message.trim();
+    let text = serde_json::from_str::<serde_json::Value>(trimmed)
+        .ok()
+        .and_then(|json| {
+            ["message", "msg", "error"]
+                .iter()
+                .find_map(|f| json.get(*f).and_then(|v| v.as_str()).map(str::to_string))
+        })
+        .unwrap_or_else(|| trimmed.lines().next().unwrap_or_default().to_string());
+    text.split_whitespace()
+        .map(|token| {
+            if token.chars().any(|c| c.is_ascii_digit()) {
+                "#"
+            } else {
+                token
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_SIGNATURE_CHARS)
+        .collect()
+}
+
+fn summarize(
+    log_group_name: &str,
+    filter_pattern: Option<&str>,
+    events: &[LogEvent],
+) -> RunSummary {
+    let mut signatures: HashMap<String, usize> = HashMap::new();
+    let mut error_count = 0;
+    for event in events {
+        if tail::is_error_event(&event.message) {
+            error_count += 1;
+            *signatures.entry(signature(&event.message)).or_default() += 1;
+        }
+    }
+    let mut ranked: Vec<(String, usize)> = signatures.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(MAX_SIGNATURES);
+
+    RunSummary {
+        ran_at: chrono::Utc::now().timestamp_millis(),
+        log_group_name: log_group_name.to_string(),
+        filter_pattern: filter_pattern.map(str::to_string),
+        event_count: events.len(),
+        error_count,
+        latest_timestamp: events.iter().map(|e| e.timestamp).max(),
+        error_signatures: ranked.into_iter().collect(),
+        new_errors: Vec::new(),
+    }
+}
+
+fn compare(
+    query_id: &str,
+    previous: &RunSummary,
+    current: &RunSummary,
+    events: &[LogEvent],
+) -> QueryDiff {
+    let mut new_errors = Vec::new();
+    let mut resolved_errors = Vec::new();
+    let mut changed_errors = Vec::new();
+    for (sig, &count) in &current.error_signatures {
+        match previous.error_signatures.get(sig) {
+            None => new_errors.push(SignatureChange {
+                signature: sig.clone(),
+                previous: 0,
+                current: count,
+            }),
+            Some(&before) if before != count => changed_errors.push(SignatureChange {
+                signature: sig.clone(),
+                previous: before,
+                current: count,
+            }),
+            Some(_) => {}
+        }
+    }
+    for (sig, &count) in &previous.error_signatures {
+        if !current.error_signatures.contains_key(sig) {
+            resolved_errors.push(SignatureChange {
+                signature: sig.clone(),
+                previous: count,
+                current: 0,
+            });
+        }
+    }
+    for list in [&mut new_errors, &mut resolved_errors] {
+        list.sort_by_key(|c| std::cmp::Reverse(c.current.max(c.previous)));
+    }
+
+    QueryDiff {
+        query_id: query_id.to_string(),
+        previous_ran_at: previous.ran_at,
+        current_ran_at: current.ran_at,
+        previous_count: previous.event_count,
+        current_count: current.event_count,
+        volume_change_percent: (previous.event_count > 0).then(|| {
+            (current.event_count as f64 - previous.event_count as f64) * 100.0
+                / previous.event_count as f64
+        }),
+        previous_error_count: previous.error_count,
+        current_error_count: current.error_count,
+        new_events: match previous.latest_timestamp {
+            Some(latest) => events.iter().filter(|e| e.timestamp > latest).count(),
+            None => events.len(),
+        },
+        new_errors,
+        resolved_errors,
+        changed_errors,
+    }
+}
+
+/// Record a saved query's run and, if it ran before, emit `query-diff` with the
+/// delta against the previous run. Runs in the background.
+pub fn record_run(
+    app: &AppHandle,
+    query_id: &str,
+    fetch_id: u32,
+    log_group_name: &str,
+    filter_pattern: Option<&str>,
+    events: &[LogEvent],
+) {
+    record(
+        app,
+        query_id.to_string(),
+        Some(fetch_id),
+        log_group_name,
+        filter_pattern,
+        events,
+    );
+}
+
+/// Record the latest fetch of a log group (for workspace_summary), without a diff event
+pub fn record_group_run(
+    app: &AppHandle,
+    log_group_name: &str,
+    filter_pattern: Option<&str>,
+    events: &[LogEvent],
+) {
+    record(
+        app,
+        format!("{}{}", GROUP_RUN_PREFIX, log_group_name),
+        None,
+        log_group_name,
+        filter_pattern,
+        events,
+    );
+}
+
+fn record(
+    app: &AppHandle,
+    key: String,
+    fetch_id: Option<u32>,
+    log_group_name: &str,
+    filter_pattern: Option<&str>,
+    events: &[LogEvent],
+) {
+    let app = app.clone();
+    let log_group_name = log_group_name.to_string();
+    let filter_pattern = filter_pattern.map(str::to_string);
+    let events = events.to_vec();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut current = summarize(&log_group_name, filter_pattern.as_deref(), &events);
+        let _guard = QUERY_RUNS_FILE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut runs: HashMap<String, RunSummary> = storage::read_json(&app, QUERY_RUNS_FILE);
+        if let Some(previous) = runs.get(&key) {
+            let diff = compare(&key, previous, &current, &events);
+            current.new_errors = diff.new_errors.clone();
+            if let Some(fetch_id) = fetch_id {
+                app.emit_tagged("query-diff", QueryDiffPayload { fetch_id, diff })
+                    .ok();
+            }
+        }
+        runs.insert(key, current);
+        if let Err(e) = storage::write_json(&app, QUERY_RUNS_FILE, &runs) {
+            log::warn!("Failed to persist query run: {}", e);
+        }
+    });
+}
+
+/// The last recorded fetch of a log group
+pub fn last_group_run(app: &AppHandle, log_group_name: &str) -> Option<RunSummary> {
+    let mut runs: HashMap<String, RunSummary> = storage::read_json(app, QUERY_RUNS_FILE);
+    runs.remove(&format!("{}{}", GROUP_RUN_PREFIX, log_group_name))
+}
+
+/// The last recorded run of a saved query
+#[tauri::command]
+pub fn get_last_query_run(app: AppHandle, query_id: String) -> Option<RunSummary> {
+    let mut runs: HashMap<String, RunSummary> = storage::read_json(&app, QUERY_RUNS_FILE);
+    runs.remove(&query_id)
+}
+
+/// Forget a saved query's previous run (e.g. when the query is deleted)
+#[tauri::command]
+pub fn clear_query_run(app: AppHandle, query_id: String) -> Result<(), String> {
+    let _guard = QUERY_RUNS_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut runs: HashMap<String, RunSummary> = storage::read_json(&app, QUERY_RUNS_FILE);
+    if runs.remove(&query_id).is_some() {
+        storage::write_json(&app, QUERY_RUNS_FILE, &runs)?;
+    }
+    Ok(())
+}
+
+/// How a field differs between two events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One differing field, addressed like `http.status` or `items[2].id`
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub path: String,
+    pub change: FieldChangeKind,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Structured diff of two events, from `a` to `b`
+#[derive(Debug, Clone, Serialize)]
+pub struct EventDiff {
+    pub a: LogEvent,
+    pub b: LogEvent,
+    /// Both messages parsed as JSON or into fields; otherwise the whole message is
+    /// compared as one `message` field
+    pub structured: bool,
+    pub changes: Vec<FieldChange>,
+    /// Leaf fields equal in both
+    pub unchanged: usize,
+}
+
+/// The JSON object in a message: the whole message, else the span from its first
+/// `{` to its last `}` (JSON after a Lambda or logger prefix)
+pub(crate) fn embedded_json(message: &str) -> Option<serde_json::Value> {
+    let trimmed = message.trim();
+    let start = trimmed.find('{')?;
+    let end = trimmed.rfind('}')?;
+    serde_json::from_str::<serde_json::Value>(trimmed.get(start..=end)?)
+        .ok()
+        .filter(serde_json::Value::is_object)
+}
+
+/// An event as a JSON value to diff: its JSON, else the fields its log group
+/// pipeline extracts (without multiline stitching)
+fn structured(app: &AppHandle, event: &LogEvent) -> Option<serde_json::Value> {
+    if let Some(json) = embedded_json(&event.message) {
+        return Some(json);
+    }
+    let mut processed = vec![ProcessedEvent::from(event)];
+    let source = event.source.clone().unwrap_or_default();
+    for stage in pipeline::pipeline_for_group(app, &source) {
+        if matches!(stage, PipelineStage::StitchMultiline { .. }) {
+            continue;
+        }
+        if let Ok(next) = pipeline::apply_stage(&stage, processed.clone()) {
+            processed = next;
+        }
+    }
+    let processed = processed.pop()?;
+    if let Some(json) = processed.json.filter(serde_json::Value::is_object) {
+        return Some(json);
+    }
+    (!processed.fields.is_empty()).then(|| {
+        serde_json::Value::Object(
+            processed
+                .fields
+                .into_iter()
+                .map(|(k, v)| (k, serde_json::Value::String(v)))
+                .collect(),
+        )
+    })
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn diff_values(
+    path: &str,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+    changes: &mut Vec<FieldChange>,
+    unchanged: &mut usize,
+) {
+    use serde_json::Value;
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, value) in a {
+                let path = child_path(path, key);
+                match b.get(key) {
+                    Some(other) => diff_values(&path, value, other, changes, unchanged),
+                    None => changes.push(FieldChange {
+                        path,
+                        change: FieldChangeKind::Removed,
+                        before: Some(value.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, value) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                changes.push(FieldChange {
+                    path: child_path(path, key),
+                    change: FieldChangeKind::Added,
+                    before: None,
+                    after: Some(value.clone()),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let path = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => diff_values(&path, x, y, changes, unchanged),
+                    (Some(x), None) => changes.push(FieldChange {
+                        path,
+                        change: FieldChangeKind::Removed,
+                        before: Some(x.clone()),
+                        after: None,
+                    }),
+                    (None, Some(y)) => changes.push(FieldChange {
+                        path,
+                        change: FieldChangeKind::Added,
+                        before: None,
+                        after: Some(y.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if before == after => *unchanged += 1,
+        _ => changes.push(FieldChange {
+            path: path.to_string(),
+            change: FieldChangeKind::Changed,
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+    }
+}
+
+/// Diff two events of a session field by field (added, removed and changed fields
+/// of their parsed JSON), e.g. a succeeding and a failing request payload. Values
+/// are compared after display masking.
+#[tauri::command]
+pub async fn diff_events(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    a: String,
+    b: String,
+) -> Result<EventDiff, String> {
+    let (event_a, event_b) = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let find = |id: &str| {
+            session
+                .event(id)
+                .ok_or_else(|| format!("Event {} is not in session {}", id, session_id))
+        };
+        (find(&a)?, find(&b)?)
+    };
+    let mut events = [event_a, event_b];
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    let [event_a, event_b] = events;
+
+    let (before, after, structured) = match (structured(&app, &event_a), structured(&app, &event_b))
+    {
+        (Some(before), Some(after)) => (before, after, true),
+        _ => (
+            serde_json::json!({ "message": event_a.message }),
+            serde_json::json!({ "message": event_b.message }),
+            false,
+        ),
+    };
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    diff_values("", &before, &after, &mut changes, &mut unchanged);
+    Ok(EventDiff {
+        a: event_a,
+        b: event_b,
+        structured,
+        changes,
+        unchanged,
+    })
+}
+
+/// Cap on events fetched per filter by compare_filters
+const DEFAULT_COMPARE_MAX_EVENTS: usize = 20_000;
+
+/// Templates listed per filter by compare_filters
+const MAX_COMPARED_TEMPLATES: usize = 50;
+
+/// A message template and how many events of a result it covers
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateCount {
+    pub template: String,
+    pub count: usize,
+}
+
+/// What one filter of a comparison matched
+#[derive(Debug, Clone, Serialize)]
+pub struct FilterResult {
+    pub filter_pattern: String,
+    pub count: usize,
+    /// The fetch stopped at the event cap, so counts are lower bounds
+    pub truncated: bool,
+    pub template_count: usize,
+    /// Most frequent templates of the result
+    pub top_templates: Vec<TemplateCount>,
+    /// Templates the other filter matched none of, most frequent first
+    pub unique_templates: Vec<TemplateCount>,
+}
+
+/// Events both filters matched, compared by event id
+#[derive(Debug, Clone, Serialize)]
+pub struct FilterOverlap {
+    pub both: usize,
+    pub only_a: usize,
+    pub only_b: usize,
+    /// both / (matched by either), 0 when neither matched anything
+    pub jaccard: f64,
+}
+
+/// Side-by-side results of two filters over the same group and range
+#[derive(Debug, Clone, Serialize)]
+pub struct FilterComparison {
+    pub a: FilterResult,
+    pub b: FilterResult,
+    pub overlap: FilterOverlap,
+}
+
+fn ranked_templates(events: &[LogEvent]) -> Vec<TemplateCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for event in events {
+        *counts.entry(signature(&event.message)).or_default() += 1;
+    }
+    let mut ranked: Vec<TemplateCount> = counts
+        .into_iter()
+        .map(|(template, count)| TemplateCount { template, count })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.template.cmp(&b.template))
+    });
+    ranked
+}
+
+fn filter_result(
+    filter_pattern: String,
+    events: &[LogEvent],
+    truncated: bool,
+    templates: &[TemplateCount],
+    other: &[TemplateCount],
+) -> FilterResult {
+    let other: std::collections::HashSet<&str> =
+        other.iter().map(|t| t.template.as_str()).collect();
+    FilterResult {
+        filter_pattern,
+        count: events.len(),
+        truncated,
+        template_count: templates.len(),
+        top_templates: templates
+            .iter()
+            .take(MAX_COMPARED_TEMPLATES)
+            .cloned()
+            .collect(),
+        unique_templates: templates
+            .iter()
+            .filter(|t| !other.contains(t.template.as_str()))
+            .take(MAX_COMPARED_TEMPLATES)
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Run two filter patterns over the same log group and time range concurrently
+/// and compare what they match: counts, message templates unique to each and the
+/// overlap of matched events, e.g. to check a refined alert pattern still catches
+/// what the noisy one did
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn compare_filters(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_a: String,
+    filter_b: String,
+    max_count: Option<usize>,
+) -> Result<FilterComparison, String> {
+    let client = state.logs_client().await?;
+    let max_events = max_count.unwrap_or(DEFAULT_COMPARE_MAX_EVENTS);
+    let (result_a, result_b) = tokio::join!(
+        crate::filter_all_events(
+            &client,
+            &log_group_name,
+            start_time,
+            end_time,
+            Some(filter_a.as_str()),
+            max_events,
+        ),
+        crate::filter_all_events(
+            &client,
+            &log_group_name,
+            start_time,
+            end_time,
+            Some(filter_b.as_str()),
+            max_events,
+        ),
+    );
+    let (mut events_a, truncated_a) = result_a?;
+    let (mut events_b, truncated_b) = result_b?;
+    let id = |e: &LogEvent| e.event_id.clone().unwrap_or_else(|| e.synthesized_id());
+    let ids_a: std::collections::HashSet<String> = events_a.iter().map(id).collect();
+    let ids_b: std::collections::HashSet<String> = events_b.iter().map(id).collect();
+    let both = ids_a.intersection(&ids_b).count();
+    let either = ids_a.len() + ids_b.len() - both;
+    let overlap = FilterOverlap {
+        both,
+        only_a: ids_a.len() - both,
+        only_b: ids_b.len() - both,
+        jaccard: if either == 0 {
+            0.0
+        } else {
+            both as f64 / either as f64
+        },
+    };
+
+    // Templates quote message text, so they are built from masked messages
+    redaction::mask_for_display(&app, &state, None, &mut events_a).await;
+    redaction::mask_for_display(&app, &state, None, &mut events_b).await;
+    let templates_a = ranked_templates(&events_a);
+    let templates_b = ranked_templates(&events_b);
+    Ok(FilterComparison {
+        a: filter_result(filter_a, &events_a, truncated_a, &templates_a, &templates_b),
+        b: filter_result(filter_b, &events_b, truncated_b, &templates_b, &templates_a),
+        overlap,
+    })
+}
@@ -0,0 +1,247 @@
+use crate::catalog::log_group_tags;
+use crate::{humanize_aws_error, storage, AppState};
+use aws_sdk_sqs::types::{MessageSystemAttributeName, QueueAttributeName};
+use aws_sdk_sqs::Client as SqsClient;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory mapping log groups to their DLQ
+const DLQ_MAPPINGS_FILE: &str = "dlq-mappings.json";
+
+/// Serializes read-modify-write of the mappings file
+static DLQ_MAPPINGS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Log group tag keys naming a dead-letter queue (URL, ARN or name)
+const DLQ_TAG_KEYS: [&str; 6] = [
+    "dlq",
+    "DLQ",
+    "dlq-url",
+    "dlq_url",
+    "dead-letter-queue",
+    "DeadLetterQueue",
+];
+
+/// ReceiveMessage returns at most 10 messages per call
+const RECEIVE_BATCH: i32 = 10;
+
+const DEFAULT_PEEK_MESSAGES: usize = 50;
+const MAX_PEEK_MESSAGES: usize = 200;
+
+/// The DLQ related to a log group and how it was found
+#[derive(Debug, Clone, Serialize)]
+pub struct DlqInfo {
+    pub queue_url: String,
+    /// "mapping" (set by the user), "tag", or "name" (naming convention match)
+    pub source: String,
+    pub approximate_messages: Option<i64>,
+}
+
+/// A message peeked from a DLQ (left in the queue)
+#[derive(Debug, Clone, Serialize)]
+pub struct DlqMessage {
+    pub message_id: Option<String>,
+    pub body: Option<String>,
+    /// Epoch milliseconds the message was first sent (to the source queue)
+    pub sent_timestamp: Option<i64>,
+    pub receive_count: Option<u32>,
+    pub attributes: BTreeMap<String, String>,
+    /// String-valued message attributes (binary values are omitted)
+    pub message_attributes: BTreeMap<String, String>,
+}
+
+/// Resolve a queue reference (URL, ARN, or name) to its URL
+async fn queue_url(client: &SqsClient, reference: &str) -> Result<String, String> {
+    if reference.starts_with("https://") {
+        return Ok(reference.to_string());
+    }
+    // arn:aws:sqs:<region>:<account>:<name>
+    let (name, owner) = match reference.strip_prefix("arn:") {
+        Some(_) => {
+            let parts: Vec<&str> = reference.split(':').collect();
+            if parts.len() != 6 || parts[2] != "sqs" {
+                return Err(format!("{} is not an SQS queue ARN", reference));
+            }
+            (parts[5], Some(parts[4]))
+        }
+        None => (reference, None),
+    };
+    client
+        .get_queue_url()
+        .queue_name(name)
+        .set_queue_owner_aws_account_id(owner.map(str::to_string))
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+        .queue_url
+        .ok_or_else(|| format!("Queue {} not found", reference))
+}
+
+/// Queues whose name contains the group's service name and marks a DLQ
+async fn dlq_by_name(client: &SqsClient, log_group_name: &str) -> Option<String> {
+    let service = log_group_name
+        .rsplit('/')
+        .find(|p| !p.is_empty())?
+        .to_lowercase();
+    let response = client
+        .list_queues()
+        .send()
+        .await
+        .map_err(|e| log::debug!("ListQueues failed: {:?}", e))
+        .ok()?;
+    response.queue_urls().iter().find_map(|url| {
+        let name = url.rsplit('/').next()?.to_lowercase();
+        let is_dlq =
+            name.contains("dlq") || name.contains("deadletter") || name.contains("dead-letter");
+        (is_dlq && name.contains(&service)).then(|| url.clone())
+    })
+}
+
+/// Find the dead-letter queue related to a log group: a user mapping first, then a
+/// DLQ tag on the group, then a queue named after the group's service with "dlq"
+#[tauri::command]
+pub async fn find_dlq(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+) -> Result<Option<DlqInfo>, String> {
+    let config = state.sdk_config().await?;
+    let client = SqsClient::new(&config);
+
+    let mappings: HashMap<String, String> = storage::read_json(&app, DLQ_MAPPINGS_FILE);
+    let mut found = mappings
+        .get(&log_group_name)
+        .map(|queue| (queue.clone(), "mapping"));
+
+    if found.is_none() {
+        let logs = state.logs_client().await?;
+        let arn = crate::catalog::describe_all_log_groups(&logs)
+            .await?
+            .into_iter()
+            .find(|g| g.name == log_group_name)
+            .and_then(|g| g.arn);
+        if let Some(arn) = arn {
+            let tags = log_group_tags(&logs, &arn).await.unwrap_or_default();
+            found = DLQ_TAG_KEYS
+                .iter()
+                .find_map(|key| tags.get(*key))
+                .map(|queue| (queue.clone(), "tag"));
+        }
+    }
+    let (queue_url, source) = match found {
+        Some((reference, source)) => (queue_url(&client, &reference).await?, source),
+        None => match dlq_by_name(&client, &log_group_name).await {
+            Some(url) => (url, "name"),
+            None => return Ok(None),
+        },
+    };
+
+    let approximate_messages = client
+        .get_queue_attributes()
+        .queue_url(&queue_url)
+        .attribute_names(QueueAttributeName::ApproximateNumberOfMessages)
+        .send()
+        .await
+        .ok()
+        .and_then(|r| {
+            r.attributes?
+                .get(&QueueAttributeName::ApproximateNumberOfMessages)?
+                .parse()
+                .ok()
+        });
+    Ok(Some(DlqInfo {
+        queue_url,
+        source: source.to_string(),
+        approximate_messages,
+    }))
+}
+
+/// Map a log group to its DLQ (queue URL, ARN or name), or remove the mapping with None
+#[tauri::command]
+pub fn set_dlq_mapping(
+    app: AppHandle,
+    log_group_name: String,
+    queue: Option<String>,
+) -> Result<(), String> {
+    let _guard = DLQ_MAPPINGS_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut mappings: HashMap<String, String> = storage::read_json(&app, DLQ_MAPPINGS_FILE);
+    match queue.filter(|q| !q.trim().is_empty()) {
+        Some(queue) => mappings.insert(log_group_name, queue.trim().to_string()),
+        None => mappings.remove(&log_group_name),
+    };
+    storage::write_json(&app, DLQ_MAPPINGS_FILE, &mappings)
+}
+
+/// Peek messages in a DLQ without consuming them: they are received with a zero
+/// visibility timeout, so they stay available. Each peek does increment the
+/// messages' receive count.
+#[tauri::command]
+pub async fn peek_dlq_messages(
+    state: State<'_, AppState>,
+    queue_url: String,
+    max_messages: Option<usize>,
+) -> Result<Vec<DlqMessage>, String> {
+    let config = state.sdk_config().await?;
+    let client = SqsClient::new(&config);
+    let max = max_messages
+        .unwrap_or(DEFAULT_PEEK_MESSAGES)
+        .clamp(1, MAX_PEEK_MESSAGES);
+
+    let mut messages: Vec<DlqMessage> = Vec::new();
+    // With a zero visibility timeout the same messages can come back; stop once a
+    // receive brings nothing new
+    while messages.len() < max {
+        let response = client
+            .receive_message()
+            .queue_url(&queue_url)
+            .max_number_of_messages(RECEIVE_BATCH)
+            .visibility_timeout(0)
+            .message_system_attribute_names(MessageSystemAttributeName::All)
+            .message_attribute_names("All")
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        let before = messages.len();
+        for message in response.messages() {
+            if messages
+                .iter()
+                .any(|m| m.message_id.as_deref() == message.message_id())
+            {
+                continue;
+            }
+            let attributes: BTreeMap<String, String> = message
+                .attributes()
+                .map(|a| {
+                    a.iter()
+                        .map(|(k, v)| (k.as_str().to_string(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            messages.push(DlqMessage {
+                message_id: message.message_id().map(str::to_string),
+                body: message.body().map(str::to_string),
+                sent_timestamp: attributes.get("SentTimestamp").and_then(|t| t.parse().ok()),
+                receive_count: attributes
+                    .get("ApproximateReceiveCount")
+                    .and_then(|c| c.parse().ok()),
+                message_attributes: message
+                    .message_attributes()
+                    .map(|a| {
+                        a.iter()
+                            .filter_map(|(k, v)| Some((k.clone(), v.string_value()?.to_string())))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                attributes,
+            });
+        }
+        if messages.len() == before {
+            break;
+        }
+    }
+    messages.truncate(max);
+    messages.sort_by_key(|m| m.sent_timestamp);
+    Ok(messages)
+}
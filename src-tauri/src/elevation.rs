@@ -0,0 +1,216 @@
+use crate::connections::{self, Connection};
+use crate::mutations::{self, BulkAction, PermissionCheck, PermissionStatus};
+use crate::{audit, regions, AppState};
+use aws_config::BehaviorVersion;
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// A mutating command that failed with AccessDenied and can be re-run under
+/// another connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mutation {
+    SetRetention {
+        log_group_name: String,
+        retention_days: Option<i32>,
+    },
+    Tag {
+        log_group_arn: String,
+        tags: HashMap<String, String>,
+    },
+    Untag {
+        log_group_arn: String,
+        tag_keys: Vec<String>,
+    },
+    DeleteLogGroup {
+        log_group_name: String,
+        /// Must repeat the group name, as for delete_log_group
+        confirm_name: String,
+    },
+}
+
+impl Mutation {
+    fn log_group_name(&self) -> &str {
+        match self {
+            Self::SetRetention { log_group_name, .. }
+            | Self::DeleteLogGroup { log_group_name, .. } => log_group_name,
+            Self::Tag { log_group_arn, .. } | Self::Untag { log_group_arn, .. } => {
+                let name = log_group_arn
+                    .split_once(":log-group:")
+                    .map_or(log_group_arn.as_str(), |(_, name)| name);
+                name.strip_suffix(":*").unwrap_or(name)
+            }
+        }
+    }
+
+    fn bulk_action(&self) -> (BulkAction, Option<i32>) {
+        match self {
+            Self::SetRetention { retention_days, .. } => {
+                (BulkAction::SetRetention, *retention_days)
+            }
+            Self::Tag { .. } => (BulkAction::Tag, None),
+            Self::Untag { .. } => (BulkAction::Untag, None),
+            Self::DeleteLogGroup { .. } => (BulkAction::Delete, None),
+        }
+    }
+
+    /// Audit action name, matching the direct command
+    fn audit_action(&self) -> &'static str {
+        match self {
+            Self::SetRetention { .. } => "set_retention_policy",
+            Self::Tag { .. } => "tag_log_group",
+            Self::Untag { .. } => "untag_log_group",
+            Self::DeleteLogGroup { .. } => "delete_log_group",
+        }
+    }
+}
+
+/// A configured connection that may be allowed to run a denied mutation
+#[derive(Debug, Clone, Serialize)]
+pub struct ElevationCandidate {
+    pub connection: String,
+    pub profile: Option<String>,
+    pub environment: Option<String>,
+    /// Allowed, or Unverified when no side-effect-free probe exists
+    pub check: PermissionCheck,
+}
+
+/// CloudWatch Logs client for a connection's profile, in `region` so it sees the
+/// same resources as the active connection
+async fn client_for(
+    app: &AppHandle,
+    connection: &Connection,
+    region: Option<String>,
+) -> CloudWatchClient {
+    let mut config_loader = aws_config::defaults(BehaviorVersion::latest());
+    if let Some(p) = &connection.profile {
+        config_loader = config_loader.profile_name(p);
+    }
+    if let Some(r) = region.or_else(|| connection.region.clone()) {
+        config_loader = config_loader.region(aws_config::Region::new(r));
+    }
+    let config = config_loader.load().await;
+    let signing_region = regions::signing_region_for(app, connection.profile.as_deref());
+    regions::logs_client(&config, signing_region.as_deref())
+}
+
+async fn current_region(state: &AppState) -> Option<String> {
+    state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()))
+}
+
+/// After a mutation failed with AccessDenied, find the other writable connections
+/// whose profile can run it, by probing each with a no-op call of the same action
+/// (see mutations::probe). Allowed connections come first.
+#[tauri::command]
+pub async fn find_elevated_connection(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    mutation: Mutation,
+) -> Result<Vec<ElevationCandidate>, String> {
+    let region = current_region(&state).await;
+    let active = state
+        .active_connection
+        .lock()
+        .await
+        .as_ref()
+        .map(|c| c.name.clone());
+    let current_profile = state.current_profile.lock().await.clone();
+    let (action, retention_days) = mutation.bulk_action();
+
+    let mut candidates = Vec::new();
+    for connection in connections::load_connections(&app) {
+        if connection.read_only
+            || Some(&connection.name) == active.as_ref()
+            || (active.is_none() && connection.profile == current_profile)
+        {
+            continue;
+        }
+        let client = client_for(&app, &connection, region.clone()).await;
+        let check =
+            mutations::probe(&client, action, mutation.log_group_name(), retention_days).await;
+        if matches!(
+            check.status,
+            PermissionStatus::Allowed | PermissionStatus::Unverified
+        ) {
+            candidates.push(ElevationCandidate {
+                connection: connection.name,
+                profile: connection.profile,
+                environment: connection.environment,
+                check,
+            });
+        }
+    }
+    candidates.sort_by_key(|c| c.check.status != PermissionStatus::Allowed);
+    Ok(candidates)
+}
+
+/// Re-run one mutation under another connection's profile without switching the
+/// app to it. `confirm_connection` must repeat the connection name. The audit entry
+/// records the connection and profile the change was made with.
+#[tauri::command]
+pub async fn run_elevated(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection: String,
+    confirm_connection: String,
+    mutation: Mutation,
+) -> Result<(), String> {
+    if confirm_connection != connection {
+        return Err("Type the connection name to confirm running with it".to_string());
+    }
+    let target = connections::load_connections(&app)
+        .into_iter()
+        .find(|c| c.name == connection)
+        .ok_or_else(|| format!("Connection {} not found", connection))?;
+    if target.read_only {
+        return Err(format!("Connection {} is read-only", connection));
+    }
+    if let Mutation::DeleteLogGroup {
+        log_group_name,
+        confirm_name,
+    } = &mutation
+    {
+        if confirm_name != log_group_name {
+            return Err("Type the log group name to confirm deletion".to_string());
+        }
+    }
+
+    let client = client_for(&app, &target, current_region(&state).await).await;
+    log::info!(
+        "Running {} with connection {}",
+        mutation.audit_action(),
+        connection
+    );
+    let result = match &mutation {
+        Mutation::SetRetention {
+            log_group_name,
+            retention_days,
+        } => mutations::put_retention(&client, log_group_name, *retention_days).await,
+        Mutation::Tag {
+            log_group_arn,
+            tags,
+        } => mutations::tag(&client, log_group_arn, tags.clone()).await,
+        Mutation::Untag {
+            log_group_arn,
+            tag_keys,
+        } => mutations::untag(&client, log_group_arn, tag_keys.clone()).await,
+        Mutation::DeleteLogGroup { log_group_name, .. } => {
+            mutations::delete_group(&client, log_group_name).await
+        }
+    };
+    let mut parameters = serde_json::to_value(&mutation).unwrap_or_default();
+    if let serde_json::Value::Object(map) = &mut parameters {
+        map.remove("kind");
+        map.remove("confirm_name");
+        map.insert("elevated_connection".to_string(), connection.into());
+        map.insert("elevated_profile".to_string(), target.profile.into());
+    }
+    audit::record(&app, &state, mutation.audit_action(), parameters, &result).await;
+    result
+}
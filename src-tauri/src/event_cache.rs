@@ -0,0 +1,523 @@
+use crate::{columns, preview, redaction, AppState, LogEvent};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+/// Directory under the app cache directory holding cached fetches
+const CACHE_DIR: &str = "event-cache";
+
+/// Index of the cached segments, inside the cache directory
+const INDEX_FILE: &str = "index.json";
+
+/// Compressed size the cache is kept under; least recently used segments go first
+const MAX_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Events this recent may still be arriving, so they are never cached
+const SETTLE_MS: i64 = 5 * 60 * 1000;
+
+/// zstd level for segments: written on every fetch, so favour speed
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Events returned by search_cached_logs unless overridden
+const DEFAULT_SEARCH_MAX_EVENTS: usize = 10_000;
+
+/// Guards read-modify-write of the index and its segment files
+static CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Connection and log group a cached fetch belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub profile: Option<String>,
+    /// ARN of the role assumed on top of the profile, which may be another account
+    pub assumed_role: Option<String>,
+    pub region: Option<String>,
+    pub log_group_name: String,
+}
+
+impl CacheKey {
+    /// Key of `log_group_name` for the connected profile, assumed role and region
+    pub async fn current(state: &AppState, log_group_name: &str) -> CacheKey {
+        CacheKey {
+            profile: state.current_profile.lock().await.clone(),
+            assumed_role: state
+                .assumed_role
+                .lock()
+                .await
+                .as_ref()
+                .map(|r| r.info.role_arn.clone()),
+            region: state
+                .config
+                .lock()
+                .await
+                .as_ref()
+                .and_then(|c| c.region().map(|r| r.to_string())),
+            log_group_name: log_group_name.to_string(),
+        }
+    }
+}
+
+/// One completely fetched time range of a log group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Segment {
+    id: String,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    assumed_role: Option<String>,
+    #[serde(default)]
+    region: Option<String>,
+    log_group_name: String,
+    start: i64,
+    end: i64,
+    /// Filter pattern the range was fetched with; None holds every event
+    #[serde(default)]
+    filter_pattern: Option<String>,
+    event_count: usize,
+    /// Size of the compressed segment file
+    bytes: u64,
+    cached_at: i64,
+    last_used: i64,
+}
+
+impl Segment {
+    fn belongs_to(&self, key: &CacheKey) -> bool {
+        self.profile == key.profile
+            && self.assumed_role == key.assumed_role
+            && self.region == key.region
+            && self.log_group_name == key.log_group_name
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheIndex {
+    #[serde(default)]
+    segments: Vec<Segment>,
+}
+
+/// Size of the local event cache
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub segments: usize,
+    pub events: usize,
+    pub bytes: u64,
+    pub log_groups: Vec<String>,
+}
+
+/// Events found in the cache for a log group
+#[derive(Debug, Clone, Serialize)]
+pub struct CachedSearchResult {
+    pub events: Vec<LogEvent>,
+    /// More cached events matched than were returned
+    pub truncated: bool,
+    /// Cached time ranges of the group within the searched range, merged; events
+    /// outside them were never fetched completely
+    pub covered_ranges: Vec<(i64, i64)>,
+}
+
+/// A term of a CloudWatch text filter pattern
+enum Term {
+    Required(String),
+    Any(String),
+    Excluded(String),
+}
+
+/// Parse a CloudWatch text filter pattern (terms, "quoted phrases", ?any and
+/// -excluded terms). JSON, space-delimited and regex patterns return None as they
+/// are only evaluated by CloudWatch.
+fn text_terms(pattern: &str) -> Option<Vec<Term>> {
+    let pattern = pattern.trim();
+    if pattern.starts_with('{') || pattern.starts_with('[') || pattern.contains('%') {
+        return None;
+    }
+    let mut terms = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let prefix = match c {
+            '?' | '-' => chars.next(),
+            _ => None,
+        };
+        let mut text = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                text.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+        }
+        if text.is_empty() {
+            continue;
+        }
+        terms.push(match prefix {
+            Some('?') => Term::Any(text),
+            Some('-') => Term::Excluded(text),
+            _ => Term::Required(text),
+        });
+    }
+    Some(terms)
+}
+
+/// Whether a message matches parsed text terms, case-sensitively like CloudWatch
+fn matches_terms(terms: &[Term], message: &str) -> bool {
+    let mut any_seen = false;
+    let mut any_matched = false;
+    for term in terms {
+        match term {
+            Term::Required(text) if !message.contains(text.as_str()) => return false,
+            Term::Excluded(text) if message.contains(text.as_str()) => return false,
+            Term::Any(text) => {
+                any_seen = true;
+                any_matched |= message.contains(text.as_str());
+            }
+            _ => {}
+        }
+    }
+    !any_seen || any_matched
+}
+
+fn normalize_pattern(pattern: Option<&str>) -> Option<&str> {
+    pattern.map(str::trim).filter(|p| !p.is_empty())
+}
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Could not determine app cache directory: {}", e))?
+        .join(CACHE_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+fn read_index(dir: &std::path::Path) -> CacheIndex {
+    match std::fs::read_to_string(dir.join(INDEX_FILE)) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable event cache index: {}", e);
+            CacheIndex::default()
+        }),
+        Err(_) => CacheIndex::default(),
+    }
+}
+
+fn write_index(dir: &std::path::Path, index: &CacheIndex) -> Result<(), String> {
+    let json = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    let path = dir.join(INDEX_FILE);
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn segment_path(dir: &std::path::Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json.zst", id))
+}
+
+fn read_segment(dir: &std::path::Path, id: &str) -> Option<Vec<LogEvent>> {
+    let data = std::fs::read(segment_path(dir, id)).ok()?;
+    let raw = zstd::decode_all(data.as_slice())
+        .map_err(|e| log::warn!("Dropping corrupt cache segment {}: {}", id, e))
+        .ok()?;
+    serde_json::from_slice(&raw)
+        .map_err(|e| log::warn!("Dropping unreadable cache segment {}: {}", id, e))
+        .ok()
+}
+
+fn remove_segment(dir: &std::path::Path, id: &str) {
+    let _ = std::fs::remove_file(segment_path(dir, id));
+}
+
+/// Events of a fetch the cache can answer without calling CloudWatch: a cached
+/// range of the same group covering `start..=end`, fetched with the same pattern
+/// or unfiltered when the pattern can be evaluated locally. Open-ended ranges are
+/// never served. Events come back oldest first.
+pub fn lookup(
+    app: &AppHandle,
+    key: &CacheKey,
+    start: Option<i64>,
+    end: Option<i64>,
+    filter_pattern: Option<&str>,
+) -> Option<Vec<LogEvent>> {
+    let (start, end) = (start?, end?);
+    let pattern = normalize_pattern(filter_pattern);
+    let terms = pattern.and_then(text_terms);
+    let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = cache_dir(app).ok()?;
+    let mut index = read_index(&dir);
+
+    // Prefer a range fetched with the same pattern over filtering an unfiltered one
+    let position = index
+        .segments
+        .iter()
+        .filter(|s| s.belongs_to(key) && s.start <= start && s.end >= end)
+        .filter(|s| {
+            s.filter_pattern.as_deref() == pattern
+                || (s.filter_pattern.is_none() && terms.is_some())
+        })
+        .min_by_key(|s| s.filter_pattern.is_none())
+        .and_then(|found| index.segments.iter().position(|s| s.id == found.id))?;
+    let segment = &mut index.segments[position];
+    let Some(events) = read_segment(&dir, &segment.id) else {
+        remove_segment(&dir, &segment.id);
+        index.segments.remove(position);
+        let _ = write_index(&dir, &index);
+        return None;
+    };
+    let refilter = segment.filter_pattern.is_none() && pattern.is_some();
+    segment.last_used = chrono::Utc::now().timestamp_millis();
+    let _ = write_index(&dir, &index);
+
+    let terms = terms.unwrap_or_default();
+    Some(
+        events
+            .into_iter()
+            .filter(|e| e.timestamp >= start && e.timestamp <= end)
+            .filter(|e| !refilter || matches_terms(&terms, &e.message))
+            .collect(),
+    )
+}
+
+/// Cache the events of a completed fetch of `start..=end`. The part of the range
+/// that may still receive events is left out; ranges already cached are skipped
+/// and cached ranges the new one covers are replaced.
+pub fn store(
+    app: &AppHandle,
+    key: &CacheKey,
+    start: Option<i64>,
+    end: Option<i64>,
+    filter_pattern: Option<&str>,
+    events: &[LogEvent],
+) {
+    let Some(start) = start else {
+        return;
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    let end = end.unwrap_or(now).min(now - SETTLE_MS);
+    if end <= start {
+        return;
+    }
+    let pattern = normalize_pattern(filter_pattern).map(str::to_string);
+    if let Err(e) = store_segment(app, key, start, end, pattern, events, now) {
+        log::warn!("Failed to cache events of {}: {}", key.log_group_name, e);
+    }
+}
+
+fn store_segment(
+    app: &AppHandle,
+    key: &CacheKey,
+    start: i64,
+    end: i64,
+    filter_pattern: Option<String>,
+    events: &[LogEvent],
+    now: i64,
+) -> Result<(), String> {
+    let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = cache_dir(app)?;
+    let mut index = read_index(&dir);
+    let same_query = |s: &Segment| s.belongs_to(key) && s.filter_pattern == filter_pattern;
+    if index
+        .segments
+        .iter()
+        .any(|s| same_query(s) && s.start <= start && s.end >= end)
+    {
+        return Ok(());
+    }
+
+    let mut kept: Vec<&LogEvent> = events
+        .iter()
+        .filter(|e| e.timestamp >= start && e.timestamp <= end)
+        .collect();
+    kept.sort_by_key(|e| e.timestamp);
+    let raw = serde_json::to_vec(&kept).map_err(|e| e.to_string())?;
+    let data = zstd::encode_all(raw.as_slice(), COMPRESSION_LEVEL)
+        .map_err(|e| format!("Failed to compress events: {}", e))?;
+    let id = format!(
+        "{:x}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or(now)
+    );
+    let path = segment_path(&dir, &id);
+    std::fs::write(&path, &data)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    index.segments.retain(|s| {
+        let replaced = same_query(s) && s.start >= start && s.end <= end;
+        if replaced {
+            remove_segment(&dir, &s.id);
+        }
+        !replaced
+    });
+    index.segments.push(Segment {
+        id,
+        profile: key.profile.clone(),
+        assumed_role: key.assumed_role.clone(),
+        region: key.region.clone(),
+        log_group_name: key.log_group_name.clone(),
+        start,
+        end,
+        filter_pattern,
+        event_count: kept.len(),
+        bytes: data.len() as u64,
+        cached_at: now,
+        last_used: now,
+    });
+
+    let mut total: u64 = index.segments.iter().map(|s| s.bytes).sum();
+    if total > MAX_CACHE_BYTES {
+        index.segments.sort_by_key(|s| s.last_used);
+        while total > MAX_CACHE_BYTES && index.segments.len() > 1 {
+            let evicted = index.segments.remove(0);
+            remove_segment(&dir, &evicted.id);
+            total -= evicted.bytes;
+        }
+    }
+    write_index(&dir, &index)
+}
+
+fn stats_of(index: &CacheIndex) -> CacheStats {
+    let mut log_groups: Vec<String> = index
+        .segments
+        .iter()
+        .map(|s| s.log_group_name.clone())
+        .collect();
+    log_groups.sort();
+    log_groups.dedup();
+    CacheStats {
+        segments: index.segments.len(),
+        events: index.segments.iter().map(|s| s.event_count).sum(),
+        bytes: index.segments.iter().map(|s| s.bytes).sum(),
+        log_groups,
+    }
+}
+
+/// Search the cached events of a log group for the connected profile, assumed role
+/// and region without calling CloudWatch. `query` is a CloudWatch text pattern
+/// (terms, quoted phrases, ?any, -excluded) or, with `regex`, a case-insensitive
+/// regex. Only ranges fetched unfiltered are searched, so the result is exactly
+/// what CloudWatch would return within `covered_ranges`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_cached_logs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    query: Option<String>,
+    regex: Option<bool>,
+    max_count: Option<usize>,
+) -> Result<CachedSearchResult, String> {
+    let query = query.filter(|q| !q.trim().is_empty());
+    let matcher: Box<dyn Fn(&str) -> bool + Send + Sync> = match (&query, regex.unwrap_or(false)) {
+        (None, _) => Box::new(|_| true),
+        (Some(q), true) => {
+            let re = RegexBuilder::new(q)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Invalid regex: {}", e))?;
+            Box::new(move |m| re.is_match(m))
+        }
+        (Some(q), false) => {
+            let terms = text_terms(q).ok_or(
+                "JSON, space-delimited and regex filter patterns can't be evaluated locally",
+            )?;
+            Box::new(move |m| matches_terms(&terms, m))
+        }
+    };
+    let key = CacheKey::current(&state, &log_group_name).await;
+    let start = start_time.unwrap_or(i64::MIN);
+    let end = end_time.unwrap_or(i64::MAX);
+    let max_events = max_count.unwrap_or(DEFAULT_SEARCH_MAX_EVENTS);
+
+    let (mut events, covered_ranges) = {
+        let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = cache_dir(&app)?;
+        let mut segments: Vec<Segment> = read_index(&dir)
+            .segments
+            .into_iter()
+            .filter(|s| s.belongs_to(&key) && s.filter_pattern.is_none())
+            .filter(|s| s.start <= end && s.end >= start)
+            .collect();
+        segments.sort_by_key(|s| s.start);
+
+        let mut covered_ranges: Vec<(i64, i64)> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut events = Vec::new();
+        for segment in &segments {
+            let range = (segment.start.max(start), segment.end.min(end));
+            match covered_ranges.last_mut() {
+                Some(last) if range.0 <= last.1 + 1 => last.1 = last.1.max(range.1),
+                _ => covered_ranges.push(range),
+            }
+            for event in read_segment(&dir, &segment.id).into_iter().flatten() {
+                if event.timestamp < start || event.timestamp > end || !matcher(&event.message) {
+                    continue;
+                }
+                if let Some(id) = &event.event_id {
+                    if !seen.insert(id.clone()) {
+                        continue;
+                    }
+                }
+                events.push(event);
+            }
+        }
+        (events, covered_ranges)
+    };
+    events.sort_by_key(|e| e.timestamp);
+    let truncated = events.len() > max_events;
+    events.truncate(max_events);
+
+    redaction::mask_for_display(&app, &state, None, &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(CachedSearchResult {
+        events,
+        truncated,
+        covered_ranges,
+    })
+}
+
+/// Size of the local event cache across all profiles
+#[tauri::command]
+pub fn get_cache_stats(app: AppHandle) -> Result<CacheStats, String> {
+    let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(stats_of(&read_index(&cache_dir(&app)?)))
+}
+
+/// Drop cached events of one log group (any profile or region), or everything
+/// when omitted. Returns what is left.
+#[tauri::command]
+pub fn clear_cache(app: AppHandle, log_group_name: Option<String>) -> Result<CacheStats, String> {
+    let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = cache_dir(&app)?;
+    let mut index = read_index(&dir);
+    index.segments.retain(|s| {
+        let cleared = log_group_name
+            .as_ref()
+            .is_none_or(|name| *name == s.log_group_name);
+        if cleared {
+            remove_segment(&dir, &s.id);
+        }
+        !cleared
+    });
+    write_index(&dir, &index)?;
+    log::info!(
+        "Cleared event cache{}",
+        log_group_name
+            .map(|n| format!(" of {}", n))
+            .unwrap_or_default()
+    );
+    Ok(stats_of(&index))
+}
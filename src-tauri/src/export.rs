@@ -0,0 +1,1204 @@
+use crate::connections::EnvironmentEmitter;
+use crate::operations::{CancelBy, Operation};
+use crate::pipeline::{apply_stage, PipelineStage, ProcessedEvent};
+use crate::{
+    budget, columns, connectivity, pacing, redaction, send_filter_with_retry, storage, AppState,
+    LogEvent,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use tokio::io::AsyncWriteExt;
+
+/// Syslog facility used for exported events (1 = user-level messages)
+const SYSLOG_FACILITY: u8 = 1;
+
+/// Private enterprise number of the structured data element (RFC 5612 documentation PEN)
+const SYSLOG_SD_ID: &str = "cloudwatch@32473";
+
+/// RFC 5424 field length limits
+const SYSLOG_HOSTNAME_MAX: usize = 255;
+const SYSLOG_APP_NAME_MAX: usize = 48;
+
+const FORWARD_TIMEOUT_SECS: u64 = 10;
+
+/// File in the app config directory holding the default export location
+const EXPORT_PATHS_FILE: &str = "export-paths.json";
+
+/// Path of an export under the default directory when no template is saved
+const DEFAULT_EXPORT_TEMPLATE: &str = "{group}/{date}/{query}.{ext}";
+
+/// Placeholders an export path template may use
+const EXPORT_PLACEHOLDERS: [&str; 6] = ["group", "query", "date", "time", "profile", "ext"];
+
+/// Longest slug put into an export path by one placeholder
+const MAX_SLUG_CHARS: usize = 80;
+
+/// Suffix of the evidence record written next to an NDJSON export
+const EVIDENCE_SUFFIX: &str = ".evidence.json";
+
+fn default_export_template() -> String {
+    DEFAULT_EXPORT_TEMPLATE.to_string()
+}
+
+/// What happens when a templated export path already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollisionPolicy {
+    /// Add "-2", "-3", ... before the extension
+    #[default]
+    Suffix,
+    Overwrite,
+    Fail,
+}
+
+/// Where exports go when no path is chosen: `template` rendered under `directory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPathSettings {
+    /// Defaults to Loggy in the Downloads folder
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// Relative path with {group}, {query}, {date}, {time}, {profile} and {ext};
+    /// "/" separates folders
+    #[serde(default = "default_export_template")]
+    pub template: String,
+    #[serde(default)]
+    pub collision: CollisionPolicy,
+}
+
+impl Default for ExportPathSettings {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            template: default_export_template(),
+            collision: CollisionPolicy::default(),
+        }
+    }
+}
+
+/// A file written by an export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedFile {
+    pub path: String,
+    pub events: usize,
+    /// SHA-256 of the evidence record, when one was written; keep it (e.g. in
+    /// the ticket) to prove the export unchanged later
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_sha256: Option<String>,
+}
+
+/// Hash of one exported event: SHA-256 of its NDJSON line, without the newline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDigest {
+    /// 1-based line in the NDJSON file
+    pub line: usize,
+    #[serde(default)]
+    pub event_id: Option<String>,
+    pub timestamp: i64,
+    pub sha256: String,
+}
+
+/// `<file>.evidence.json`: hashes of an NDJSON export and each of its events,
+/// so a reviewer can check the evidence wasn't altered after export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    pub algorithm: String,
+    /// The NDJSON file, relative to the record
+    pub file: String,
+    pub file_sha256: String,
+    pub exported_at: i64,
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub events: Vec<EventDigest>,
+}
+
+/// Outcome of checking an export against its evidence record
+#[derive(Debug, Clone, Serialize)]
+pub struct EvidenceVerification {
+    pub file: String,
+    /// The file and every event match the record
+    pub intact: bool,
+    pub file_hash_matches: bool,
+    /// The record matches its `.sha256` file; None when that file is missing
+    pub record_hash_matches: Option<bool>,
+    /// Lines whose content changed
+    pub altered_lines: Vec<usize>,
+    /// Events in the record no longer in the file
+    pub missing_events: usize,
+    /// Lines in the file the record doesn't know
+    pub extra_lines: usize,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Events as NDJSON lines, without newlines
+fn ndjson_lines(events: &[LogEvent]) -> Result<Vec<String>, String> {
+    events
+        .iter()
+        .map(|event| serde_json::to_string(event).map_err(|e| e.to_string()))
+        .collect()
+}
+
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write `<path>.evidence.json` for an NDJSON export of `lines`, and next to it
+/// `<path>.evidence.json.sha256` in `sha256sum` format, so the record itself can
+/// be checked with standard tools. Returns the record's hash.
+fn write_evidence(
+    path: &Path,
+    contents: &str,
+    lines: &[String],
+    events: &[LogEvent],
+    exported_at: i64,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let file = path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let record = EvidenceRecord {
+        algorithm: "sha256".to_string(),
+        file: file.clone(),
+        file_sha256: sha256_hex(contents.as_bytes()),
+        exported_at,
+        profile,
+        events: lines
+            .iter()
+            .zip(events)
+            .enumerate()
+            .map(|(i, (line, event))| EventDigest {
+                line: i + 1,
+                event_id: event.event_id.clone(),
+                timestamp: event.timestamp,
+                sha256: sha256_hex(line.as_bytes()),
+            })
+            .collect(),
+    };
+    let record_path = PathBuf::from(format!("{}{}", path.display(), EVIDENCE_SUFFIX));
+    let record_json = serde_json::to_string_pretty(&record).map_err(|e| e.to_string())?;
+    write_file(&record_path, &record_json)?;
+    let record_sha256 = sha256_hex(record_json.as_bytes());
+    write_file(
+        &PathBuf::from(format!("{}.sha256", record_path.display())),
+        format!("{}  {}{}\n", record_sha256, file, EVIDENCE_SUFFIX),
+    )?;
+    Ok(record_sha256)
+}
+
+/// The events of a session in display order, restricted to `event_ids` (the
+/// filtered view or a selection) when given, with display masking applied
+pub async fn view_events(
+    app: &AppHandle,
+    state: &AppState,
+    session_id: &str,
+    event_ids: Option<Vec<String>>,
+) -> Result<Vec<LogEvent>, String> {
+    let mut events = state
+        .sessions
+        .lock()
+        .await
+        .get(session_id)
+        .map(|s| s.display_events())
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    if let Some(ids) = event_ids {
+        let ids: HashSet<String> = ids.into_iter().collect();
+        events.retain(|e| e.event_id.as_ref().is_some_and(|id| ids.contains(id)));
+    }
+    redaction::mask_for_display(app, state, Some(session_id), &mut events).await;
+    columns::apply(app, &mut events);
+    Ok(events)
+}
+
+/// Parsed JSON and severity ("error", "warn", ...) of each event, using the
+/// built-in keyword rules
+pub fn classify(events: &[LogEvent]) -> Vec<ProcessedEvent> {
+    let processed: Vec<ProcessedEvent> = events.iter().map(ProcessedEvent::from).collect();
+    [
+        PipelineStage::ParseJson,
+        PipelineStage::ClassifySeverity { rules: Vec::new() },
+    ]
+    .iter()
+    .try_fold(processed.clone(), |events, stage| {
+        apply_stage(stage, events)
+    })
+    .unwrap_or(processed)
+}
+
+/// Syslog severity for a Loggy level (informational when unknown)
+fn syslog_severity(level: Option<&str>) -> u8 {
+    match level {
+        Some("error") => 3,
+        Some("warn") => 4,
+        Some("debug") | Some("trace") => 7,
+        _ => 6,
+    }
+}
+
+/// A header field: printable US-ASCII without spaces, "-" when empty
+fn syslog_token(value: Option<&str>, max: usize) -> String {
+    let token: String = value
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max)
+        .collect();
+    if token.is_empty() {
+        "-".to_string()
+    } else {
+        token
+    }
+}
+
+/// Escape a structured data parameter value
+fn sd_escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace(']', r"\]")
+}
+
+/// One event as an RFC 5424 message: the log group becomes HOSTNAME, the stream
+/// APP-NAME, and the originals are kept in structured data
+fn syslog_line(event: &LogEvent, level: Option<&str>) -> String {
+    let pri = SYSLOG_FACILITY * 8 + syslog_severity(level);
+    let timestamp = chrono::DateTime::from_timestamp_millis(event.timestamp)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let mut params = Vec::new();
+    if let Some(group) = &event.source {
+        params.push(format!("logGroup=\"{}\"", sd_escape(group)));
+    }
+    if let Some(stream) = &event.log_stream_name {
+        params.push(format!("logStream=\"{}\"", sd_escape(stream)));
+    }
+    if let Some(id) = &event.event_id {
+        params.push(format!("eventId=\"{}\"", sd_escape(id)));
+    }
+    let structured = if params.is_empty() {
+        "-".to_string()
+    } else {
+        format!("[{} {}]", SYSLOG_SD_ID, params.join(" "))
+    };
+    format!(
+        "<{}>1 {} {} {} - - {} {}",
+        pri,
+        timestamp,
+        syslog_token(event.source.as_deref(), SYSLOG_HOSTNAME_MAX),
+        syslog_token(event.log_stream_name.as_deref(), SYSLOG_APP_NAME_MAX),
+        structured,
+        event.message.trim_end()
+    )
+}
+
+/// Outcome of a syslog export
+#[derive(Debug, Clone, Serialize)]
+pub struct SyslogExport {
+    /// Events written to `path`
+    pub written: usize,
+    /// Events sent to `forward_to`
+    pub forwarded: usize,
+}
+
+/// Export a session's events (or the `event_ids` of the current view) as RFC 5424
+/// syslog, to a file with one message per line (embedded newlines as "#012", as
+/// rsyslog writes them) and/or over TCP to `forward_to` ("host:port", octet-counted
+/// framing per RFC 6587), for replay into on-prem SIEM tooling
+#[tauri::command]
+pub async fn export_syslog(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_ids: Option<Vec<String>>,
+    path: Option<String>,
+    forward_to: Option<String>,
+) -> Result<SyslogExport, String> {
+    if path.is_none() && forward_to.is_none() {
+        return Err("Choose a file or a syslog address to export to".to_string());
+    }
+    let events = view_events(&app, &state, &session_id, event_ids).await?;
+    let processed = classify(&events);
+    let lines: Vec<String> = events
+        .iter()
+        .zip(&processed)
+        .map(|(event, p)| syslog_line(event, p.level.as_deref()))
+        .collect();
+
+    let mut written = 0;
+    if let Some(path) = &path {
+        let mut contents = String::new();
+        for line in &lines {
+            contents.push_str(&line.replace('\n', "#012"));
+            contents.push('\n');
+        }
+        std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        written = lines.len();
+    }
+
+    let mut forwarded = 0;
+    if let Some(address) = &forward_to {
+        let connect = tokio::net::TcpStream::connect(address.as_str());
+        let mut stream = tokio::time::timeout(Duration::from_secs(FORWARD_TIMEOUT_SECS), connect)
+            .await
+            .map_err(|_| format!("Timed out connecting to {}", address))?
+            .map_err(|e| format!("Failed to connect to {}: {}", address, e))?;
+        for line in &lines {
+            let frame = format!("{} {}", line.len(), line);
+            stream.write_all(frame.as_bytes()).await.map_err(|e| {
+                format!(
+                    "Failed to send to {} after {} events: {}",
+                    address, forwarded, e
+                )
+            })?;
+            forwarded += 1;
+        }
+        stream
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to send to {}: {}", address, e))?;
+    }
+
+    log::info!(
+        "Syslog export of session {}: {} written, {} forwarded",
+        session_id,
+        written,
+        forwarded
+    );
+    Ok(SyslogExport { written, forwarded })
+}
+
+/// Inline stylesheet of the HTML export; severity classes match the app's default colors
+const HTML_STYLE: &str = "\
+body{font:13px/1.45 -apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;margin:24px;color:#1f2328;background:#fff}\
+h1{font-size:18px;margin:0 0 4px}\
+.meta{color:#656d76;margin-bottom:16px}\
+table{border-collapse:collapse;width:100%}\
+th,td{text-align:left;vertical-align:top;padding:4px 8px;border-bottom:1px solid #d8dee4}\
+th{background:#f6f8fa;position:sticky;top:0}\
+td.time,td.stream{white-space:nowrap;font-family:ui-monospace,Menlo,monospace;color:#656d76}\
+td.message{font-family:ui-monospace,Menlo,monospace;white-space:pre-wrap;word-break:break-word}\
+pre{margin:4px 0 0;padding:8px;background:#f6f8fa;border-radius:4px;white-space:pre-wrap}\
+summary{cursor:pointer}\
+tr.error td.message{color:#cf222e}tr.error{background:#ffebe9}\
+tr.warn td.message{color:#9a6700}tr.warn{background:#fff8c5}\
+tr.debug td.message,tr.trace td.message{color:#656d76}";
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// A standalone HTML page listing events: inline CSS, rows colored by severity,
+/// and JSON payloads pretty-printed in collapsible blocks
+fn render_html(title: &str, events: &[LogEvent], processed: &[ProcessedEvent]) -> String {
+    let computed = columns::names(events);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <title>{title}</title><style>{HTML_STYLE}</style></head><body>\n\
+         <h1>{title}</h1><div class=\"meta\">{count} events &middot; exported {exported}</div>\n\
+         <table><thead><tr><th>Time</th><th>Source</th><th>Stream</th>{computed}<th>Message</th></tr></thead><tbody>\n",
+        title = html_escape(title),
+        count = events.len(),
+        exported = format_timestamp(chrono::Utc::now().timestamp_millis()),
+        computed = computed
+            .iter()
+            .map(|name| format!("<th>{}</th>", html_escape(name)))
+            .collect::<String>(),
+    );
+    for (event, p) in events.iter().zip(processed) {
+        let message = match &p.json {
+            Some(json) => {
+                let pretty = serde_json::to_string_pretty(json).unwrap_or_default();
+                let summary: String = event.message.chars().take(200).collect();
+                format!(
+                    "<details><summary>{}</summary><pre>{}</pre></details>",
+                    html_escape(&summary),
+                    html_escape(&pretty)
+                )
+            }
+            None => html_escape(event.message.trim_end()),
+        };
+        html.push_str(&format!(
+            "<tr class=\"{}\"><td class=\"time\">{}</td><td class=\"stream\">{}</td>\
+             <td class=\"stream\">{}</td>{}<td class=\"message\">{}</td></tr>\n",
+            p.level.as_deref().unwrap_or("unknown"),
+            format_timestamp(event.timestamp),
+            html_escape(event.source.as_deref().unwrap_or_default()),
+            html_escape(event.log_stream_name.as_deref().unwrap_or_default()),
+            computed
+                .iter()
+                .map(|name| format!("<td>{}</td>", html_escape(columns::value(event, name))))
+                .collect::<String>(),
+            message
+        ));
+    }
+    html.push_str("</tbody></table>\n</body></html>\n");
+    html
+}
+
+/// Export a session's events (or the `event_ids` of the current filtered view) as a
+/// standalone HTML page for attaching to tickets, to `path` or the templated
+/// default export path
+#[tauri::command]
+pub async fn export_html(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_ids: Option<Vec<String>>,
+    path: Option<String>,
+    title: Option<String>,
+) -> Result<ExportedFile, String> {
+    let events = view_events(&app, &state, &session_id, event_ids).await?;
+    let processed = classify(&events);
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| "Loggy export".to_string());
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => resolve_export_path(&app, &state, &session_id, "html").await?,
+    };
+    std::fs::write(&path, render_html(&title, &events, &processed))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    log::info!(
+        "HTML export of {} events written to {}",
+        events.len(),
+        path.display()
+    );
+    Ok(ExportedFile {
+        path: path.display().to_string(),
+        events: events.len(),
+        evidence_sha256: None,
+    })
+}
+
+/// Export a session's events (or the `event_ids` of the current view) as NDJSON,
+/// one event per line, to `path` or the templated default export path. With
+/// `evidence`, a `.evidence.json` record of per-event and file hashes is written
+/// next to it (see verify_export_evidence).
+#[tauri::command]
+pub async fn export_ndjson(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_ids: Option<Vec<String>>,
+    path: Option<String>,
+    evidence: Option<bool>,
+) -> Result<ExportedFile, String> {
+    let events = view_events(&app, &state, &session_id, event_ids).await?;
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => resolve_export_path(&app, &state, &session_id, "ndjson").await?,
+    };
+    let lines = ndjson_lines(&events)?;
+    let contents: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    write_file(&path, &contents)?;
+    let evidence_sha256 = if evidence.unwrap_or(false) {
+        Some(write_evidence(
+            &path,
+            &contents,
+            &lines,
+            &events,
+            chrono::Utc::now().timestamp_millis(),
+            state.current_profile.lock().await.clone(),
+        )?)
+    } else {
+        None
+    };
+    log::info!(
+        "NDJSON export of {} events written to {}",
+        events.len(),
+        path.display()
+    );
+    Ok(ExportedFile {
+        path: path.display().to_string(),
+        events: events.len(),
+        evidence_sha256,
+    })
+}
+
+/// File format of a streamed log export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    Ndjson,
+    Csv,
+    /// Raw messages, one per line
+    Text,
+}
+
+/// Payload for export-progress
+#[derive(Debug, Clone, Serialize)]
+struct ExportProgress {
+    export_id: u32,
+    events: usize,
+    bytes: usize,
+}
+
+/// Outcome of a streamed log export
+#[derive(Debug, Clone, Serialize)]
+pub struct LogExportSummary {
+    pub path: String,
+    pub events: usize,
+    pub bytes: usize,
+    /// Stopped early with cancel_operation; the file holds what was written
+    pub cancelled: bool,
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One page of events in the export format
+fn export_lines(format: LogExportFormat, events: &[LogEvent]) -> Result<String, String> {
+    let mut out = String::new();
+    for event in events {
+        match format {
+            LogExportFormat::Ndjson => {
+                out.push_str(&serde_json::to_string(event).map_err(|e| e.to_string())?);
+            }
+            LogExportFormat::Csv => {
+                let time = chrono::DateTime::from_timestamp_millis(event.timestamp)
+                    .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "{},{},{},{},{}",
+                    time,
+                    event.timestamp,
+                    csv_field(event.log_stream_name.as_deref().unwrap_or_default()),
+                    csv_field(event.event_id.as_deref().unwrap_or_default()),
+                    csv_field(event.message.trim_end_matches(['\n', '\r'])),
+                ));
+            }
+            LogExportFormat::Text => out.push_str(event.message.trim_end_matches(['\n', '\r'])),
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Export a log group's events straight to `path` as NDJSON, CSV or raw text,
+/// writing each FilterLogEvents page as it arrives instead of collecting the
+/// results, so exports of any size run in constant memory. Takes the same time
+/// range and filter pattern as fetch_logs; emits `export-progress` per page and
+/// can be stopped with cancel_operation.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_logs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    path: String,
+    format: LogExportFormat,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+    export_id: Option<u32>,
+) -> Result<LogExportSummary, String> {
+    connectivity::ensure_online()?;
+    let client = state.logs_client().await?;
+    let export_id = export_id.unwrap_or(0);
+    let op = Operation::begin(
+        "export",
+        &log_group_name,
+        CancelBy::Flag(Arc::new(AtomicBool::new(false))),
+    );
+
+    let file =
+        std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    let write_err = |e: std::io::Error| format!("Failed to write {}: {}", path, e);
+    if format == LogExportFormat::Csv {
+        writer
+            .write_all(b"time,timestamp,log_stream,event_id,message\n")
+            .map_err(write_err)?;
+    }
+
+    let mut events_written = 0;
+    let mut bytes_written = 0;
+    let mut downloaded = 0;
+    let mut cancelled = false;
+    let mut next_token: Option<String> = None;
+    let mut tuner = pacing::PageTuner::new();
+    loop {
+        if op.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        let mut request = client
+            .filter_log_events()
+            .log_group_name(&log_group_name)
+            .set_start_time(start_time)
+            .set_end_time(end_time)
+            .set_next_token(next_token);
+        if let Some(pattern) = filter_pattern.as_deref().filter(|p| !p.is_empty()) {
+            request = request.filter_pattern(pattern);
+        }
+        let response = send_filter_with_retry(request, &op, &mut tuner)
+            .await
+            .map_err(|e| e.humanized())?;
+        next_token = response.next_token.clone();
+        let mut events: Vec<LogEvent> = response
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .map(LogEvent::from)
+            .collect();
+        op.page(events.len());
+        downloaded += events.iter().map(|e| e.message.len()).sum::<usize>();
+
+        redaction::mask_for_display(&app, &state, None, &mut events).await;
+        let page = export_lines(format, &events)?;
+        writer.write_all(page.as_bytes()).map_err(write_err)?;
+        events_written += events.len();
+        bytes_written += page.len();
+        app.emit_tagged(
+            "export-progress",
+            ExportProgress {
+                export_id,
+                events: events_written,
+                bytes: bytes_written,
+            },
+        )
+        .ok();
+
+        if next_token.is_none() {
+            break;
+        }
+    }
+    writer.flush().map_err(write_err)?;
+    budget::record(&app, &state, downloaded as u64, 0).await;
+    log::info!(
+        "Streamed export of {} events from {} written to {}{}",
+        events_written,
+        log_group_name,
+        path,
+        if cancelled { " (cancelled)" } else { "" }
+    );
+    Ok(LogExportSummary {
+        path,
+        events: events_written,
+        bytes: bytes_written,
+        cancelled,
+    })
+}
+
+/// Check an NDJSON export against its evidence record (`path` is the export or
+/// its `.evidence.json`): the file hash, each event's line hash, and the
+/// record's own `.sha256` file. Lines are matched by position, so reordered,
+/// edited, removed and added events are all reported.
+#[tauri::command]
+pub fn verify_export_evidence(path: String) -> Result<EvidenceVerification, String> {
+    let record_path = if path.ends_with(EVIDENCE_SUFFIX) {
+        PathBuf::from(&path)
+    } else {
+        PathBuf::from(format!("{}{}", path, EVIDENCE_SUFFIX))
+    };
+    let record_json = std::fs::read_to_string(&record_path)
+        .map_err(|e| format!("Failed to read {}: {}", record_path.display(), e))?;
+    let record: EvidenceRecord = serde_json::from_str(&record_json)
+        .map_err(|e| format!("{} is not an evidence record: {}", record_path.display(), e))?;
+    if record.algorithm != "sha256" {
+        return Err(format!("Unsupported hash algorithm {}", record.algorithm));
+    }
+    let record_hash_matches = std::fs::read_to_string(format!("{}.sha256", record_path.display()))
+        .ok()
+        .map(|sums| sums.split_whitespace().next() == Some(&sha256_hex(record_json.as_bytes())));
+
+    let file_path = record_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&record.file);
+    let contents = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let lines: Vec<&str> = contents.lines().collect();
+    let altered_lines: Vec<usize> = record
+        .events
+        .iter()
+        .filter(|digest| {
+            digest
+                .line
+                .checked_sub(1)
+                .and_then(|i| lines.get(i))
+                .is_some_and(|line| sha256_hex(line.as_bytes()) != digest.sha256)
+        })
+        .map(|digest| digest.line)
+        .collect();
+    let file_hash_matches = sha256_hex(contents.as_bytes()) == record.file_sha256;
+    let missing_events = record.events.len().saturating_sub(lines.len());
+    let extra_lines = lines.len().saturating_sub(record.events.len());
+    Ok(EvidenceVerification {
+        file: file_path.display().to_string(),
+        intact: file_hash_matches
+            && altered_lines.is_empty()
+            && missing_events == 0
+            && extra_lines == 0
+            && record_hash_matches != Some(false),
+        file_hash_matches,
+        record_hash_matches,
+        altered_lines,
+        missing_events,
+        extra_lines,
+    })
+}
+
+fn load_export_path_settings(app: &AppHandle) -> ExportPathSettings {
+    storage::read_json(app, EXPORT_PATHS_FILE)
+}
+
+fn validate_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("The export path template is empty".to_string());
+    }
+    if template.starts_with('/') || template.starts_with('\\') {
+        return Err(
+            "The export path template must be relative to the export directory".to_string(),
+        );
+    }
+    if template
+        .split(['/', '\\'])
+        .any(|part| part == ".." || part.is_empty())
+    {
+        return Err("The export path template has an empty or \"..\" folder".to_string());
+    }
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let close = rest[open..]
+            .find('}')
+            .ok_or("The export path template has an unclosed {")?;
+        let name = &rest[open + 1..open + close];
+        if !EXPORT_PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder {{{}}} (use {})",
+                name,
+                EXPORT_PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    Ok(())
+}
+
+fn export_directory(settings: &ExportPathSettings) -> Result<PathBuf, String> {
+    match &settings.directory {
+        Some(directory) if !directory.trim().is_empty() => Ok(PathBuf::from(directory)),
+        _ => dirs::download_dir()
+            .map(|d| d.join("Loggy"))
+            .ok_or_else(|| "Choose a default export directory".to_string()),
+    }
+}
+
+/// The export path of a session before collision handling: the template filled
+/// from the session's log groups and filter, today's date and the profile
+async fn render_export_path(
+    state: &AppState,
+    settings: &ExportPathSettings,
+    session_id: &str,
+    extension: &str,
+) -> Result<PathBuf, String> {
+    validate_template(&settings.template)?;
+    let (groups, query) = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let mut groups: Vec<String> = session.fetches.keys().cloned().collect();
+        groups.sort();
+        let query = groups
+            .iter()
+            .find_map(|g| session.fetches[g].filter_pattern.clone())
+            .filter(|p| !p.trim().is_empty());
+        (groups, query)
+    };
+    let group = match groups.as_slice() {
+        [] => "session".to_string(),
+        [only] => slug(only, "group"),
+        [first, rest @ ..] => format!("{}-and-{}-more", slug(first, "group"), rest.len()),
+    };
+    let now = chrono::Local::now();
+    let profile = state.current_profile.lock().await.clone();
+    let values = [
+        ("group", group),
+        (
+            "query",
+            slug(query.as_deref().unwrap_or_default(), "all-events"),
+        ),
+        ("date", now.format("%Y-%m-%d").to_string()),
+        ("time", now.format("%H%M%S").to_string()),
+        (
+            "profile",
+            slug(profile.as_deref().unwrap_or_default(), "default"),
+        ),
+        ("ext", slug(extension, "txt")),
+    ];
+    let mut relative = settings.template.clone();
+    for (name, value) in &values {
+        relative = relative.replace(&format!("{{{}}}", name), value);
+    }
+    let mut path = export_directory(settings)?;
+    path.extend(relative.split(['/', '\\']));
+    Ok(path)
+}
+
+/// Where an export of a session goes when no path was chosen: the rendered
+/// template, with its folders created and an existing file handled by the
+/// collision policy
+pub async fn resolve_export_path(
+    app: &AppHandle,
+    state: &AppState,
+    session_id: &str,
+    extension: &str,
+) -> Result<PathBuf, String> {
+    let settings = load_export_path_settings(app);
+    let path = render_export_path(state, &settings, session_id, extension).await?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    if !path.exists() {
+        return Ok(path);
+    }
+    match settings.collision {
+        CollisionPolicy::Overwrite => Ok(path),
+        CollisionPolicy::Fail => Err(format!("{} already exists", path.display())),
+        CollisionPolicy::Suffix => {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let extension = path.extension().map(|e| e.to_string_lossy().to_string());
+            (2..)
+                .map(|n| {
+                    let name = match &extension {
+                        Some(ext) => format!("{}-{}.{}", stem, n, ext),
+                        None => format!("{}-{}", stem, n),
+                    };
+                    path.with_file_name(name)
+                })
+                .find(|candidate| !candidate.exists())
+                .ok_or_else(|| format!("No free file name next to {}", path.display()))
+        }
+    }
+}
+
+/// Default export directory, path template and collision policy
+#[tauri::command]
+pub fn get_export_path_settings(app: AppHandle) -> ExportPathSettings {
+    load_export_path_settings(&app)
+}
+
+/// Save the default export directory, path template and collision policy
+#[tauri::command]
+pub fn set_export_path_settings(
+    app: AppHandle,
+    settings: ExportPathSettings,
+) -> Result<(), String> {
+    validate_template(&settings.template)?;
+    storage::write_json(&app, EXPORT_PATHS_FILE, &settings)
+}
+
+/// Where an export of the session would be written with the saved settings (or
+/// `settings` being edited), before collision handling
+#[tauri::command]
+pub async fn preview_export_path(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    extension: String,
+    settings: Option<ExportPathSettings>,
+) -> Result<String, String> {
+    let settings = settings.unwrap_or_else(|| load_export_path_settings(&app));
+    let path = render_export_path(&state, &settings, &session_id, &extension).await?;
+    Ok(path.display().to_string())
+}
+
+/// Escape text for a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.trim_end()
+        .replace('\\', r"\\")
+        .replace('|', r"\|")
+        .replace('\n', "<br>")
+}
+
+/// A fence longer than any backtick run in `text`
+fn markdown_fence(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Markdown for pasting into issues and runbooks: a table of timestamp, stream and
+/// message for plain single-line events; otherwise one heading line per event with
+/// its message fenced (JSON pretty-printed)
+fn render_markdown(events: &[LogEvent], processed: &[ProcessedEvent]) -> String {
+    let tabular = processed
+        .iter()
+        .all(|p| p.json.is_none() && !p.message.trim_end().contains('\n'));
+    let mut markdown = String::new();
+    if tabular {
+        let computed = columns::names(events);
+        markdown.push_str("| Timestamp | Stream |");
+        for name in &computed {
+            markdown.push_str(&format!(" {} |", markdown_cell(name)));
+        }
+        markdown.push_str(" Message |\n| --- | --- |");
+        markdown.push_str(&" --- |".repeat(computed.len()));
+        markdown.push_str(" --- |\n");
+        for event in events {
+            markdown.push_str(&format!(
+                "| {} | {} |",
+                format_timestamp(event.timestamp),
+                markdown_cell(event.log_stream_name.as_deref().unwrap_or_default()),
+            ));
+            for name in &computed {
+                markdown.push_str(&format!(
+                    " {} |",
+                    markdown_cell(columns::value(event, name))
+                ));
+            }
+            markdown.push_str(&format!(" {} |\n", markdown_cell(&event.message)));
+        }
+        return markdown;
+    }
+    for (event, p) in events.iter().zip(processed) {
+        markdown.push_str(&format!("**{}**", format_timestamp(event.timestamp)));
+        if let Some(stream) = &event.log_stream_name {
+            markdown.push_str(&format!(" · `{}`", stream.replace('`', "'")));
+        }
+        let (language, body) = match &p.json {
+            Some(json) => (
+                "json",
+                serde_json::to_string_pretty(json).unwrap_or_else(|_| event.message.clone()),
+            ),
+            None => ("", event.message.trim_end().to_string()),
+        };
+        let fence = markdown_fence(&body);
+        markdown.push_str(&format!("\n\n{fence}{language}\n{body}\n{fence}\n\n"));
+    }
+    markdown
+}
+
+/// Format selected events as Markdown (see render_markdown) for the clipboard
+#[tauri::command]
+pub async fn format_events_markdown(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_ids: Vec<String>,
+) -> Result<String, String> {
+    let events = view_events(&app, &state, &session_id, Some(event_ids)).await?;
+    Ok(render_markdown(&events, &classify(&events)))
+}
+
+/// One view to include in an evidence export
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewSelection {
+    pub name: String,
+    pub session_id: String,
+    /// The view's filtered events; the whole session when absent
+    #[serde(default)]
+    pub event_ids: Option<Vec<String>>,
+}
+
+/// How one log group of a view was queried
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestSource {
+    pub log_group_name: String,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub filter_pattern: Option<String>,
+    /// The fetch stopped at its limit, so the file may not hold every match
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestView {
+    pub name: String,
+    /// NDJSON file holding the view's events, relative to the manifest
+    pub file: String,
+    pub events: usize,
+    pub first_timestamp: Option<i64>,
+    pub last_timestamp: Option<i64>,
+    pub sources: Vec<ManifestSource>,
+    /// SHA-256 of the view's evidence record, for evidence exports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_sha256: Option<String>,
+}
+
+/// manifest.json of a multi-view export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportManifest {
+    pub workspace: Option<String>,
+    pub exported_at: i64,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub views: Vec<ManifestView>,
+    /// SHA-256 of manifest.json as written (evidence exports only; returned, not
+    /// part of the file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_sha256: Option<String>,
+}
+
+/// Lowercase alphanumerics and dashes of `text`, or `fallback` when none are left
+fn slug(text: &str, fallback: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug
+        .trim_matches('-')
+        .chars()
+        .take(MAX_SLUG_CHARS)
+        .collect();
+    match slug.trim_end_matches('-') {
+        "" => fallback.to_string(),
+        s => s.to_string(),
+    }
+}
+
+/// File name for a view: lowercase alphanumerics and dashes, unique within the export
+fn view_file_name(name: &str, taken: &[ManifestView]) -> String {
+    let slug = slug(name, "view");
+    let mut file = format!("{}.ndjson", slug);
+    let mut n = 2;
+    while taken.iter().any(|v| v.file == file) {
+        file = format!("{}-{}.ndjson", slug, n);
+        n += 1;
+    }
+    file
+}
+
+/// Export several views (e.g. the views of a workspace) into one folder: an NDJSON
+/// file per view plus manifest.json recording each view's log groups, filters and
+/// time ranges, so collecting incident evidence is a single action. With
+/// `evidence`, each file gets an evidence record of per-event hashes, the
+/// manifest lists their hashes, and manifest.json.sha256 holds the manifest's
+/// hash, so one hash vouches for the whole export.
+#[tauri::command]
+pub async fn export_views(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    directory: String,
+    views: Vec<ViewSelection>,
+    workspace: Option<String>,
+    evidence: Option<bool>,
+) -> Result<ExportManifest, String> {
+    if views.is_empty() {
+        return Err("Select at least one view to export".to_string());
+    }
+    let evidence = evidence.unwrap_or(false);
+    let dir = Path::new(&directory);
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let mut manifest = ExportManifest {
+        workspace,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        profile: state.current_profile.lock().await.clone(),
+        region: match state.sdk_config().await {
+            Ok(config) => config.region().map(|r| r.to_string()),
+            Err(_) => None,
+        },
+        views: Vec::new(),
+        manifest_sha256: None,
+    };
+    for view in views {
+        let mut sources: Vec<ManifestSource> = state
+            .sessions
+            .lock()
+            .await
+            .get(&view.session_id)
+            .map(|s| {
+                s.fetches
+                    .iter()
+                    .map(|(group, context)| ManifestSource {
+                        log_group_name: group.clone(),
+                        start_time: context.start_time,
+                        end_time: context.end_time,
+                        filter_pattern: context.filter_pattern.clone(),
+                        truncated: context.truncated,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        sources.sort_by(|a, b| a.log_group_name.cmp(&b.log_group_name));
+        let events = view_events(&app, &state, &view.session_id, view.event_ids).await?;
+
+        let file = view_file_name(&view.name, &manifest.views);
+        let lines = ndjson_lines(&events)?;
+        let contents: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+        let path = dir.join(&file);
+        write_file(&path, &contents)?;
+        let evidence_sha256 = if evidence {
+            Some(write_evidence(
+                &path,
+                &contents,
+                &lines,
+                &events,
+                manifest.exported_at,
+                manifest.profile.clone(),
+            )?)
+        } else {
+            None
+        };
+
+        manifest.views.push(ManifestView {
+            name: view.name,
+            file,
+            events: events.len(),
+            first_timestamp: events.iter().map(|e| e.timestamp).min(),
+            last_timestamp: events.iter().map(|e| e.timestamp).max(),
+            sources,
+            evidence_sha256,
+        });
+    }
+
+    let path = dir.join("manifest.json");
+    let contents = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    write_file(&path, &contents)?;
+    if evidence {
+        let manifest_sha256 = sha256_hex(contents.as_bytes());
+        write_file(
+            &dir.join("manifest.json.sha256"),
+            format!("{}  manifest.json\n", manifest_sha256),
+        )?;
+        manifest.manifest_sha256 = Some(manifest_sha256);
+    }
+    log::info!(
+        "Exported {} views to {}",
+        manifest.views.len(),
+        dir.display()
+    );
+    Ok(manifest)
+}
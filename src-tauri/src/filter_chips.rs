@@ -0,0 +1,449 @@
+use crate::pipeline::{json_lookup, value_to_string};
+use crate::{storage, AppState};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding the saved chips
+const CHIPS_FILE: &str = "filter-chips.json";
+
+/// Serializes read-modify-write of the chips file
+static CHIPS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Compiled size limit for regex chips
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Event ids returned by filter_session_by_chips
+const MAX_MATCH_IDS: usize = 100_000;
+
+/// What a chip matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ChipMatch {
+    /// The message contains `text` (case-sensitive, like CloudWatch terms)
+    Term { text: String },
+    /// JSON field `field` (dot path) equals `value`, e.g. customer=acme
+    Field { field: String, value: String },
+    /// JSON field `field` is a number within `min..=max`, e.g. status 500-599 for "5xx"
+    Range {
+        field: String,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// The message matches a Rust regex; only applied client-side
+    Regex { pattern: String },
+}
+
+/// A saved atomic filter, toggled on and off when building a query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterChip {
+    /// Unique name shown on the chip ("5xx", "customer=acme", "not healthcheck")
+    pub label: String,
+    #[serde(rename = "match")]
+    pub matcher: ChipMatch,
+    /// Match events the chip would not match
+    #[serde(default)]
+    pub negate: bool,
+}
+
+/// Boolean composition of chips, referenced by label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum ChipExpr {
+    Chip { label: String },
+    And { items: Vec<ChipExpr> },
+    Or { items: Vec<ChipExpr> },
+    Not { item: Box<ChipExpr> },
+}
+
+/// The query a chip composition amounts to
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveQuery {
+    /// CloudWatch filter pattern to fetch with, if any part can run server-side
+    pub server_pattern: Option<String>,
+    /// The server pattern selects exactly the composed events; otherwise it
+    /// selects a superset and filter_session_by_chips narrows it down
+    pub exact: bool,
+    /// Readable form of the composition, e.g. `5xx AND NOT healthcheck`
+    pub description: String,
+    /// Why the composition couldn't run fully server-side
+    pub note: Option<String>,
+}
+
+/// Events of a session matching a chip composition
+#[derive(Debug, Clone, Serialize)]
+pub struct ChipFilterResult {
+    pub total: usize,
+    pub matched: usize,
+    /// Ids of the matching events, oldest first
+    pub event_ids: Vec<String>,
+}
+
+/// A composition with negations pushed down to the chips
+enum Node {
+    Leaf(Matcher, bool),
+    And(Vec<Node>),
+    Or(Vec<Node>),
+}
+
+enum Matcher {
+    Term(String),
+    Field(String, String),
+    Range(String, Option<f64>, Option<f64>),
+    Regex(Regex),
+}
+
+fn field_path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[A-Za-z_][\w\-]*(\.[\w\-]+)*$").expect("valid regex"))
+}
+
+fn compile(matcher: &ChipMatch) -> Result<Matcher, String> {
+    match matcher {
+        ChipMatch::Term { text } => {
+            if text.is_empty() {
+                return Err("Term chips need some text".to_string());
+            }
+            Ok(Matcher::Term(text.clone()))
+        }
+        ChipMatch::Field { field, value } => {
+            if !field_path_regex().is_match(field) {
+                return Err(format!("Invalid field path: {}", field));
+            }
+            Ok(Matcher::Field(field.clone(), value.clone()))
+        }
+        ChipMatch::Range { field, min, max } => {
+            if !field_path_regex().is_match(field) {
+                return Err(format!("Invalid field path: {}", field));
+            }
+            if min.is_none() && max.is_none() {
+                return Err("Range chips need a minimum or a maximum".to_string());
+            }
+            Ok(Matcher::Range(field.clone(), *min, *max))
+        }
+        ChipMatch::Regex { pattern } => RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map(Matcher::Regex)
+            .map_err(|e| format!("Invalid pattern: {}", e)),
+    }
+}
+
+/// Resolve chip labels and push `Not` down to the chips (De Morgan)
+fn resolve(
+    expr: &ChipExpr,
+    negated: bool,
+    chips: &HashMap<String, FilterChip>,
+) -> Result<Node, String> {
+    match expr {
+        ChipExpr::Chip { label } => {
+            let chip = chips
+                .get(label)
+                .ok_or_else(|| format!("Filter chip {} not found", label))?;
+            Ok(Node::Leaf(compile(&chip.matcher)?, chip.negate != negated))
+        }
+        ChipExpr::And { items } | ChipExpr::Or { items } => {
+            let nodes = items
+                .iter()
+                .map(|item| resolve(item, negated, chips))
+                .collect::<Result<Vec<_>, _>>()?;
+            if nodes.is_empty() {
+                return Err("Empty chip group".to_string());
+            }
+            let conjunction = matches!(expr, ChipExpr::And { .. }) != negated;
+            Ok(if conjunction {
+                Node::And(nodes)
+            } else {
+                Node::Or(nodes)
+            })
+        }
+        ChipExpr::Not { item } => resolve(item, !negated, chips),
+    }
+}
+
+fn describe(expr: &ChipExpr) -> String {
+    match expr {
+        ChipExpr::Chip { label } => label.clone(),
+        ChipExpr::And { items } | ChipExpr::Or { items } => {
+            let joiner = if matches!(expr, ChipExpr::And { .. }) {
+                " AND "
+            } else {
+                " OR "
+            };
+            let parts: Vec<String> = items
+                .iter()
+                .map(|item| match item {
+                    ChipExpr::And { .. } | ChipExpr::Or { .. } => format!("({})", describe(item)),
+                    _ => describe(item),
+                })
+                .collect();
+            parts.join(joiner)
+        }
+        ChipExpr::Not { item } => match item.as_ref() {
+            ChipExpr::And { .. } | ChipExpr::Or { .. } => format!("NOT ({})", describe(item)),
+            _ => format!("NOT {}", describe(item)),
+        },
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Right-hand side of a JSON comparison: numbers bare, anything else quoted
+fn json_operand(value: &str) -> String {
+    if value.parse::<f64>().is_ok() {
+        value.to_string()
+    } else {
+        quote(value)
+    }
+}
+
+/// Body of a JSON pattern for a tree of field/range chips, or None if a chip
+/// can't be expressed in one
+fn json_pattern(node: &Node) -> Option<String> {
+    match node {
+        Node::Leaf(Matcher::Field(field, value), negate) => Some(format!(
+            "($.{} {} {})",
+            field,
+            if *negate { "!=" } else { "=" },
+            json_operand(value)
+        )),
+        Node::Leaf(Matcher::Range(field, min, max), negate) => {
+            let bounds: Vec<String> = match negate {
+                false => [
+                    min.map(|v| format!("$.{} >= {}", field, v)),
+                    max.map(|v| format!("$.{} <= {}", field, v)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+                true => [
+                    min.map(|v| format!("$.{} < {}", field, v)),
+                    max.map(|v| format!("$.{} > {}", field, v)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            };
+            Some(format!(
+                "({})",
+                bounds.join(if *negate { " || " } else { " && " })
+            ))
+        }
+        Node::Leaf(..) => None,
+        Node::And(nodes) | Node::Or(nodes) => {
+            let parts = nodes.iter().map(json_pattern).collect::<Option<Vec<_>>>()?;
+            let joiner = if matches!(node, Node::And(_)) {
+                " && "
+            } else {
+                " || "
+            };
+            Some(format!("({})", parts.join(joiner)))
+        }
+    }
+}
+
+/// Term chips a matching event must contain, for a superset pattern
+fn required_terms(node: &Node) -> Vec<String> {
+    match node {
+        Node::Leaf(Matcher::Term(text), false) => vec![text.clone()],
+        Node::And(nodes) => nodes.iter().flat_map(required_terms).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Term chips directly under an OR, if all of them are positive terms
+fn any_terms(node: &Node) -> Option<Vec<String>> {
+    let Node::Or(nodes) = node else {
+        return None;
+    };
+    nodes
+        .iter()
+        .map(|n| match n {
+            Node::Leaf(Matcher::Term(text), false) => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Unstructured pattern of a flat AND of terms with at least one positive term
+fn and_terms_pattern(node: &Node) -> Option<String> {
+    let leaves: Vec<&Node> = match node {
+        Node::And(nodes) => nodes.iter().collect(),
+        leaf @ Node::Leaf(..) => vec![leaf],
+        Node::Or(_) => return None,
+    };
+    let mut parts = Vec::new();
+    let mut positive = false;
+    for leaf in leaves {
+        let Node::Leaf(Matcher::Term(text), negate) = leaf else {
+            return None;
+        };
+        positive |= !negate;
+        parts.push(format!("{}{}", if *negate { "-" } else { "" }, quote(text)));
+    }
+    positive.then(|| parts.join(" "))
+}
+
+fn server_query(node: &Node) -> (Option<String>, bool, Option<String>) {
+    if let Some(body) = json_pattern(node) {
+        return (Some(format!("{{ {} }}", body)), true, None);
+    }
+    if let Some(pattern) = and_terms_pattern(node) {
+        return (Some(pattern), true, None);
+    }
+    if let Some(terms) = any_terms(node) {
+        let parts: Vec<String> = terms.iter().map(|t| format!("?{}", quote(t))).collect();
+        return (Some(parts.join(" ")), true, None);
+    }
+    let note = Some(
+        "CloudWatch patterns can't express this combination; the rest is applied to loaded events"
+            .to_string(),
+    );
+    let terms = required_terms(node);
+    if terms.is_empty() {
+        return (None, false, note);
+    }
+    let parts: Vec<String> = terms.iter().map(|t| quote(t)).collect();
+    (Some(parts.join(" ")), false, note)
+}
+
+/// The message parsed as JSON, parsed at most once per event
+fn parsed_json<'a>(
+    message: &str,
+    json: &'a mut Option<Option<serde_json::Value>>,
+) -> Option<&'a serde_json::Value> {
+    json.get_or_insert_with(|| {
+        let trimmed = message.trim();
+        trimmed
+            .starts_with('{')
+            .then(|| serde_json::from_str(trimmed).ok())
+            .flatten()
+    })
+    .as_ref()
+}
+
+fn leaf_matches(
+    matcher: &Matcher,
+    message: &str,
+    json: &mut Option<Option<serde_json::Value>>,
+) -> bool {
+    match matcher {
+        Matcher::Term(text) => message.contains(text.as_str()),
+        Matcher::Regex(regex) => regex.is_match(message),
+        Matcher::Field(field, value) => parsed_json(message, json)
+            .and_then(|j| json_lookup(j, field))
+            .is_some_and(|v| value_to_string(v) == *value),
+        Matcher::Range(field, min, max) => parsed_json(message, json)
+            .and_then(|j| json_lookup(j, field))
+            .and_then(|v| v.as_f64().or_else(|| v.as_str()?.parse().ok()))
+            .is_some_and(|n| min.is_none_or(|m| n >= m) && max.is_none_or(|m| n <= m)),
+    }
+}
+
+fn matches(node: &Node, message: &str, json: &mut Option<Option<serde_json::Value>>) -> bool {
+    match node {
+        Node::Leaf(matcher, negate) => leaf_matches(matcher, message, json) != *negate,
+        Node::And(nodes) => nodes.iter().all(|n| matches(n, message, json)),
+        Node::Or(nodes) => nodes.iter().any(|n| matches(n, message, json)),
+    }
+}
+
+fn load_chips(app: &AppHandle) -> Vec<FilterChip> {
+    storage::read_json(app, CHIPS_FILE)
+}
+
+fn resolve_expr(app: &AppHandle, expr: &ChipExpr) -> Result<Node, String> {
+    let chips: HashMap<String, FilterChip> = load_chips(app)
+        .into_iter()
+        .map(|chip| (chip.label.clone(), chip))
+        .collect();
+    resolve(expr, false, &chips)
+}
+
+/// List saved filter chips
+#[tauri::command]
+pub fn list_filter_chips(app: AppHandle) -> Vec<FilterChip> {
+    load_chips(&app)
+}
+
+/// Save a filter chip, replacing any chip with the same label. The match is
+/// validated first so mistakes are reported on save.
+#[tauri::command]
+pub fn save_filter_chip(app: AppHandle, chip: FilterChip) -> Result<(), String> {
+    if chip.label.trim().is_empty() {
+        return Err("Filter chips need a label".to_string());
+    }
+    compile(&chip.matcher)?;
+    let _guard = CHIPS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut chips = load_chips(&app);
+    chips.retain(|c| c.label != chip.label);
+    chips.push(chip);
+    chips.sort_by(|a, b| a.label.cmp(&b.label));
+    storage::write_json(&app, CHIPS_FILE, &chips)
+}
+
+/// Remove a saved filter chip
+#[tauri::command]
+pub fn delete_filter_chip(app: AppHandle, label: String) -> Result<(), String> {
+    let _guard = CHIPS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut chips = load_chips(&app);
+    chips.retain(|c| c.label != label);
+    storage::write_json(&app, CHIPS_FILE, &chips)
+}
+
+/// Compose chips into the query to fetch with: the CloudWatch filter pattern
+/// that selects the composed events, or the closest superset of them when
+/// CloudWatch syntax can't express the combination (text terms can't be mixed
+/// with JSON fields, or AND with OR)
+#[tauri::command]
+pub fn compose_filter_chips(app: AppHandle, expr: ChipExpr) -> Result<EffectiveQuery, String> {
+    let node = resolve_expr(&app, &expr)?;
+    let (server_pattern, exact, note) = server_query(&node);
+    Ok(EffectiveQuery {
+        server_pattern,
+        exact,
+        description: describe(&expr),
+        note,
+    })
+}
+
+/// Apply a chip composition to a session's loaded events, returning the ids of
+/// the events it matches. Matching uses the raw messages, as server-side
+/// patterns do.
+#[tauri::command]
+pub async fn filter_session_by_chips(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    expr: ChipExpr,
+) -> Result<ChipFilterResult, String> {
+    let node = resolve_expr(&app, &expr)?;
+    let sessions = state.sessions.lock().await;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let events = session.events();
+    let mut matched = 0;
+    let mut event_ids = Vec::new();
+    for event in &events {
+        if matches(&node, &event.message, &mut None) {
+            matched += 1;
+            if let Some(id) = &event.event_id {
+                if event_ids.len() < MAX_MATCH_IDS {
+                    event_ids.push(id.clone());
+                }
+            }
+        }
+    }
+    Ok(ChipFilterResult {
+        total: events.len(),
+        matched,
+        event_ids,
+    })
+}
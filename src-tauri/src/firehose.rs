@@ -0,0 +1,345 @@
+use crate::kinesis::{decode_subscription_data, gunzip, DecodedPayload};
+use crate::{humanize_aws_error, redaction, AppState};
+use aws_sdk_firehose::types::DestinationDescription;
+use aws_sdk_firehose::Client as FirehoseClient;
+use aws_sdk_s3::Client as S3Client;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// Default max number of failed batch objects listed per call
+const DEFAULT_MAX_OBJECTS: usize = 200;
+
+/// A subscription filter on a log group that delivers to a Firehose delivery stream
+#[derive(Debug, Clone, Serialize)]
+pub struct FirehoseSubscription {
+    pub filter_name: String,
+    pub filter_pattern: Option<String>,
+    pub destination_arn: String,
+    pub delivery_stream_name: String,
+}
+
+/// Where a delivery stream writes records it failed to deliver
+#[derive(Debug, Clone, Serialize)]
+pub struct FirehoseErrorLocation {
+    pub delivery_stream_name: String,
+    /// "splunk", "http_endpoint", "extended_s3", "opensearch", ...
+    pub destination_type: String,
+    pub bucket: String,
+    /// Literal S3 prefix searched for failed batches
+    pub error_prefix: String,
+}
+
+/// An S3 object holding a batch of records Firehose failed to deliver
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedBatchObject {
+    pub bucket: String,
+    pub key: String,
+    pub size_bytes: i64,
+    pub last_modified: Option<i64>,
+}
+
+/// Result of listing failed batches for a delivery stream
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedBatchListing {
+    pub location: FirehoseErrorLocation,
+    pub objects: Vec<FailedBatchObject>,
+    pub truncated: bool,
+}
+
+/// One failed record as written by Firehose to its error output
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFailedRecord {
+    #[serde(default)]
+    attempts_made: Option<i64>,
+    #[serde(default)]
+    arrival_timestamp: Option<i64>,
+    #[serde(default)]
+    error_code: Option<String>,
+    #[serde(default)]
+    error_message: Option<String>,
+    #[serde(default)]
+    raw_data: Option<String>,
+}
+
+/// A failed record decoded back into the original CloudWatch log events
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedRecord {
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub attempts_made: Option<i64>,
+    pub arrival_timestamp: Option<i64>,
+    pub payload: DecodedPayload,
+}
+
+/// Extract the delivery stream name from a Firehose ARN
+/// (arn:aws:firehose:region:account:deliverystream/name)
+fn delivery_stream_name_from_arn(arn: &str) -> Option<String> {
+    if !arn.contains(":firehose:") {
+        return None;
+    }
+    arn.split_once(":deliverystream/")
+        .map(|(_, name)| name.to_string())
+}
+
+/// Bucket name from an S3 bucket ARN (arn:aws:s3:::bucket)
+fn bucket_from_arn(arn: &str) -> String {
+    arn.rsplit(':').next().unwrap_or(arn).to_string()
+}
+
+/// Firehose error prefixes may contain `!{...}` expressions (timestamps, error type).
+/// Only the literal part before the first expression can be used as a list prefix.
+fn literal_prefix(prefix: &str) -> String {
+    prefix.split("!{").next().unwrap_or_default().to_string()
+}
+
+/// Resolve the S3 error output location of the first destination of a delivery stream.
+/// When no error prefix is configured, Firehose uses a per-destination default folder.
+fn error_location(
+    delivery_stream_name: &str,
+    destination: &DestinationDescription,
+) -> Option<FirehoseErrorLocation> {
+    let (destination_type, bucket_arn, error_prefix, prefix, default_folder) =
+        if let Some(d) = destination.splunk_destination_description() {
+            let s3 = d.s3_destination_description()?;
+            (
+                "splunk",
+                s3.bucket_arn(),
+                s3.error_output_prefix(),
+                s3.prefix(),
+                "splunk-failed/",
+            )
+        } else if let Some(d) = destination.http_endpoint_destination_description() {
+            let s3 = d.s3_destination_description()?;
+            (
+                "http_endpoint",
+                s3.bucket_arn(),
+                s3.error_output_prefix(),
+                s3.prefix(),
+                "http-endpoint-failed/",
+            )
+        } else if let Some(d) = destination.amazonopensearchservice_destination_description() {
+            let s3 = d.s3_destination_description()?;
+            (
+                "opensearch",
+                s3.bucket_arn(),
+                s3.error_output_prefix(),
+                s3.prefix(),
+                "AmazonOpenSearchService-failed/",
+            )
+        } else if let Some(d) = destination.elasticsearch_destination_description() {
+            let s3 = d.s3_destination_description()?;
+            (
+                "elasticsearch",
+                s3.bucket_arn(),
+                s3.error_output_prefix(),
+                s3.prefix(),
+                "elasticsearch-failed/",
+            )
+        } else if let Some(d) = destination.extended_s3_destination_description() {
+            (
+                "extended_s3",
+                d.bucket_arn(),
+                d.error_output_prefix(),
+                d.prefix(),
+                "processing-failed/",
+            )
+        } else {
+            return None;
+        };
+
+    let error_prefix = match error_prefix.filter(|p| !p.is_empty()) {
+        Some(p) => literal_prefix(p),
+        None => format!(
+            "{}{}",
+            prefix.map(literal_prefix).unwrap_or_default(),
+            default_folder
+        ),
+    };
+
+    Some(FirehoseErrorLocation {
+        delivery_stream_name: delivery_stream_name.to_string(),
+        destination_type: destination_type.to_string(),
+        bucket: bucket_from_arn(bucket_arn),
+        error_prefix,
+    })
+}
+
+/// List subscription filters on a log group that deliver to Firehose
+#[tauri::command]
+pub async fn list_firehose_subscriptions(
+    state: State<'_, AppState>,
+    log_group_name: String,
+) -> Result<Vec<FirehoseSubscription>, String> {
+    let client = state.logs_client().await?;
+
+    let response = client
+        .describe_subscription_filters()
+        .log_group_name(&log_group_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+    Ok(response
+        .subscription_filters()
+        .iter()
+        .filter_map(|f| {
+            let destination_arn = f.destination_arn()?;
+            Some(FirehoseSubscription {
+                filter_name: f.filter_name().unwrap_or_default().to_string(),
+                filter_pattern: f.filter_pattern().map(str::to_string),
+                destination_arn: destination_arn.to_string(),
+                delivery_stream_name: delivery_stream_name_from_arn(destination_arn)?,
+            })
+        })
+        .collect())
+}
+
+/// List the failed batches a delivery stream wrote to its S3 error output,
+/// newest first, optionally only those modified at or after `since` (epoch ms)
+#[tauri::command]
+pub async fn list_firehose_failed_batches(
+    state: State<'_, AppState>,
+    delivery_stream_name: String,
+    since: Option<i64>,
+    max_objects: Option<usize>,
+) -> Result<FailedBatchListing, String> {
+    let config = state.sdk_config().await?;
+    let firehose = FirehoseClient::new(&config);
+    let s3 = S3Client::new(&config);
+    let max_objects = max_objects.unwrap_or(DEFAULT_MAX_OBJECTS);
+
+    let description = firehose
+        .describe_delivery_stream()
+        .delivery_stream_name(&delivery_stream_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+    let location = description
+        .delivery_stream_description()
+        .and_then(|d| d.destinations().first())
+        .and_then(|d| error_location(&delivery_stream_name, d))
+        .ok_or_else(|| {
+            format!(
+                "Delivery stream {} has no S3 error output configured",
+                delivery_stream_name
+            )
+        })?;
+
+    let mut objects = Vec::new();
+    let mut continuation_token: Option<String> = None;
+    let mut truncated = false;
+    loop {
+        let mut request = s3
+            .list_objects_v2()
+            .bucket(&location.bucket)
+            .prefix(&location.error_prefix);
+        if let Some(token) = continuation_token {
+            request = request.continuation_token(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        for object in response.contents() {
+            let last_modified = object.last_modified().and_then(|t| t.to_millis().ok());
+            if let (Some(since), Some(modified)) = (since, last_modified) {
+                if modified < since {
+                    continue;
+                }
+            }
+            objects.push(FailedBatchObject {
+                bucket: location.bucket.clone(),
+                key: object.key().unwrap_or_default().to_string(),
+                size_bytes: object.size().unwrap_or(0),
+                last_modified,
+            });
+        }
+
+        continuation_token = response.next_continuation_token;
+        if continuation_token.is_none() {
+            break;
+        }
+        // Error prefixes are time-partitioned, so cap the scan on very old streams
+        if objects.len() >= max_objects * 10 {
+            truncated = true;
+            break;
+        }
+    }
+
+    objects.sort_by_key(|o| std::cmp::Reverse(o.last_modified));
+    if objects.len() > max_objects {
+        objects.truncate(max_objects);
+        truncated = true;
+    }
+
+    Ok(FailedBatchListing {
+        location,
+        objects,
+        truncated,
+    })
+}
+
+/// Load a failed batch from S3 and decode each record back into the log events
+/// that failed to reach the destination, along with Firehose's error details
+#[tauri::command]
+pub async fn load_firehose_failed_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    bucket: String,
+    key: String,
+) -> Result<Vec<FailedRecord>, String> {
+    let config = state.sdk_config().await?;
+    let s3 = S3Client::new(&config);
+
+    let object = s3
+        .get_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let body = object
+        .body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read s3://{}/{}: {}", bucket, key, e))?
+        .into_bytes();
+
+    // Error output is gzip-compressed when the stream has compression enabled
+    let content = gunzip(&body).unwrap_or_else(|| body.to_vec());
+
+    // Records are concatenated JSON objects, usually (but not always) newline-delimited
+    let mut records = Vec::new();
+    for (index, raw) in serde_json::Deserializer::from_slice(&content)
+        .into_iter::<RawFailedRecord>()
+        .enumerate()
+    {
+        let raw = raw.map_err(|e| format!("Unexpected failed record format in {}: {}", key, e))?;
+        let data = raw
+            .raw_data
+            .as_deref()
+            .and_then(|d| base64::engine::general_purpose::STANDARD.decode(d).ok())
+            .unwrap_or_default();
+        let fallback_id = format!("{}#{}", key, index);
+        records.push(FailedRecord {
+            error_code: raw.error_code,
+            error_message: raw.error_message,
+            attempts_made: raw.attempts_made,
+            arrival_timestamp: raw.arrival_timestamp,
+            payload: decode_subscription_data(
+                &data,
+                raw.arrival_timestamp.unwrap_or(0),
+                &fallback_id,
+            ),
+        });
+    }
+
+    for record in &mut records {
+        redaction::mask_for_display(&app, &state, None, &mut record.payload.events).await;
+    }
+    Ok(records)
+}
@@ -0,0 +1,362 @@
+use crate::{storage, tail, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use tauri::AppHandle;
+
+/// Append-only JSON-lines file in the app config directory
+const HISTORY_FILE: &str = "group-history.jsonl";
+
+/// File in the app config directory listing the tracked groups
+const TRACKED_FILE: &str = "group-history-groups.json";
+
+/// Serializes appends and compaction of the history file
+static HISTORY_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Serializes read-modify-write of the tracked groups file
+static TRACKED_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+const MINUTE_MS: i64 = 60_000;
+
+/// Samples one fetch contributes at most; longer ranges get coarser samples
+const MAX_SAMPLES_PER_FETCH: i64 = 1_440;
+
+/// Minutes a live minute stays open for late events before it is recorded
+const LIVE_SETTLE_MINUTES: i64 = 2;
+
+/// Samples older than this are dropped when the file is compacted
+const RETENTION_DAYS: i64 = 90;
+
+/// History file size that triggers compaction on the next append
+const COMPACT_AT_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Resolution of get_group_history when none is given
+const DEFAULT_RESOLUTION_MINUTES: i64 = 60;
+
+/// Event and error counts of a group over one span
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySample {
+    pub log_group_name: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Start of the span, epoch ms
+    pub start: i64,
+    pub duration_ms: i64,
+    pub events: u64,
+    pub errors: u64,
+    /// "fetch" or "tail"
+    pub origin: String,
+    pub recorded_at: i64,
+}
+
+/// Rates of a group over one bucket of get_group_history
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPoint {
+    pub start: i64,
+    pub events_per_min: f64,
+    pub errors_per_min: f64,
+    /// Minutes of the bucket observed; rates of thinly covered buckets are rough
+    pub covered_minutes: f64,
+}
+
+fn append(app: &AppHandle, samples: &[HistorySample]) -> Result<(), String> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+    let path = storage::config_dir(app)?.join(HISTORY_FILE);
+    let mut lines = String::new();
+    for sample in samples {
+        lines.push_str(&serde_json::to_string(sample).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+    let _guard = HISTORY_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > COMPACT_AT_BYTES) {
+        compact(&path)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    file.write_all(lines.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn read_samples(path: &std::path::Path) -> Vec<HistorySample> {
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Rewrite the history without samples past retention
+fn compact(path: &std::path::Path) -> Result<(), String> {
+    let cutoff = chrono::Utc::now().timestamp_millis() - RETENTION_DAYS * 24 * 60 * MINUTE_MS;
+    let mut contents = String::new();
+    for sample in read_samples(path) {
+        if sample.start >= cutoff {
+            contents.push_str(&serde_json::to_string(&sample).map_err(|e| e.to_string())?);
+            contents.push('\n');
+        }
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp_path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn tracked_groups(app: &AppHandle) -> Vec<String> {
+    storage::read_json(app, TRACKED_FILE)
+}
+
+fn is_tracked(app: &AppHandle, log_group_name: &str) -> bool {
+    tracked_groups(app).iter().any(|g| g == log_group_name)
+}
+
+/// Record the rates of a complete, unfiltered fetch of a tracked group. Only
+/// whole minutes inside the range count, in samples of at least a minute.
+pub fn record_fetch(
+    app: &AppHandle,
+    profile: Option<String>,
+    log_group_name: &str,
+    start_time: i64,
+    end_time: i64,
+    events: &[LogEvent],
+) {
+    if !is_tracked(app, log_group_name) {
+        return;
+    }
+    let first = (start_time + MINUTE_MS - 1).div_euclid(MINUTE_MS) * MINUTE_MS;
+    let end = (end_time + 1).div_euclid(MINUTE_MS) * MINUTE_MS;
+    let minutes = (end - first) / MINUTE_MS;
+    if minutes <= 0 {
+        return;
+    }
+    let width = (minutes + MAX_SAMPLES_PER_FETCH - 1) / MAX_SAMPLES_PER_FETCH * MINUTE_MS;
+    let recorded_at = chrono::Utc::now().timestamp_millis();
+    let mut samples: Vec<HistorySample> = (first..end)
+        .step_by(width as usize)
+        .map(|start| HistorySample {
+            log_group_name: log_group_name.to_string(),
+            profile: profile.clone(),
+            start,
+            duration_ms: width.min(end - start),
+            events: 0,
+            errors: 0,
+            origin: "fetch".to_string(),
+            recorded_at,
+        })
+        .collect();
+    for event in events {
+        if event.timestamp < first || event.timestamp >= end {
+            continue;
+        }
+        let sample = &mut samples[((event.timestamp - first) / width) as usize];
+        sample.events += 1;
+        if tail::is_error_event(&event.message) {
+            sample.errors += 1;
+        }
+    }
+    if let Err(e) = append(app, &samples) {
+        log::warn!("Failed to record history of {}: {}", log_group_name, e);
+    }
+}
+
+/// Per-minute counts of a tail's tracked groups, recorded once each minute has
+/// settled. The minute the tail started in is partial and never recorded.
+pub struct LiveCounter {
+    profile: Option<String>,
+    groups: HashSet<String>,
+    /// Next minute to record
+    next_minute: i64,
+    counts: BTreeMap<(String, i64), (u64, u64)>,
+}
+
+impl LiveCounter {
+    /// Count `groups` that are tracked; pass only groups followed in full
+    /// (no filter pattern, not a single stream)
+    pub fn new(app: &AppHandle, profile: Option<String>, groups: Vec<String>) -> Self {
+        let tracked = tracked_groups(app);
+        let now = chrono::Utc::now().timestamp_millis();
+        Self {
+            profile,
+            groups: groups.into_iter().filter(|g| tracked.contains(g)).collect(),
+            next_minute: now.div_euclid(MINUTE_MS) * MINUTE_MS + MINUTE_MS,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, log_group_name: &str, events: &[LogEvent]) {
+        if !self.groups.contains(log_group_name) {
+            return;
+        }
+        for event in events {
+            let minute = event.timestamp.div_euclid(MINUTE_MS) * MINUTE_MS;
+            // Too late: that minute was already recorded
+            if minute < self.next_minute {
+                continue;
+            }
+            let counts = self
+                .counts
+                .entry((log_group_name.to_string(), minute))
+                .or_default();
+            counts.0 += 1;
+            if tail::is_error_event(&event.message) {
+                counts.1 += 1;
+            }
+        }
+    }
+
+    /// Record the minutes that have settled, including the quiet ones
+    pub fn flush(&mut self, app: &AppHandle) {
+        if self.groups.is_empty() {
+            return;
+        }
+        let settled = chrono::Utc::now().timestamp_millis().div_euclid(MINUTE_MS) * MINUTE_MS
+            - LIVE_SETTLE_MINUTES * MINUTE_MS;
+        if settled <= self.next_minute {
+            return;
+        }
+        let recorded_at = chrono::Utc::now().timestamp_millis();
+        let mut samples = Vec::new();
+        for minute in (self.next_minute..settled).step_by(MINUTE_MS as usize) {
+            for group in &self.groups {
+                let (events, errors) = self
+                    .counts
+                    .remove(&(group.clone(), minute))
+                    .unwrap_or_default();
+                samples.push(HistorySample {
+                    log_group_name: group.clone(),
+                    profile: self.profile.clone(),
+                    start: minute,
+                    duration_ms: MINUTE_MS,
+                    events,
+                    errors,
+                    origin: "tail".to_string(),
+                    recorded_at,
+                });
+            }
+        }
+        self.next_minute = settled;
+        if let Err(e) = append(app, &samples) {
+            log::warn!("Failed to record tail history: {}", e);
+        }
+    }
+}
+
+/// Groups whose rates are recorded
+#[tauri::command]
+pub fn list_history_groups(app: AppHandle) -> Vec<String> {
+    tracked_groups(&app)
+}
+
+/// Start or stop recording a group's events/min and errors/min from fetches and
+/// tails
+#[tauri::command]
+pub fn set_group_history_tracked(
+    app: AppHandle,
+    log_group_name: String,
+    tracked: bool,
+) -> Result<(), String> {
+    let _guard = TRACKED_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut groups = tracked_groups(&app);
+    groups.retain(|g| *g != log_group_name);
+    if tracked {
+        groups.push(log_group_name);
+        groups.sort();
+    }
+    storage::write_json(&app, TRACKED_FILE, &groups)
+}
+
+/// Events/min and errors/min of a group across days of usage, in buckets of
+/// `resolution_minutes` (hourly by default). Overlapping samples (a range
+/// fetched twice, or fetched while tailed) are averaged rather than added;
+/// buckets never observed are left out.
+#[tauri::command]
+pub fn get_group_history(
+    app: AppHandle,
+    log_group_name: String,
+    profile: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    resolution_minutes: Option<i64>,
+) -> Result<Vec<HistoryPoint>, String> {
+    let resolution = resolution_minutes
+        .unwrap_or(DEFAULT_RESOLUTION_MINUTES)
+        .max(1)
+        * MINUTE_MS;
+    let path = storage::config_dir(&app)?.join(HISTORY_FILE);
+    let samples = {
+        let _guard = HISTORY_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        read_samples(&path)
+    };
+
+    // Bucket start -> (observed ms, events weighted by ms, errors weighted by ms)
+    let mut buckets: BTreeMap<i64, (f64, f64, f64)> = BTreeMap::new();
+    for sample in samples {
+        if sample.log_group_name != log_group_name
+            || profile
+                .as_ref()
+                .is_some_and(|p| sample.profile.as_ref() != Some(p))
+            || sample.duration_ms <= 0
+            || since.is_some_and(|since| sample.start + sample.duration_ms <= since)
+            || until.is_some_and(|until| sample.start > until)
+        {
+            continue;
+        }
+        let events_per_ms = sample.events as f64 / sample.duration_ms as f64;
+        let errors_per_ms = sample.errors as f64 / sample.duration_ms as f64;
+        // Spread the sample over the buckets it overlaps
+        let end = sample.start + sample.duration_ms;
+        let mut start = sample.start;
+        while start < end {
+            let bucket = start.div_euclid(resolution) * resolution;
+            let overlap = (end.min(bucket + resolution) - start) as f64;
+            let entry = buckets.entry(bucket).or_default();
+            entry.0 += overlap;
+            entry.1 += events_per_ms * overlap;
+            entry.2 += errors_per_ms * overlap;
+            start = bucket + resolution;
+        }
+    }
+
+    let per_min = MINUTE_MS as f64;
+    Ok(buckets
+        .into_iter()
+        .map(|(start, (observed, events, errors))| HistoryPoint {
+            start,
+            events_per_min: events / observed * per_min,
+            errors_per_min: errors / observed * per_min,
+            covered_minutes: (observed / per_min).min((resolution / MINUTE_MS) as f64),
+        })
+        .collect())
+}
+
+/// Delete the recorded history of a group, or of every group
+#[tauri::command]
+pub fn clear_group_history(app: AppHandle, log_group_name: Option<String>) -> Result<(), String> {
+    let path = storage::config_dir(&app)?.join(HISTORY_FILE);
+    let _guard = HISTORY_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(name) = log_group_name else {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to delete {}: {}", path.display(), e))
+            }
+            _ => Ok(()),
+        };
+    };
+    let mut contents = String::new();
+    for sample in read_samples(&path) {
+        if sample.log_group_name != name {
+            contents.push_str(&serde_json::to_string(&sample).map_err(|e| e.to_string())?);
+            contents.push('\n');
+        }
+    }
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
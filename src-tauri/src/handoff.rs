@@ -0,0 +1,275 @@
+use crate::archive::ArchivedQuery;
+use crate::session::{self, SortOrder};
+use crate::{columns, filter_all_events, preview, redaction, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// Marker identifying a Loggy session handoff file
+const HANDOFF_FORMAT: &str = "loggy-handoff";
+
+/// Current handoff layout version; bumped on incompatible changes
+const HANDOFF_VERSION: u32 = 1;
+
+/// Events fetched per query when importing, unless overridden
+const DEFAULT_IMPORT_MAX_EVENTS: usize = 50_000;
+
+/// Events returned with an imported handoff; the rest are read with get_session_window
+const HANDOFF_WINDOW: usize = 500;
+
+/// A bookmarked event, identified without its message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffBookmark {
+    pub event_id: String,
+    /// Missing when the event was no longer loaded at handoff time
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    #[serde(default)]
+    pub log_group_name: Option<String>,
+    #[serde(default)]
+    pub log_stream_name: Option<String>,
+}
+
+/// A session's queries and bookmarks, without any event data, for a teammate to
+/// reproduce the same view with their own credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionHandoff {
+    pub format: String,
+    pub version: u32,
+    pub created_at: i64,
+    pub title: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// Profile and region of the sender, for reference only
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
+    pub queries: Vec<ArchivedQuery>,
+    /// Time ranges zoomed into, outermost first
+    #[serde(default)]
+    pub zoom_stack: Vec<(i64, i64)>,
+    #[serde(default)]
+    pub order: SortOrder,
+    #[serde(default)]
+    pub bookmarks: Vec<HandoffBookmark>,
+}
+
+/// A handoff loaded into a session by re-running its queries
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedHandoff {
+    pub session_id: String,
+    pub handoff: SessionHandoff,
+    pub event_count: usize,
+    /// Queries that stopped at the event limit
+    pub truncated: Vec<String>,
+    /// Bookmarks not among the re-fetched events (retention, permissions, limits)
+    pub missing_bookmarks: Vec<String>,
+    /// The sender was connected to a different region than the importer
+    pub region_mismatch: bool,
+    /// The first window of the session
+    pub events: Vec<LogEvent>,
+}
+
+fn read_handoff(path: &str) -> Result<SessionHandoff, String> {
+    let json =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let handoff: SessionHandoff = serde_json::from_str(&json)
+        .map_err(|e| format!("{} is not a Loggy handoff file: {}", path, e))?;
+    if handoff.format != HANDOFF_FORMAT {
+        return Err(format!("{} is not a Loggy handoff file", path));
+    }
+    if handoff.version > HANDOFF_VERSION {
+        return Err(format!(
+            "{} was written by a newer version of Loggy (handoff version {})",
+            path, handoff.version
+        ));
+    }
+    Ok(handoff)
+}
+
+/// Write a small handoff file at `path` holding the session's queries, zoom and
+/// sort order and its bookmarked (selected) events by id and timestamp. No
+/// messages are included; the receiver re-runs the queries with their own access.
+#[tauri::command]
+pub async fn export_session_handoff(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    path: String,
+    title: String,
+    note: Option<String>,
+) -> Result<SessionHandoff, String> {
+    let (queries, zoom_stack, order, bookmarks) = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        if session.read_only {
+            return Err(
+                "Sessions opened from an archive have no queries to hand off; share the archive instead"
+                    .to_string(),
+            );
+        }
+        let mut queries: Vec<ArchivedQuery> = session
+            .fetches
+            .iter()
+            .map(|(group, context)| ArchivedQuery {
+                log_group_name: group.clone(),
+                start_time: context.start_time,
+                end_time: context.end_time,
+                filter_pattern: context.filter_pattern.clone(),
+                truncated: context.truncated,
+            })
+            .collect();
+        queries.sort_by(|a, b| a.log_group_name.cmp(&b.log_group_name));
+        let bookmarks: Vec<HandoffBookmark> = session
+            .selection()
+            .event_ids
+            .into_iter()
+            .map(|event_id| {
+                let event = session.event(&event_id);
+                HandoffBookmark {
+                    timestamp: event.as_ref().map(|e| e.timestamp),
+                    log_group_name: event.as_ref().and_then(|e| e.source.clone()),
+                    log_stream_name: event.and_then(|e| e.log_stream_name),
+                    event_id,
+                }
+            })
+            .collect();
+        (
+            queries,
+            session.zoom_stack.clone(),
+            session.order,
+            bookmarks,
+        )
+    };
+    if queries.is_empty() {
+        return Err("The session has no CloudWatch queries to hand off".to_string());
+    }
+
+    let region = state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+    let handoff = SessionHandoff {
+        format: HANDOFF_FORMAT.to_string(),
+        version: HANDOFF_VERSION,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        title,
+        note: note.filter(|n| !n.trim().is_empty()),
+        app_version: Some(app.package_info().version.to_string()),
+        profile: state.current_profile.lock().await.clone(),
+        region,
+        queries,
+        zoom_stack,
+        order,
+        bookmarks,
+    };
+
+    let json = serde_json::to_string_pretty(&handoff).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    log::info!(
+        "Wrote handoff of session {} ({} queries, {} bookmarks) to {}",
+        session_id,
+        handoff.queries.len(),
+        handoff.bookmarks.len(),
+        path
+    );
+    Ok(handoff)
+}
+
+/// Read a handoff file without running its queries, to show what it will fetch
+#[tauri::command]
+pub fn read_session_handoff(path: String) -> Result<SessionHandoff, String> {
+    read_handoff(&path)
+}
+
+/// Re-run a handoff's queries with the current connection into `session_id`,
+/// replacing what it held, and restore its zoom, sort order and bookmarks
+#[tauri::command]
+pub async fn import_session_handoff(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    session_id: String,
+    max_count: Option<usize>,
+) -> Result<ImportedHandoff, String> {
+    let handoff = read_handoff(&path)?;
+    let client = state.logs_client().await?;
+    let max_events = max_count.unwrap_or(DEFAULT_IMPORT_MAX_EVENTS);
+
+    let mut fetched = Vec::new();
+    for query in &handoff.queries {
+        let (mut events, truncated) = filter_all_events(
+            &client,
+            &query.log_group_name,
+            query.start_time,
+            query.end_time,
+            query.filter_pattern.as_deref(),
+            max_events,
+        )
+        .await?;
+        session::tag_source(&mut events, &query.log_group_name);
+        fetched.push((query, events, truncated));
+    }
+
+    let region = state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+    let region_mismatch = matches!((&handoff.region, &region), (Some(a), Some(b)) if a != b);
+
+    let mut truncated_queries = Vec::new();
+    let (event_count, missing_bookmarks, mut window) = {
+        let mut sessions = state.sessions.lock().await;
+        sessions.remove(&session_id);
+        let result = sessions.get_or_create(&session_id);
+        for (query, events, truncated) in fetched {
+            if truncated {
+                truncated_queries.push(query.log_group_name.clone());
+            }
+            result.replace_source(&query.log_group_name, events);
+            result.fetches.insert(
+                query.log_group_name.clone(),
+                session::FetchContext {
+                    start_time: query.start_time,
+                    end_time: query.end_time,
+                    filter_pattern: query.filter_pattern.clone(),
+                    truncated,
+                    complete_ranges: Vec::new(),
+                },
+            );
+        }
+        result.zoom_stack = handoff.zoom_stack.clone();
+        result.order = handoff.order;
+        result.selected = handoff
+            .bookmarks
+            .iter()
+            .map(|b| b.event_id.clone())
+            .collect();
+        let missing: Vec<String> = handoff
+            .bookmarks
+            .iter()
+            .filter(|b| result.position_of(&b.event_id).is_none())
+            .map(|b| b.event_id.clone())
+            .collect();
+        (result.len(), missing, result.window(0, HANDOFF_WINDOW))
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+
+    Ok(ImportedHandoff {
+        session_id,
+        handoff,
+        event_count,
+        truncated: truncated_queries,
+        missing_bookmarks,
+        region_mismatch,
+        events: window,
+    })
+}
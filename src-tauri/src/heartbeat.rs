@@ -0,0 +1,130 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{connectivity, AppState};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Time between heartbeat calls
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A heartbeat slower than this marks the connection degraded
+const SLOW_LATENCY_MS: u64 = 2_000;
+
+/// Consecutive failures after which the connection counts as down
+const DOWN_AFTER_FAILURES: u32 = 3;
+
+/// Weight of the newest sample in the latency average
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+/// Latency and failures of the active connection, as seen by the heartbeat
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectionHealth {
+    /// Active connection name, or the profile when connected without one
+    pub connection: Option<String>,
+    /// "healthy", "degraded" (slow or failing) or "down"
+    pub status: &'static str,
+    /// Latency of the last successful heartbeat
+    pub latency_ms: Option<u64>,
+    /// Smoothed latency of recent heartbeats
+    pub average_latency_ms: Option<u64>,
+    /// Epoch milliseconds of the last successful heartbeat
+    pub last_success_at: Option<i64>,
+    pub last_error_at: Option<i64>,
+    pub last_error: Option<String>,
+    pub failure_streak: u32,
+}
+
+static HEALTH: Mutex<Option<ConnectionHealth>> = Mutex::new(None);
+
+fn status_of(health: &ConnectionHealth) -> &'static str {
+    if health.failure_streak >= DOWN_AFTER_FAILURES {
+        "down"
+    } else if health.failure_streak > 0 || health.latency_ms.is_some_and(|l| l > SLOW_LATENCY_MS) {
+        "degraded"
+    } else {
+        "healthy"
+    }
+}
+
+/// Health of the active connection (None before the first heartbeat)
+#[tauri::command]
+pub fn get_connection_health() -> Option<ConnectionHealth> {
+    HEALTH.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+async fn connection_name(state: &AppState) -> Option<String> {
+    match state.active_connection.lock().await.as_ref() {
+        Some(connection) => Some(connection.name.clone()),
+        None => state.current_profile.lock().await.clone(),
+    }
+}
+
+/// One heartbeat: a DescribeLogGroups call with limit 1, timed
+async fn beat(app: &AppHandle, state: &AppState) {
+    // Cloned so a slow call doesn't hold the client lock
+    let Some(client) = state.client.lock().await.clone() else {
+        return;
+    };
+    let connection = connection_name(state).await;
+    let started = Instant::now();
+    let result = client.describe_log_groups().limit(1).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let health = {
+        let mut guard = HEALTH.lock().unwrap_or_else(|e| e.into_inner());
+        let health = guard.get_or_insert_with(ConnectionHealth::default);
+        if health.connection != connection {
+            *health = ConnectionHealth {
+                connection,
+                ..Default::default()
+            };
+        }
+        match &result {
+            Ok(_) => {
+                health.latency_ms = Some(latency_ms);
+                health.average_latency_ms = Some(match health.average_latency_ms {
+                    Some(average) => {
+                        (LATENCY_SMOOTHING * latency_ms as f64
+                            + (1.0 - LATENCY_SMOOTHING) * average as f64)
+                            as u64
+                    }
+                    None => latency_ms,
+                });
+                health.last_success_at = Some(now);
+                health.failure_streak = 0;
+            }
+            Err(e) => {
+                health.last_error_at = Some(now);
+                health.last_error = Some(crate::humanize_aws_error(&format!("{}", e)));
+                health.failure_streak += 1;
+            }
+        }
+        health.status = status_of(health);
+        health.clone()
+    };
+    if let Err(e) = &result {
+        // A network failure hands over to the connectivity watcher, which pauses
+        // tails and switches to offline mode until AWS answers again
+        if connectivity::is_network_error(&format!("{:?}", e)) {
+            connectivity::connection_lost();
+        }
+    }
+    app.emit_tagged("connection-health", health).ok();
+}
+
+/// Time a cheap API call against the active connection every minute, emitting
+/// `connection-health` with latency and failure streaks so the UI can show how
+/// AWS is responding. Skipped while disconnected or offline.
+pub async fn run_heartbeat(app: AppHandle) {
+    loop {
+        tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+        if connectivity::ensure_online().is_err() {
+            continue;
+        }
+        if let Some(state) = app.try_state::<AppState>() {
+            beat(&app, &state).await;
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::catalog::log_group_name_from_arn;
+use crate::{daily_summaries, humanize_aws_error, AppState};
+use aws_sdk_cloudwatch::primitives::DateTime;
+use aws_sdk_cloudwatch::types::{Dimension, Metric, MetricDataQuery, MetricStat};
+use aws_sdk_cloudwatch::Client as MetricsClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// GetMetricData accepts at most this many queries per call
+const MAX_QUERIES_PER_CALL: usize = 500;
+
+/// A cell this many times the row's median is a spike
+const SPIKE_FACTOR: f64 = 3.0;
+
+/// A cell below this fraction of the row's median is a drop
+const DROP_FACTOR: f64 = 0.1;
+
+/// Time span of the heatmap
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum HeatmapWindow {
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+}
+
+impl HeatmapWindow {
+    /// (span, bucket) in milliseconds: hourly cells for 24h, 6-hourly for 7d
+    fn span_and_bucket(self) -> (i64, i64) {
+        const HOUR: i64 = 3_600_000;
+        match self {
+            HeatmapWindow::Day => (24 * HOUR, HOUR),
+            HeatmapWindow::Week => (7 * 24 * HOUR, 6 * HOUR),
+        }
+    }
+}
+
+/// Volume of one group in one bucket
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HeatmapCell {
+    pub bytes: f64,
+    pub events: f64,
+    /// Share of the busiest cell of the whole heatmap, 0.0-1.0
+    pub intensity: f64,
+    /// "spike" or "drop" relative to the group's median bucket
+    pub anomaly: Option<String>,
+}
+
+/// One log group's row of the heatmap
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatmapRow {
+    pub log_group_name: String,
+    pub total_bytes: f64,
+    pub total_events: f64,
+    pub cells: Vec<HeatmapCell>,
+}
+
+/// Ingested volume per group and time bucket, busiest group first
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeHeatmap {
+    pub window: HeatmapWindow,
+    pub bucket_ms: i64,
+    /// Start of each column
+    pub bucket_starts: Vec<i64>,
+    pub rows: Vec<HeatmapRow>,
+    pub max_cell_bytes: f64,
+    /// Groups that reported no IncomingBytes in the window
+    pub silent: Vec<String>,
+}
+
+fn metric_query(
+    id: String,
+    log_group_name: &str,
+    metric_name: &str,
+    period: i32,
+) -> MetricDataQuery {
+    let metric = Metric::builder()
+        .namespace("AWS/Logs")
+        .metric_name(metric_name)
+        .dimensions(
+            Dimension::builder()
+                .name("LogGroupName")
+                .value(log_group_name)
+                .build(),
+        )
+        .build();
+    MetricDataQuery::builder()
+        .id(id)
+        .metric_stat(
+            MetricStat::builder()
+                .metric(metric)
+                .period(period)
+                .stat("Sum")
+                .build(),
+        )
+        .return_data(true)
+        .build()
+}
+
+/// Sum of IncomingBytes and IncomingLogEvents of each group per bucket, keyed by
+/// query id ("b<i>" / "e<i>" for group i)
+async fn bucket_sums(
+    client: &MetricsClient,
+    groups: &[String],
+    start: i64,
+    end: i64,
+    bucket_ms: i64,
+) -> Result<HashMap<String, Vec<(i64, f64)>>, String> {
+    let period = (bucket_ms / 1000) as i32;
+    let queries: Vec<MetricDataQuery> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, group)| {
+            [
+                metric_query(format!("b{}", i), group, "IncomingBytes", period),
+                metric_query(format!("e{}", i), group, "IncomingLogEvents", period),
+            ]
+        })
+        .collect();
+
+    let mut sums: HashMap<String, Vec<(i64, f64)>> = HashMap::new();
+    for chunk in queries.chunks(MAX_QUERIES_PER_CALL) {
+        let mut next_token: Option<String> = None;
+        loop {
+            let response = client
+                .get_metric_data()
+                .set_metric_data_queries(Some(chunk.to_vec()))
+                .start_time(DateTime::from_millis(start))
+                .end_time(DateTime::from_millis(end))
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+            for result in response.metric_data_results() {
+                let Some(id) = result.id() else {
+                    continue;
+                };
+                sums.entry(id.to_string()).or_default().extend(
+                    result
+                        .timestamps()
+                        .iter()
+                        .zip(result.values())
+                        .filter_map(|(t, v)| Some((t.to_millis().ok()?, *v))),
+                );
+            }
+            next_token = response.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+    }
+    Ok(sums)
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    values[values.len() / 2]
+}
+
+/// Pinned groups of the connected profile, as log group names
+async fn pinned_group_names(app: &AppHandle, state: &AppState) -> Vec<String> {
+    let profile = state.current_profile.lock().await.clone();
+    let mut names: Vec<String> = daily_summaries::pinned_groups(app)
+        .into_iter()
+        .filter(|g| g.profile.is_none() || g.profile == profile)
+        .map(|g| log_group_name_from_arn(&g.log_group_name).unwrap_or(g.log_group_name))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Account overview of ingested log volume (CloudWatch IncomingBytes) per group
+/// over the last 24h or 7d, with cells flagged where a group spikes or goes quiet
+/// against its own median. Covers `log_group_names`, or the pinned groups of the
+/// connected profile when omitted.
+#[tauri::command]
+pub async fn get_volume_heatmap(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    window: HeatmapWindow,
+    log_group_names: Option<Vec<String>>,
+) -> Result<VolumeHeatmap, String> {
+    let groups = match log_group_names {
+        Some(names) if !names.is_empty() => names,
+        _ => pinned_group_names(&app, &state).await,
+    };
+    if groups.is_empty() {
+        return Err("No log groups given and no pinned groups for this profile".to_string());
+    }
+
+    let (span, bucket_ms) = window.span_and_bucket();
+    let now = chrono::Utc::now().timestamp_millis();
+    let end = now - now % bucket_ms + bucket_ms;
+    let start = end - span;
+    let bucket_starts: Vec<i64> = (start..end).step_by(bucket_ms as usize).collect();
+
+    let client = MetricsClient::new(&state.sdk_config().await?);
+    let sums = bucket_sums(&client, &groups, start, end, bucket_ms).await?;
+    let column = |t: i64| ((t - start) / bucket_ms) as usize;
+
+    let mut rows = Vec::new();
+    let mut silent = Vec::new();
+    for (i, group) in groups.iter().enumerate() {
+        let mut cells = vec![HeatmapCell::default(); bucket_starts.len()];
+        for &(t, v) in sums.get(&format!("b{}", i)).into_iter().flatten() {
+            if let Some(cell) = cells.get_mut(column(t)) {
+                cell.bytes += v;
+            }
+        }
+        for &(t, v) in sums.get(&format!("e{}", i)).into_iter().flatten() {
+            if let Some(cell) = cells.get_mut(column(t)) {
+                cell.events += v;
+            }
+        }
+        let total_bytes: f64 = cells.iter().map(|c| c.bytes).sum();
+        if total_bytes == 0.0 {
+            silent.push(group.clone());
+            continue;
+        }
+
+        // The current bucket is still filling, so it is never flagged
+        let mut complete: Vec<f64> = cells[..cells.len() - 1].iter().map(|c| c.bytes).collect();
+        let typical = median(&mut complete);
+        let last = cells.len() - 1;
+        for cell in &mut cells[..last] {
+            if typical > 0.0 && cell.bytes >= typical * SPIKE_FACTOR {
+                cell.anomaly = Some("spike".to_string());
+            } else if typical > 0.0 && cell.bytes <= typical * DROP_FACTOR {
+                cell.anomaly = Some("drop".to_string());
+            }
+        }
+        rows.push(HeatmapRow {
+            log_group_name: group.clone(),
+            total_bytes,
+            total_events: cells.iter().map(|c| c.events).sum(),
+            cells,
+        });
+    }
+
+    let max_cell_bytes = rows
+        .iter()
+        .flat_map(|r| r.cells.iter().map(|c| c.bytes))
+        .fold(0.0, f64::max);
+    if max_cell_bytes > 0.0 {
+        for cell in rows.iter_mut().flat_map(|r| r.cells.iter_mut()) {
+            cell.intensity = cell.bytes / max_cell_bytes;
+        }
+    }
+    rows.sort_by(|a, b| b.total_bytes.total_cmp(&a.total_bytes));
+
+    Ok(VolumeHeatmap {
+        window,
+        bucket_ms,
+        bucket_starts,
+        rows,
+        max_cell_bytes,
+        silent,
+    })
+}
@@ -0,0 +1,358 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tauri::AppHandle;
+
+/// File in the app config directory holding the language preference
+const LANGUAGE_FILE: &str = "language.json";
+
+/// Language used for backend-generated text (error guidance, native menu labels)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Es,
+    Fr,
+    Ja,
+}
+
+impl Language {
+    /// Match a locale string such as "de_DE.UTF-8" or "fr-CA"
+    fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.get(..2)?.to_ascii_lowercase();
+        match code.as_str() {
+            "en" => Some(Self::En),
+            "de" => Some(Self::De),
+            "es" => Some(Self::Es),
+            "fr" => Some(Self::Fr),
+            "ja" => Some(Self::Ja),
+            _ => None,
+        }
+    }
+
+    /// Best guess from the environment, falling back to English
+    fn from_system() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+/// Persisted preference; None follows the system locale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageSettings {
+    #[serde(default)]
+    pub language: Option<Language>,
+}
+
+/// Language currently used by `tr`
+static CURRENT: RwLock<Language> = RwLock::new(Language::En);
+
+/// Keys for every backend-generated user-facing string
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    SessionExpired,
+    NoCredentials,
+    AccessDenied,
+    AccessDeniedDetail,
+    InvalidCredentials,
+    CredentialsError,
+    UnableToConnectMaybeExpired,
+    UnableToConnect,
+    Timeout,
+    DnsFailure,
+    LogGroupNotFound,
+    Throttled,
+    InvalidRegion,
+    ServiceUnavailable,
+    Offline,
+    UnexpectedError,
+    MenuAbout,
+    MenuCheckForUpdates,
+    MenuPreferences,
+    MenuRefresh,
+    MenuSearch,
+    MenuClear,
+    MenuFind,
+    MenuThemeDark,
+    MenuThemeLight,
+    MenuThemeSystem,
+    MenuTheme,
+    MenuDemoMode,
+    MenuEdit,
+    MenuView,
+    MenuWindow,
+    MenuFile,
+    MenuExport,
+    MenuRecentWorkspaces,
+    MenuNoRecentWorkspaces,
+}
+
+fn english(message: Message) -> &'static str {
+    use Message::*;
+    match message {
+        SessionExpired => "Your AWS session has expired. Sign in again from Loggy to refresh your credentials.",
+        NoCredentials => "No AWS credentials found. Sign in with AWS SSO from Loggy or configure your AWS credentials.",
+        AccessDenied => "Access denied. Your AWS credentials don't have permission for this operation.",
+        AccessDeniedDetail => "Access denied. Your AWS credentials are not allowed to perform {action} on {resource}.",
+        InvalidCredentials => "Invalid AWS credentials. Please check your AWS configuration.",
+        CredentialsError => "AWS credentials error. Sign in again from Loggy or check your AWS configuration.",
+        UnableToConnectMaybeExpired => "Unable to connect to AWS. This could be a network issue or expired credentials. Try signing in again from Loggy.",
+        UnableToConnect => "Unable to connect to AWS. Please check your network connection.",
+        Timeout => "Connection to AWS timed out. Please try again.",
+        DnsFailure => "Unable to resolve AWS endpoint. Please check your network connection.",
+        LogGroupNotFound => "The requested log group was not found.",
+        Throttled => "AWS rate limit exceeded. Please wait a moment and try again.",
+        InvalidRegion => "Invalid or missing AWS region. Please check your AWS configuration.",
+        ServiceUnavailable => "AWS CloudWatch Logs service is temporarily unavailable. Please try again later.",
+        Offline => "You are offline. Cached results, imported files and saved views remain available; AWS requests resume once the connection is back.",
+        UnexpectedError => "An unexpected error occurred while connecting to AWS.",
+        MenuAbout => "About Loggy",
+        MenuCheckForUpdates => "Check for Updates...",
+        MenuPreferences => "Preferences...",
+        MenuRefresh => "Refresh",
+        MenuSearch => "Search",
+        MenuClear => "Clear",
+        MenuFind => "Find",
+        MenuThemeDark => "Dark",
+        MenuThemeLight => "Light",
+        MenuThemeSystem => "System",
+        MenuTheme => "Theme",
+        MenuDemoMode => "Demo Mode",
+        MenuEdit => "Edit",
+        MenuView => "View",
+        MenuWindow => "Window",
+        MenuFile => "File",
+        MenuExport => "Export Logs...",
+        MenuRecentWorkspaces => "Recent Workspaces",
+        MenuNoRecentWorkspaces => "No Recent Workspaces",
+    }
+}
+
+fn german(message: Message) -> &'static str {
+    use Message::*;
+    match message {
+        SessionExpired => "Ihre AWS-Sitzung ist abgelaufen. Melden Sie sich in Loggy erneut an, um Ihre Anmeldedaten zu erneuern.",
+        NoCredentials => "Keine AWS-Anmeldedaten gefunden. Melden Sie sich in Loggy mit AWS SSO an oder konfigurieren Sie Ihre AWS-Anmeldedaten.",
+        AccessDenied => "Zugriff verweigert. Ihre AWS-Anmeldedaten haben keine Berechtigung für diesen Vorgang.",
+        AccessDeniedDetail => "Zugriff verweigert. Ihre AWS-Anmeldedaten dürfen {action} auf {resource} nicht ausführen.",
+        InvalidCredentials => "Ungültige AWS-Anmeldedaten. Bitte überprüfen Sie Ihre AWS-Konfiguration.",
+        CredentialsError => "Fehler bei den AWS-Anmeldedaten. Melden Sie sich in Loggy erneut an oder überprüfen Sie Ihre AWS-Konfiguration.",
+        UnableToConnectMaybeExpired => "Verbindung zu AWS nicht möglich. Ursache kann ein Netzwerkproblem oder abgelaufene Anmeldedaten sein. Melden Sie sich in Loggy erneut an.",
+        UnableToConnect => "Verbindung zu AWS nicht möglich. Bitte überprüfen Sie Ihre Netzwerkverbindung.",
+        Timeout => "Zeitüberschreitung bei der Verbindung zu AWS. Bitte versuchen Sie es erneut.",
+        DnsFailure => "AWS-Endpunkt konnte nicht aufgelöst werden. Bitte überprüfen Sie Ihre Netzwerkverbindung.",
+        LogGroupNotFound => "Die angeforderte Protokollgruppe wurde nicht gefunden.",
+        Throttled => "AWS-Ratenlimit überschritten. Bitte warten Sie einen Moment und versuchen Sie es erneut.",
+        InvalidRegion => "Ungültige oder fehlende AWS-Region. Bitte überprüfen Sie Ihre AWS-Konfiguration.",
+        ServiceUnavailable => "Der Dienst AWS CloudWatch Logs ist vorübergehend nicht verfügbar. Bitte versuchen Sie es später erneut.",
+        Offline => "Sie sind offline. Zwischengespeicherte Ergebnisse, importierte Dateien und gespeicherte Ansichten bleiben verfügbar; AWS-Anfragen werden fortgesetzt, sobald die Verbindung wieder besteht.",
+        UnexpectedError => "Beim Verbinden mit AWS ist ein unerwarteter Fehler aufgetreten.",
+        MenuAbout => "Über Loggy",
+        MenuCheckForUpdates => "Nach Updates suchen...",
+        MenuPreferences => "Einstellungen...",
+        MenuRefresh => "Aktualisieren",
+        MenuSearch => "Suchen",
+        MenuClear => "Leeren",
+        MenuFind => "Finden",
+        MenuThemeDark => "Dunkel",
+        MenuThemeLight => "Hell",
+        MenuThemeSystem => "System",
+        MenuTheme => "Design",
+        MenuDemoMode => "Demomodus",
+        MenuEdit => "Bearbeiten",
+        MenuView => "Darstellung",
+        MenuWindow => "Fenster",
+        MenuFile => "Datei",
+        MenuExport => "Logs exportieren...",
+        MenuRecentWorkspaces => "Zuletzt verwendete Arbeitsbereiche",
+        MenuNoRecentWorkspaces => "Keine zuletzt verwendeten Arbeitsbereiche",
+    }
+}
+
+fn spanish(message: Message) -> &'static str {
+    use Message::*;
+    match message {
+        SessionExpired => "Tu sesión de AWS ha caducado. Vuelve a iniciar sesión desde Loggy para renovar tus credenciales.",
+        NoCredentials => "No se encontraron credenciales de AWS. Inicia sesión con AWS SSO desde Loggy o configura tus credenciales de AWS.",
+        AccessDenied => "Acceso denegado. Tus credenciales de AWS no tienen permiso para esta operación.",
+        AccessDeniedDetail => "Acceso denegado. Tus credenciales de AWS no pueden ejecutar {action} sobre {resource}.",
+        InvalidCredentials => "Credenciales de AWS no válidas. Revisa tu configuración de AWS.",
+        CredentialsError => "Error en las credenciales de AWS. Vuelve a iniciar sesión desde Loggy o revisa tu configuración de AWS.",
+        UnableToConnectMaybeExpired => "No se puede conectar con AWS. Puede ser un problema de red o credenciales caducadas. Prueba a iniciar sesión de nuevo desde Loggy.",
+        UnableToConnect => "No se puede conectar con AWS. Revisa tu conexión de red.",
+        Timeout => "Se agotó el tiempo de conexión con AWS. Inténtalo de nuevo.",
+        DnsFailure => "No se pudo resolver el endpoint de AWS. Revisa tu conexión de red.",
+        LogGroupNotFound => "No se encontró el grupo de registros solicitado.",
+        Throttled => "Se superó el límite de solicitudes de AWS. Espera un momento e inténtalo de nuevo.",
+        InvalidRegion => "Región de AWS no válida o ausente. Revisa tu configuración de AWS.",
+        ServiceUnavailable => "El servicio AWS CloudWatch Logs no está disponible temporalmente. Inténtalo más tarde.",
+        Offline => "Estás sin conexión. Los resultados en caché, los archivos importados y las vistas guardadas siguen disponibles; las solicitudes a AWS se reanudarán cuando vuelva la conexión.",
+        UnexpectedError => "Se produjo un error inesperado al conectar con AWS.",
+        MenuAbout => "Acerca de Loggy",
+        MenuCheckForUpdates => "Buscar actualizaciones...",
+        MenuPreferences => "Preferencias...",
+        MenuRefresh => "Actualizar",
+        MenuSearch => "Buscar",
+        MenuClear => "Limpiar",
+        MenuFind => "Encontrar",
+        MenuThemeDark => "Oscuro",
+        MenuThemeLight => "Claro",
+        MenuThemeSystem => "Sistema",
+        MenuTheme => "Tema",
+        MenuDemoMode => "Modo demo",
+        MenuEdit => "Edición",
+        MenuView => "Ver",
+        MenuWindow => "Ventana",
+        MenuFile => "Archivo",
+        MenuExport => "Exportar logs...",
+        MenuRecentWorkspaces => "Espacios de trabajo recientes",
+        MenuNoRecentWorkspaces => "No hay espacios de trabajo recientes",
+    }
+}
+
+fn french(message: Message) -> &'static str {
+    use Message::*;
+    match message {
+        SessionExpired => "Votre session AWS a expiré. Reconnectez-vous depuis Loggy pour renouveler vos identifiants.",
+        NoCredentials => "Aucun identifiant AWS trouvé. Connectez-vous avec AWS SSO depuis Loggy ou configurez vos identifiants AWS.",
+        AccessDenied => "Accès refusé. Vos identifiants AWS n'ont pas l'autorisation pour cette opération.",
+        AccessDeniedDetail => "Accès refusé. Vos identifiants AWS ne sont pas autorisés à effectuer {action} sur {resource}.",
+        InvalidCredentials => "Identifiants AWS invalides. Vérifiez votre configuration AWS.",
+        CredentialsError => "Erreur d'identifiants AWS. Reconnectez-vous depuis Loggy ou vérifiez votre configuration AWS.",
+        UnableToConnectMaybeExpired => "Impossible de se connecter à AWS. Il peut s'agir d'un problème réseau ou d'identifiants expirés. Essayez de vous reconnecter depuis Loggy.",
+        UnableToConnect => "Impossible de se connecter à AWS. Vérifiez votre connexion réseau.",
+        Timeout => "Délai de connexion à AWS dépassé. Veuillez réessayer.",
+        DnsFailure => "Impossible de résoudre le point de terminaison AWS. Vérifiez votre connexion réseau.",
+        LogGroupNotFound => "Le groupe de journaux demandé est introuvable.",
+        Throttled => "Limite de requêtes AWS dépassée. Patientez un instant puis réessayez.",
+        InvalidRegion => "Région AWS invalide ou manquante. Vérifiez votre configuration AWS.",
+        ServiceUnavailable => "Le service AWS CloudWatch Logs est temporairement indisponible. Réessayez plus tard.",
+        Offline => "Vous êtes hors ligne. Les résultats en cache, les fichiers importés et les vues enregistrées restent disponibles ; les requêtes AWS reprendront au retour de la connexion.",
+        UnexpectedError => "Une erreur inattendue s'est produite lors de la connexion à AWS.",
+        MenuAbout => "À propos de Loggy",
+        MenuCheckForUpdates => "Rechercher des mises à jour...",
+        MenuPreferences => "Préférences...",
+        MenuRefresh => "Actualiser",
+        MenuSearch => "Rechercher",
+        MenuClear => "Effacer",
+        MenuFind => "Trouver",
+        MenuThemeDark => "Sombre",
+        MenuThemeLight => "Clair",
+        MenuThemeSystem => "Système",
+        MenuTheme => "Thème",
+        MenuDemoMode => "Mode démo",
+        MenuEdit => "Édition",
+        MenuView => "Présentation",
+        MenuWindow => "Fenêtre",
+        MenuFile => "Fichier",
+        MenuExport => "Exporter les logs...",
+        MenuRecentWorkspaces => "Espaces de travail récents",
+        MenuNoRecentWorkspaces => "Aucun espace de travail récent",
+    }
+}
+
+fn japanese(message: Message) -> &'static str {
+    use Message::*;
+    match message {
+        SessionExpired => "AWS セッションの有効期限が切れました。Loggy から再度サインインして認証情報を更新してください。",
+        NoCredentials => "AWS 認証情報が見つかりません。Loggy から AWS SSO でサインインするか、AWS 認証情報を設定してください。",
+        AccessDenied => "アクセスが拒否されました。AWS 認証情報にこの操作の権限がありません。",
+        AccessDeniedDetail => "アクセスが拒否されました。AWS 認証情報では {resource} に対する {action} が許可されていません。",
+        InvalidCredentials => "AWS 認証情報が無効です。AWS の設定を確認してください。",
+        CredentialsError => "AWS 認証情報のエラーです。Loggy から再度サインインするか、AWS の設定を確認してください。",
+        UnableToConnectMaybeExpired => "AWS に接続できません。ネットワークの問題か認証情報の期限切れの可能性があります。Loggy から再度サインインしてみてください。",
+        UnableToConnect => "AWS に接続できません。ネットワーク接続を確認してください。",
+        Timeout => "AWS への接続がタイムアウトしました。もう一度お試しください。",
+        DnsFailure => "AWS エンドポイントを解決できません。ネットワーク接続を確認してください。",
+        LogGroupNotFound => "指定されたロググループが見つかりません。",
+        Throttled => "AWS のレート制限を超えました。しばらく待ってから再試行してください。",
+        InvalidRegion => "AWS リージョンが無効か未設定です。AWS の設定を確認してください。",
+        ServiceUnavailable => "AWS CloudWatch Logs サービスは一時的に利用できません。後でもう一度お試しください。",
+        Offline => "オフラインです。キャッシュ済みの結果、インポートしたファイル、保存したビューは引き続き利用できます。接続が戻ると AWS リクエストを再開します。",
+        UnexpectedError => "AWS への接続中に予期しないエラーが発生しました。",
+        MenuAbout => "Loggy について",
+        MenuCheckForUpdates => "アップデートを確認...",
+        MenuPreferences => "環境設定...",
+        MenuRefresh => "更新",
+        MenuSearch => "検索",
+        MenuClear => "クリア",
+        MenuFind => "検索...",
+        MenuThemeDark => "ダーク",
+        MenuThemeLight => "ライト",
+        MenuThemeSystem => "システム",
+        MenuTheme => "テーマ",
+        MenuDemoMode => "デモモード",
+        MenuEdit => "編集",
+        MenuView => "表示",
+        MenuWindow => "ウインドウ",
+        MenuFile => "ファイル",
+        MenuExport => "ログを書き出す...",
+        MenuRecentWorkspaces => "最近のワークスペース",
+        MenuNoRecentWorkspaces => "最近のワークスペースはありません",
+    }
+}
+
+/// Text for a message in a specific language
+pub fn text(language: Language, message: Message) -> &'static str {
+    match language {
+        Language::En => english(message),
+        Language::De => german(message),
+        Language::Es => spanish(message),
+        Language::Fr => french(message),
+        Language::Ja => japanese(message),
+    }
+}
+
+/// Current language
+pub fn current() -> Language {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Text for a message in the current language
+pub fn tr(message: Message) -> String {
+    text(current(), message).to_string()
+}
+
+fn apply(settings: &LanguageSettings) {
+    let language = settings.language.unwrap_or_else(Language::from_system);
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = language;
+}
+
+/// Load the saved preference; call before building menus
+pub fn load(app: &AppHandle) {
+    apply(&storage::read_json(app, LANGUAGE_FILE));
+}
+
+/// Saved language preference (None = follow system) and the language in effect
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageInfo {
+    pub preference: Option<Language>,
+    pub effective: Language,
+}
+
+#[tauri::command]
+pub fn get_language(app: AppHandle) -> LanguageInfo {
+    let settings: LanguageSettings = storage::read_json(&app, LANGUAGE_FILE);
+    LanguageInfo {
+        preference: settings.language,
+        effective: current(),
+    }
+}
+
+/// Set the language for backend messages (None follows the system locale).
+/// Error messages switch immediately; native menu labels on next launch.
+#[tauri::command]
+pub fn set_language(app: AppHandle, language: Option<Language>) -> Result<LanguageInfo, String> {
+    let settings = LanguageSettings { language };
+    storage::write_json(&app, LANGUAGE_FILE, &settings)?;
+    apply(&settings);
+    Ok(LanguageInfo {
+        preference: language,
+        effective: current(),
+    })
+}
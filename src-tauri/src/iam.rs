@@ -0,0 +1,171 @@
+use crate::i18n::{self, Message};
+use crate::AppState;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use tauri::State;
+
+/// Most recent AccessDenied seen by humanize_aws_error, with its raw error text
+static LAST_DENIED: Mutex<Option<(DeniedPermission, String)>> = Mutex::new(None);
+
+/// The IAM action and resource an AccessDenied error refers to
+#[derive(Debug, Clone, Serialize)]
+pub struct DeniedPermission {
+    /// e.g. "logs:FilterLogEvents"
+    pub action: Option<String>,
+    /// ARN the action was denied on
+    pub resource: Option<String>,
+    /// Identity that made the call
+    pub principal: Option<String>,
+    /// e.g. "no identity-based policy allows the logs:FilterLogEvents action"
+    pub reason: Option<String>,
+    /// True when an explicit Deny statement matched (from a decoded message)
+    pub explicit_deny: bool,
+    /// True when the details came from STS DecodeAuthorizationMessage
+    pub decoded: bool,
+    /// Minimal identity-policy statement that would allow the call
+    pub suggested_statement: Option<serde_json::Value>,
+}
+
+impl DeniedPermission {
+    fn suggest(mut self) -> Self {
+        self.suggested_statement = self.action.as_ref().map(|action| {
+            serde_json::json!({
+                "Effect": "Allow",
+                "Action": action,
+                "Resource": self.resource.as_deref().unwrap_or("*"),
+            })
+        });
+        self
+    }
+}
+
+fn not_authorized_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "User: <arn> is not authorized to perform: <action> [on resource: <arn>]
+    //  [because <reason>]"; stops at a quote so Debug-formatted errors parse too
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"User: (\S+) is not authorized to perform: ([\w-]+:\w+)(?: on resource: ([^\s"\\]+))?(?: (?:because|with an explicit deny in) ([^"\\]+))?"#,
+        )
+        .expect("valid regex")
+    })
+}
+
+fn encoded_message_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"Encoded authorization failure message: ([A-Za-z0-9_\-]+)")
+            .expect("valid regex")
+    })
+}
+
+/// Extract the denied action/resource from an AWS error message (Display or Debug form)
+pub fn parse_denied(error_msg: &str) -> Option<DeniedPermission> {
+    let captures = not_authorized_regex().captures(error_msg)?;
+    let reason = captures
+        .get(4)
+        .map(|m| m.as_str().trim().trim_end_matches('.').to_string());
+    let explicit_deny = error_msg.contains("explicit deny");
+    Some(
+        DeniedPermission {
+            principal: captures.get(1).map(|m| m.as_str().to_string()),
+            action: captures.get(2).map(|m| m.as_str().to_string()),
+            resource: captures
+                .get(3)
+                .map(|m| m.as_str().trim_end_matches(['.', ',']).to_string()),
+            reason,
+            explicit_deny,
+            decoded: false,
+            suggested_statement: None,
+        }
+        .suggest(),
+    )
+}
+
+/// Remember an AccessDenied so explain_access_denied can return it later
+pub fn remember(denied: &DeniedPermission, error_msg: &str) {
+    *LAST_DENIED.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some((denied.clone(), error_msg.to_string()));
+}
+
+/// Localized one-line description naming the denied action and resource
+pub fn describe(denied: &DeniedPermission) -> String {
+    let Some(action) = &denied.action else {
+        return i18n::tr(Message::AccessDenied);
+    };
+    i18n::tr(Message::AccessDeniedDetail)
+        .replace("{action}", action)
+        .replace("{resource}", denied.resource.as_deref().unwrap_or("*"))
+}
+
+/// Whether a humanized message is an AccessDenied explanation
+pub fn is_access_denied_message(message: &str) -> bool {
+    let detail = i18n::tr(Message::AccessDeniedDetail);
+    let lead = detail.split('{').next().unwrap_or_default();
+    message == i18n::tr(Message::AccessDenied) || (!lead.is_empty() && message.starts_with(lead))
+}
+
+/// Parse the JSON returned by DecodeAuthorizationMessage
+fn parse_decoded(decoded: &str) -> Option<DeniedPermission> {
+    let json: serde_json::Value = serde_json::from_str(decoded).ok()?;
+    let context = &json["context"];
+    Some(
+        DeniedPermission {
+            action: context["action"].as_str().map(str::to_string),
+            resource: context["resource"].as_str().map(str::to_string),
+            principal: context["principal"]["arn"].as_str().map(str::to_string),
+            reason: None,
+            explicit_deny: json["explicitDeny"].as_bool().unwrap_or(false),
+            decoded: true,
+            suggested_statement: None,
+        }
+        .suggest(),
+    )
+}
+
+/// Explain which IAM action on which ARN was denied. Parses `error` (raw AWS error
+/// text) or, when omitted, the last AccessDenied the app hit. Encoded authorization
+/// messages are decoded through STS when the caller is allowed to.
+#[tauri::command]
+pub async fn explain_access_denied(
+    state: State<'_, AppState>,
+    error: Option<String>,
+) -> Result<Option<DeniedPermission>, String> {
+    let (parsed, raw) = match error {
+        Some(text) => (parse_denied(&text), text),
+        None => match LAST_DENIED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+        {
+            Some((denied, raw)) => (Some(denied), raw),
+            None => return Ok(None),
+        },
+    };
+
+    let Some(encoded) = encoded_message_regex()
+        .captures(&raw)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+    else {
+        return Ok(parsed);
+    };
+    let config = state.sdk_config().await?;
+    match aws_sdk_sts::Client::new(&config)
+        .decode_authorization_message()
+        .encoded_message(encoded)
+        .send()
+        .await
+    {
+        Ok(response) => Ok(response
+            .decoded_message()
+            .and_then(parse_decoded)
+            .or(parsed)),
+        // Decoding needs sts:DecodeAuthorizationMessage; fall back to the plain text
+        Err(e) => {
+            log::warn!("DecodeAuthorizationMessage failed: {:?}", e);
+            Ok(parsed)
+        }
+    }
+}
@@ -0,0 +1,576 @@
+use crate::{humanize_aws_error, AppState};
+use aws_sdk_cloudtrail::primitives::DateTime;
+use aws_sdk_cloudtrail::types::{LookupAttribute, LookupAttributeKey};
+use aws_sdk_cloudwatchlogs::types::{LogGroupClass, OrderBy};
+use serde::Serialize;
+use std::time::Duration;
+use tauri::State;
+
+/// Ingestion silent for longer than this counts as stopped
+const STALE_INGESTION_MS: i64 = 60 * 60 * 1000;
+
+/// Gap between an event's own timestamp and its ingestion that suggests a
+/// misconfigured clock or timestamp format
+const SKEW_THRESHOLD_MS: i64 = 15 * 60 * 1000;
+
+/// Furthest back CloudTrail is searched for denied writes
+const MAX_TRAIL_LOOKBACK_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// LookupEvents pages read at most (50 events each)
+const MAX_TRAIL_PAGES: usize = 10;
+
+/// LookupEvents allows 2 calls per second
+const TRAIL_PAGE_DELAY: Duration = Duration::from_millis(500);
+
+/// Streams inspected for the latest ingestion
+const RECENT_STREAMS: i32 = 5;
+
+/// A possible reason a group shows no events, with the evidence for it
+#[derive(Debug, Clone, Serialize)]
+pub struct LikelyCause {
+    /// Stable identifier, e.g. "ingestion_stopped"
+    pub cause: String,
+    /// "high", "medium" or "low"
+    pub likelihood: String,
+    pub summary: String,
+    /// What to check or change next
+    pub suggestion: String,
+    pub evidence: Vec<String>,
+}
+
+/// Outcome of one diagnostic check
+#[derive(Debug, Clone, Serialize)]
+pub struct GapCheck {
+    pub name: String,
+    /// "ok", "issue" or "skipped"
+    pub status: String,
+    pub detail: String,
+}
+
+/// Why a log group may be missing events, most likely causes first
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestDiagnosis {
+    pub log_group_name: String,
+    pub retention_days: Option<i32>,
+    pub stored_bytes: Option<i64>,
+    pub log_group_class: Option<String>,
+    /// Timestamp of the newest event of the group
+    pub last_event_time: Option<i64>,
+    /// When the group last received an event
+    pub last_ingestion_time: Option<i64>,
+    pub subscription_filters: Vec<String>,
+    pub causes: Vec<LikelyCause>,
+    pub checks: Vec<GapCheck>,
+}
+
+fn cause(
+    cause: &str,
+    likelihood: &str,
+    summary: String,
+    suggestion: &str,
+    evidence: Vec<String>,
+) -> LikelyCause {
+    LikelyCause {
+        cause: cause.to_string(),
+        likelihood: likelihood.to_string(),
+        summary,
+        suggestion: suggestion.to_string(),
+        evidence,
+    }
+}
+
+fn check(name: &str, status: &str, detail: String) -> GapCheck {
+    GapCheck {
+        name: name.to_string(),
+        status: status.to_string(),
+        detail,
+    }
+}
+
+fn format_time(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| millis.to_string())
+}
+
+fn format_age(millis: i64) -> String {
+    let minutes = millis / 60_000;
+    match minutes {
+        m if m < 120 => format!("{} minutes", m),
+        m if m < 48 * 60 => format!("{} hours", m / 60),
+        m => format!("{} days", m / (24 * 60)),
+    }
+}
+
+/// Where the group's events usually come from, and what to check on that side
+fn writer_hint(
+    log_group_name: &str,
+    stream_names: &[String],
+) -> Option<(&'static str, &'static str)> {
+    if log_group_name.starts_with("/aws/lambda/") {
+        Some((
+            "Lambda function",
+            "Check that the function is being invoked and that its execution role allows logs:CreateLogStream and logs:PutLogEvents, and whether its logging config points at a different log group",
+        ))
+    } else if log_group_name.starts_with("/ecs/") || log_group_name.starts_with("/aws/ecs/") {
+        Some((
+            "ECS task (awslogs driver)",
+            "Check the task definition's awslogs-group and awslogs-region options, that tasks are running, and that the task execution role can write logs",
+        ))
+    } else if log_group_name.starts_with("/aws/eks/") {
+        Some((
+            "EKS control plane or Fluent Bit",
+            "Check the cluster's enabled control plane log types, or the Fluent Bit/CloudWatch agent DaemonSet and its IAM role",
+        ))
+    } else if log_group_name.starts_with("/aws/codebuild/") {
+        Some((
+            "CodeBuild project",
+            "Check that builds are running and that the project's logs config has CloudWatch Logs enabled for this group",
+        ))
+    } else if log_group_name.starts_with("/aws/vendedlogs/")
+        || log_group_name.starts_with("/aws/vpc/")
+    {
+        Some((
+            "Vended logs (delivery)",
+            "Check the delivery or flow log configuration still targets this group and that its delivery role or resource policy allows writing",
+        ))
+    } else if stream_names
+        .iter()
+        .any(|s| s.starts_with("i-") && s.len() >= 10)
+    {
+        Some((
+            "CloudWatch agent on EC2",
+            "Check that the agent is running on the instances, that its config's log_group_name matches, and that the instance role allows logs:PutLogEvents",
+        ))
+    } else {
+        None
+    }
+}
+
+/// CloudWatch Logs calls for the group that CloudTrail recorded as denied
+async fn denied_writes(
+    state: &AppState,
+    log_group_name: &str,
+    since: i64,
+) -> Result<Vec<String>, String> {
+    let config = state.sdk_config().await?;
+    let client = aws_sdk_cloudtrail::Client::new(&config);
+    let attribute = LookupAttribute::builder()
+        .attribute_key(LookupAttributeKey::EventSource)
+        .attribute_value("logs.amazonaws.com")
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut denials = Vec::new();
+    let mut next_token: Option<String> = None;
+    for page in 0..MAX_TRAIL_PAGES {
+        if page > 0 {
+            tokio::time::sleep(TRAIL_PAGE_DELAY).await;
+        }
+        let response = client
+            .lookup_events()
+            .lookup_attributes(attribute.clone())
+            .start_time(DateTime::from_millis(since))
+            .end_time(DateTime::from_millis(chrono::Utc::now().timestamp_millis()))
+            .max_results(50)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for event in response.events() {
+            let Some(record) = event
+                .cloud_trail_event()
+                .and_then(|e| serde_json::from_str::<serde_json::Value>(e).ok())
+            else {
+                continue;
+            };
+            let denied = record["errorCode"]
+                .as_str()
+                .is_some_and(|code| code.contains("AccessDenied") || code.contains("Unauthorized"));
+            if !denied
+                || record["requestParameters"]["logGroupName"].as_str() != Some(log_group_name)
+            {
+                continue;
+            }
+            denials.push(format!(
+                "{} by {} on {} denied at {}",
+                record["eventName"].as_str().unwrap_or("a call"),
+                record["userIdentity"]["arn"]
+                    .as_str()
+                    .unwrap_or("a principal"),
+                log_group_name,
+                record["eventTime"].as_str().unwrap_or_default()
+            ));
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+    Ok(denials)
+}
+
+/// Work out why a log group shows no events for a time range when the cause is
+/// likely delivery rather than the query: checks the group itself, its
+/// retention, class, latest ingestion and writers' clocks, whether the range
+/// actually holds events, subscription filters, hints for the usual writer,
+/// and (if the caller may read CloudTrail) denied writes to the group.
+/// Returns likely causes, most likely first, with every check's outcome.
+#[tauri::command]
+pub async fn diagnose_ingest_gap(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+) -> Result<IngestDiagnosis, String> {
+    let client = state.logs_client().await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let end = end_time.unwrap_or(now);
+    let start = start_time.unwrap_or(end - STALE_INGESTION_MS);
+    let mut causes = Vec::new();
+    let mut checks = Vec::new();
+
+    let groups = client
+        .describe_log_groups()
+        .log_group_name_prefix(&log_group_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let Some(group) = groups
+        .log_groups()
+        .iter()
+        .find(|g| g.log_group_name() == Some(log_group_name.as_str()))
+    else {
+        checks.push(check(
+            "log_group",
+            "issue",
+            "The log group does not exist".to_string(),
+        ));
+        causes.push(cause(
+            "group_missing",
+            "high",
+            format!("{} does not exist in this account and region", log_group_name),
+            "Check the profile and region, or whether the writer creates the group under another name",
+            Vec::new(),
+        ));
+        return Ok(IngestDiagnosis {
+            log_group_name,
+            retention_days: None,
+            stored_bytes: None,
+            log_group_class: None,
+            last_event_time: None,
+            last_ingestion_time: None,
+            subscription_filters: Vec::new(),
+            causes,
+            checks,
+        });
+    };
+    checks.push(check("log_group", "ok", "The log group exists".to_string()));
+    let retention_days = group.retention_in_days();
+    let stored_bytes = group.stored_bytes();
+    let log_group_class = group.log_group_class().map(|c| c.as_str().to_string());
+
+    // Retention
+    match retention_days {
+        Some(days) if start < now - i64::from(days) * 24 * 60 * 60 * 1000 => {
+            let expired_through = now - i64::from(days) * 24 * 60 * 60 * 1000;
+            checks.push(check(
+                "retention",
+                "issue",
+                format!("Retention is {} days; the range starts before it", days),
+            ));
+            causes.push(cause(
+                "expired_by_retention",
+                if end < expired_through {
+                    "high"
+                } else {
+                    "medium"
+                },
+                format!(
+                    "Events before {} have aged out ({}-day retention)",
+                    format_time(expired_through),
+                    days
+                ),
+                "Search a more recent range, or raise the retention for the future",
+                vec![format!("Retention: {} days", days)],
+            ));
+        }
+        Some(days) => checks.push(check(
+            "retention",
+            "ok",
+            format!("{} days, covers the range", days),
+        )),
+        None => checks.push(check("retention", "ok", "Never expire".to_string())),
+    }
+
+    // Log class
+    if group.log_group_class() == Some(&LogGroupClass::InfrequentAccess) {
+        checks.push(check("log_class", "issue", "Infrequent Access".to_string()));
+        causes.push(cause(
+            "infrequent_access_class",
+            "high",
+            "The group is Infrequent Access, which doesn't support filtering or live tail"
+                .to_string(),
+            "Query it with Logs Insights instead",
+            vec!["Log group class: INFREQUENT_ACCESS".to_string()],
+        ));
+    } else {
+        checks.push(check(
+            "log_class",
+            "ok",
+            log_group_class
+                .clone()
+                .unwrap_or_else(|| "STANDARD".to_string()),
+        ));
+    }
+
+    // Latest ingestion
+    let streams = client
+        .describe_log_streams()
+        .log_group_name(&log_group_name)
+        .order_by(OrderBy::LastEventTime)
+        .descending(true)
+        .limit(RECENT_STREAMS)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let stream_names: Vec<String> = streams
+        .log_streams()
+        .iter()
+        .filter_map(|s| s.log_stream_name().map(str::to_string))
+        .collect();
+    let last_event_time = streams
+        .log_streams()
+        .iter()
+        .filter_map(|s| s.last_event_timestamp())
+        .max();
+    let last_ingestion_time = streams
+        .log_streams()
+        .iter()
+        .filter_map(|s| s.last_ingestion_time())
+        .max();
+    match last_ingestion_time {
+        None => {
+            checks.push(check(
+                "ingestion",
+                "issue",
+                "The group has no log streams".to_string(),
+            ));
+            causes.push(cause(
+                "never_ingested",
+                "high",
+                "Nothing has ever been written to the group".to_string(),
+                "Check that the writer is configured with this group name and can create log streams",
+                Vec::new(),
+            ));
+        }
+        Some(ingested) if ingested < end && now - ingested > STALE_INGESTION_MS => {
+            checks.push(check(
+                "ingestion",
+                "issue",
+                format!("Last ingestion {} ago", format_age(now - ingested)),
+            ));
+            causes.push(cause(
+                "ingestion_stopped",
+                "high",
+                format!(
+                    "The group stopped receiving events {} ago, at {}",
+                    format_age(now - ingested),
+                    format_time(ingested)
+                ),
+                "Check whether the writer is still running and allowed to write",
+                stream_names
+                    .first()
+                    .map(|s| vec![format!("Most recent stream: {}", s)])
+                    .unwrap_or_default(),
+            ));
+        }
+        Some(ingested) => checks.push(check(
+            "ingestion",
+            "ok",
+            format!("Last ingestion at {}", format_time(ingested)),
+        )),
+    }
+
+    // Writer clocks: events ingested long after (or before) their own timestamps
+    // land outside the range that was searched
+    if let Some(skew) = streams
+        .log_streams()
+        .iter()
+        .filter_map(|s| Some(s.last_ingestion_time()? - s.last_event_timestamp()?))
+        .max_by_key(|skew| skew.abs())
+        .filter(|skew| skew.abs() > SKEW_THRESHOLD_MS)
+    {
+        checks.push(check(
+            "timestamps",
+            "issue",
+            format!(
+                "Events are ingested {} away from their timestamps",
+                format_age(skew.abs())
+            ),
+        ));
+        causes.push(cause(
+            "timestamp_skew",
+            "medium",
+            format!(
+                "Event timestamps are {} {} their ingestion",
+                format_age(skew.abs()),
+                if skew > 0 { "behind" } else { "ahead of" }
+            ),
+            "Widen the time range, and check the writer's clock, time zone and timestamp format",
+            Vec::new(),
+        ));
+    } else if last_ingestion_time.is_some() {
+        checks.push(check(
+            "timestamps",
+            "ok",
+            "Event timestamps match ingestion".to_string(),
+        ));
+    }
+
+    // Whether the range holds events at all, with and without the pattern
+    let pattern = filter_pattern.filter(|p| !p.trim().is_empty());
+    if group.log_group_class() != Some(&LogGroupClass::InfrequentAccess) {
+        let any = client
+            .filter_log_events()
+            .log_group_name(&log_group_name)
+            .start_time(start)
+            .end_time(end)
+            .limit(1)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        if let Some(event) = any.events().first() {
+            checks.push(check("range", "ok", "The range holds events".to_string()));
+            if let Some(pattern) = &pattern {
+                causes.push(cause(
+                    "query_excludes_events",
+                    "high",
+                    "The range holds events; the filter pattern excludes all of them".to_string(),
+                    "This is a query problem, not a delivery one: loosen or fix the filter pattern",
+                    vec![
+                        format!("Filter pattern: {}", pattern),
+                        format!(
+                            "Event at {} in {}",
+                            format_time(event.timestamp().unwrap_or_default()),
+                            event.log_stream_name().unwrap_or_default()
+                        ),
+                    ],
+                ));
+            }
+        } else {
+            checks.push(check(
+                "range",
+                "issue",
+                "No events in the range".to_string(),
+            ));
+        }
+    } else {
+        checks.push(check(
+            "range",
+            "skipped",
+            "Infrequent Access groups can't be filtered".to_string(),
+        ));
+    }
+
+    // Subscription filters only copy events out; listed for context
+    let subscription_filters: Vec<String> = match client
+        .describe_subscription_filters()
+        .log_group_name(&log_group_name)
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let names: Vec<String> = response
+                .subscription_filters()
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{} -> {}",
+                        f.filter_name().unwrap_or_default(),
+                        f.destination_arn().unwrap_or_default()
+                    )
+                })
+                .collect();
+            checks.push(check(
+                "subscriptions",
+                "ok",
+                format!("{} subscription filters", names.len()),
+            ));
+            names
+        }
+        Err(e) => {
+            checks.push(check(
+                "subscriptions",
+                "skipped",
+                humanize_aws_error(&format!("{}", e)),
+            ));
+            Vec::new()
+        }
+    };
+
+    // Denied writes recorded by CloudTrail
+    let since = last_ingestion_time
+        .unwrap_or(start)
+        .max(now - MAX_TRAIL_LOOKBACK_MS);
+    match denied_writes(&state, &log_group_name, since).await {
+        Ok(denials) if !denials.is_empty() => {
+            checks.push(check(
+                "cloudtrail",
+                "issue",
+                format!("{} denied calls for the group", denials.len()),
+            ));
+            causes.insert(
+                0,
+                cause(
+                    "write_denied",
+                    "high",
+                    "Writes to the group are being denied".to_string(),
+                    "Grant the writer's role logs:CreateLogStream and logs:PutLogEvents on this group (and check SCPs and permission boundaries)",
+                    denials.into_iter().take(5).collect(),
+                ),
+            );
+        }
+        Ok(_) => checks.push(check(
+            "cloudtrail",
+            "ok",
+            format!("No denied calls for the group since {}", format_time(since)),
+        )),
+        Err(e) => checks.push(check("cloudtrail", "skipped", e)),
+    }
+
+    // The usual writer, when nothing points at the query itself
+    if !causes.iter().any(|c| c.cause == "query_excludes_events") {
+        if let Some((writer, suggestion)) = writer_hint(&log_group_name, &stream_names) {
+            causes.push(cause(
+                "writer_config",
+                if last_ingestion_time.is_some_and(|t| now - t > STALE_INGESTION_MS) {
+                    "medium"
+                } else {
+                    "low"
+                },
+                format!("The group looks written by a {}", writer),
+                suggestion,
+                Vec::new(),
+            ));
+        }
+    }
+
+    let rank = |likelihood: &str| match likelihood {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    };
+    causes.sort_by_key(|c| rank(&c.likelihood));
+    Ok(IngestDiagnosis {
+        log_group_name,
+        retention_days,
+        stored_bytes,
+        log_group_class,
+        last_event_time,
+        last_ingestion_time,
+        subscription_filters,
+        causes,
+        checks,
+    })
+}
@@ -0,0 +1,376 @@
+use crate::connections::EnvironmentEmitter;
+use crate::operations::{CancelBy, Operation};
+use crate::{budget, humanize_aws_error, redaction, AppState};
+use aws_sdk_cloudwatchlogs::types::QueryStatus;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, State};
+
+/// StartQuery accepts at most 50 log groups (names or identifiers)
+pub const MAX_QUERY_LOG_GROUPS: usize = 50;
+
+/// Delay between GetQueryResults polls
+const POLL_INTERVAL_MS: u64 = 1000;
+
+/// Default range when the view has none: the last hour
+const DEFAULT_RANGE_MS: i64 = 3_600_000;
+
+/// Concurrent StartQuery calls Loggy allows itself, well below the account-wide
+/// Insights quota so other tools and teammates keep headroom
+pub const MAX_CONCURRENT_QUERIES: usize = 5;
+
+/// How often a queued query re-checks its position
+const QUEUE_POLL_MS: u64 = 250;
+
+/// Queries running and waiting for a slot, in arrival order
+struct QueryQueue {
+    running: usize,
+    waiting: VecDeque<u64>,
+}
+
+static QUEUE: Mutex<QueryQueue> = Mutex::new(QueryQueue {
+    running: 0,
+    waiting: VecDeque::new(),
+});
+
+static NEXT_TICKET: AtomicU64 = AtomicU64::new(1);
+
+/// Cancellation flag of the latest query per view; a new query from the same view
+/// abandons the previous one
+static VIEW_QUERIES: Mutex<BTreeMap<String, Arc<AtomicBool>>> = Mutex::new(BTreeMap::new());
+
+/// Payload of the insights-queue event
+#[derive(Debug, Clone, Serialize)]
+struct QueuePayload {
+    operation_id: String,
+    view_id: Option<String>,
+    /// 1-based place in the queue; 0 once the query has started
+    position: usize,
+    running: usize,
+}
+
+/// A held query slot, released on drop
+struct QuerySlot;
+
+impl Drop for QuerySlot {
+    fn drop(&mut self) {
+        let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+        queue.running = queue.running.saturating_sub(1);
+    }
+}
+
+/// Wait for a query slot, emitting the queue position whenever it changes.
+/// Returns None when the operation was cancelled while waiting.
+async fn acquire_slot(app: &AppHandle, op: &Operation, view_id: Option<&str>) -> Option<QuerySlot> {
+    let ticket = NEXT_TICKET.fetch_add(1, Ordering::Relaxed);
+    QUEUE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .waiting
+        .push_back(ticket);
+    let mut last_position = None;
+    loop {
+        let (position, running) = {
+            let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+            let index = queue.waiting.iter().position(|t| *t == ticket).unwrap_or(0);
+            if op.is_cancelled() {
+                queue.waiting.retain(|t| *t != ticket);
+                return None;
+            }
+            if index == 0 && queue.running < MAX_CONCURRENT_QUERIES {
+                queue.waiting.pop_front();
+                queue.running += 1;
+                (0, queue.running)
+            } else {
+                (index + 1, queue.running)
+            }
+        };
+        if last_position != Some(position) {
+            app.emit_tagged(
+                "insights-queue",
+                QueuePayload {
+                    operation_id: op.operation_id(),
+                    view_id: view_id.map(str::to_string),
+                    position,
+                    running,
+                },
+            )
+            .ok();
+            last_position = Some(position);
+        }
+        if position == 0 {
+            return Some(QuerySlot);
+        }
+        tokio::time::sleep(Duration::from_millis(QUEUE_POLL_MS)).await;
+    }
+}
+
+/// Cancellation flag for a new query, abandoning the view's previous query
+fn claim_view(view_id: Option<&str>) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    if let Some(view_id) = view_id {
+        let mut views = VIEW_QUERIES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = views.insert(view_id.to_string(), flag.clone()) {
+            previous.store(true, Ordering::SeqCst);
+        }
+    }
+    flag
+}
+
+/// Forget the view's flag if it still belongs to this query
+fn release_view(view_id: Option<&str>, flag: &Arc<AtomicBool>) {
+    if let Some(view_id) = view_id {
+        let mut views = VIEW_QUERIES.lock().unwrap_or_else(|e| e.into_inner());
+        if views.get(view_id).is_some_and(|f| Arc::ptr_eq(f, flag)) {
+            views.remove(view_id);
+        }
+    }
+}
+
+/// Outcome of a Logs Insights query
+#[derive(Debug, Clone, Serialize)]
+pub struct InsightsResult {
+    pub query_id: String,
+    /// "Complete", "Failed", "Cancelled", "Timeout", ...
+    pub status: String,
+    /// One map per result row, field name to value
+    pub rows: Vec<BTreeMap<String, String>>,
+    pub records_matched: f64,
+    pub records_scanned: f64,
+    pub bytes_scanned: f64,
+}
+
+/// The log groups a query runs against, in the StartQuery parameter they need
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryTargets {
+    /// Plain names in the connected account
+    Names(Vec<String>),
+    /// Names or ARNs; required as soon as one group lives in another account
+    Identifiers(Vec<String>),
+}
+
+impl QueryTargets {
+    /// Combine names and identifiers (ARNs from linked source accounts) into one
+    /// StartQuery target list, enforcing the API's log group limit
+    pub fn new(names: Vec<String>, identifiers: Vec<String>) -> Result<Self, String> {
+        let mut groups: Vec<String> = Vec::new();
+        for group in names.into_iter().chain(identifiers.iter().cloned()) {
+            // Identifiers must not carry the trailing ":*" of DescribeLogGroups ARNs
+            let group = group.trim().trim_end_matches(":*").to_string();
+            if !group.is_empty() && !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+        if groups.is_empty() {
+            return Err("Select at least one log group to query".to_string());
+        }
+        if groups.len() > MAX_QUERY_LOG_GROUPS {
+            return Err(format!(
+                "Logs Insights can query at most {} log groups at once ({} selected)",
+                MAX_QUERY_LOG_GROUPS,
+                groups.len()
+            ));
+        }
+        if identifiers.is_empty() && !groups.iter().any(|g| g.starts_with("arn:")) {
+            Ok(Self::Names(groups))
+        } else {
+            Ok(Self::Identifiers(groups))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Names(groups) | Self::Identifiers(groups) => groups.len(),
+        }
+    }
+
+    /// Short description for the operations list
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Names(groups) | Self::Identifiers(groups) if groups.len() == 1 => {
+                groups[0].clone()
+            }
+            _ => format!("{} log groups", self.len()),
+        }
+    }
+}
+
+fn is_terminal(status: &QueryStatus) -> bool {
+    !matches!(status, QueryStatus::Scheduled | QueryStatus::Running)
+}
+
+/// Run a Logs Insights query across up to 50 log groups and wait for its results.
+/// `log_group_identifiers` takes ARNs for cross-account queries from a monitoring
+/// account; with `session_id` and no groups given, the session's log groups are used.
+/// Listed by list_active_operations; cancelling stops the query and returns the rows
+/// found so far.
+///
+/// At most MAX_CONCURRENT_QUERIES queries run at once; others wait in order and get
+/// "insights-queue" events with their position. A new query with the same `view_id`
+/// abandons the previous one (dequeued, or stopped if already running).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn run_insights_query(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_names: Option<Vec<String>>,
+    log_group_identifiers: Option<Vec<String>>,
+    query: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    limit: Option<i32>,
+    session_id: Option<String>,
+    view_id: Option<String>,
+) -> Result<InsightsResult, String> {
+    if query.trim().is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+    let mut names = log_group_names.unwrap_or_default();
+    let identifiers = log_group_identifiers.unwrap_or_default();
+    if names.is_empty() && identifiers.is_empty() {
+        if let Some(session_id) = &session_id {
+            let sessions = state.sessions.lock().await;
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| format!("Session {} not found", session_id))?;
+            names = session.fetches.keys().cloned().collect();
+            names.sort();
+        }
+    }
+    let targets = QueryTargets::new(names, identifiers)?;
+
+    // StartQuery takes epoch seconds
+    let now = chrono::Utc::now().timestamp_millis();
+    let end = end_time.unwrap_or(now);
+    let start = start_time.unwrap_or(end - DEFAULT_RANGE_MS);
+
+    let client = state.logs_client().await?;
+    let view_id = view_id.as_deref();
+    let flag = claim_view(view_id);
+    let result = run_query(
+        &app, &client, &targets, &query, start, end, limit, &flag, view_id,
+    )
+    .await;
+    release_view(view_id, &flag);
+    let mut result = result?;
+    if result.bytes_scanned > 0.0 {
+        budget::record(&app, &state, 0, result.bytes_scanned as u64).await;
+    }
+    redaction::mask_rows_for_display(&app, &state, session_id.as_deref(), &mut result.rows).await;
+    Ok(result)
+}
+
+/// Run a query for a background job (no view), through the same queue and
+/// operations list as interactive queries
+pub async fn run_background_query(
+    app: &AppHandle,
+    client: &aws_sdk_cloudwatchlogs::Client,
+    targets: &QueryTargets,
+    query: &str,
+    start: i64,
+    end: i64,
+    limit: Option<i32>,
+) -> Result<InsightsResult, String> {
+    let flag = Arc::new(AtomicBool::new(false));
+    run_query(app, client, targets, query, start, end, limit, &flag, None).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_query(
+    app: &AppHandle,
+    client: &aws_sdk_cloudwatchlogs::Client,
+    targets: &QueryTargets,
+    query: &str,
+    start: i64,
+    end: i64,
+    limit: Option<i32>,
+    flag: &Arc<AtomicBool>,
+    view_id: Option<&str>,
+) -> Result<InsightsResult, String> {
+    let op = Operation::begin("insights", targets.describe(), CancelBy::Flag(flag.clone()));
+    let Some(_slot) = acquire_slot(app, &op, view_id).await else {
+        log::info!(
+            "Insights query over {} abandoned while queued",
+            targets.describe()
+        );
+        return Ok(InsightsResult {
+            query_id: String::new(),
+            status: QueryStatus::Cancelled.as_str().to_string(),
+            rows: Vec::new(),
+            records_matched: 0.0,
+            records_scanned: 0.0,
+            bytes_scanned: 0.0,
+        });
+    };
+
+    let request = client
+        .start_query()
+        .query_string(query)
+        .start_time(start / 1000)
+        .end_time(end / 1000)
+        .set_limit(limit);
+    let request = match targets {
+        QueryTargets::Names(groups) => request.set_log_group_names(Some(groups.clone())),
+        QueryTargets::Identifiers(groups) => {
+            request.set_log_group_identifiers(Some(groups.clone()))
+        }
+    };
+    let query_id = request
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?
+        .query_id
+        .ok_or("StartQuery returned no query id")?;
+    log::info!(
+        "Started Insights query {} over {}",
+        query_id,
+        targets.describe()
+    );
+
+    loop {
+        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        let cancelled = op.is_cancelled();
+        if cancelled {
+            if let Err(e) = client.stop_query().query_id(&query_id).send().await {
+                log::debug!("StopQuery {} failed: {:?}", query_id, e);
+            }
+        }
+        let response = client
+            .get_query_results()
+            .query_id(&query_id)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        let status = response.status().cloned().unwrap_or(QueryStatus::Running);
+        if !cancelled && !is_terminal(&status) {
+            continue;
+        }
+
+        let rows: Vec<BTreeMap<String, String>> = response
+            .results()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter_map(|f| Some((f.field()?.to_string(), f.value()?.to_string())))
+                    .collect()
+            })
+            .collect();
+        op.page(rows.len());
+        let statistics = response.statistics();
+        return Ok(InsightsResult {
+            query_id,
+            status: if cancelled {
+                QueryStatus::Cancelled.as_str().to_string()
+            } else {
+                status.as_str().to_string()
+            },
+            rows,
+            records_matched: statistics.map(|s| s.records_matched()).unwrap_or_default(),
+            records_scanned: statistics.map(|s| s.records_scanned()).unwrap_or_default(),
+            bytes_scanned: statistics.map(|s| s.bytes_scanned()).unwrap_or_default(),
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{storage, AppState};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+/// File in the app config directory holding the latest session state snapshot
+const JOURNAL_FILE: &str = "journal.json";
+
+/// Present while the app is running; left behind by a crash or forced quit
+const RUNNING_MARKER_FILE: &str = "running.marker";
+
+/// How often a changed snapshot is written to disk
+const FLUSH_INTERVAL_SECS: u64 = 5;
+
+/// Session state as last reported by the frontend (open views, cursors, unsaved
+/// annotations). The backend treats `state` as opaque.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalSnapshot {
+    pub saved_at: i64,
+    pub state: serde_json::Value,
+}
+
+#[derive(Debug, Default)]
+pub struct Journal {
+    latest: Option<JournalSnapshot>,
+    dirty: bool,
+    /// Snapshot left by a previous run that didn't exit cleanly
+    recovered: Option<JournalSnapshot>,
+}
+
+fn write_snapshot(app: &AppHandle, snapshot: &JournalSnapshot) {
+    if let Err(e) = storage::write_json(app, JOURNAL_FILE, snapshot) {
+        log::warn!("Failed to write session journal: {}", e);
+    }
+}
+
+/// Detect an unclean previous exit (running marker still present) and keep its
+/// journal for recovery, then mark this run as in progress. Called once at startup.
+pub async fn recover(app: &AppHandle) {
+    let dir = match storage::config_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("{}", e);
+            return;
+        }
+    };
+    let marker = dir.join(RUNNING_MARKER_FILE);
+
+    if marker.exists() {
+        let snapshot: Option<JournalSnapshot> = storage::read_json(app, JOURNAL_FILE);
+        if let Some(snapshot) = snapshot {
+            log::warn!(
+                "Previous session did not exit cleanly; journal from {} is recoverable",
+                snapshot.saved_at
+            );
+            if let Some(state) = app.try_state::<AppState>() {
+                state.journal.lock().await.recovered = Some(snapshot);
+            }
+        }
+    }
+
+    if let Err(e) = std::fs::write(&marker, std::process::id().to_string()) {
+        log::warn!("Failed to write {}: {}", marker.display(), e);
+    }
+}
+
+/// Periodically write the latest snapshot if it changed since the last write
+pub async fn run_journal_flusher(app: AppHandle) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(FLUSH_INTERVAL_SECS)).await;
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        let snapshot = {
+            let mut journal = state.journal.lock().await;
+            if !journal.dirty {
+                continue;
+            }
+            journal.dirty = false;
+            journal.latest.clone()
+        };
+        if let Some(snapshot) = snapshot {
+            write_snapshot(&app, &snapshot);
+        }
+    }
+}
+
+/// Clean shutdown: nothing to recover next time
+pub fn mark_clean_exit(app: &AppHandle) {
+    if let Ok(dir) = storage::config_dir(app) {
+        std::fs::remove_file(dir.join(RUNNING_MARKER_FILE)).ok();
+        std::fs::remove_file(dir.join(JOURNAL_FILE)).ok();
+    }
+}
+
+/// Record the current session state. Significant transitions (opening/closing a
+/// view, saving an annotation) are written immediately; everything else is
+/// written by the periodic flusher.
+#[tauri::command]
+pub async fn journal_session_state(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_state: serde_json::Value,
+    significant: Option<bool>,
+) -> Result<(), String> {
+    let snapshot = JournalSnapshot {
+        saved_at: chrono::Utc::now().timestamp_millis(),
+        state: session_state,
+    };
+    let significant = significant.unwrap_or(false);
+    {
+        let mut journal = state.journal.lock().await;
+        journal.latest = Some(snapshot.clone());
+        journal.dirty = !significant;
+    }
+    if significant {
+        write_snapshot(&app, &snapshot);
+    }
+    Ok(())
+}
+
+/// Session state left by a previous run that crashed or was killed, if any
+#[tauri::command]
+pub async fn get_recovered_session(
+    state: State<'_, AppState>,
+) -> Result<Option<JournalSnapshot>, String> {
+    Ok(state.journal.lock().await.recovered.clone())
+}
+
+/// Forget the recovered session state (after restoring it or declining to)
+#[tauri::command]
+pub async fn discard_recovered_session(state: State<'_, AppState>) -> Result<(), String> {
+    state.journal.lock().await.recovered = None;
+    Ok(())
+}
@@ -0,0 +1,509 @@
+use crate::connections::EnvironmentEmitter;
+use crate::operations::{CancelBy, Operation};
+use crate::{humanize_aws_error, redaction, AppState, LogEvent};
+use aws_sdk_kinesis::types::ShardIteratorType;
+use aws_sdk_kinesis::Client as KinesisClient;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Read;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+
+/// Delay between GetRecords calls per shard (Kinesis allows 5 reads/sec/shard,
+/// shared with any other consumers of the stream)
+const POLL_INTERVAL_MS: u64 = 1000;
+
+/// Max records returned by a single GetRecords call
+const GET_RECORDS_LIMIT: i32 = 1000;
+
+/// Attempts at a new shard iterator after a throttled read before the shard gives up
+const ITERATOR_ATTEMPTS: u32 = 5;
+
+/// CloudWatch Logs subscription payload, as delivered to Kinesis (gzip-compressed JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscriptionPayload {
+    message_type: String,
+    owner: Option<String>,
+    log_group: Option<String>,
+    log_stream: Option<String>,
+    #[serde(default)]
+    subscription_filters: Vec<String>,
+    #[serde(default)]
+    log_events: Vec<SubscriptionLogEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SubscriptionLogEvent {
+    id: Option<String>,
+    timestamp: i64,
+    message: String,
+}
+
+/// A decoded CloudWatch Logs subscription payload (or a single RAW event for data
+/// that isn't one). Shared by the Kinesis tail and Firehose failed-batch loader.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedPayload {
+    /// "DATA_MESSAGE", "CONTROL_MESSAGE", or "RAW" for records that are not
+    /// CloudWatch Logs subscription payloads
+    pub message_type: String,
+    pub owner: Option<String>,
+    pub log_group: Option<String>,
+    pub log_stream: Option<String>,
+    pub subscription_filters: Vec<String>,
+    pub events: Vec<LogEvent>,
+}
+
+/// A single decoded Kinesis record
+#[derive(Debug, Clone, Serialize)]
+pub struct KinesisLogRecord {
+    pub shard_id: String,
+    pub sequence_number: String,
+    #[serde(flatten)]
+    pub payload: DecodedPayload,
+}
+
+/// Payload for kinesis-tail-event
+#[derive(Debug, Clone, Serialize)]
+struct KinesisTailEventPayload {
+    tail_id: String,
+    records: Vec<KinesisLogRecord>,
+}
+
+/// Payload for kinesis-tail-error
+#[derive(Debug, Clone, Serialize)]
+struct KinesisTailErrorPayload {
+    tail_id: String,
+    shard_id: Option<String>,
+    message: String,
+}
+
+/// Gunzip a buffer, returning None if it isn't valid gzip
+pub fn gunzip(data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut decoded).ok()?;
+    Some(decoded)
+}
+
+/// Decode data produced by a CloudWatch Logs subscription filter.
+/// The payload is gzip-compressed JSON; some producers additionally base64-encode it.
+/// Anything that doesn't decode is returned as a single RAW event (using the
+/// fallback timestamp and id) so it's still visible.
+pub fn decode_subscription_data(
+    data: &[u8],
+    fallback_ts: i64,
+    fallback_id: &str,
+) -> DecodedPayload {
+    let decompressed = gunzip(data).or_else(|| {
+        let text = std::str::from_utf8(data).ok()?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(text.trim())
+            .ok()?;
+        gunzip(&bytes)
+    });
+
+    if let Some(payload) = decompressed
+        .as_deref()
+        .and_then(|bytes| serde_json::from_slice::<SubscriptionPayload>(bytes).ok())
+    {
+        let events = payload
+            .log_events
+            .into_iter()
+            .map(|e| LogEvent {
+                timestamp: e.timestamp,
+                message: e.message,
+                log_stream_name: payload.log_stream.clone(),
+                event_id: e.id,
+                source: payload.log_group.clone(),
+                columns: None,
+                full_length: None,
+            })
+            .collect();
+        return DecodedPayload {
+            message_type: payload.message_type,
+            owner: payload.owner,
+            log_group: payload.log_group,
+            log_stream: payload.log_stream,
+            subscription_filters: payload.subscription_filters,
+            events,
+        };
+    }
+
+    let raw = decompressed.unwrap_or_else(|| data.to_vec());
+    DecodedPayload {
+        message_type: "RAW".to_string(),
+        owner: None,
+        log_group: None,
+        log_stream: None,
+        subscription_filters: Vec::new(),
+        events: vec![LogEvent {
+            timestamp: fallback_ts,
+            message: String::from_utf8_lossy(&raw).to_string(),
+            log_stream_name: None,
+            event_id: Some(fallback_id.to_string()),
+            source: None,
+            columns: None,
+            full_length: None,
+        }],
+    }
+}
+
+/// List all shard ids of a stream
+pub(crate) async fn list_shard_ids(
+    client: &KinesisClient,
+    stream_name: &str,
+) -> Result<Vec<String>, String> {
+    let mut shard_ids = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        // ListShards rejects stream_name when a next_token is given
+        let request = match next_token {
+            Some(token) => client.list_shards().next_token(token),
+            None => client.list_shards().stream_name(stream_name),
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+        shard_ids.extend(response.shards().iter().map(|s| s.shard_id().to_string()));
+
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(shard_ids)
+}
+
+/// Iterator resuming a throttled shard right after the last record seen, retried
+/// with a growing backoff while the shard stays throttled
+async fn resume_iterator(
+    client: &KinesisClient,
+    stream_name: &str,
+    shard_id: &str,
+    last_sequence_number: Option<&str>,
+) -> Result<Option<String>, String> {
+    let mut backoff = Duration::from_millis(POLL_INTERVAL_MS * 5);
+    let mut attempt = 1;
+    loop {
+        tokio::time::sleep(backoff).await;
+        let request = client
+            .get_shard_iterator()
+            .stream_name(stream_name)
+            .shard_id(shard_id);
+        let request = match last_sequence_number {
+            Some(seq) => request
+                .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+                .starting_sequence_number(seq),
+            None => request.shard_iterator_type(ShardIteratorType::Latest),
+        };
+        match request.send().await {
+            Ok(response) => return Ok(response.shard_iterator),
+            Err(e) if attempt < ITERATOR_ATTEMPTS => {
+                log::warn!(
+                    "Kinesis shard {} iterator attempt {} failed: {}",
+                    shard_id,
+                    attempt,
+                    e
+                );
+                attempt += 1;
+                backoff *= 2;
+            }
+            Err(e) => return Err(humanize_aws_error(&format!("{}", e))),
+        }
+    }
+}
+
+/// Read one shard until the task is aborted or the shard is closed, returning the
+/// shards it was split or merged into
+async fn tail_shard(
+    app: AppHandle,
+    client: KinesisClient,
+    tail_id: String,
+    stream_name: String,
+    shard_id: String,
+    iterator_type: ShardIteratorType,
+) -> Vec<String> {
+    let iterator = client
+        .get_shard_iterator()
+        .stream_name(&stream_name)
+        .shard_id(&shard_id)
+        .shard_iterator_type(iterator_type)
+        .send()
+        .await;
+
+    let mut shard_iterator = match iterator {
+        Ok(response) => response.shard_iterator,
+        Err(e) => {
+            let message = humanize_aws_error(&format!("{}", e));
+            log::error!("Failed to get iterator for shard {}: {}", shard_id, message);
+            app.emit_tagged(
+                "kinesis-tail-error",
+                KinesisTailErrorPayload {
+                    tail_id,
+                    shard_id: Some(shard_id),
+                    message,
+                },
+            )
+            .ok();
+            return Vec::new();
+        }
+    };
+
+    let mut last_sequence_number: Option<String> = None;
+    let mut child_shards = Vec::new();
+
+    while let Some(iterator) = shard_iterator.take() {
+        match client
+            .get_records()
+            .shard_iterator(iterator)
+            .limit(GET_RECORDS_LIMIT)
+            .send()
+            .await
+        {
+            Ok(response) => {
+                let mut records: Vec<KinesisLogRecord> = response
+                    .records()
+                    .iter()
+                    .map(|r| {
+                        let arrival_ms = r
+                            .approximate_arrival_timestamp()
+                            .and_then(|t| t.to_millis().ok())
+                            .unwrap_or(0);
+                        KinesisLogRecord {
+                            shard_id: shard_id.clone(),
+                            sequence_number: r.sequence_number().to_string(),
+                            payload: decode_subscription_data(
+                                r.data().as_ref(),
+                                arrival_ms,
+                                r.sequence_number(),
+                            ),
+                        }
+                    })
+                    // Control messages are CloudWatch's connectivity checks, not log data
+                    .filter(|r| r.payload.message_type != "CONTROL_MESSAGE")
+                    .collect();
+
+                if let Some(last) = response.records().last() {
+                    last_sequence_number = Some(last.sequence_number().to_string());
+                }
+
+                if !records.is_empty() {
+                    for record in &mut records {
+                        redaction::mask_with_handle(&app, &tail_id, &mut record.payload.events)
+                            .await;
+                    }
+                    app.emit_tagged(
+                        "kinesis-tail-event",
+                        KinesisTailEventPayload {
+                            tail_id: tail_id.clone(),
+                            records,
+                        },
+                    )
+                    .ok();
+                }
+
+                // Set on the last read of a shard closed by resharding
+                if !response.child_shards().is_empty() {
+                    child_shards = response
+                        .child_shards()
+                        .iter()
+                        .map(|c| c.shard_id().to_string())
+                        .collect();
+                }
+                shard_iterator = response.next_shard_iterator;
+            }
+            Err(e) => {
+                let error_msg = format!("{}", e);
+                let lower = error_msg.to_lowercase();
+                if lower.contains("throughput") || lower.contains("throttl") {
+                    // Provisioned throughput exceeded - back off, then resume right after
+                    // the last record we saw so nothing is skipped
+                    log::warn!("Kinesis shard {} throttled, backing off", shard_id);
+                    match resume_iterator(
+                        &client,
+                        &stream_name,
+                        &shard_id,
+                        last_sequence_number.as_deref(),
+                    )
+                    .await
+                    {
+                        Ok(iterator) => {
+                            shard_iterator = iterator;
+                            continue;
+                        }
+                        Err(message) => {
+                            app.emit_tagged(
+                                "kinesis-tail-error",
+                                KinesisTailErrorPayload {
+                                    tail_id: tail_id.clone(),
+                                    shard_id: Some(shard_id.clone()),
+                                    message,
+                                },
+                            )
+                            .ok();
+                            return Vec::new();
+                        }
+                    }
+                }
+
+                app.emit_tagged(
+                    "kinesis-tail-error",
+                    KinesisTailErrorPayload {
+                        tail_id: tail_id.clone(),
+                        shard_id: Some(shard_id.clone()),
+                        message: humanize_aws_error(&error_msg),
+                    },
+                )
+                .ok();
+                return Vec::new();
+            }
+        }
+
+        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+    }
+
+    log::info!("Kinesis shard {} closed", shard_id);
+    child_shards
+}
+
+/// List Kinesis data streams in the current account/region
+#[tauri::command]
+pub async fn list_kinesis_streams(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config = state.sdk_config().await?;
+    let client = KinesisClient::new(&config);
+
+    let mut streams = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let mut request = client.list_streams();
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        streams.extend(response.stream_names().iter().cloned());
+
+        next_token = response.next_token;
+        if next_token.is_none() || !response.has_more_streams {
+            break;
+        }
+    }
+
+    streams.sort();
+    Ok(streams)
+}
+
+/// Tail every shard of a Kinesis data stream, decoding CloudWatch Logs subscription
+/// payloads and emitting them as `kinesis-tail-event` (tagged with `tail_id`).
+/// `from_start` reads from the oldest retained record instead of only new records.
+/// Shards closed by resharding are followed into their child shards.
+#[tauri::command]
+pub async fn start_kinesis_tail(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+    stream_name: String,
+    from_start: Option<bool>,
+) -> Result<usize, String> {
+    let config = state.sdk_config().await?;
+    let client = KinesisClient::new(&config);
+
+    let shard_ids = list_shard_ids(&client, &stream_name).await?;
+    if shard_ids.is_empty() {
+        return Err(format!("Kinesis stream {} has no shards", stream_name));
+    }
+
+    let iterator_type = if from_start.unwrap_or(false) {
+        ShardIteratorType::TrimHorizon
+    } else {
+        ShardIteratorType::Latest
+    };
+
+    let shard_count = shard_ids.len();
+    let task_app = app.clone();
+    let task_tail_id = tail_id.clone();
+    let kinesis_tails = state.kinesis_tails.clone();
+    let op = Operation::begin(
+        "kinesis-tail",
+        stream_name.clone(),
+        CancelBy::KinesisTail(tail_id.clone()),
+    );
+    // Held until the handle is inserted, so a task ending early can't miss it
+    let mut tails = state.kinesis_tails.lock().await;
+    let handle = tokio::spawn(async move {
+        let _op = op;
+        // Dropping the JoinSet (when this task is aborted) aborts every shard reader
+        let mut shards = tokio::task::JoinSet::new();
+        // A merged shard is the child of two parents; it's read once
+        let mut started: HashSet<String> = shard_ids.iter().cloned().collect();
+        for shard_id in shard_ids {
+            shards.spawn(tail_shard(
+                task_app.clone(),
+                client.clone(),
+                task_tail_id.clone(),
+                stream_name.clone(),
+                shard_id,
+                iterator_type.clone(),
+            ));
+        }
+        while let Some(finished) = shards.join_next().await {
+            // Children hold everything written after the reshard, so read them whole
+            for child in finished.unwrap_or_default() {
+                if started.insert(child.clone()) {
+                    log::info!(
+                        "Kinesis tail {} following child shard {}",
+                        task_tail_id,
+                        child
+                    );
+                    shards.spawn(tail_shard(
+                        task_app.clone(),
+                        client.clone(),
+                        task_tail_id.clone(),
+                        stream_name.clone(),
+                        child,
+                        ShardIteratorType::TrimHorizon,
+                    ));
+                }
+            }
+        }
+
+        task_app
+            .emit_tagged(
+                "kinesis-tail-ended",
+                serde_json::json!({ "tail_id": task_tail_id }),
+            )
+            .ok();
+        // A newer tail under the same id keeps its entry
+        let mut tails = kinesis_tails.lock().await;
+        if tails
+            .get(&task_tail_id)
+            .is_some_and(|h| h.id() == tokio::task::id())
+        {
+            tails.remove(&task_tail_id);
+        }
+    });
+
+    if let Some(previous) = tails.insert(tail_id, handle) {
+        previous.abort();
+    }
+
+    Ok(shard_count)
+}
+
+/// Stop a Kinesis tail started with start_kinesis_tail
+#[tauri::command]
+pub async fn stop_kinesis_tail(state: State<'_, AppState>, tail_id: String) -> Result<(), String> {
+    if let Some(handle) = state.kinesis_tails.lock().await.remove(&tail_id) {
+        handle.abort();
+        log::info!("Kinesis tail {} stopped", tail_id);
+    }
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use crate::{
+    columns, filter_stream_events, humanize_aws_error, preview, redaction, session, tail, AppState,
+    LogEvent,
+};
+use aws_sdk_cloudwatchlogs::types::OrderBy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tauri::{AppHandle, State};
+
+/// Cap on events fetched for one instance
+const DEFAULT_INSTANCE_MAX_EVENTS: usize = 50_000;
+
+/// Events returned with the lifetime; the rest are read with get_session_window
+const DEFAULT_INSTANCE_WINDOW: usize = 500;
+
+/// DescribeLogStreams pages scanned for instances (50 streams each)
+const MAX_STREAM_PAGES: usize = 20;
+
+/// CloudWatch updates a stream's last event timestamp lazily (within an hour), so
+/// streams are scanned until they are this much older than the range start
+const STREAM_TIMESTAMP_LAG_MS: i64 = 3_600_000;
+
+fn stream_name_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "2024/01/15/[$LATEST]0123456789abcdef0123456789abcdef"
+    RE.get_or_init(|| {
+        Regex::new(r"^\d{4}/\d{2}/\d{2}/\[([^\]]+)\]([0-9A-Za-z]+)$").expect("valid regex")
+    })
+}
+
+/// The function version and execution environment instance id of a Lambda log
+/// stream name, or None for streams not named by Lambda
+pub fn parse_stream_name(name: &str) -> Option<(String, String)> {
+    let captures = stream_name_regex().captures(name)?;
+    Some((captures[1].to_string(), captures[2].to_string()))
+}
+
+/// One Lambda execution environment (warm container) seen in a log group
+#[derive(Debug, Clone, Serialize)]
+pub struct LambdaInstance {
+    pub instance_id: String,
+    /// "$LATEST" or a published version number
+    pub version: String,
+    pub log_stream_names: Vec<String>,
+    pub first_event_at: Option<i64>,
+    pub last_event_at: Option<i64>,
+}
+
+/// Lambda instances whose streams overlap the range, most recently active first
+async fn instances_in(
+    state: &AppState,
+    log_group_name: &str,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+) -> Result<Vec<LambdaInstance>, String> {
+    let client = state.logs_client().await?;
+    let mut instances: BTreeMap<String, LambdaInstance> = BTreeMap::new();
+    let mut next_token: Option<String> = None;
+    'pages: for _ in 0..MAX_STREAM_PAGES {
+        let response = client
+            .describe_log_streams()
+            .log_group_name(log_group_name)
+            .order_by(OrderBy::LastEventTime)
+            .descending(true)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for stream in response.log_streams() {
+            let (first, last) = (
+                stream.first_event_timestamp(),
+                stream.last_event_timestamp(),
+            );
+            if let (Some(start), Some(last)) = (start_time, last) {
+                if last < start - STREAM_TIMESTAMP_LAG_MS {
+                    break 'pages;
+                }
+            }
+            if let (Some(end), Some(first)) = (end_time, first) {
+                if first > end {
+                    continue;
+                }
+            }
+            let Some(name) = stream.log_stream_name() else {
+                continue;
+            };
+            let Some((version, instance_id)) = parse_stream_name(name) else {
+                continue;
+            };
+            let instance = instances
+                .entry(instance_id.clone())
+                .or_insert_with(|| LambdaInstance {
+                    instance_id,
+                    version,
+                    log_stream_names: Vec::new(),
+                    first_event_at: first,
+                    last_event_at: last,
+                });
+            instance.log_stream_names.push(name.to_string());
+            instance.first_event_at = match (instance.first_event_at, first) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            instance.last_event_at = instance.last_event_at.max(last);
+        }
+        next_token = response.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+    let mut instances: Vec<LambdaInstance> = instances.into_values().collect();
+    instances.sort_by_key(|i| std::cmp::Reverse(i.last_event_at));
+    Ok(instances)
+}
+
+/// Lambda execution environments that wrote to a log group in the range, parsed
+/// from its stream names (`YYYY/MM/DD/[version]instance-id`)
+#[tauri::command]
+pub async fn list_lambda_instances(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+) -> Result<Vec<LambdaInstance>, String> {
+    instances_in(&state, &log_group_name, start_time, end_time).await
+}
+
+/// Everything one Lambda instance logged in the range
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceLifetime {
+    pub instance: LambdaInstance,
+    /// The first window of the session, in its sort order
+    pub events: Vec<LogEvent>,
+    pub event_count: usize,
+    pub truncated: bool,
+    /// INIT_START of the instance, when its cold start is inside the range
+    pub cold_start_at: Option<i64>,
+    /// START lines, one per invocation the instance served
+    pub invocation_count: usize,
+    pub error_count: usize,
+    /// Timestamp of the first error, if any
+    pub first_error_at: Option<i64>,
+}
+
+/// Fetch every event of one Lambda execution environment (its instance id from
+/// the stream name) across the range into a result session, to follow one warm
+/// container from its cold start through its invocations to its errors
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_lambda_instance(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    log_group_name: String,
+    instance_id: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    max_count: Option<usize>,
+) -> Result<InstanceLifetime, String> {
+    let instance = instances_in(&state, &log_group_name, start_time, end_time)
+        .await?
+        .into_iter()
+        .find(|i| i.instance_id == instance_id)
+        .ok_or_else(|| {
+            format!(
+                "No stream of {} belongs to Lambda instance {} in this range",
+                log_group_name, instance_id
+            )
+        })?;
+    let client = state.logs_client().await?;
+    let (mut events, truncated) = filter_stream_events(
+        &client,
+        &log_group_name,
+        &instance.log_stream_names,
+        start_time,
+        end_time,
+        None,
+        max_count.unwrap_or(DEFAULT_INSTANCE_MAX_EVENTS),
+    )
+    .await?;
+    events.sort_by_key(|e| e.timestamp);
+
+    let cold_start_at = events
+        .iter()
+        .find(|e| e.message.starts_with("INIT_START"))
+        .map(|e| e.timestamp);
+    let invocation_count = events
+        .iter()
+        .filter(|e| e.message.starts_with("START RequestId:"))
+        .count();
+    let errors: Vec<i64> = events
+        .iter()
+        .filter(|e| tail::is_error_event(&e.message))
+        .map(|e| e.timestamp)
+        .collect();
+
+    session::tag_source(&mut events, &log_group_name);
+    let event_count = events.len();
+    let mut window = {
+        let mut sessions = state.sessions.lock().await;
+        let result = sessions.get_or_create(&session_id);
+        result.clear();
+        result.fetches.clear();
+        result.replace_source(&log_group_name, events);
+        result.fetches.insert(
+            log_group_name.clone(),
+            session::FetchContext {
+                start_time,
+                end_time,
+                filter_pattern: None,
+                truncated,
+                complete_ranges: Vec::new(),
+            },
+        );
+        result.window(0, DEFAULT_INSTANCE_WINDOW)
+    };
+
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(InstanceLifetime {
+        instance,
+        events: window,
+        event_count,
+        truncated,
+        cold_start_at,
+        invocation_count,
+        error_count: errors.len(),
+        first_error_at: errors.first().copied(),
+    })
+}
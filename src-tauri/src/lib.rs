@@ -1,20 +1,97 @@
 use aws_config::BehaviorVersion;
 use aws_credential_types::provider::ProvideCredentials;
 use aws_sdk_cloudwatchlogs::{types::FilteredLogEvent, Client as CloudWatchClient};
+use connections::EnvironmentEmitter;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use std::sync::Arc;
 use tauri::{
-    menu::{CheckMenuItem, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
-    AppHandle, Emitter, Manager, State,
+    menu::{CheckMenuItem, MenuBuilder, MenuItemBuilder, Submenu, SubmenuBuilder},
+    AppHandle, Manager, State,
 };
+use tauri_plugin_opener::OpenerExt;
 use tokio::sync::Mutex;
 
+mod aggregate;
+mod alarms;
+mod alerts;
+mod archive;
+mod assume_role;
+mod audit;
+mod bench;
+mod budget;
+mod canaries;
+mod catalog;
+mod cli;
+mod codebuild;
+mod columns;
+mod connections;
+mod connectivity;
+mod container_insights;
+mod correlation;
+mod cursors;
+mod daily_summaries;
+mod data_protection;
+mod delivery;
+mod deploy;
+mod diff;
+mod dlq;
+mod elevation;
+mod event_cache;
+mod export;
+mod filter_chips;
+mod firehose;
+mod group_history;
+mod handoff;
+mod heartbeat;
+mod heatmap;
+mod i18n;
+mod iam;
+mod ingest_gap;
+mod insights;
+mod journal;
+mod kinesis;
+mod lambda;
+mod links;
+mod mutations;
+mod operations;
+mod pacing;
+mod panes;
+mod pipeline;
+mod prefetch;
+mod preflight;
+mod preview;
+mod profile_groups;
+mod redaction;
+mod regex_test;
+mod regions;
+mod resource_groups;
+mod retention;
+mod schema;
+mod scripting;
+mod session;
+mod settings;
+mod share;
+mod sliced_fetch;
+mod sso;
+mod step_functions;
+mod storage;
+mod streams;
+mod synthetic;
+mod tail;
+mod tail_sink;
+mod watch;
+mod workspace;
+mod zoom;
+
+use i18n::Message;
+use session::SessionStore;
+
 /// Represents a log event returned to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEvent {
@@ -22,6 +99,59 @@ pub struct LogEvent {
     pub message: String,
     pub log_stream_name: Option<String>,
     pub event_id: Option<String>,
+    /// Where the event came from (log group name or imported file label).
+    /// Only set for events that are merged into a session timeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Computed column values, filled in for display and export only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<BTreeMap<String, String>>,
+    /// Byte length of the full message when `message` is a shortened preview
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_length: Option<usize>,
+}
+
+/// Prefix marking an event id Loggy synthesized (CloudWatch ids are all digits)
+const SYNTHESIZED_ID_PREFIX: &str = "loggy-";
+
+impl LogEvent {
+    /// Stable id for an event CloudWatch didn't give one (live tail, imported files):
+    /// a 64-bit FNV-1a hash of source, stream, timestamp and message
+    pub fn synthesized_id(&self) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let parts = [
+            self.source.as_deref().unwrap_or_default().as_bytes(),
+            self.log_stream_name
+                .as_deref()
+                .unwrap_or_default()
+                .as_bytes(),
+            &self.timestamp.to_le_bytes(),
+            self.message.as_bytes(),
+        ];
+        for part in parts {
+            for byte in part.iter().chain(std::iter::once(&0xff)) {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        format!("{}{:016x}", SYNTHESIZED_ID_PREFIX, hash)
+    }
+
+    /// Give the event an id if it has none, so bookmarks and deep links can refer to it
+    pub fn ensure_event_id(&mut self) {
+        if self.event_id.is_none() {
+            self.event_id = Some(self.synthesized_id());
+        }
+    }
+
+    /// Approximate memory held by this event (struct plus owned strings)
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.message.capacity()
+            + self.log_stream_name.as_ref().map_or(0, String::capacity)
+            + self.event_id.as_ref().map_or(0, String::capacity)
+            + self.source.as_ref().map_or(0, String::capacity)
+    }
 }
 
 impl From<FilteredLogEvent> for LogEvent {
@@ -31,6 +161,9 @@ impl From<FilteredLogEvent> for LogEvent {
             message: event.message.unwrap_or_default(),
             log_stream_name: event.log_stream_name,
             event_id: event.event_id,
+            source: None,
+            columns: None,
+            full_length: None,
         }
     }
 }
@@ -48,8 +181,34 @@ pub struct AppState {
     pub client: Arc<Mutex<Option<CloudWatchClient>>>,
     pub config: Arc<Mutex<Option<aws_config::SdkConfig>>>,
     pub current_profile: Arc<Mutex<Option<String>>>,
+    /// Region chosen explicitly instead of the profile's default
+    pub region_override: Arc<Mutex<Option<String>>>,
+    /// SigV4 signing region override of the connected profile
+    pub signing_region: Arc<Mutex<Option<String>>>,
     pub fetch_cancelled: Arc<AtomicBool>,
     pub live_tail_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    pub sessions: Arc<Mutex<SessionStore>>,
+    pub kinesis_tails: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    /// Multi-source tail sessions started with start_tail, keyed by tail id
+    pub tails: Arc<Mutex<HashMap<String, tail::TailSession>>>,
+    pub journal: Arc<Mutex<journal::Journal>>,
+    pub reveal: Arc<Mutex<redaction::RevealState>>,
+    /// Compiled masking rules per profile, so display masking doesn't re-read them
+    pub redactors: Arc<std::sync::Mutex<redaction::RedactorCache>>,
+    pub alert_states: Arc<Mutex<HashMap<String, alerts::AlertState>>>,
+    /// One-shot pattern watches started with watch_for, keyed by watch id
+    pub watches: Arc<Mutex<HashMap<String, watch::WatchSession>>>,
+    /// Primary/comparison pane state per session id
+    pub panes: Arc<Mutex<HashMap<String, HashMap<panes::Pane, panes::PaneState>>>>,
+    /// Named connection in use (None when connected by raw profile)
+    pub active_connection: Arc<Mutex<Option<connections::Connection>>>,
+    /// Clients connected so far, keyed by (profile, region), so switching back to a
+    /// region or browsing another one skips reconnecting
+    pub clients: Arc<Mutex<HashMap<regions::ClientKey, regions::RegionalClient>>>,
+    /// Role assumed on top of the connected profile, whose credentials are in use
+    pub assumed_role: Arc<Mutex<Option<assume_role::AssumedRole>>>,
+    /// Cancel tokens of in-progress fetch_logs calls, keyed by fetch_id
+    pub fetch_tokens: Arc<std::sync::Mutex<HashMap<u32, sliced_fetch::CancelToken>>>,
 }
 
 /// Validates an AWS profile name for security
@@ -82,17 +241,92 @@ impl Default for AppState {
             client: Arc::new(Mutex::new(None)),
             config: Arc::new(Mutex::new(None)),
             current_profile: Arc::new(Mutex::new(None)),
+            region_override: Arc::new(Mutex::new(None)),
+            signing_region: Arc::new(Mutex::new(None)),
             fetch_cancelled: Arc::new(AtomicBool::new(false)),
             live_tail_handle: Arc::new(Mutex::new(None)),
+            sessions: Arc::new(Mutex::new(SessionStore::default())),
+            kinesis_tails: Arc::new(Mutex::new(HashMap::new())),
+            tails: Arc::new(Mutex::new(HashMap::new())),
+            journal: Arc::new(Mutex::new(journal::Journal::default())),
+            reveal: Arc::new(Mutex::new(redaction::RevealState::default())),
+            redactors: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            alert_states: Arc::new(Mutex::new(HashMap::new())),
+            watches: Arc::new(Mutex::new(HashMap::new())),
+            panes: Arc::new(Mutex::new(HashMap::new())),
+            active_connection: Arc::new(Mutex::new(None)),
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            assumed_role: Arc::new(Mutex::new(None)),
+            fetch_tokens: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 }
 
+impl AppState {
+    /// Clone the CloudWatch Logs client so the state lock isn't held across API calls
+    pub async fn logs_client(&self) -> Result<CloudWatchClient, String> {
+        connectivity::ensure_online()?;
+        connectivity::revalidate_if_idle(self).await;
+        self.client
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| "AWS client not initialized".to_string())
+    }
+
+    /// CloudWatch Logs client of the connected profile in `region` (the connected
+    /// region when None). Other regions reuse the loaded credentials and the client
+    /// is kept for the next call.
+    pub async fn logs_client_in(&self, region: Option<&str>) -> Result<CloudWatchClient, String> {
+        let config = self.sdk_config().await?;
+        let Some(region) = region.filter(|r| config.region().map(|c| c.as_ref()) != Some(*r))
+        else {
+            return self.logs_client().await;
+        };
+        let key = (
+            self.current_profile.lock().await.clone(),
+            region.to_string(),
+        );
+        let mut clients = self.clients.lock().await;
+        if let Some(regional) = clients.get(&key) {
+            return Ok(regional.client.clone());
+        }
+        let config = config
+            .to_builder()
+            .region(aws_config::Region::new(region.to_string()))
+            .build();
+        let signing_region = self.signing_region.lock().await.clone();
+        let client = regions::logs_client(&config, signing_region.as_deref());
+        clients.insert(
+            key,
+            regions::RegionalClient {
+                client: client.clone(),
+                config,
+                signing_region,
+            },
+        );
+        Ok(client)
+    }
+
+    /// Clone the loaded AWS SDK config, for building clients of other AWS services
+    pub async fn sdk_config(&self) -> Result<aws_config::SdkConfig, String> {
+        connectivity::ensure_online()?;
+        connectivity::revalidate_if_idle(self).await;
+        self.config
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| "AWS client not initialized".to_string())
+    }
+}
+
 /// State for theme menu items (needed for sync_theme_menu command)
 pub struct MenuState {
     pub theme_dark: CheckMenuItem<tauri::Wry>,
     pub theme_light: CheckMenuItem<tauri::Wry>,
     pub theme_system: CheckMenuItem<tauri::Wry>,
+    /// File > Recent Workspaces, rebuilt as workspaces are opened
+    pub recent_workspaces: Submenu<tauri::Wry>,
 }
 
 /// Get the AWS config directory path
@@ -100,14 +334,15 @@ fn get_aws_config_path() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(".aws").join("config"))
 }
 
-/// List available AWS profiles from ~/.aws/config
+/// List available AWS profiles from ~/.aws/config, arranged by the user's profile
+/// groups and with their labels
 #[tauri::command]
-async fn list_aws_profiles() -> Result<Vec<String>, String> {
+async fn list_aws_profiles(app: AppHandle) -> Result<Vec<profile_groups::ProfileEntry>, String> {
     let config_path =
         get_aws_config_path().ok_or_else(|| "Could not determine home directory".to_string())?;
 
     if !config_path.exists() {
-        return Ok(vec!["default".to_string()]);
+        return Ok(profile_groups::arrange(&app, vec!["default".to_string()]));
     }
 
     let contents = std::fs::read_to_string(&config_path)
@@ -131,7 +366,7 @@ async fn list_aws_profiles() -> Result<Vec<String>, String> {
 
     let mut profiles_vec: Vec<String> = profiles.into_iter().collect();
     profiles_vec.sort();
-    Ok(profiles_vec)
+    Ok(profile_groups::arrange(&app, profiles_vec))
 }
 
 /// Check if a profile uses SSO by looking for sso_start_url in config
@@ -139,74 +374,15 @@ fn profile_uses_sso(profile: Option<&String>) -> bool {
     get_sso_start_url(profile).is_some()
 }
 
-/// Get the SSO start URL for a profile from AWS config
+/// Get the SSO start URL for a profile from AWS config, directly or through its
+/// `[sso-session]` section
 fn get_sso_start_url(profile: Option<&String>) -> Option<String> {
-    let config_path = get_aws_config_path()?;
-    if !config_path.exists() {
-        log::debug!("AWS config file not found at: {:?}", config_path);
-        return None;
+    let target = sso::resolve(profile.map(String::as_str));
+    match &target {
+        Some(t) => log::debug!("Found SSO start URL for {}: {}", t.profile, t.start_url),
+        None => log::debug!("SSO start URL not found for profile: {:?}", profile),
     }
-
-    let contents = match std::fs::read_to_string(&config_path) {
-        Ok(c) => c,
-        Err(e) => {
-            log::debug!("Failed to read AWS config file: {}", e);
-            return None;
-        }
-    };
-
-    // Determine which profile to look for
-    let env_profile = std::env::var("AWS_PROFILE").ok();
-    let profile_name = if let Some(p) = profile {
-        p.as_str()
-    } else {
-        // Check environment variable or default to "default"
-        env_profile.as_deref().unwrap_or("default")
-    };
-
-    log::debug!("Looking for SSO start URL for profile: {}", profile_name);
-
-    let mut in_target_section = false;
-
-    for line in contents.lines() {
-        let line = line.trim();
-
-        // Check if we're entering a profile section
-        if line.starts_with('[') && line.ends_with(']') {
-            let section = &line[1..line.len() - 1];
-
-            if profile_name == "default" {
-                // Looking for [default]
-                in_target_section = section == "default";
-            } else {
-                // Looking for [profile name]
-                if let Some(name) = section.strip_prefix("profile ") {
-                    in_target_section = name == profile_name;
-                } else {
-                    in_target_section = false;
-                }
-            }
-            if in_target_section {
-                log::debug!("Found profile section: {}", line);
-            }
-            continue;
-        }
-
-        // If we're in the target section, look for sso_start_url
-        if in_target_section {
-            if let Some(url) = line.strip_prefix("sso_start_url") {
-                // Handle both "sso_start_url = ..." and "sso_start_url=..." formats
-                let url = url.trim_start_matches(|c: char| c == '=' || c.is_whitespace());
-                if !url.is_empty() {
-                    log::debug!("Found SSO start URL: {}", url);
-                    return Some(url.to_string());
-                }
-            }
-        }
-    }
-
-    log::debug!("SSO start URL not found for profile: {}", profile_name);
-    None
+    target.map(|t| t.start_url)
 }
 
 /// Check if credentials are valid for a profile by attempting to load them
@@ -248,66 +424,29 @@ async fn check_credentials_valid(profile: Option<&String>) -> Result<(), String>
     }
 }
 
-/// Poll for valid credentials after SSO login, then emit refresh event
-async fn poll_for_credentials_and_refresh(
-    app: AppHandle,
-    profile: Option<String>,
-    max_attempts: u32,
-) {
-    let profile_clone = profile.clone();
-    let profile_ref = profile_clone.as_ref();
-    let mut last_error: Option<String> = None;
-
-    for attempt in 1..=max_attempts {
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-
-        log::debug!(
-            "Checking credentials (attempt {}/{})",
-            attempt,
-            max_attempts
-        );
-        emit_debug_log(
-            Some(&app),
-            &format!(
-                "Checking credentials (attempt {}/{})...",
-                attempt, max_attempts
-            ),
-        );
-
-        match check_credentials_valid(profile_ref).await {
-            Ok(()) => {
-                log::info!(
-                    "Credentials validated successfully after {} attempts",
-                    attempt
-                );
-                emit_debug_log(
-                    Some(&app),
-                    "✓ Credentials are now valid! Refreshing connection...",
-                );
-                // Emit event to trigger frontend refresh
-                app.emit("aws-session-refreshed", ()).ok();
-                return;
-            }
-            Err(e) => {
-                log::debug!("Credential check failed (attempt {}): {}", attempt, e);
-                last_error = Some(e);
-                // Continue polling
-            }
+/// Check the profile's credentials once SSO login has cached a new token, then
+/// emit `aws-session-refreshed` (or `sso-login-failed`)
+async fn refresh_after_sso_login(app: AppHandle, profile: Option<String>) {
+    match check_credentials_valid(profile.as_ref()).await {
+        Ok(()) => {
+            log::info!("Credentials validated after SSO login");
+            emit_debug_log(
+                Some(&app),
+                "✓ Credentials are now valid! Refreshing connection...",
+            );
+            // Emit event to trigger frontend refresh
+            app.emit_tagged("aws-session-refreshed", ()).ok();
+        }
+        Err(e) => {
+            let msg = format!(
+                "SSO login completed but credentials are still invalid: {}",
+                e
+            );
+            log::warn!("{}", msg);
+            emit_debug_log(Some(&app), &msg);
+            app.emit_tagged("sso-login-failed", msg).ok();
         }
     }
-
-    // Timeout - provide helpful message based on last error
-    let timeout_msg = if let Some(err) = last_error {
-        format!("SSO login timeout after {} seconds. Last error: {}. Please complete the login in your browser.", max_attempts * 2, err)
-    } else {
-        format!(
-            "SSO login timeout after {} seconds. Please complete the login in your browser.",
-            max_attempts * 2
-        )
-    };
-
-    log::warn!("{}", timeout_msg);
-    emit_debug_log(Some(&app), &timeout_msg);
 }
 
 /// Spawn an AWS CLI command with a timeout
@@ -360,18 +499,20 @@ async fn spawn_aws_cli_with_timeout(args: Vec<&str>, timeout_secs: u64) -> Resul
     }
 }
 
-/// Cooldown so we don't open the SSO browser twice when init is called twice (e.g. React Strict Mode)
+/// Cooldown so we don't open the SSO browser twice when init is called twice (e.g. React
+/// Strict Mode), keyed by SSO session so profiles sharing a portal share the cooldown
 const SSO_OPEN_COOLDOWN_SECS: u64 = 10;
-static LAST_SSO_OPEN: OnceLock<std::sync::Mutex<Option<(Option<String>, Instant)>>> =
-    OnceLock::new();
+type LastSsoOpen = Option<(Option<String>, Instant)>;
+static LAST_SSO_OPEN: OnceLock<std::sync::Mutex<LastSsoOpen>> = OnceLock::new();
 
-fn last_sso_open_guard() -> &'static std::sync::Mutex<Option<(Option<String>, Instant)>> {
+fn last_sso_open_guard() -> &'static std::sync::Mutex<LastSsoOpen> {
     LAST_SSO_OPEN.get_or_init(|| std::sync::Mutex::new(None))
 }
 
-/// Open SSO login URL for a profile
-/// This uses `aws sso login --profile` to handle the profile-aware SSO login
-/// After opening, it polls for successful authentication and triggers a refresh
+/// Sign a profile in to its SSO portal with the OAuth device flow (no AWS CLI
+/// needed). A cached refresh token is used when the session allows it; otherwise
+/// the verification page opens in the browser and approval is awaited in the
+/// background. Once the token is cached the connection is told to refresh.
 async fn open_sso_login_url(app: AppHandle, profile: Option<&String>) -> Result<(), String> {
     log::debug!("=== Attempting to open SSO URL for profile ===");
 
@@ -383,7 +524,11 @@ async fn open_sso_login_url(app: AppHandle, profile: Option<&String>) -> Result<
         }
     }
 
-    let profile_key = profile.cloned();
+    let target = sso::resolve(profile.map(String::as_str));
+    let profile_key = target
+        .as_ref()
+        .map(|t| t.session_key())
+        .or_else(|| profile.cloned());
     let now = Instant::now();
     {
         let mut guard = last_sso_open_guard().lock().unwrap();
@@ -395,7 +540,7 @@ async fn open_sso_login_url(app: AppHandle, profile: Option<&String>) -> Result<
             };
             if same && now.duration_since(last_time) < Duration::from_secs(SSO_OPEN_COOLDOWN_SECS) {
                 log::info!(
-                    "Skipping SSO URL open (already opened for this profile within {}s)",
+                    "Skipping SSO URL open (already opened for this SSO session within {}s)",
                     SSO_OPEN_COOLDOWN_SECS
                 );
                 return Ok(());
@@ -404,79 +549,57 @@ async fn open_sso_login_url(app: AppHandle, profile: Option<&String>) -> Result<
         *guard = Some((profile_key.clone(), now));
     }
 
-    let profile_clone = profile.cloned();
-
-    // Use AWS CLI to handle profile-aware SSO login
-    let mut cmd = std::process::Command::new("aws");
-    cmd.arg("sso").arg("login");
-
-    if let Some(p) = profile {
-        cmd.arg("--profile").arg(p);
-        log::info!("Using profile: {}", p);
-    } else {
-        log::info!("No profile specified, using default");
-    }
-
-    // Spawn the command - it will open the browser automatically
-    match cmd.spawn() {
-        Ok(_) => {
-            log::info!("Successfully started AWS SSO login process");
+    let target = target.ok_or_else(|| {
+        format!(
+            "The {} profile is not configured for SSO (no sso_start_url in ~/.aws/config)",
+            profile.map(String::as_str).unwrap_or("default")
+        )
+    })?;
+    let profile = profile.cloned();
 
-            // Start polling for credentials to become valid (poll for up to 2 minutes)
-            let app_clone = app.clone();
+    match sso::start_login(&target).await? {
+        sso::SsoLogin::Refreshed => {
+            log::info!("Refreshed the SSO token of {}", target.session_key());
+            tokio::spawn(refresh_after_sso_login(app, profile));
+        }
+        sso::SsoLogin::Pending(authorization) => {
+            app.opener()
+                .open_url(&authorization.code.verification_uri, None::<&str>)
+                .map_err(|e| format!("Failed to open the SSO sign-in page: {}", e))?;
+            log::info!(
+                "Started SSO device authorization for {}",
+                target.session_key()
+            );
+            // Tell the UI which portal the browser is opening and the code to confirm
+            app.emit_tagged("sso-login-started", &target).ok();
+            app.emit_tagged("sso-device-code", &authorization.code).ok();
             tokio::spawn(async move {
-                poll_for_credentials_and_refresh(app_clone, profile_clone, 60).await;
+                match authorization.wait().await {
+                    Ok(()) => refresh_after_sso_login(app, profile).await,
+                    Err(e) => {
+                        log::warn!("SSO login failed: {}", e);
+                        emit_debug_log(Some(&app), &e);
+                        app.emit_tagged("sso-login-failed", e).ok();
+                    }
+                }
             });
-
-            Ok(())
-        }
-        Err(e) => {
-            let error_msg = format!("{}", e);
-            log::error!("Failed to start AWS SSO login: {}", error_msg);
-
-            // Provide helpful error messages based on error type
-            let user_msg = if error_msg.to_lowercase().contains("not found")
-                || error_msg.to_lowercase().contains("no such file")
-            {
-                "AWS CLI not found. Please install the AWS CLI (https://aws.amazon.com/cli/) to use SSO login.".to_string()
-            } else if error_msg.to_lowercase().contains("permission") {
-                "Permission denied when running AWS CLI. Please check file permissions.".to_string()
-            } else {
-                format!("Failed to start AWS SSO login: {}. Please ensure AWS CLI is installed and accessible.", error_msg)
-            };
-
-            Err(user_msg)
         }
     }
+    Ok(())
 }
 
 /// Emit a debug log message to the frontend
 fn emit_debug_log(app: Option<&AppHandle>, message: &str) {
     log::debug!("{}", message);
     if let Some(app_handle) = app {
-        app_handle.emit("debug-log", message).ok();
+        app_handle.emit_tagged("debug-log", message).ok();
     }
 }
 
 /// Trigger SSO login for a profile
 #[tauri::command]
-async fn trigger_sso_login(profile: Option<String>) -> Result<(), String> {
-    // Validate profile if provided
-    if let Some(p) = &profile {
-        validate_profile_name(p)?;
-    }
-
-    let mut cmd = std::process::Command::new("aws");
-    cmd.arg("sso").arg("login");
-
-    if let Some(p) = &profile {
-        cmd.arg("--profile").arg(p);
-    }
-
-    cmd.spawn()
-        .map_err(|e| format!("Failed to start SSO login: {}", e))?;
-
-    Ok(())
+async fn trigger_sso_login(app: AppHandle, profile: Option<String>) -> Result<(), String> {
+    open_sso_login_url(app, profile.as_ref()).await
 }
 
 /// Open SSO login URL in browser for a profile
@@ -554,14 +677,14 @@ async fn handle_sso_expiration(
         }
         Err(e) => {
             log::error!("Failed to automatically open SSO login URL: {}", e);
-            log::warn!("User will need to manually run 'aws sso login' to re-authenticate");
+            log::warn!("User will need to sign in again from the SSO prompt");
             // Continue anyway - we'll still emit the event
         }
     }
 
     // Emit the event to notify frontend
     log::debug!("Emitting aws-session-expired event to frontend");
-    app.emit("aws-session-expired", ()).ok();
+    app.emit_tagged("aws-session-expired", ()).ok();
 }
 
 /// Convert AWS SDK errors to human-friendly messages
@@ -577,7 +700,7 @@ fn humanize_aws_error(error_msg: &str) -> String {
         || error_lower.contains("expired sso token")
         || error_lower.contains("sso token")
     {
-        return "Your AWS session has expired. Please run 'aws sso login' to refresh your credentials.".to_string();
+        return i18n::tr(Message::SessionExpired);
     }
 
     // Missing credentials (often wrapped in DispatchFailure)
@@ -589,7 +712,7 @@ fn humanize_aws_error(error_msg: &str) -> String {
         || (error_lower.contains("could not find")
             && (error_lower.contains("profile") || error_lower.contains("credential")))
     {
-        return "No AWS credentials found. Please run 'aws sso login' or configure your AWS credentials.".to_string();
+        return i18n::tr(Message::NoCredentials);
     }
 
     // Access denied / authorization errors
@@ -598,13 +721,16 @@ fn humanize_aws_error(error_msg: &str) -> String {
         || error_lower.contains("not authorized")
         || error_lower.contains("unauthorized")
     {
-        return "Access denied. Your AWS credentials don't have permission for this operation."
-            .to_string();
+        if let Some(denied) = iam::parse_denied(error_msg) {
+            iam::remember(&denied, error_msg);
+            return iam::describe(&denied);
+        }
+        return i18n::tr(Message::AccessDenied);
     }
 
     // Invalid credentials
     if error_lower.contains("invalid") && error_lower.contains("credential") {
-        return "Invalid AWS credentials. Please check your AWS configuration.".to_string();
+        return i18n::tr(Message::InvalidCredentials);
     }
 
     // Dispatch failure - check what's inside it
@@ -616,43 +742,42 @@ fn humanize_aws_error(error_msg: &str) -> String {
             || error_lower.contains("token")
             || error_lower.contains("profile")
         {
-            return "AWS credentials error. Please run 'aws sso login' or check your AWS configuration.".to_string();
+            return i18n::tr(Message::CredentialsError);
         }
         // Otherwise, it's likely a network issue
-        return "Unable to connect to AWS. This could be a network issue or expired credentials. Try running 'aws sso login'.".to_string();
+        return i18n::tr(Message::UnableToConnectMaybeExpired);
     }
 
     // Network-specific errors (only if not credential-related)
     if error_lower.contains("connector error") || error_lower.contains("hyper::error") {
-        return "Unable to connect to AWS. Please check your network connection.".to_string();
+        return i18n::tr(Message::UnableToConnect);
     }
 
     if error_lower.contains("timeout") || error_lower.contains("timed out") {
-        return "Connection to AWS timed out. Please try again.".to_string();
+        return i18n::tr(Message::Timeout);
     }
 
     if error_lower.contains("dns") || error_lower.contains("name resolution") {
-        return "Unable to resolve AWS endpoint. Please check your network connection.".to_string();
+        return i18n::tr(Message::DnsFailure);
     }
 
     // Resource errors
     if error_lower.contains("resourcenotfound") || error_lower.contains("does not exist") {
-        return "The requested log group was not found.".to_string();
+        return i18n::tr(Message::LogGroupNotFound);
     }
 
     if error_lower.contains("throttling") || error_lower.contains("rate exceeded") {
-        return "AWS rate limit exceeded. Please wait a moment and try again.".to_string();
+        return i18n::tr(Message::Throttled);
     }
 
     // Region errors
     if error_lower.contains("region") && error_lower.contains("not") {
-        return "Invalid or missing AWS region. Please check your AWS configuration.".to_string();
+        return i18n::tr(Message::InvalidRegion);
     }
 
     // Service errors
     if error_lower.contains("service") && error_lower.contains("unavailable") {
-        return "AWS CloudWatch Logs service is temporarily unavailable. Please try again later."
-            .to_string();
+        return i18n::tr(Message::ServiceUnavailable);
     }
 
     // Default: return a cleaned up version of the original error
@@ -665,7 +790,7 @@ fn humanize_aws_error(error_msg: &str) -> String {
         .to_string();
 
     if cleaned.is_empty() || cleaned.len() < 5 {
-        return "An unexpected error occurred while connecting to AWS.".to_string();
+        return i18n::tr(Message::UnexpectedError);
     }
 
     cleaned
@@ -716,18 +841,60 @@ async fn init_aws_client(
     app: AppHandle,
     state: State<'_, AppState>,
     profile: Option<String>,
+    region: Option<String>,
 ) -> Result<AwsConnectionInfo, String> {
-    // Build config with optional profile
-    let mut config_loader = aws_config::defaults(BehaviorVersion::latest());
-    if let Some(ref p) = profile {
-        config_loader = config_loader.profile_name(p);
-    }
-    let config = config_loader.load().await;
+    // Connecting by raw profile leaves any named connection (use_connection sets it after)
+    connections::set_active(&state, None).await;
+    // and returns to the profile's own credentials
+    assume_role::release(&state).await;
 
     // Use provided profile or fall back to environment variable
     let effective_profile = profile
         .clone()
         .or_else(|| std::env::var("AWS_PROFILE").ok());
+
+    // A region this profile is already connected to switches without reconnecting
+    if let Some(r) = &region {
+        let cached = state
+            .clients
+            .lock()
+            .await
+            .get(&(effective_profile.clone(), r.clone()))
+            .cloned();
+        if let Some(regional) = cached {
+            *state.current_profile.lock().await = effective_profile.clone();
+            *state.region_override.lock().await = Some(r.clone());
+            *state.signing_region.lock().await = regional.signing_region;
+            *state.config.lock().await = Some(regional.config);
+            *state.client.lock().await = Some(regional.client);
+            return Ok(AwsConnectionInfo {
+                profile: effective_profile,
+                region: Some(r.clone()),
+            });
+        }
+    }
+
+    // Build config with optional profile and region override, unless one was
+    // prepared for a pinned region of this profile
+    let warm = region
+        .as_deref()
+        .and_then(|r| regions::warm_config(effective_profile.as_deref(), r));
+    let config = match warm {
+        Some(config) => config,
+        None => {
+            let mut config_loader = aws_config::defaults(BehaviorVersion::latest());
+            if let Some(ref p) = profile {
+                config_loader = config_loader.profile_name(p);
+            }
+            if let Some(ref r) = region {
+                config_loader = config_loader.region(aws_config::Region::new(r.clone()));
+            }
+            config_loader.load().await
+        }
+    };
+    // Only an explicitly requested region is kept as the override; the profile's
+    // default is resolved again on every reconnect
+    let region_override = region;
     let region = config.region().map(|r| r.to_string());
 
     // Step 1: Verify credentials can be loaded (this catches SSO expiration, missing creds, etc.)
@@ -769,30 +936,26 @@ async fn init_aws_client(
                     {
                         emit_debug_log(Some(&app), &format!("Failed to open SSO URL: {}", e));
                     }
-                    return Err(
-                        "Your AWS session has expired. Please run 'aws sso login' to refresh."
-                            .to_string(),
-                    );
+                    return Err(i18n::tr(Message::SessionExpired));
                 }
                 emit_debug_log(
                     Some(&app),
                     "Error does not match SSO expiration patterns, returning generic error",
                 );
                 return Err(format!(
-                    "AWS credentials error: {}. Please run 'aws sso login' or check your AWS configuration.",
+                    "{} ({})",
+                    i18n::tr(Message::CredentialsError),
                     error_msg
                 ));
             }
         }
     } else {
-        return Err(
-            "No AWS credentials configured. Please run 'aws sso login' or configure credentials."
-                .to_string(),
-        );
+        return Err(i18n::tr(Message::NoCredentials));
     }
 
     // Step 2: Create client and test connection (this catches network issues)
-    let client = CloudWatchClient::new(&config);
+    let signing_region = regions::signing_region_for(&app, effective_profile.as_deref());
+    let client = regions::logs_client(&config, signing_region.as_deref());
 
     match client.describe_log_groups().limit(1).send().await {
         Ok(_) => {
@@ -800,6 +963,19 @@ async fn init_aws_client(
             let mut profile_lock = state.current_profile.lock().await;
             *profile_lock = effective_profile.clone();
             drop(profile_lock);
+            *state.region_override.lock().await = region_override;
+            *state.signing_region.lock().await = signing_region.clone();
+            regions::prewarm(&app, effective_profile.as_deref(), &config);
+            if let Some(r) = &region {
+                state.clients.lock().await.insert(
+                    (effective_profile.clone(), r.clone()),
+                    regions::RegionalClient {
+                        client: client.clone(),
+                        config: config.clone(),
+                        signing_region,
+                    },
+                );
+            }
 
             // Store both client and config (config holds the credential provider for auto-refresh)
             let mut config_lock = state.config.lock().await;
@@ -821,19 +997,20 @@ async fn init_aws_client(
                 if let Err(e) = open_sso_login_url(app.clone(), effective_profile.as_ref()).await {
                     log::error!("Failed to open SSO URL: {}", e);
                 }
-                return Err(
-                    "Your AWS session has expired. Please run 'aws sso login' to refresh."
-                        .to_string(),
-                );
+                return Err(i18n::tr(Message::SessionExpired));
             }
             // At this point, credentials are valid, so it's likely a network or permission issue
             if error_msg.to_lowercase().contains("accessdenied")
                 || error_msg.to_lowercase().contains("not authorized")
             {
-                return Err("Access denied. Your credentials don't have permission to access CloudWatch Logs.".to_string());
+                return Err(humanize_aws_error(&error_msg));
+            }
+            if let Some(hint) = regions::opt_in_hint(region.as_deref(), &format!("{:?}", e)) {
+                return Err(hint);
             }
             Err(format!(
-                "Unable to connect to AWS. Please check your network connection. ({})",
+                "{} ({})",
+                i18n::tr(Message::UnableToConnect),
                 humanize_aws_error(&error_msg)
             ))
         }
@@ -841,14 +1018,26 @@ async fn init_aws_client(
 }
 
 /// Reconnect to AWS with fresh credentials
-/// Call this after running `aws sso login` or `aws-switch` when the SSO session has expired
+/// Call this after signing in again (or `aws-switch`) when the SSO session has expired
 /// If profile is provided, switches to that profile; otherwise uses the current profile
 #[tauri::command]
 async fn reconnect_aws(
     app: AppHandle,
     state: State<'_, AppState>,
     profile: Option<String>,
+    region: Option<String>,
 ) -> Result<AwsConnectionInfo, String> {
+    // A region chosen explicitly (e.g. by open_by_arn) sticks while the profile is
+    // unchanged; it's saved once the connection succeeds
+    let region_override = if profile.is_none() {
+        state.region_override.lock().await.clone()
+    } else {
+        connections::set_active(&state, None).await;
+        None
+    };
+    // An explicit region replaces it
+    let region_override = region.or(region_override);
+
     // Get the profile to use: provided > stored > environment
     let effective_profile = match profile {
         Some(p) => Some(p),
@@ -858,6 +1047,27 @@ async fn reconnect_aws(
         }
     };
 
+    // Operations bound to the old profile's client would fail against a torn-down
+    // connection (or keep the reconnect waiting on the client lock)
+    let previous_profile = state.current_profile.lock().await.clone();
+    if previous_profile != effective_profile {
+        let reason = format!(
+            "Profile switched from {} to {}",
+            previous_profile.as_deref().unwrap_or("default"),
+            effective_profile.as_deref().unwrap_or("default")
+        );
+        operations::cancel_all(&app, state.clone(), &reason).await;
+    }
+
+    // Reconnecting returns to the profile's own credentials
+    assume_role::release(&state).await;
+    // Clients of this profile hold the old credentials; rebuild them on demand
+    state
+        .clients
+        .lock()
+        .await
+        .retain(|(p, _), _| *p != effective_profile);
+
     // Clear existing client and config
     {
         let mut client_lock = state.client.lock().await;
@@ -873,6 +1083,9 @@ async fn reconnect_aws(
     if let Some(ref p) = effective_profile {
         config_loader = config_loader.profile_name(p);
     }
+    if let Some(r) = region_override.clone() {
+        config_loader = config_loader.region(aws_config::Region::new(r));
+    }
     let config = config_loader.load().await;
 
     let region = config.region().map(|r| r.to_string());
@@ -916,30 +1129,26 @@ async fn reconnect_aws(
                     {
                         emit_debug_log(Some(&app), &format!("Failed to open SSO URL: {}", e));
                     }
-                    return Err(
-                        "Your AWS session has expired. Please run 'aws sso login' to refresh."
-                            .to_string(),
-                    );
+                    return Err(i18n::tr(Message::SessionExpired));
                 }
                 emit_debug_log(
                     Some(&app),
                     "Error does not match SSO expiration patterns, returning generic error",
                 );
                 return Err(format!(
-                    "AWS credentials error: {}. Please run 'aws sso login' or check your AWS configuration.",
+                    "{} ({})",
+                    i18n::tr(Message::CredentialsError),
                     error_msg
                 ));
             }
         }
     } else {
-        return Err(
-            "No AWS credentials configured. Please run 'aws sso login' or configure credentials."
-                .to_string(),
-        );
+        return Err(i18n::tr(Message::NoCredentials));
     }
 
     // Step 2: Create client and test connection
-    let client = CloudWatchClient::new(&config);
+    let signing_region = regions::signing_region_for(&app, effective_profile.as_deref());
+    let client = regions::logs_client(&config, signing_region.as_deref());
 
     match client.describe_log_groups().limit(1).send().await {
         Ok(_) => {
@@ -947,6 +1156,19 @@ async fn reconnect_aws(
             let mut profile_lock = state.current_profile.lock().await;
             *profile_lock = effective_profile.clone();
             drop(profile_lock);
+            *state.region_override.lock().await = region_override;
+            *state.signing_region.lock().await = signing_region.clone();
+            regions::prewarm(&app, effective_profile.as_deref(), &config);
+            if let Some(r) = &region {
+                state.clients.lock().await.insert(
+                    (effective_profile.clone(), r.clone()),
+                    regions::RegionalClient {
+                        client: client.clone(),
+                        config: config.clone(),
+                        signing_region,
+                    },
+                );
+            }
 
             let mut config_lock = state.config.lock().await;
             *config_lock = Some(config);
@@ -954,6 +1176,9 @@ async fn reconnect_aws(
 
             let mut client_lock = state.client.lock().await;
             *client_lock = Some(client);
+            drop(client_lock);
+            // Tails paused by the expired session pick up where they stopped
+            tail::resume_expired(&app);
             Ok(AwsConnectionInfo {
                 profile: effective_profile,
                 region,
@@ -975,32 +1200,44 @@ async fn reconnect_aws(
                 if let Err(e) = open_sso_login_url(app.clone(), effective_profile.as_ref()).await {
                     emit_debug_log(Some(&app), &format!("Failed to open SSO URL: {}", e));
                 }
-                return Err(
-                    "Your AWS session has expired. Please run 'aws sso login' to refresh."
-                        .to_string(),
-                );
+                return Err(i18n::tr(Message::SessionExpired));
             }
             if error_msg.to_lowercase().contains("accessdenied")
                 || error_msg.to_lowercase().contains("not authorized")
             {
-                return Err("Access denied. Your credentials don't have permission to access CloudWatch Logs.".to_string());
+                return Err(humanize_aws_error(&error_msg));
+            }
+            if let Some(hint) = regions::opt_in_hint(region.as_deref(), &format!("{:?}", e)) {
+                return Err(hint);
             }
             Err(format!(
-                "Unable to connect to AWS. Please check your network connection. ({})",
+                "{} ({})",
+                i18n::tr(Message::UnableToConnect),
                 humanize_aws_error(&error_msg)
             ))
         }
     }
 }
 
-/// List all available log groups
+/// List all available log groups, or those of another `region` browsed with the
+/// connected profile
 #[tauri::command]
 async fn list_log_groups(
     app: AppHandle,
     state: State<'_, AppState>,
+    region: Option<String>,
 ) -> Result<Vec<LogGroup>, String> {
-    let client_lock = state.client.lock().await;
-    let client = client_lock.as_ref().ok_or("AWS client not initialized")?;
+    connectivity::ensure_online()?;
+    // A pinned profile in its default region answers from the prefetched catalog
+    if region.is_none() && state.region_override.lock().await.is_none() {
+        if let Some(profile) = state.current_profile.lock().await.as_deref() {
+            if let Some(groups) = prefetch::cached(profile) {
+                return Ok(groups);
+            }
+        }
+    }
+    // Another region is browsed with the connected profile without switching to it
+    let client = state.logs_client_in(region.as_deref()).await?;
 
     let mut log_groups = Vec::new();
     let mut next_token: Option<String> = None;
@@ -1056,6 +1293,177 @@ struct LogsTruncated {
     count: usize,
     size_bytes: usize,
     reason: String, // "count" or "size"
+    /// The truncated group of a multi-group fetch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_group_name: Option<String>,
+}
+
+/// Sent when a fetch fails part-way and returns the events collected so far
+#[derive(Clone, serde::Serialize)]
+struct LogsPartial {
+    fetch_id: u32,
+    count: usize,
+    error: String,
+    /// The failing group of a multi-group fetch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_group_name: Option<String>,
+}
+
+/// Payload for group-unavailable: a log group was deleted (or is otherwise gone)
+/// while it was being viewed, tailed or watched
+#[derive(Clone, serde::Serialize)]
+struct GroupUnavailable {
+    log_group_name: String,
+    /// "fetch", "tail", "live-tail" or "watch"
+    context: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tail_id: Option<String>,
+    message: String,
+}
+
+/// Whether an AWS error (Display or Debug text) says the log group or stream no longer exists
+pub(crate) fn is_resource_not_found(error_text: &str) -> bool {
+    let lower = error_text.to_lowercase();
+    lower.contains("resourcenotfound")
+        || lower.contains("resource_not_found")
+        || lower.contains("does not exist")
+}
+
+/// Tell the frontend a group is gone so it can stop the affected view while keeping
+/// the data already loaded
+pub(crate) fn emit_group_unavailable(
+    app: &AppHandle,
+    log_group_name: &str,
+    context: &str,
+    tail_id: Option<&str>,
+) {
+    log::warn!(
+        "Log group {} is no longer available ({})",
+        log_group_name,
+        context
+    );
+    app.emit_tagged(
+        "group-unavailable",
+        GroupUnavailable {
+            log_group_name: log_group_name.to_string(),
+            context: context.to_string(),
+            tail_id: tail_id.map(str::to_string),
+            message: i18n::tr(Message::LogGroupNotFound),
+        },
+    )
+    .ok();
+}
+
+/// A FilterLogEvents page that failed for good
+struct PageError {
+    /// Display text of the SDK error
+    message: String,
+    /// The log group no longer exists
+    group_missing: bool,
+}
+
+impl PageError {
+    fn humanized(&self) -> String {
+        if self.group_missing {
+            i18n::tr(Message::LogGroupNotFound)
+        } else {
+            humanize_aws_error(&self.message)
+        }
+    }
+}
+
+/// Attempts per FilterLogEvents page before giving up on a transient error
+const PAGE_RETRY_ATTEMPTS: u32 = 4;
+
+/// Backoff before the first retry; doubles on each attempt
+const PAGE_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// First time window of a newest-first fetch; doubles while windows come back sparse
+const DESCENDING_INITIAL_SPAN_MS: i64 = 15 * 60 * 1000;
+
+/// Errors worth retrying: throttling, timeouts, network blips, 5xx
+fn is_transient_aws_error(error_msg: &str) -> bool {
+    let error_lower = error_msg.to_lowercase();
+    if is_sso_session_expired(error_msg) || error_lower.contains("credential") {
+        return false;
+    }
+    error_lower.contains("throttl")
+        || error_lower.contains("rate exceeded")
+        || error_lower.contains("timeout")
+        || error_lower.contains("timed out")
+        || error_lower.contains("dispatch failure")
+        || error_lower.contains("connector error")
+        || error_lower.contains("io error")
+        || error_lower.contains("service unavailable")
+        || error_lower.contains("serviceunavailable")
+        || error_lower.contains("internal failure")
+        || error_lower.contains("internalfailure")
+}
+
+/// Send a FilterLogEvents page with the tuner's page size and pacing, retrying
+/// transient failures with exponential backoff. Returns the error of the last
+/// attempt on failure.
+async fn send_filter_with_retry(
+    request: aws_sdk_cloudwatchlogs::operation::filter_log_events::builders::FilterLogEventsFluentBuilder,
+    op: &operations::Operation,
+    tuner: &mut pacing::PageTuner,
+) -> Result<aws_sdk_cloudwatchlogs::operation::filter_log_events::FilterLogEventsOutput, PageError>
+{
+    tuner.pace().await;
+    let request = request.limit(tuner.limit());
+    let mut attempt = 1;
+    loop {
+        let started = Instant::now();
+        let error_msg = match request.clone().send().await {
+            Ok(response) => {
+                tuner.observe(started.elapsed(), response.events().len());
+                return Ok(response);
+            }
+            Err(e) => {
+                let display = format!("{}", e);
+                let debug = format!("{:?}", e);
+                if is_resource_not_found(&debug) {
+                    return Err(PageError {
+                        message: display,
+                        group_missing: true,
+                    });
+                }
+                if !(is_transient_aws_error(&display) || is_transient_aws_error(&debug)) {
+                    // The Debug form carries the service message (denied action/resource)
+                    let message = if iam::parse_denied(&debug).is_some() {
+                        debug
+                    } else {
+                        display
+                    };
+                    return Err(PageError {
+                        message,
+                        group_missing: false,
+                    });
+                }
+                let throttled = debug.to_lowercase().contains("throttl");
+                if throttled {
+                    tuner.throttled();
+                }
+                op.retry(throttled);
+                display
+            }
+        };
+        if attempt >= PAGE_RETRY_ATTEMPTS || op.is_cancelled() {
+            return Err(PageError {
+                message: error_msg,
+                group_missing: false,
+            });
+        }
+        let delay = PAGE_RETRY_BASE_DELAY_MS << (attempt - 1);
+        log::warn!(
+            "FilterLogEvents attempt {} failed ({}), retrying in {}ms",
+            attempt,
+            error_msg,
+            delay
+        );
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+        attempt += 1;
+    }
 }
 
 /// Payload for live-tail-event
@@ -1071,16 +1479,30 @@ struct LiveTailErrorPayload {
     message: String,
 }
 
-/// Cancel any in-progress log fetch
+/// Cancel the in-progress log fetch started with `fetch_id`, stopping its requests
+/// in flight, or every fetch when omitted
 #[tauri::command]
-fn cancel_fetch(state: State<'_, AppState>) {
-    log::info!("Cancelling log fetch");
-    state.fetch_cancelled.store(true, Ordering::SeqCst);
+fn cancel_fetch(state: State<'_, AppState>, fetch_id: Option<u32>) {
+    match fetch_id {
+        Some(id) => log::info!("Cancelling log fetch {}", id),
+        None => {
+            log::info!("Cancelling log fetch");
+            state.fetch_cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+    sliced_fetch::cancel(&state, fetch_id);
 }
 
 /// Fetch logs from a specific log group with automatic pagination
 /// Fetches all available logs up to max_count or max_size_bytes, whichever is hit first
+/// If session_id is provided, the results are also merged into that session's timeline.
+/// If query_id (a saved query) is provided, a `query-diff` against its previous run is emitted.
+/// Newest-first fetches (`descending`, defaulting to the group's sticky sort order) walk
+/// the range backwards, so a fetch stopped at its limit holds the most recent events.
+/// Oldest-first fetches of longer ranges are split into time slices fetched in
+/// parallel; `cancel_fetch` with the fetch_id stops them mid-request.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn fetch_logs(
     app: AppHandle,
     state: State<'_, AppState>,
@@ -1091,13 +1513,34 @@ async fn fetch_logs(
     max_count: Option<i32>,
     max_size_mb: Option<i32>,
     fetch_id: Option<u32>,
+    session_id: Option<String>,
+    query_id: Option<String>,
+    descending: Option<bool>,
 ) -> Result<Vec<LogEvent>, String> {
     let fetch_id = fetch_id.unwrap_or(0);
+    let order = match descending {
+        Some(true) => session::SortOrder::Descending,
+        Some(false) => session::SortOrder::Ascending,
+        None => session::sort_order_for(&app, &log_group_name),
+    };
+    let descending = order == session::SortOrder::Descending;
     // Reset cancellation flag at start of new fetch
     state.fetch_cancelled.store(false, Ordering::SeqCst);
+    let op = Arc::new(operations::Operation::begin(
+        "fetch",
+        &log_group_name,
+        operations::CancelBy::Flag(state.fetch_cancelled.clone()),
+    ));
+    let registration = sliced_fetch::register(&state, fetch_id);
+    let run = FetchRun {
+        fetch_id,
+        log_group_name: &log_group_name,
+        filter_pattern: filter_pattern.as_deref(),
+        session_id: session_id.as_deref(),
+        query_id: query_id.as_deref(),
+    };
 
-    let client_lock = state.client.lock().await;
-    let client = client_lock.as_ref().ok_or("AWS client not initialized")?;
+    let client = state.logs_client().await?;
 
     let max_events: usize = max_count.map(|l| l as usize).unwrap_or(50_000);
     let max_bytes: usize = max_size_mb
@@ -1106,113 +1549,803 @@ async fn fetch_logs(
     let mut all_events: Vec<LogEvent> = Vec::new();
     let mut total_size: usize = 0;
     let mut next_token: Option<String> = None;
+    let mut truncated = false;
+
+    // Newest-first: the window being fetched and its events, older than all_events
+    let lower_bound = start_time.unwrap_or(0);
+    let mut span = DESCENDING_INITIAL_SPAN_MS;
+    let mut window = descending.then(|| {
+        let upper = end_time.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        ((upper - span).max(lower_bound), upper)
+    });
+    let mut window_events: Vec<LogEvent> = Vec::new();
+    let mut tuner = pacing::PageTuner::new();
+
+    // A range already fetched in full is answered from the local cache
+    let cache_key = event_cache::CacheKey::current(&state, &log_group_name).await;
+    let cached = event_cache::lookup(
+        &app,
+        &cache_key,
+        start_time,
+        end_time,
+        filter_pattern.as_deref(),
+    );
+    let served_from_cache = cached.is_some();
+    if let Some(mut events) = cached {
+        log::info!(
+            "Serving {} events of {} from the local cache",
+            events.len(),
+            log_group_name
+        );
+        let mut reason = None;
+        if events.len() > max_events {
+            // Keep the end a newest-first fetch would have reached
+            if descending {
+                events.drain(..events.len() - max_events);
+            } else {
+                events.truncate(max_events);
+            }
+            reason = Some("count");
+        }
+        total_size = events.iter().map(|e| e.message.len()).sum();
+        if total_size > max_bytes {
+            // A live fetch stops once the events it holds reach the size limit
+            let mut size = 0;
+            let fits = |len: &usize| {
+                let fits = size < max_bytes;
+                size += len;
+                fits
+            };
+            let sizes = events.iter().map(|e| e.message.len());
+            if descending {
+                let kept = sizes.rev().take_while(fits).count();
+                events.drain(..events.len() - kept);
+            } else {
+                let kept = sizes.take_while(fits).count();
+                events.truncate(kept);
+            }
+            total_size = events.iter().map(|e| e.message.len()).sum();
+            reason = Some("size");
+        }
+        all_events = events;
+        if let Some(reason) = reason {
+            truncated = true;
+            app.emit_tagged(
+                "logs-truncated",
+                LogsTruncated {
+                    count: all_events.len(),
+                    size_bytes: total_size,
+                    reason: reason.to_string(),
+                    log_group_name: None,
+                },
+            )
+            .ok();
+        }
+    }
 
-    loop {
-        // Check if fetch was cancelled
-        if state.fetch_cancelled.load(Ordering::SeqCst) {
+    // Long oldest-first ranges are fetched as time slices in parallel
+    let sliced_range = start_time
+        .filter(|_| !served_from_cache && !descending)
+        .map(|start| {
+            let end = end_time.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+            (start, end)
+        })
+        .filter(|&(start, end)| sliced_fetch::slice_count(start, end) > 1);
+    if let Some((start, end)) = sliced_range {
+        let sliced = sliced_fetch::fetch_sliced(
+            &app,
+            &client,
+            &op,
+            &registration.token,
+            fetch_id,
+            &log_group_name,
+            start,
+            end,
+            filter_pattern.as_deref(),
+            max_events,
+            max_bytes,
+        )
+        .await;
+        all_events = sliced.events;
+        total_size = sliced.size_bytes;
+        if sliced.cancelled {
             log::info!(
                 "Log fetch cancelled, returning {} logs fetched so far",
                 all_events.len()
             );
-            return Ok(all_events);
+            return Ok(finish_fetch(&app, &state, &run, true, all_events).await);
+        }
+        if let Some(reason) = sliced.truncated {
+            truncated = true;
+            app.emit_tagged(
+                "logs-truncated",
+                LogsTruncated {
+                    count: all_events.len(),
+                    size_bytes: total_size,
+                    reason: reason.to_string(),
+                    log_group_name: None,
+                },
+            )
+            .ok();
+        }
+        if let Some(error) = sliced.error {
+            if is_sso_session_expired(&error.message) {
+                handle_sso_expiration(&app, &state, None).await;
+            }
+            if error.group_missing {
+                emit_group_unavailable(&app, &log_group_name, "fetch", None);
+            }
+            if all_events.is_empty() {
+                return Err(error.humanized());
+            }
+            log::warn!(
+                "Fetch failed after {} events, returning partial results: {}",
+                all_events.len(),
+                error.message
+            );
+            truncated = true;
+            app.emit_tagged(
+                "logs-partial",
+                LogsPartial {
+                    fetch_id,
+                    count: all_events.len(),
+                    error: error.humanized(),
+                    log_group_name: None,
+                },
+            )
+            .ok();
         }
+    } else if !served_from_cache {
+        loop {
+            // Check if fetch was cancelled
+            if state.fetch_cancelled.load(Ordering::SeqCst) || registration.token.is_cancelled() {
+                log::info!(
+                    "Log fetch cancelled, returning {} logs fetched so far",
+                    all_events.len()
+                );
+                if descending {
+                    window_events.append(&mut all_events);
+                    all_events = window_events;
+                    all_events.reverse();
+                }
+                return Ok(finish_fetch(&app, &state, &run, true, all_events).await);
+            }
 
-        let mut request = client.filter_log_events().log_group_name(&log_group_name);
+            let mut request = client.filter_log_events().log_group_name(&log_group_name);
 
-        if let Some(start) = start_time {
-            request = request.start_time(start);
-        }
+            if let Some(start) = window.map(|w| w.0).or(start_time) {
+                request = request.start_time(start);
+            }
 
-        if let Some(end) = end_time {
-            request = request.end_time(end);
-        }
+            if let Some(end) = window.map(|w| w.1).or(end_time) {
+                request = request.end_time(end);
+            }
 
-        if let Some(ref pattern) = filter_pattern {
-            if !pattern.is_empty() {
-                request = request.filter_pattern(pattern);
+            if let Some(ref pattern) = filter_pattern {
+                if !pattern.is_empty() {
+                    request = request.filter_pattern(pattern);
+                }
             }
-        }
 
-        if let Some(ref token) = next_token {
-            request = request.next_token(token);
-        }
+            if let Some(ref token) = next_token {
+                request = request.next_token(token);
+            }
 
-        match request.send().await {
-            Ok(response) => {
-                let events: Vec<LogEvent> = response
-                    .events
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(LogEvent::from)
-                    .collect();
-
-                // Calculate size of new events
-                let new_size: usize = events.iter().map(|e| e.message.len()).sum();
-                total_size += new_size;
-                all_events.extend(events);
-
-                // Emit progress update to frontend
-                app.emit(
-                    "logs-progress",
-                    LogsProgress {
-                        fetch_id,
-                        count: all_events.len(),
-                        size_bytes: total_size,
-                    },
-                )
-                .ok();
+            // Cancelling abandons the request in flight; the check above then returns
+            let sent = tokio::select! {
+                _ = registration.token.cancelled() => continue,
+                sent = send_filter_with_retry(request, &op, &mut tuner) => sent,
+            };
+            match sent {
+                Ok(response) => {
+                    let events: Vec<LogEvent> = response
+                        .events
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(LogEvent::from)
+                        .collect();
+                    op.page(events.len());
+
+                    // Calculate size of new events
+                    let new_size: usize = events.iter().map(|e| e.message.len()).sum();
+                    total_size += new_size;
+                    if descending {
+                        window_events.extend(events);
+                    } else {
+                        all_events.extend(events);
+                    }
 
-                // Check for more pages
-                next_token = response.next_token.clone();
+                    // Emit progress update to frontend
+                    app.emit_tagged(
+                        "logs-progress",
+                        LogsProgress {
+                            fetch_id,
+                            count: all_events.len() + window_events.len(),
+                            size_bytes: total_size,
+                        },
+                    )
+                    .ok();
+
+                    // Check for more pages
+                    next_token = response.next_token.clone();
+
+                    if let Some((window_start, _)) = window {
+                        // A window is fetched whole, then only its newest events that
+                        // still fit are kept in front of the newer ones
+                        if next_token.is_some() && total_size < max_bytes {
+                            continue;
+                        }
+                        let remaining = max_events - all_events.len();
+                        let window_count = window_events.len();
+                        let dropped = window_count.saturating_sub(remaining);
+                        window_events.drain(..dropped);
+                        window_events.append(&mut all_events);
+                        all_events = std::mem::take(&mut window_events);
+
+                        let more_older = window_start > lower_bound;
+                        let reason = if dropped > 0
+                            || (all_events.len() >= max_events && more_older)
+                        {
+                            Some("count")
+                        } else if next_token.is_some() || (total_size >= max_bytes && more_older) {
+                            Some("size")
+                        } else {
+                            None
+                        };
+                        if let Some(reason) = reason {
+                            truncated = true;
+                            app.emit_tagged(
+                                "logs-truncated",
+                                LogsTruncated {
+                                    count: all_events.len(),
+                                    size_bytes: total_size,
+                                    reason: reason.to_string(),
+                                    log_group_name: None,
+                                },
+                            )
+                            .ok();
+                            break;
+                        }
+                        if window_start <= lower_bound {
+                            break;
+                        }
+                        // Widen the next window while results are sparse
+                        if window_count < remaining / 4 {
+                            span = span.saturating_mul(2);
+                        }
+                        window = Some((
+                            window_start.saturating_sub(span).max(lower_bound),
+                            window_start - 1,
+                        ));
+                        continue;
+                    }
 
-                // Check if we've hit count limit
-                if all_events.len() >= max_events {
-                    all_events.truncate(max_events);
-                    if next_token.is_some() {
-                        app.emit(
-                            "logs-truncated",
-                            LogsTruncated {
-                                count: all_events.len(),
-                                size_bytes: total_size,
-                                reason: "count".to_string(),
-                            },
-                        )
-                        .ok();
+                    // Check if we've hit count limit
+                    if all_events.len() >= max_events {
+                        all_events.truncate(max_events);
+                        if next_token.is_some() {
+                            truncated = true;
+                            app.emit_tagged(
+                                "logs-truncated",
+                                LogsTruncated {
+                                    count: all_events.len(),
+                                    size_bytes: total_size,
+                                    reason: "count".to_string(),
+                                    log_group_name: None,
+                                },
+                            )
+                            .ok();
+                        }
+                        break;
+                    }
+
+                    // Check if we've hit size limit
+                    if total_size >= max_bytes {
+                        if next_token.is_some() {
+                            truncated = true;
+                            app.emit_tagged(
+                                "logs-truncated",
+                                LogsTruncated {
+                                    count: all_events.len(),
+                                    size_bytes: total_size,
+                                    reason: "size".to_string(),
+                                    log_group_name: None,
+                                },
+                            )
+                            .ok();
+                        }
+                        break;
                     }
+
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    if is_sso_session_expired(&error.message) {
+                        handle_sso_expiration(&app, &state, None).await;
+                    }
+                    if error.group_missing {
+                        emit_group_unavailable(&app, &log_group_name, "fetch", None);
+                    }
+                    // A partly fetched window is older than everything else collected
+                    window_events.append(&mut all_events);
+                    all_events = window_events;
+                    if all_events.is_empty() {
+                        return Err(error.humanized());
+                    }
+                    // Keep what was collected and tell the frontend it is incomplete
+                    log::warn!(
+                        "Fetch failed after {} events, returning partial results: {}",
+                        all_events.len(),
+                        error.message
+                    );
+                    truncated = true;
+                    app.emit_tagged(
+                        "logs-partial",
+                        LogsPartial {
+                            fetch_id,
+                            count: all_events.len(),
+                            error: error.humanized(),
+                            log_group_name: None,
+                        },
+                    )
+                    .ok();
                     break;
                 }
+            }
+        }
+    }
+
+    if let Some(session_id) = &session_id {
+        let mut tagged = all_events.clone();
+        session::tag_source(&mut tagged, &log_group_name);
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions.get_or_create(session_id);
+        session.replace_source(&log_group_name, tagged);
+        session.order = order;
+        session.fetches.insert(
+            log_group_name.clone(),
+            session::FetchContext {
+                start_time,
+                end_time,
+                filter_pattern: filter_pattern.clone(),
+                truncated,
+                complete_ranges: Vec::new(),
+            },
+        );
+    }
+
+    // Learn the group's fields in the background for autocomplete and column pickers
+    schema::record_sample(&app, &log_group_name, &all_events);
+    // and pick a parsing pipeline from its format the first time it is opened
+    pipeline::detect_on_first_open(&app, &log_group_name, &all_events);
+    // Warn when the oldest loaded events are about to age out of retention
+    retention::warn_if_expiring(
+        &app,
+        &log_group_name,
+        all_events
+            .first()
+            .zip(all_events.last())
+            .map(|(first, last)| (first.timestamp, last.timestamp)),
+        fetch_id,
+        session_id.clone(),
+    );
+    if !served_from_cache {
+        // Count the download against the profile's daily volume budget
+        budget::record(&app, &state, total_size as u64, 0).await;
+        // and keep a complete fetch for repeat queries of the same range
+        if !truncated {
+            event_cache::store(
+                &app,
+                &cache_key,
+                start_time,
+                end_time,
+                filter_pattern.as_deref(),
+                &all_events,
+            );
+        }
+    }
+    // Record the group's rates if it is tracked and the whole range was fetched
+    if let Some(start) = start_time.filter(|_| !truncated) {
+        if filter_pattern.as_deref().is_none_or(str::is_empty) {
+            group_history::record_fetch(
+                &app,
+                state.current_profile.lock().await.clone(),
+                &log_group_name,
+                start,
+                end_time.unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+                &all_events,
+            );
+        }
+    }
+
+    if descending {
+        all_events.reverse();
+    }
+    Ok(finish_fetch(&app, &state, &run, false, all_events).await)
+}
+
+/// What a fetch_logs call was asked for, as needed when handing back its events
+struct FetchRun<'a> {
+    fetch_id: u32,
+    log_group_name: &'a str,
+    filter_pattern: Option<&'a str>,
+    session_id: Option<&'a str>,
+    query_id: Option<&'a str>,
+}
+
+/// Every fetch_logs return goes through here, cancelled ones included: `events`
+/// (in display order) are masked for display, recorded for query diffs unless the
+/// fetch was cancelled, and long messages are cut to the preview length
+async fn finish_fetch(
+    app: &AppHandle,
+    state: &AppState,
+    run: &FetchRun<'_>,
+    cancelled: bool,
+    mut events: Vec<LogEvent>,
+) -> Vec<LogEvent> {
+    redaction::mask_for_display(app, state, run.session_id, &mut events).await;
+    if !cancelled {
+        diff::record_group_run(app, run.log_group_name, run.filter_pattern, &events);
+        if let Some(query_id) = run.query_id {
+            diff::record_run(
+                app,
+                query_id,
+                run.fetch_id,
+                run.log_group_name,
+                run.filter_pattern,
+                &events,
+            );
+        }
+    }
+    preview::truncate(app, &mut events);
+    events
+}
+
+/// Pagination state of one log group in a multi-group fetch
+struct GroupCursor {
+    log_group_name: String,
+    next_token: Option<String>,
+    events: Vec<LogEvent>,
+    size_bytes: usize,
+    pages: usize,
+    /// No pages left, the group hit its limits, or it failed
+    done: bool,
+    /// "count" or "size" when the group stopped at its limit
+    truncated_reason: Option<&'static str>,
+    error: Option<String>,
+}
 
-                // Check if we've hit size limit
-                if total_size >= max_bytes {
-                    if next_token.is_some() {
-                        app.emit(
+/// How far one group of a multi-group fetch got
+#[derive(Debug, Clone, Serialize)]
+struct GroupFetchSummary {
+    log_group_name: String,
+    count: usize,
+    size_bytes: usize,
+    pages: usize,
+    /// Every matching event was fetched
+    complete: bool,
+    /// "count" or "size" when the group stopped at its limit
+    truncated_reason: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MultiFetchResult {
+    /// Events of all groups merged in timestamp order, tagged with their source
+    events: Vec<LogEvent>,
+    groups: Vec<GroupFetchSummary>,
+}
+
+/// Fetch several log groups at once. Pages are requested round-robin with an
+/// independent cursor per group, so one gigantic group can't hold up the others.
+/// max_count and max_size_mb apply to each group separately; a group hitting them
+/// gets its own logs-truncated event (with `log_group_name`) while the rest go on.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn fetch_log_groups(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_names: Vec<String>,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+    max_count: Option<i32>,
+    max_size_mb: Option<i32>,
+    fetch_id: Option<u32>,
+    session_id: Option<String>,
+) -> Result<MultiFetchResult, String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in log_group_names {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if names.is_empty() {
+        return Err("Select at least one log group".to_string());
+    }
+    let fetch_id = fetch_id.unwrap_or(0);
+    state.fetch_cancelled.store(false, Ordering::SeqCst);
+    let op = operations::Operation::begin(
+        "fetch",
+        names.join(", "),
+        operations::CancelBy::Flag(state.fetch_cancelled.clone()),
+    );
+    let client = state.logs_client().await?;
+
+    let max_events: usize = max_count.map(|l| l as usize).unwrap_or(50_000);
+    let max_bytes: usize = max_size_mb
+        .map(|mb| mb as usize * 1024 * 1024)
+        .unwrap_or(100 * 1024 * 1024);
+    let mut cursors: Vec<GroupCursor> = names
+        .into_iter()
+        .map(|log_group_name| GroupCursor {
+            log_group_name,
+            next_token: None,
+            events: Vec::new(),
+            size_bytes: 0,
+            pages: 0,
+            done: false,
+            truncated_reason: None,
+            error: None,
+        })
+        .collect();
+    let mut total_count = 0;
+    let mut total_size = 0;
+    let mut tuner = pacing::PageTuner::new();
+
+    'rounds: while cursors.iter().any(|c| !c.done) {
+        for cursor in cursors.iter_mut().filter(|c| !c.done) {
+            if state.fetch_cancelled.load(Ordering::SeqCst) {
+                log::info!(
+                    "Multi-group fetch cancelled, returning {} logs fetched so far",
+                    total_count
+                );
+                break 'rounds;
+            }
+            let mut request = client
+                .filter_log_events()
+                .log_group_name(&cursor.log_group_name)
+                .set_start_time(start_time)
+                .set_end_time(end_time)
+                .set_next_token(cursor.next_token.take());
+            if let Some(pattern) = filter_pattern.as_deref().filter(|p| !p.is_empty()) {
+                request = request.filter_pattern(pattern);
+            }
+
+            match send_filter_with_retry(request, &op, &mut tuner).await {
+                Ok(response) => {
+                    let events: Vec<LogEvent> = response
+                        .events
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(LogEvent::from)
+                        .collect();
+                    op.page(events.len());
+                    let size: usize = events.iter().map(|e| e.message.len()).sum();
+                    cursor.pages += 1;
+                    cursor.size_bytes += size;
+                    total_size += size;
+                    total_count += events.len();
+                    cursor.events.extend(events);
+                    cursor.next_token = response.next_token;
+                    cursor.done = cursor.next_token.is_none();
+
+                    app.emit_tagged(
+                        "logs-progress",
+                        LogsProgress {
+                            fetch_id,
+                            count: total_count,
+                            size_bytes: total_size,
+                        },
+                    )
+                    .ok();
+
+                    let reason = if cursor.events.len() >= max_events {
+                        // The page that crossed the limit gives back its excess
+                        let excess = cursor.events.len() - max_events;
+                        cursor.events.truncate(max_events);
+                        total_count -= excess;
+                        (excess > 0 || !cursor.done).then_some("count")
+                    } else if cursor.size_bytes >= max_bytes && !cursor.done {
+                        Some("size")
+                    } else {
+                        None
+                    };
+                    if let Some(reason) = reason {
+                        cursor.done = true;
+                        cursor.truncated_reason = Some(reason);
+                        app.emit_tagged(
                             "logs-truncated",
                             LogsTruncated {
-                                count: all_events.len(),
-                                size_bytes: total_size,
-                                reason: "size".to_string(),
+                                count: cursor.events.len(),
+                                size_bytes: cursor.size_bytes,
+                                reason: reason.to_string(),
+                                log_group_name: Some(cursor.log_group_name.clone()),
                             },
                         )
                         .ok();
                     }
-                    break;
                 }
-
-                if next_token.is_none() {
-                    break;
+                Err(error) => {
+                    if is_sso_session_expired(&error.message) {
+                        handle_sso_expiration(&app, &state, None).await;
+                    }
+                    if error.group_missing {
+                        emit_group_unavailable(&app, &cursor.log_group_name, "fetch", None);
+                    }
+                    let message = error.humanized();
+                    log::warn!(
+                        "Fetch of {} failed after {} events: {}",
+                        cursor.log_group_name,
+                        cursor.events.len(),
+                        error.message
+                    );
+                    app.emit_tagged(
+                        "logs-partial",
+                        LogsPartial {
+                            fetch_id,
+                            count: cursor.events.len(),
+                            error: message.clone(),
+                            log_group_name: Some(cursor.log_group_name.clone()),
+                        },
+                    )
+                    .ok();
+                    cursor.error = Some(message);
+                    cursor.done = true;
                 }
             }
-            Err(e) => {
-                let error_msg = format!("{}", e);
-                if is_sso_session_expired(&error_msg) {
-                    handle_sso_expiration(&app, &state, None).await;
-                }
-                return Err(humanize_aws_error(&error_msg));
+        }
+    }
+
+    if total_count == 0 {
+        if let Some(error) = cursors.iter().find_map(|c| c.error.clone()) {
+            if cursors.iter().all(|c| c.error.is_some()) {
+                return Err(error);
             }
         }
     }
 
-    Ok(all_events)
+    let mut merged: Vec<LogEvent> = Vec::with_capacity(total_count);
+    let mut groups = Vec::with_capacity(cursors.len());
+    for cursor in cursors {
+        let complete = cursor.done && cursor.error.is_none() && cursor.truncated_reason.is_none();
+        let mut events = cursor.events;
+        session::tag_source(&mut events, &cursor.log_group_name);
+        schema::record_sample(&app, &cursor.log_group_name, &events);
+        if cursor.error.is_none() {
+            diff::record_group_run(
+                &app,
+                &cursor.log_group_name,
+                filter_pattern.as_deref(),
+                &events,
+            );
+        }
+        pipeline::detect_on_first_open(&app, &cursor.log_group_name, &events);
+        if let Some(session_id) = &session_id {
+            let mut sessions = state.sessions.lock().await;
+            let session = sessions.get_or_create(session_id);
+            session.replace_source(&cursor.log_group_name, events.clone());
+            session.fetches.insert(
+                cursor.log_group_name.clone(),
+                session::FetchContext {
+                    start_time,
+                    end_time,
+                    filter_pattern: filter_pattern.clone(),
+                    truncated: !complete,
+                    complete_ranges: Vec::new(),
+                },
+            );
+        }
+        groups.push(GroupFetchSummary {
+            log_group_name: cursor.log_group_name,
+            count: events.len(),
+            size_bytes: cursor.size_bytes,
+            pages: cursor.pages,
+            complete,
+            truncated_reason: cursor.truncated_reason.map(str::to_string),
+            error: cursor.error,
+        });
+        merged.extend(events);
+    }
+    merged.sort_by_key(|e| e.timestamp);
+    budget::record(&app, &state, total_size as u64, 0).await;
+    redaction::mask_for_display(&app, &state, session_id.as_deref(), &mut merged).await;
+    preview::truncate(&app, &mut merged);
+    Ok(MultiFetchResult {
+        events: merged,
+        groups,
+    })
+}
+
+/// Fetch every event matching a filter in a time range, up to max_events.
+/// Used by backend features that need raw results without streaming progress to the UI.
+/// Returns the events and whether the result was truncated at max_events.
+async fn filter_all_events(
+    client: &CloudWatchClient,
+    log_group_name: &str,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<&str>,
+    max_events: usize,
+) -> Result<(Vec<LogEvent>, bool), String> {
+    filter_stream_events(
+        client,
+        log_group_name,
+        &[],
+        start_time,
+        end_time,
+        filter_pattern,
+        max_events,
+    )
+    .await
+}
+
+/// filter_all_events restricted to `log_stream_names` (the whole group when empty)
+async fn filter_stream_events(
+    client: &CloudWatchClient,
+    log_group_name: &str,
+    log_stream_names: &[String],
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<&str>,
+    max_events: usize,
+) -> Result<(Vec<LogEvent>, bool), String> {
+    let op = operations::Operation::fetch(log_group_name);
+    let mut events: Vec<LogEvent> = Vec::new();
+    let mut next_token: Option<String> = None;
+    let mut tuner = pacing::PageTuner::new();
+
+    loop {
+        if op.is_cancelled() {
+            return Ok((events, true));
+        }
+        let mut request = client
+            .filter_log_events()
+            .log_group_name(log_group_name)
+            .set_start_time(start_time)
+            .set_end_time(end_time)
+            .set_next_token(next_token);
+        if !log_stream_names.is_empty() {
+            request = request.set_log_stream_names(Some(log_stream_names.to_vec()));
+        }
+        if let Some(pattern) = filter_pattern.filter(|p| !p.is_empty()) {
+            request = request.filter_pattern(pattern);
+        }
+
+        let response = send_filter_with_retry(request, &op, &mut tuner)
+            .await
+            .map_err(|e| e.humanized())?;
+        op.page(response.events().len());
+        events.extend(
+            response
+                .events
+                .unwrap_or_default()
+                .into_iter()
+                .map(LogEvent::from),
+        );
+
+        next_token = response.next_token;
+        if events.len() >= max_events {
+            let truncated = next_token.is_some() || events.len() > max_events;
+            events.truncate(max_events);
+            return Ok((events, truncated));
+        }
+        if next_token.is_none() {
+            return Ok((events, false));
+        }
+    }
+}
+
+/// One page of fetch_logs_paginated
+#[derive(Debug, Clone, Serialize)]
+struct PaginatedLogs {
+    events: Vec<LogEvent>,
+    next_token: Option<String>,
+    /// The given next_token had expired and the query was restarted from the last
+    /// timestamp seen, skipping events already returned
+    cursor_refreshed: bool,
 }
 
 /// Fetch logs with pagination support for tailing
@@ -1225,40 +2358,59 @@ async fn fetch_logs_paginated(
     end_time: Option<i64>,
     filter_pattern: Option<String>,
     next_token: Option<String>,
-) -> Result<(Vec<LogEvent>, Option<String>), String> {
-    let client_lock = state.client.lock().await;
-    let client = client_lock.as_ref().ok_or("AWS client not initialized")?;
-
-    let mut request = client.filter_log_events().log_group_name(&log_group_name);
-
-    if let Some(start) = start_time {
-        request = request.start_time(start);
-    }
-
-    if let Some(end) = end_time {
-        request = request.end_time(end);
-    }
+) -> Result<PaginatedLogs, String> {
+    let client = state.logs_client().await?;
+    let filter_pattern = filter_pattern.filter(|p| !p.is_empty());
+
+    let build = |start: Option<i64>, token: Option<String>| {
+        client
+            .filter_log_events()
+            .log_group_name(&log_group_name)
+            .set_start_time(start)
+            .set_end_time(end_time)
+            .set_filter_pattern(filter_pattern.clone())
+            .set_next_token(token)
+    };
 
-    if let Some(pattern) = filter_pattern {
-        if !pattern.is_empty() {
-            request = request.filter_pattern(pattern);
+    let mut cursor_refreshed = false;
+    let mut skip_ids: Vec<String> = Vec::new();
+    let mut result = build(start_time, next_token.clone()).send().await;
+    if let (Err(e), Some(token)) = (&result, &next_token) {
+        if cursors::is_invalid_token(e) {
+            let (restart_from, seen_ids) = cursors::restart_point(token, start_time)
+                .ok_or("The pagination cursor expired; run the query again from the start")?;
+            log::info!(
+                "Cursor for {} expired, restarting from {:?}",
+                log_group_name,
+                restart_from
+            );
+            cursor_refreshed = true;
+            skip_ids = seen_ids;
+            result = build(restart_from, None).send().await;
         }
     }
 
-    if let Some(token) = next_token {
-        request = request.next_token(token);
-    }
-
-    match request.send().await {
+    match result {
         Ok(response) => {
-            let events = response
+            let mut events: Vec<LogEvent> = response
                 .events
                 .unwrap_or_default()
                 .into_iter()
                 .map(LogEvent::from)
+                .filter(|e| e.event_id.as_ref().is_none_or(|id| !skip_ids.contains(id)))
                 .collect();
-            let new_token = response.next_token;
-            Ok((events, new_token))
+            if let Some(new_token) = &response.next_token {
+                cursors::track(next_token.as_deref(), new_token, &events);
+            }
+            let size: usize = events.iter().map(|e| e.message.len()).sum();
+            budget::record(&app, &state, size as u64, 0).await;
+            redaction::mask_for_display(&app, &state, None, &mut events).await;
+            preview::truncate(&app, &mut events);
+            Ok(PaginatedLogs {
+                events,
+                next_token: response.next_token,
+                cursor_refreshed,
+            })
         }
         Err(e) => {
             let error_msg = format!("{}", e);
@@ -1274,11 +2426,20 @@ async fn fetch_logs_paginated(
 /// (no brackets, no asterisk). Strip optional surrounding brackets and trailing ":*".
 fn normalize_log_group_identifier(raw: &str) -> String {
     let s = raw.trim();
-    let s = s.strip_prefix('[').unwrap_or(s).strip_suffix(']').unwrap_or(s).trim();
+    let s = s
+        .strip_prefix('[')
+        .unwrap_or(s)
+        .strip_suffix(']')
+        .unwrap_or(s)
+        .trim();
     let s = s.strip_suffix(":*").unwrap_or(s).trim();
     s.to_string()
 }
 
+/// Session id of the Live Tail for request_reveal/confirm_reveal; its batches are
+/// masked until it is revealed
+const LIVE_TAIL_SESSION_ID: &str = "live-tail";
+
 #[tauri::command]
 async fn start_live_tail(
     log_group_arn: String,
@@ -1294,14 +2455,11 @@ async fn start_live_tail(
         handle.abort();
     }
 
-    let client_lock = state.client.lock().await;
-    let client = client_lock
-        .as_ref()
-        .ok_or("AWS client not initialized")?
-        .clone();
-    drop(client_lock);
+    let client = state.logs_client().await?;
 
     let live_tail_handle = state.live_tail_handle.clone();
+    let op =
+        operations::Operation::begin("live-tail", &log_group_arn, operations::CancelBy::LiveTail);
 
     let handle = tokio::spawn(async move {
         let mut request = client
@@ -1324,15 +2482,24 @@ async fn start_live_tail(
                                 aws_sdk_cloudwatchlogs::types::StartLiveTailResponseStream::SessionUpdate(update) => {
                                     let results = update.session_results.unwrap_or_default();
                                     let count = results.len();
-                                    let logs: Vec<LogEvent> = results.into_iter().map(|e| LogEvent {
-                                        timestamp: e.timestamp.unwrap_or(0),
-                                        message: e.message.unwrap_or_default(),
-                                        log_stream_name: e.log_stream_name,
-                                        event_id: None,
+                                    op.page(count);
+                                    let mut logs: Vec<LogEvent> = results.into_iter().map(|e| {
+                                        let mut event = LogEvent {
+                                            timestamp: e.timestamp.unwrap_or(0),
+                                            message: e.message.unwrap_or_default(),
+                                            log_stream_name: e.log_stream_name,
+                                            event_id: None,
+                                            source: None,
+                                            columns: None,
+                                            full_length: None,
+                                        };
+                                        event.ensure_event_id();
+                                        event
                                     }).collect();
 
                                     if !logs.is_empty() {
-                                        app.emit("live-tail-event", LiveTailEventPayload { logs, count }).ok();
+                                        redaction::mask_with_handle(&app, LIVE_TAIL_SESSION_ID, &mut logs).await;
+                                        app.emit_tagged("live-tail-event", LiveTailEventPayload { logs, count }).ok();
                                     }
                                 }
                                 aws_sdk_cloudwatchlogs::types::StartLiveTailResponseStream::SessionStart(_) => {
@@ -1344,13 +2511,18 @@ async fn start_live_tail(
                         Ok(None) => {
                             // Stream ended (3-hour timeout)
                             log::info!("Live tail stream ended for {}", log_group_arn);
-                            app.emit("live-tail-ended", serde_json::json!({})).ok();
+                            app.emit_tagged("live-tail-ended", serde_json::json!({})).ok();
                             break;
                         }
                         Err(e) => {
                             let error_msg = format!("{:?}", e);
                             log::error!("Live tail stream error: {}", error_msg);
-                            app.emit("live-tail-error", LiveTailErrorPayload { message: error_msg }).ok();
+                            if is_resource_not_found(&error_msg) {
+                                emit_group_unavailable(&app, &normalized, "live-tail", None);
+                                app.emit_tagged("live-tail-ended", serde_json::json!({})).ok();
+                            } else {
+                                app.emit_tagged("live-tail-error", LiveTailErrorPayload { message: error_msg }).ok();
+                            }
                             break;
                         }
                     }
@@ -1359,11 +2531,17 @@ async fn start_live_tail(
             Err(e) => {
                 let error_msg = format!("{:?}", e);
                 log::error!("Failed to start live tail: {}", error_msg);
-                app.emit(
-                    "live-tail-error",
-                    LiveTailErrorPayload { message: error_msg },
-                )
-                .ok();
+                if is_resource_not_found(&error_msg) {
+                    emit_group_unavailable(&app, &normalized, "live-tail", None);
+                    app.emit_tagged("live-tail-ended", serde_json::json!({}))
+                        .ok();
+                } else {
+                    app.emit_tagged(
+                        "live-tail-error",
+                        LiveTailErrorPayload { message: error_msg },
+                    )
+                    .ok();
+                }
             }
         }
 
@@ -1397,71 +2575,91 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .manage(AppState::default())
         .setup(|app| {
+            // Resolve the language before any labels are built
+            i18n::load(app.handle());
+
             // Create menu items
-            let about_item = MenuItemBuilder::new("About Loggy").id("about").build(app)?;
+            let about_item = MenuItemBuilder::new(i18n::tr(Message::MenuAbout))
+                .id("about")
+                .build(app)?;
 
-            let check_updates_item = MenuItemBuilder::new("Check for Updates...")
+            let check_updates_item = MenuItemBuilder::new(i18n::tr(Message::MenuCheckForUpdates))
                 .id("check-for-updates")
                 .build(app)?;
 
-            let preferences_item = MenuItemBuilder::new("Preferences...")
+            let preferences_item = MenuItemBuilder::new(i18n::tr(Message::MenuPreferences))
                 .id("preferences")
                 .accelerator("CmdOrCtrl+,")
                 .build(app)?;
 
-            let refresh_item = MenuItemBuilder::new("Refresh")
+            let refresh_item = MenuItemBuilder::new(i18n::tr(Message::MenuRefresh))
                 .id("refresh")
                 .accelerator("CmdOrCtrl+R")
                 .build(app)?;
 
-            let focus_filter_item = MenuItemBuilder::new("Search")
+            let focus_filter_item = MenuItemBuilder::new(i18n::tr(Message::MenuSearch))
                 .id("focus-filter")
                 .accelerator("CmdOrCtrl+L")
                 .build(app)?;
 
-            let clear_item = MenuItemBuilder::new("Clear")
+            let clear_item = MenuItemBuilder::new(i18n::tr(Message::MenuClear))
                 .id("clear")
                 .accelerator("CmdOrCtrl+K")
                 .build(app)?;
 
-            let find_item = MenuItemBuilder::new("Find")
+            let find_item = MenuItemBuilder::new(i18n::tr(Message::MenuFind))
                 .id("find")
                 .accelerator("CmdOrCtrl+F")
                 .build(app)?;
 
-            // Theme menu items (checkable)
-            let theme_dark_item = tauri::menu::CheckMenuItemBuilder::new("Dark")
-                .id("theme-dark")
-                .checked(false)
-                .build(app)?;
-            let theme_light_item = tauri::menu::CheckMenuItemBuilder::new("Light")
-                .id("theme-light")
-                .checked(false)
-                .build(app)?;
-            let theme_system_item = tauri::menu::CheckMenuItemBuilder::new("System")
-                .id("theme-system")
-                .checked(true) // Default to system
+            let export_item = MenuItemBuilder::new(i18n::tr(Message::MenuExport))
+                .id("export-logs")
+                .accelerator("CmdOrCtrl+Shift+E")
                 .build(app)?;
 
+            // Theme menu items (checkable)
+            let theme_dark_item =
+                tauri::menu::CheckMenuItemBuilder::new(i18n::tr(Message::MenuThemeDark))
+                    .id("theme-dark")
+                    .checked(false)
+                    .build(app)?;
+            let theme_light_item =
+                tauri::menu::CheckMenuItemBuilder::new(i18n::tr(Message::MenuThemeLight))
+                    .id("theme-light")
+                    .checked(false)
+                    .build(app)?;
+            let theme_system_item =
+                tauri::menu::CheckMenuItemBuilder::new(i18n::tr(Message::MenuThemeSystem))
+                    .id("theme-system")
+                    .checked(true) // Default to system
+                    .build(app)?;
+
+            // Filled from the recent workspaces file once the menu state is managed
+            let recent_workspaces_submenu =
+                SubmenuBuilder::new(app, i18n::tr(Message::MenuRecentWorkspaces)).build()?;
+
             // Store theme menu items in state for sync_theme_menu command
             app.manage(MenuState {
                 theme_dark: theme_dark_item.clone(),
                 theme_light: theme_light_item.clone(),
                 theme_system: theme_system_item.clone(),
+                recent_workspaces: recent_workspaces_submenu.clone(),
             });
+            workspace::refresh_recent_menu(app.handle());
 
             // Theme submenu
-            let theme_submenu = SubmenuBuilder::new(app, "Theme")
+            let theme_submenu = SubmenuBuilder::new(app, i18n::tr(Message::MenuTheme))
                 .item(&theme_dark_item)
                 .item(&theme_light_item)
                 .item(&theme_system_item)
                 .build()?;
 
             // Demo mode menu item (checkable)
-            let demo_mode_item = tauri::menu::CheckMenuItemBuilder::new("Demo Mode")
-                .id("demo-mode")
-                .checked(false)
-                .build(app)?;
+            let demo_mode_item =
+                tauri::menu::CheckMenuItemBuilder::new(i18n::tr(Message::MenuDemoMode))
+                    .id("demo-mode")
+                    .checked(false)
+                    .build(app)?;
 
             // App submenu (macOS application menu)
             let app_submenu = SubmenuBuilder::new(app, "Loggy")
@@ -1480,8 +2678,15 @@ pub fn run() {
                 .quit()
                 .build()?;
 
+            // File submenu
+            let file_submenu = SubmenuBuilder::new(app, i18n::tr(Message::MenuFile))
+                .item(&recent_workspaces_submenu)
+                .separator()
+                .item(&export_item)
+                .build()?;
+
             // Edit submenu with standard editing commands
-            let edit_submenu = SubmenuBuilder::new(app, "Edit")
+            let edit_submenu = SubmenuBuilder::new(app, i18n::tr(Message::MenuEdit))
                 .undo()
                 .redo()
                 .separator()
@@ -1494,7 +2699,7 @@ pub fn run() {
                 .build()?;
 
             // View submenu
-            let view_submenu = SubmenuBuilder::new(app, "View")
+            let view_submenu = SubmenuBuilder::new(app, i18n::tr(Message::MenuView))
                 .item(&refresh_item)
                 .item(&clear_item)
                 .item(&focus_filter_item)
@@ -1505,7 +2710,7 @@ pub fn run() {
                 .build()?;
 
             // Window submenu
-            let window_submenu = SubmenuBuilder::new(app, "Window")
+            let window_submenu = SubmenuBuilder::new(app, i18n::tr(Message::MenuWindow))
                 .minimize()
                 .separator()
                 .close_window()
@@ -1515,6 +2720,7 @@ pub fn run() {
             let menu = MenuBuilder::new(app)
                 .items(&[
                     &app_submenu,
+                    &file_submenu,
                     &edit_submenu,
                     &view_submenu,
                     &window_submenu,
@@ -1535,37 +2741,30 @@ pub fn run() {
                         // Only check when user has connected (we have a client)
                         let has_client = state.client.lock().await.is_some();
                         if has_client {
-                            let profile =
-                                { state.current_profile.lock().await.clone() };
+                            let profile = { state.current_profile.lock().await.clone() };
                             // Reload config from disk so we see cleared cache (e.g. aws sso logout)
-                            let mut config_loader =
-                                aws_config::defaults(BehaviorVersion::latest());
+                            let mut config_loader = aws_config::defaults(BehaviorVersion::latest());
                             if let Some(ref p) = profile {
                                 config_loader = config_loader.profile_name(p);
                             }
                             let config = config_loader.load().await;
                             if let Some(provider) = config.credentials_provider() {
-                                match provider.provide_credentials().await {
-                                    Err(e) => {
-                                        let msg = format!("{}", e);
-                                        let debug = format!("{:?}", e);
-                                        let source = e
-                                            .source()
-                                            .map(|s| format!("{}", s))
-                                            .unwrap_or_default();
-                                        let is_expired = is_sso_session_expired(&msg)
-                                            || is_sso_session_expired(&debug)
-                                            || is_sso_session_expired(&source);
-                                        if is_expired {
-                                            handle_sso_expiration(
-                                                &app_handle,
-                                                &state,
-                                                profile.as_ref(),
-                                            )
-                                            .await;
-                                        }
+                                if let Err(e) = provider.provide_credentials().await {
+                                    let msg = format!("{}", e);
+                                    let debug = format!("{:?}", e);
+                                    let source =
+                                        e.source().map(|s| format!("{}", s)).unwrap_or_default();
+                                    let is_expired = is_sso_session_expired(&msg)
+                                        || is_sso_session_expired(&debug)
+                                        || is_sso_session_expired(&source);
+                                    if is_expired {
+                                        handle_sso_expiration(
+                                            &app_handle,
+                                            &state,
+                                            profile.as_ref(),
+                                        )
+                                        .await;
                                     }
-                                    Ok(_) => {}
                                 }
                             }
                         }
@@ -1574,6 +2773,36 @@ pub fn run() {
                 }
             });
 
+            // Re-validate the connection after sleep or network changes
+            tauri::async_runtime::spawn(connectivity::run_network_watcher(app.handle().clone()));
+
+            // Latency and failure tracking of the active connection
+            tauri::async_runtime::spawn(heartbeat::run_heartbeat(app.handle().clone()));
+
+            // Session state journal: recover after an unclean exit, then keep it current
+            tauri::async_runtime::block_on(journal::recover(app.handle()));
+            tauri::async_runtime::spawn(journal::run_journal_flusher(app.handle().clone()));
+
+            // Background evaluation of workspace alert thresholds
+            tauri::async_runtime::spawn(alerts::run_alert_poller(app.handle().clone()));
+
+            // Daily credential pre-flight for favorite profiles
+            tauri::async_runtime::spawn(preflight::run_preflight_scheduler(app.handle().clone()));
+
+            // Scheduled user scripts
+            tauri::async_runtime::spawn(scripting::run_script_scheduler(app.handle().clone()));
+
+            // Delete shared gists once they expire
+            tauri::async_runtime::spawn(share::run_expiry_sweeper(app.handle().clone()));
+
+            // Warm the log group catalogs of pinned profiles (opt-in)
+            tauri::async_runtime::spawn(prefetch::run_startup_prefetch(app.handle().clone()));
+
+            // Daily error summaries of pinned groups (opt-in)
+            tauri::async_runtime::spawn(daily_summaries::run_summary_scheduler(
+                app.handle().clone(),
+            ));
+
             // Handle menu events - clone menu item references for use in closure
             let preferences_id = preferences_item.id().clone();
             let about_id = about_item.id().clone();
@@ -1581,6 +2810,7 @@ pub fn run() {
             let refresh_id = refresh_item.id().clone();
             let clear_id = clear_item.id().clone();
             let find_id = find_item.id().clone();
+            let export_id = export_item.id().clone();
             let theme_dark_id = theme_dark_item.id().clone();
             let theme_light_id = theme_light_item.id().clone();
             let theme_system_id = theme_system_item.id().clone();
@@ -1595,38 +2825,46 @@ pub fn run() {
 
             app.on_menu_event(move |app_handle, event| {
                 if *event.id() == preferences_id {
-                    app_handle.emit("open-settings", ()).ok();
+                    app_handle.emit_tagged("open-settings", ()).ok();
                 } else if *event.id() == about_id {
-                    app_handle.emit("open-about", ()).ok();
+                    app_handle.emit_tagged("open-about", ()).ok();
                 } else if *event.id() == check_updates_id {
-                    app_handle.emit("check-for-updates", ()).ok();
+                    app_handle.emit_tagged("check-for-updates", ()).ok();
                 } else if *event.id() == refresh_id {
-                    app_handle.emit("refresh-logs", ()).ok();
+                    app_handle.emit_tagged("refresh-logs", ()).ok();
                 } else if *event.id() == clear_id {
-                    app_handle.emit("clear-logs", ()).ok();
+                    app_handle.emit_tagged("clear-logs", ()).ok();
                 } else if *event.id() == find_id {
-                    app_handle.emit("open-find", ()).ok();
+                    app_handle.emit_tagged("open-find", ()).ok();
+                } else if *event.id() == export_id {
+                    app_handle.emit_tagged("open-export", ()).ok();
                 } else if *event.id() == theme_dark_id {
                     // Update checkmarks using direct references
                     theme_dark.set_checked(true).ok();
                     theme_light.set_checked(false).ok();
                     theme_system.set_checked(false).ok();
-                    app_handle.emit("set-theme", "dark").ok();
+                    app_handle.emit_tagged("set-theme", "dark").ok();
                 } else if *event.id() == theme_light_id {
                     // Update checkmarks using direct references
                     theme_dark.set_checked(false).ok();
                     theme_light.set_checked(true).ok();
                     theme_system.set_checked(false).ok();
-                    app_handle.emit("set-theme", "light").ok();
+                    app_handle.emit_tagged("set-theme", "light").ok();
                 } else if *event.id() == theme_system_id {
                     // Update checkmarks using direct references
                     theme_dark.set_checked(false).ok();
                     theme_light.set_checked(false).ok();
                     theme_system.set_checked(true).ok();
-                    app_handle.emit("set-theme", "system").ok();
+                    app_handle.emit_tagged("set-theme", "system").ok();
                 } else if *event.id() == demo_mode_id {
                     let is_checked = demo_mode.is_checked().unwrap_or(false);
-                    app_handle.emit("toggle-demo-mode", is_checked).ok();
+                    app_handle.emit_tagged("toggle-demo-mode", is_checked).ok();
+                } else if let Some(name) = event
+                    .id()
+                    .as_ref()
+                    .strip_prefix(workspace::RECENT_WORKSPACE_MENU_PREFIX)
+                {
+                    app_handle.emit_tagged("restore-workspace", name).ok();
                 }
             });
 
@@ -1635,18 +2873,232 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             init_aws_client,
             reconnect_aws,
+            assume_role::assume_role,
+            assume_role::get_assumed_role,
+            assume_role::release_assumed_role,
             list_aws_profiles,
+            profile_groups::get_profile_groups,
+            profile_groups::save_profile_groups,
             trigger_sso_login,
             open_sso_url,
+            sso::get_sso_login_target,
+            sso::list_sso_sessions,
             get_app_version,
             list_log_groups,
+            streams::list_log_streams,
+            streams::fetch_stream_events,
             fetch_logs,
+            fetch_log_groups,
+            connectivity::get_connectivity_status,
+            heartbeat::get_connection_health,
             fetch_logs_paginated,
             cancel_fetch,
+            aggregate::aggregate_logs,
+            event_cache::search_cached_logs,
+            event_cache::get_cache_stats,
+            event_cache::clear_cache,
             sync_theme_menu,
             start_live_tail,
             stop_live_tail,
+            session::import_log_file,
+            session::get_session_events,
+            session::get_session_window,
+            session::list_session_sources,
+            session::remove_session_source,
+            session::close_session,
+            archive::archive_session,
+            archive::open_archive,
+            budget::get_volume_budget_status,
+            budget::get_volume_usage_history,
+            budget::get_volume_budgets,
+            budget::set_volume_budget,
+            session::get_session_memory_usage,
+            session::goto_event,
+            session::next_event_by_severity,
+            session::get_sort_order,
+            session::set_sort_order,
+            session::update_selection,
+            session::get_selection,
+            share::get_share_settings,
+            share::set_share_settings,
+            share::share_selection,
+            share::list_shares,
+            share::delete_share,
+            session::clear_session_results,
+            kinesis::list_kinesis_streams,
+            kinesis::start_kinesis_tail,
+            kinesis::stop_kinesis_tail,
+            firehose::list_firehose_subscriptions,
+            firehose::list_firehose_failed_batches,
+            firehose::load_firehose_failed_batch,
+            step_functions::get_execution_timelines,
+            step_functions::get_session_execution_timelines,
+            lambda::list_lambda_instances,
+            lambda::fetch_lambda_instance,
+            codebuild::list_codebuild_projects,
+            codebuild::list_codebuild_builds,
+            codebuild::fetch_codebuild_log,
+            canaries::list_canaries,
+            canaries::list_canary_runs,
+            canaries::canary_for_log_group,
+            canaries::fetch_canary_run,
+            container_insights::detect_container_log_group,
+            container_insights::parse_kubernetes_event,
+            container_insights::get_kubernetes_facets,
+            container_insights::filter_session_by_workload,
+            container_insights::build_workload_filter_pattern,
+            delivery::list_log_destinations,
+            delivery::list_log_deliveries,
+            catalog::suggest_related_groups,
+            catalog::list_log_groups_by_stack,
+            catalog::list_stack_log_groups,
+            workspace::save_workspace,
+            workspace::list_workspaces,
+            workspace::load_workspace,
+            workspace::delete_workspace,
+            workspace::workspace_summary,
+            columns::list_computed_columns,
+            columns::save_computed_column,
+            columns::delete_computed_column,
+            preview::get_message_preview_settings,
+            preview::set_message_preview_settings,
+            preview::get_full_message,
+            regions::list_regions,
+            regions::get_signing_region,
+            regions::set_signing_region,
+            alerts::get_alert_states,
+            alarms::list_alarms_for_group,
+            alarms::list_alarm_notifications,
+            tail::start_tail,
+            tail::stop_tail,
+            tail::snapshot_tail_buffer,
+            tail::set_tail_sink,
+            tail::list_resumable_tails,
+            tail::resume_tail,
+            tail::discard_resumable_tail,
+            journal::journal_session_state,
+            journal::get_recovered_session,
+            journal::discard_recovered_session,
+            settings::export_settings,
+            settings::import_settings,
+            settings::get_shared_config_dir,
+            settings::set_shared_config_dir,
+            settings::load_shared_collection,
+            scripting::save_script,
+            scripting::list_scripts,
+            scripting::delete_script,
+            scripting::run_script,
+            pipeline::list_pipelines,
+            pipeline::save_pipeline,
+            pipeline::delete_pipeline,
+            pipeline::preview_pipeline,
+            regex_test::test_regex,
+            filter_chips::list_filter_chips,
+            filter_chips::save_filter_chip,
+            filter_chips::delete_filter_chip,
+            filter_chips::compose_filter_chips,
+            filter_chips::filter_session_by_chips,
+            daily_summaries::get_daily_summary_settings,
+            daily_summaries::set_daily_summary_settings,
+            daily_summaries::run_daily_summaries,
+            daily_summaries::list_daily_summaries,
+            daily_summaries::get_daily_summary_trends,
+            group_history::list_history_groups,
+            group_history::set_group_history_tracked,
+            group_history::get_group_history,
+            group_history::clear_group_history,
+            ingest_gap::diagnose_ingest_gap,
+            resource_groups::list_resource_groups,
+            resource_groups::resolve_resource_group_log_groups,
+            resource_groups::create_workspace_from_resource_group,
+            handoff::export_session_handoff,
+            handoff::read_session_handoff,
+            handoff::import_session_handoff,
+            heatmap::get_volume_heatmap,
+            pipeline::explain_event,
+            schema::get_group_schema,
+            schema::clear_group_schema,
+            schema::aggregate_field,
+            redaction::get_redaction_settings,
+            redaction::save_redaction_settings,
+            redaction::request_reveal,
+            redaction::confirm_reveal,
+            redaction::hide_revealed,
+            redaction::is_session_revealed,
+            retention::check_retention_horizon,
+            retention::export_expiring_events,
+            mutations::set_retention_policy,
+            mutations::delete_log_group,
+            mutations::tag_log_group,
+            mutations::untag_log_group,
+            mutations::create_metric_filter,
+            mutations::delete_metric_filter,
+            mutations::check_bulk_permissions,
+            elevation::find_elevated_connection,
+            elevation::run_elevated,
+            audit::list_audit_entries,
+            audit::export_audit_log,
+            i18n::get_language,
+            i18n::set_language,
+            bench::bench_fetch_pipeline,
+            synthetic::inject_synthetic_events,
+            diff::get_last_query_run,
+            diff::clear_query_run,
+            diff::diff_events,
+            diff::compare_filters,
+            deploy::find_first_errors_after_deploy,
+            correlation::find_request_origin,
+            watch::watch_for,
+            watch::cancel_watch,
+            watch::list_watches,
+            zoom::zoom_range,
+            zoom::zoom_out,
+            panes::fetch_into_pane,
+            panes::set_pane_cursor,
+            panes::get_panes,
+            panes::swap_panes,
+            panes::close_pane,
+            links::open_by_arn,
+            links::parse_console_url,
+            data_protection::audit_data_protection,
+            iam::explain_access_denied,
+            operations::list_active_operations,
+            operations::cancel_operation,
+            connections::list_connections,
+            connections::save_connection,
+            connections::delete_connection,
+            connections::use_connection,
+            connections::get_active_connection,
+            preflight::get_preflight_settings,
+            preflight::set_preflight_settings,
+            preflight::run_credential_preflight,
+            prefetch::get_prefetch_settings,
+            prefetch::set_prefetch_settings,
+            prefetch::prefetch_catalogs,
+            cli::generate_cli_command,
+            catalog::describe_log_group_origin,
+            dlq::find_dlq,
+            dlq::set_dlq_mapping,
+            dlq::peek_dlq_messages,
+            delivery::list_eventbridge_feeds,
+            delivery::test_log_delivery,
+            insights::run_insights_query,
+            export::export_syslog,
+            export::export_html,
+            export::export_ndjson,
+            export::verify_export_evidence,
+            export::export_logs,
+            export::get_export_path_settings,
+            export::set_export_path_settings,
+            export::preview_export_path,
+            export::format_events_markdown,
+            export::export_views,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                journal::mark_clean_exit(app);
+            }
+        });
 }
@@ -0,0 +1,523 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{get_aws_config_path, AppState};
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+/// A parsed CloudWatch Logs log group (or log stream) ARN
+#[derive(Debug, Clone, Serialize)]
+pub struct LogGroupArn {
+    pub partition: String,
+    pub region: String,
+    pub account_id: String,
+    pub log_group_name: String,
+    pub log_stream_name: Option<String>,
+}
+
+/// Parse `arn:aws:logs:<region>:<account>:log-group:<name>[:*]` and
+/// `...:log-group:<name>:log-stream:<stream>`. Log group and stream names cannot
+/// contain ':', so splitting on it is safe.
+pub fn parse_log_group_arn(arn: &str) -> Result<LogGroupArn, String> {
+    let invalid = || format!("Not a CloudWatch Logs log group ARN: {}", arn);
+    let parts: Vec<&str> = arn.trim().split(':').collect();
+    if parts.len() < 7 || parts[0] != "arn" || parts[2] != "logs" || parts[5] != "log-group" {
+        return Err(invalid());
+    }
+    let (partition, region, account_id) = (parts[1], parts[3], parts[4]);
+    if region.is_empty()
+        || account_id.len() != 12
+        || !account_id.chars().all(|c| c.is_ascii_digit())
+        || parts[6].is_empty()
+    {
+        return Err(invalid());
+    }
+    let log_stream_name = match &parts[7..] {
+        [] | ["*"] => None,
+        ["log-stream", stream] if !stream.is_empty() => Some(stream.to_string()),
+        _ => return Err(invalid()),
+    };
+    Ok(LogGroupArn {
+        partition: partition.to_string(),
+        region: region.to_string(),
+        account_id: account_id.to_string(),
+        log_group_name: parts[6].to_string(),
+        log_stream_name,
+    })
+}
+
+/// (profile, account id) for every profile in ~/.aws/config that names its account,
+/// via `sso_account_id` or the account of its `role_arn`
+pub(crate) fn profile_accounts() -> Vec<(String, String)> {
+    let Some(contents) = get_aws_config_path().and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    let mut accounts = Vec::new();
+    let mut profile: Option<String> = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            let section = &line[1..line.len() - 1];
+            profile = if section == "default" {
+                Some("default".to_string())
+            } else {
+                section
+                    .strip_prefix("profile ")
+                    .map(|n| n.trim().to_string())
+            };
+            continue;
+        }
+        let (Some(name), Some((key, value))) = (&profile, line.split_once('=')) else {
+            continue;
+        };
+        let value = value.trim();
+        let account = match key.trim() {
+            "sso_account_id" => Some(value.to_string()),
+            // arn:aws:iam::<account>:role/<name>
+            "role_arn" => value.split(':').nth(4).map(str::to_string),
+            _ => None,
+        };
+        if let Some(account) = account.filter(|a| !a.is_empty()) {
+            if !accounts.iter().any(|(p, _)| p == name) {
+                accounts.push((name.clone(), account));
+            }
+        }
+    }
+    accounts
+}
+
+/// Account of the current connection, if connected
+pub(crate) async fn current_account(state: &AppState) -> Option<String> {
+    let config = state.sdk_config().await.ok()?;
+    aws_sdk_sts::Client::new(&config)
+        .get_caller_identity()
+        .send()
+        .await
+        .map_err(|e| log::warn!("GetCallerIdentity failed: {}", e))
+        .ok()?
+        .account
+}
+
+/// Where a link resolved to; also the payload of open-log-group
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenTarget {
+    pub log_group_name: String,
+    pub log_stream_name: Option<String>,
+    pub profile: Option<String>,
+    pub region: String,
+    pub account_id: Option<String>,
+    /// The connection was switched to another profile or region
+    pub switched: bool,
+    /// Filter pattern from the link (console URLs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+}
+
+/// Connect to `region` in `account_id`, reusing the current profile when it already
+/// points at that account. Returns the profile used and whether the connection changed.
+pub(crate) async fn connect_for(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    region: &str,
+    account_id: Option<&str>,
+) -> Result<(Option<String>, bool), String> {
+    let current_profile = state.current_profile.lock().await.clone();
+    let current_region = state
+        .sdk_config()
+        .await
+        .ok()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+
+    let profile = match account_id {
+        Some(account) if current_account(state).await.as_deref() != Some(account) => {
+            let matching = profile_accounts()
+                .into_iter()
+                .find(|(_, a)| a == account)
+                .map(|(p, _)| p);
+            match matching {
+                Some(profile) => Some(profile),
+                None => {
+                    return Err(format!(
+                        "No AWS profile in ~/.aws/config is set up for account {}",
+                        account
+                    ))
+                }
+            }
+        }
+        _ => current_profile.clone(),
+    };
+
+    let switched = profile != current_profile || current_region.as_deref() != Some(region);
+    if switched {
+        log::info!(
+            "Switching connection to profile {:?} in {} to open a link",
+            profile,
+            region
+        );
+        crate::init_aws_client(
+            app.clone(),
+            state.clone(),
+            profile.clone(),
+            Some(region.to_string()),
+        )
+        .await?;
+    }
+    Ok((profile, switched))
+}
+
+/// Open a log group from a full ARN (console, CloudFormation outputs, IaC code):
+/// switches to a profile for the ARN's account and region if needed, then emits
+/// `open-log-group` for the frontend to select it
+#[tauri::command]
+pub async fn open_by_arn(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    arn: String,
+) -> Result<OpenTarget, String> {
+    let parsed = parse_log_group_arn(&arn)?;
+    let (profile, switched) =
+        connect_for(&app, &state, &parsed.region, Some(&parsed.account_id)).await?;
+    let target = OpenTarget {
+        log_group_name: parsed.log_group_name,
+        log_stream_name: parsed.log_stream_name,
+        profile,
+        region: parsed.region,
+        account_id: Some(parsed.account_id),
+        switched,
+        filter_pattern: None,
+        start_time: None,
+        end_time: None,
+    };
+    app.emit_tagged("open-log-group", &target).ok();
+    Ok(target)
+}
+
+/// What a CloudWatch console URL points at
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConsoleLink {
+    /// "log-group", "log-events" or "insights"
+    pub kind: String,
+    pub region: Option<String>,
+    pub log_group_names: Vec<String>,
+    pub log_stream_name: Option<String>,
+    pub filter_pattern: Option<String>,
+    /// Logs Insights query text
+    pub query: Option<String>,
+    /// Absolute epoch milliseconds (relative console ranges are resolved against now)
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+}
+
+/// Decode %XX escapes; invalid escapes are kept as-is
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// A console time value: negative = relative milliseconds, positive = epoch
+/// milliseconds, or an ISO 8601 timestamp
+fn console_time(value: &str, now: i64) -> Option<i64> {
+    match value.parse::<i64>() {
+        Ok(ms) if ms <= 0 => Some(now + ms),
+        Ok(ms) => Some(ms),
+        Err(_) => chrono::DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| dt.timestamp_millis()),
+    }
+}
+
+/// Minimal JSURL reader for the Logs Insights `queryDetail` parameter:
+/// `~(key~value~...)` objects, `~(~a~b)` arrays, `~'string`, numbers and literals
+struct Jsurl<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Jsurl<'_> {
+    fn read_raw(&mut self) -> String {
+        let mut raw = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '~' || c == ')' {
+                break;
+            }
+            raw.push(c);
+            self.chars.next();
+        }
+        // '!' stands for a quote; '*XX' and '**XXXX' are hex escapes
+        let mut out = String::new();
+        let mut chars = raw.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '!' => out.push('\''),
+                '*' => {
+                    let width = if chars.peek() == Some(&'*') {
+                        chars.next();
+                        4
+                    } else {
+                        2
+                    };
+                    let hex: String = chars.by_ref().take(width).collect();
+                    if let Some(decoded) =
+                        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                    {
+                        out.push(decoded);
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn value(&mut self) -> Option<serde_json::Value> {
+        if self.chars.next()? != '~' {
+            return None;
+        }
+        match *self.chars.peek()? {
+            '(' => {
+                self.chars.next();
+                if self.chars.peek() == Some(&')') {
+                    self.chars.next();
+                    return Some(serde_json::json!({}));
+                }
+                if self.chars.peek() == Some(&'~') {
+                    let mut items = Vec::new();
+                    while self.chars.peek() == Some(&'~') {
+                        items.push(self.value()?);
+                    }
+                    self.chars.next();
+                    return Some(serde_json::Value::Array(items));
+                }
+                let mut map = serde_json::Map::new();
+                loop {
+                    let key = self.read_raw();
+                    let value = self.value()?;
+                    map.insert(key, value);
+                    match self.chars.next()? {
+                        ')' => return Some(serde_json::Value::Object(map)),
+                        '~' => continue,
+                        _ => return None,
+                    }
+                }
+            }
+            '\'' => {
+                self.chars.next();
+                Some(serde_json::Value::String(self.read_raw()))
+            }
+            _ => {
+                let raw = self.read_raw();
+                Some(match raw.as_str() {
+                    "true" => serde_json::Value::Bool(true),
+                    "false" => serde_json::Value::Bool(false),
+                    "null" => serde_json::Value::Null,
+                    _ => raw
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(serde_json::Value::Number)
+                        .unwrap_or(serde_json::Value::String(raw)),
+                })
+            }
+        }
+    }
+}
+
+fn parse_insights(detail: &str, now: i64, link: &mut ConsoleLink) -> Result<(), String> {
+    let json = Jsurl {
+        chars: detail.chars().peekable(),
+    }
+    .value()
+    .ok_or("Could not decode the Logs Insights query in the URL")?;
+    link.kind = "insights".to_string();
+    link.query = json["editorString"].as_str().map(str::to_string);
+    link.log_group_names = json["source"]
+        .as_array()
+        .map(|sources| {
+            sources
+                .iter()
+                .filter_map(|s| s.as_str())
+                // Sources may be full ARNs
+                .map(|s| {
+                    parse_log_group_arn(s)
+                        .map(|arn| arn.log_group_name)
+                        .unwrap_or_else(|_| s.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if json["timeType"].as_str() == Some("ABSOLUTE") {
+        link.start_time = json["start"].as_str().and_then(|v| console_time(v, now));
+        link.end_time = json["end"].as_str().and_then(|v| console_time(v, now));
+    } else {
+        let unit_ms = match json["unit"].as_str().unwrap_or("seconds") {
+            "minutes" => 60_000,
+            "hours" => 3_600_000,
+            "days" => 86_400_000,
+            _ => 1_000,
+        };
+        let relative =
+            |v: &serde_json::Value| v.as_f64().map(|n| now + (n * unit_ms as f64) as i64);
+        link.start_time = relative(&json["start"]);
+        link.end_time = relative(&json["end"]);
+    }
+    Ok(())
+}
+
+/// Decode a CloudWatch console URL: logsV2 log group / log events views (with
+/// stream, filter and time range), Logs Insights queries, and legacy
+/// `#logEventViewer:` links
+pub fn decode_console_url(url: &str) -> Result<ConsoleLink, String> {
+    let url = url.trim();
+    let now = chrono::Utc::now().timestamp_millis();
+    let (base, fragment) = url
+        .split_once('#')
+        .ok_or("Not a CloudWatch console link (no #logsV2 or #logEventViewer view)")?;
+    let mut link = ConsoleLink {
+        // ?region=... in the page query, else the <region>.console.aws.amazon.com host
+        region: base
+            .split_once('?')
+            .and_then(|(_, query)| {
+                query
+                    .split('&')
+                    .find_map(|kv| kv.strip_prefix("region="))
+                    .map(str::to_string)
+            })
+            .or_else(|| {
+                let host = base.split("://").nth(1)?.split('/').next()?;
+                host.strip_suffix(".console.aws.amazon.com")
+                    .filter(|r| r.contains('-'))
+                    .map(str::to_string)
+            }),
+        ..ConsoleLink::default()
+    };
+
+    // The console escapes its view state as $XX on top of percent-encoding
+    let view = percent_decode(&fragment.replace('$', "%"));
+
+    if let Some(legacy) = view
+        .strip_prefix("logEventViewer:")
+        .or_else(|| view.strip_prefix("logStream:"))
+    {
+        link.kind = "log-events".to_string();
+        for pair in legacy.split(';') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let value = percent_decode(value);
+            match key {
+                "group" => link.log_group_names = vec![value],
+                "stream" => link.log_stream_name = Some(value),
+                "filter" => link.filter_pattern = Some(value),
+                "start" => link.start_time = console_time(&value, now),
+                "end" => link.end_time = console_time(&value, now),
+                _ => {}
+            }
+        }
+        return Ok(link);
+    }
+
+    let view = view.strip_prefix("logsV2:").ok_or(
+        "Unsupported CloudWatch console view; open a log group, log stream or Logs Insights page",
+    )?;
+    if let Some(detail) = view
+        .strip_prefix("logs-insights?queryDetail=")
+        .or_else(|| view.strip_prefix("logs-insights"))
+    {
+        parse_insights(detail.trim_start_matches("?queryDetail="), now, &mut link)?;
+        return Ok(link);
+    }
+
+    let (path, query) = view.split_once('?').unwrap_or((view, ""));
+    let segments: Vec<&str> = path.split('/').collect();
+    match segments.as_slice() {
+        ["log-groups", "log-group", group, rest @ ..] => {
+            link.log_group_names = vec![percent_decode(group)];
+            link.kind = "log-group".to_string();
+            if let ["log-events", stream @ ..] = rest {
+                link.kind = "log-events".to_string();
+                let stream = stream.join("/");
+                if !stream.is_empty() {
+                    link.log_stream_name = Some(percent_decode(&stream));
+                }
+            }
+        }
+        _ => return Err("The link does not point at a log group".to_string()),
+    }
+    for pair in query.split('&') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let value = percent_decode(value);
+        match key {
+            "filterPattern" if !value.is_empty() => link.filter_pattern = Some(value),
+            "start" => link.start_time = console_time(&value, now),
+            "end" => link.end_time = console_time(&value, now),
+            _ => {}
+        }
+    }
+    Ok(link)
+}
+
+/// Decode a CloudWatch console URL (as shared in chat) and, unless `open` is false,
+/// switch to its region and emit `open-log-group` with the equivalent Loggy view
+#[tauri::command]
+pub async fn parse_console_url(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    url: String,
+    open: Option<bool>,
+) -> Result<ConsoleLink, String> {
+    let link = decode_console_url(&url)?;
+    if !open.unwrap_or(true) {
+        return Ok(link);
+    }
+    let Some(log_group_name) = link.log_group_names.first().cloned() else {
+        return Err("The link does not name a log group".to_string());
+    };
+    let (profile, switched, region) = match &link.region {
+        Some(region) => {
+            let (profile, switched) = connect_for(&app, &state, region, None).await?;
+            (profile, switched, region.clone())
+        }
+        None => (
+            state.current_profile.lock().await.clone(),
+            false,
+            state
+                .sdk_config()
+                .await?
+                .region()
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+        ),
+    };
+    let target = OpenTarget {
+        log_group_name,
+        log_stream_name: link.log_stream_name.clone(),
+        profile,
+        region,
+        account_id: None,
+        switched,
+        filter_pattern: link.filter_pattern.clone(),
+        start_time: link.start_time,
+        end_time: link.end_time,
+    };
+    app.emit_tagged("open-log-group", &target).ok();
+    Ok(link)
+}
@@ -0,0 +1,500 @@
+use crate::{audit, connections, humanize_aws_error, iam, AppState};
+use aws_sdk_cloudwatchlogs::types::MetricTransformation;
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// Retention periods accepted by PutRetentionPolicy
+const VALID_RETENTION_DAYS: [i32; 22] = [
+    1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1096, 1827, 2192, 2557, 2922,
+    3288, 3653,
+];
+
+/// A metric filter to create on a log group
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricFilterSpec {
+    pub log_group_name: String,
+    pub filter_name: String,
+    pub filter_pattern: String,
+    pub metric_namespace: String,
+    pub metric_name: String,
+    /// Value published per match ("1", or "$.field" to publish a field)
+    #[serde(default)]
+    pub metric_value: Option<String>,
+    #[serde(default)]
+    pub default_value: Option<f64>,
+}
+
+fn arn_without_wildcard(arn: &str) -> &str {
+    arn.strip_suffix(":*").unwrap_or(arn)
+}
+
+/// Set or remove (None) a group's retention with `client`
+pub(crate) async fn put_retention(
+    client: &CloudWatchClient,
+    log_group_name: &str,
+    retention_days: Option<i32>,
+) -> Result<(), String> {
+    match retention_days {
+        Some(days) if !VALID_RETENTION_DAYS.contains(&days) => Err(format!(
+            "{} days is not a valid CloudWatch retention period",
+            days
+        )),
+        Some(days) => client
+            .put_retention_policy()
+            .log_group_name(log_group_name)
+            .retention_in_days(days)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| humanize_aws_error(&format!("{}", e))),
+        None => client
+            .delete_retention_policy()
+            .log_group_name(log_group_name)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| humanize_aws_error(&format!("{}", e))),
+    }
+}
+
+pub(crate) async fn delete_group(
+    client: &CloudWatchClient,
+    log_group_name: &str,
+) -> Result<(), String> {
+    client
+        .delete_log_group()
+        .log_group_name(log_group_name)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))
+}
+
+pub(crate) async fn tag(
+    client: &CloudWatchClient,
+    log_group_arn: &str,
+    tags: HashMap<String, String>,
+) -> Result<(), String> {
+    client
+        .tag_resource()
+        .resource_arn(arn_without_wildcard(log_group_arn))
+        .set_tags(Some(tags))
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))
+}
+
+pub(crate) async fn untag(
+    client: &CloudWatchClient,
+    log_group_arn: &str,
+    tag_keys: Vec<String>,
+) -> Result<(), String> {
+    client
+        .untag_resource()
+        .resource_arn(arn_without_wildcard(log_group_arn))
+        .set_tag_keys(Some(tag_keys))
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))
+}
+
+/// Set a log group's retention in days, or remove it (never expire) with None
+#[tauri::command]
+pub async fn set_retention_policy(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    retention_days: Option<i32>,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let result = put_retention(&client, &log_group_name, retention_days).await;
+    audit::record(
+        &app,
+        &state,
+        "set_retention_policy",
+        serde_json::json!({ "log_group_name": log_group_name, "retention_days": retention_days }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// Delete a log group and all its data. `confirm_name` must repeat the group name.
+#[tauri::command]
+pub async fn delete_log_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    confirm_name: String,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    if confirm_name != log_group_name {
+        return Err("Type the log group name to confirm deletion".to_string());
+    }
+    let client = state.logs_client().await?;
+    let result = delete_group(&client, &log_group_name).await;
+    audit::record(
+        &app,
+        &state,
+        "delete_log_group",
+        serde_json::json!({ "log_group_name": log_group_name }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// Add or update tags on a log group
+#[tauri::command]
+pub async fn tag_log_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_arn: String,
+    tags: HashMap<String, String>,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let result = tag(&client, &log_group_arn, tags.clone()).await;
+    audit::record(
+        &app,
+        &state,
+        "tag_log_group",
+        serde_json::json!({ "log_group_arn": log_group_arn, "tags": tags }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// Remove tags from a log group
+#[tauri::command]
+pub async fn untag_log_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_arn: String,
+    tag_keys: Vec<String>,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let result = untag(&client, &log_group_arn, tag_keys.clone()).await;
+    audit::record(
+        &app,
+        &state,
+        "untag_log_group",
+        serde_json::json!({ "log_group_arn": log_group_arn, "tag_keys": tag_keys }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// Create (or replace) a metric filter on a log group
+#[tauri::command]
+pub async fn create_metric_filter(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    spec: MetricFilterSpec,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let transformation = MetricTransformation::builder()
+        .metric_namespace(&spec.metric_namespace)
+        .metric_name(&spec.metric_name)
+        .metric_value(spec.metric_value.as_deref().unwrap_or("1"))
+        .set_default_value(spec.default_value)
+        .build()
+        .map_err(|e| format!("Invalid metric filter: {}", e));
+    let result = match transformation {
+        Ok(transformation) => client
+            .put_metric_filter()
+            .log_group_name(&spec.log_group_name)
+            .filter_name(&spec.filter_name)
+            .filter_pattern(&spec.filter_pattern)
+            .metric_transformations(transformation)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| humanize_aws_error(&format!("{}", e))),
+        Err(e) => Err(e),
+    };
+    audit::record(
+        &app,
+        &state,
+        "create_metric_filter",
+        serde_json::json!({
+            "log_group_name": spec.log_group_name,
+            "filter_name": spec.filter_name,
+            "filter_pattern": spec.filter_pattern,
+            "metric_namespace": spec.metric_namespace,
+            "metric_name": spec.metric_name,
+            "metric_value": spec.metric_value,
+            "default_value": spec.default_value,
+        }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// Delete a metric filter from a log group
+#[tauri::command]
+pub async fn delete_metric_filter(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    filter_name: String,
+    confirm_environment: Option<String>,
+) -> Result<(), String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let result = client
+        .delete_metric_filter()
+        .log_group_name(&log_group_name)
+        .filter_name(&filter_name)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| humanize_aws_error(&format!("{}", e)));
+    audit::record(
+        &app,
+        &state,
+        "delete_metric_filter",
+        serde_json::json!({ "log_group_name": log_group_name, "filter_name": filter_name }),
+        &result,
+    )
+    .await;
+    result
+}
+
+/// A bulk action check_bulk_permissions can dry-run
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAction {
+    SetRetention,
+    Tag,
+    Untag,
+    Delete,
+}
+
+/// Outcome of the permission check for one log group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    Allowed,
+    Denied,
+    /// The log group does not exist (or isn't visible to these credentials)
+    Missing,
+    /// No side-effect-free probe exists for this action on this group
+    Unverified,
+    /// The probe failed for a reason other than permissions
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionCheck {
+    pub log_group_name: String,
+    /// IAM action the bulk operation needs, e.g. "logs:PutRetentionPolicy"
+    pub iam_action: String,
+    pub status: PermissionStatus,
+    pub detail: Option<String>,
+}
+
+/// Result of check_bulk_permissions
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkPermissionReport {
+    pub checks: Vec<PermissionCheck>,
+    /// Groups the bulk operation would fail on (denied, missing or errored)
+    pub would_fail: usize,
+    pub unverified: usize,
+}
+
+/// Marker tag key removed by the untag probe; absent keys make UntagResource a no-op
+const PROBE_TAG_KEY: &str = "loggy-permission-check";
+
+/// Probe result from an AWS call: denied vs other failures
+fn probe_status<E: std::fmt::Debug>(result: Result<(), E>) -> (PermissionStatus, Option<String>) {
+    match result {
+        Ok(()) => (PermissionStatus::Allowed, None),
+        Err(e) => {
+            let message = humanize_aws_error(&format!("{:?}", e));
+            if iam::is_access_denied_message(&message) {
+                (PermissionStatus::Denied, Some(message))
+            } else {
+                (PermissionStatus::Error, Some(message))
+            }
+        }
+    }
+}
+
+/// The IAM action a bulk action needs
+fn iam_action(action: BulkAction, retention_days: Option<i32>) -> &'static str {
+    match action {
+        BulkAction::SetRetention if retention_days.is_none() => "logs:DeleteRetentionPolicy",
+        BulkAction::SetRetention => "logs:PutRetentionPolicy",
+        BulkAction::Tag => "logs:TagResource",
+        BulkAction::Untag => "logs:UntagResource",
+        BulkAction::Delete => "logs:DeleteLogGroup",
+    }
+}
+
+/// Check whether `client` may perform `action` on one log group with a no-op call
+/// of the same IAM action where one exists: re-applying the group's current
+/// retention, re-tagging with its current tags, or removing a tag key it doesn't
+/// have. Deletion can't be tried without deleting, so only existence is checked.
+pub(crate) async fn probe(
+    client: &CloudWatchClient,
+    action: BulkAction,
+    log_group_name: &str,
+    retention_days: Option<i32>,
+) -> PermissionCheck {
+    let check = |(status, detail): (PermissionStatus, Option<String>)| PermissionCheck {
+        log_group_name: log_group_name.to_string(),
+        iam_action: iam_action(action, retention_days).to_string(),
+        status,
+        detail,
+    };
+    let group = match client
+        .describe_log_groups()
+        .log_group_name_prefix(log_group_name)
+        .send()
+        .await
+    {
+        Ok(response) => response
+            .log_groups
+            .unwrap_or_default()
+            .into_iter()
+            .find(|g| g.log_group_name() == Some(log_group_name)),
+        Err(e) => return check(probe_status(Err(e))),
+    };
+    let Some(group) = group else {
+        return check((
+            PermissionStatus::Missing,
+            Some(format!("Log group {} not found", log_group_name)),
+        ));
+    };
+    let arn = group.arn().map(arn_without_wildcard).unwrap_or_default();
+
+    check(match action {
+        BulkAction::SetRetention => match (retention_days, group.retention_in_days()) {
+            (Some(_), Some(current)) => probe_status(
+                client
+                    .put_retention_policy()
+                    .log_group_name(log_group_name)
+                    .retention_in_days(current)
+                    .send()
+                    .await
+                    .map(|_| ()),
+            ),
+            (None, None) => probe_status(
+                client
+                    .delete_retention_policy()
+                    .log_group_name(log_group_name)
+                    .send()
+                    .await
+                    .map(|_| ()),
+            ),
+            _ => (
+                PermissionStatus::Unverified,
+                Some("No unchanged retention to re-apply".to_string()),
+            ),
+        },
+        BulkAction::Tag => {
+            match client
+                .list_tags_for_resource()
+                .resource_arn(arn)
+                .send()
+                .await
+            {
+                Ok(response) => match response.tags.filter(|t| !t.is_empty()) {
+                    Some(tags) => probe_status(
+                        client
+                            .tag_resource()
+                            .resource_arn(arn)
+                            .set_tags(Some(tags))
+                            .send()
+                            .await
+                            .map(|_| ()),
+                    ),
+                    None => (
+                        PermissionStatus::Unverified,
+                        Some("No existing tags to re-apply".to_string()),
+                    ),
+                },
+                Err(e) => probe_status(Err(e)),
+            }
+        }
+        BulkAction::Untag => probe_status(
+            client
+                .untag_resource()
+                .resource_arn(arn)
+                .tag_keys(PROBE_TAG_KEY)
+                .send()
+                .await
+                .map(|_| ()),
+        ),
+        BulkAction::Delete => (
+            PermissionStatus::Unverified,
+            Some("Deletion can't be tried without deleting".to_string()),
+        ),
+    })
+}
+
+/// Dry-run a bulk retention/tag/untag/delete over `log_group_names` with `probe`
+/// and report the groups it would fail on, before anything is changed
+#[tauri::command]
+pub async fn check_bulk_permissions(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    action: BulkAction,
+    log_group_names: Vec<String>,
+    retention_days: Option<i32>,
+    confirm_environment: Option<String>,
+) -> Result<BulkPermissionReport, String> {
+    connections::ensure_writable(&state, confirm_environment.as_deref()).await?;
+    let client = state.logs_client().await?;
+    let mut checks = Vec::with_capacity(log_group_names.len());
+    for log_group_name in &log_group_names {
+        checks.push(probe(&client, action, log_group_name, retention_days).await);
+    }
+
+    let would_fail = checks
+        .iter()
+        .filter(|c| {
+            matches!(
+                c.status,
+                PermissionStatus::Denied | PermissionStatus::Missing | PermissionStatus::Error
+            )
+        })
+        .count();
+    let unverified = checks
+        .iter()
+        .filter(|c| c.status == PermissionStatus::Unverified)
+        .count();
+    let result: Result<(), String> = Ok(());
+    audit::record(
+        &app,
+        &state,
+        "check_bulk_permissions",
+        serde_json::json!({
+            "action": iam_action(action, retention_days),
+            "log_group_names": log_group_names,
+        }),
+        &result,
+    )
+    .await;
+    Ok(BulkPermissionReport {
+        checks,
+        would_fail,
+        unverified,
+    })
+}
@@ -0,0 +1,256 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{kinesis, tail, watch, AppState};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+
+/// In-flight operations by registration order
+static REGISTRY: Mutex<BTreeMap<u64, Entry>> = Mutex::new(BTreeMap::new());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// How long cancel_all waits for cancelled fetches to finish their current page
+const DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// How an operation is stopped from cancel_operation
+#[derive(Clone)]
+pub enum CancelBy {
+    /// Paginated fetch loops poll this flag between pages
+    Flag(Arc<AtomicBool>),
+    /// Stopped through the owning subsystem so its cleanup runs
+    Tail(String),
+    KinesisTail(String),
+    Watch(String),
+    LiveTail,
+}
+
+#[derive(Default)]
+struct Counters {
+    pages: AtomicUsize,
+    events: AtomicUsize,
+    retries: AtomicUsize,
+    throttled: AtomicUsize,
+}
+
+struct Entry {
+    kind: &'static str,
+    target: String,
+    started_at: i64,
+    counters: Arc<Counters>,
+    cancel: CancelBy,
+}
+
+/// An in-flight operation, listed by list_active_operations until dropped
+pub struct Operation {
+    id: u64,
+    counters: Arc<Counters>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Operation {
+    /// Register an operation, e.g. `("fetch", "/aws/lambda/api", CancelBy::Flag(..))`
+    pub fn begin(kind: &'static str, target: impl Into<String>, cancel: CancelBy) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let counters = Arc::new(Counters::default());
+        let cancelled = match &cancel {
+            CancelBy::Flag(flag) => flag.clone(),
+            _ => Arc::new(AtomicBool::new(false)),
+        };
+        REGISTRY.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            id,
+            Entry {
+                kind,
+                target: target.into(),
+                started_at: chrono::Utc::now().timestamp_millis(),
+                counters: counters.clone(),
+                cancel,
+            },
+        );
+        Self {
+            id,
+            counters,
+            cancelled,
+        }
+    }
+
+    /// Register a paginated fetch with its own cancellation flag
+    pub fn fetch(target: impl Into<String>) -> Self {
+        Self::begin(
+            "fetch",
+            target,
+            CancelBy::Flag(Arc::new(AtomicBool::new(false))),
+        )
+    }
+
+    /// Count a page (or tail batch) of `events` events
+    pub fn page(&self, events: usize) {
+        self.counters.pages.fetch_add(1, Ordering::Relaxed);
+        self.counters.events.fetch_add(events, Ordering::Relaxed);
+    }
+
+    /// Count a retried request; `throttled` when AWS rate-limited it
+    pub fn retry(&self, throttled: bool) {
+        self.counters.retries.fetch_add(1, Ordering::Relaxed);
+        if throttled {
+            self.counters.throttled.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Handle for cancel_operation
+    pub fn operation_id(&self) -> String {
+        operation_id(self.id)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        REGISTRY
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+/// An in-flight fetch, tail, watch or query
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationInfo {
+    /// Handle for cancel_operation
+    pub operation_id: String,
+    /// "fetch", "aggregate", "tail", "kinesis-tail", "watch", "live-tail" or "insights"
+    pub kind: String,
+    /// Log group(s) or stream the operation reads
+    pub target: String,
+    pub started_at: i64,
+    pub elapsed_ms: i64,
+    /// Pages fetched (tail batches for tails)
+    pub pages: usize,
+    pub events: usize,
+    /// Requests retried after transient failures
+    pub retries: usize,
+    /// Of those, retries caused by AWS throttling
+    pub throttled: usize,
+}
+
+fn operation_id(id: u64) -> String {
+    format!("op-{}", id)
+}
+
+/// All in-flight operations, oldest first, so whatever is making the app sluggish
+/// (or getting throttled) can be seen and cancelled
+#[tauri::command]
+pub fn list_active_operations() -> Vec<OperationInfo> {
+    let now = chrono::Utc::now().timestamp_millis();
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(id, entry)| OperationInfo {
+            operation_id: operation_id(*id),
+            kind: entry.kind.to_string(),
+            target: entry.target.clone(),
+            started_at: entry.started_at,
+            elapsed_ms: now - entry.started_at,
+            pages: entry.counters.pages.load(Ordering::Relaxed),
+            events: entry.counters.events.load(Ordering::Relaxed),
+            retries: entry.counters.retries.load(Ordering::Relaxed),
+            throttled: entry.counters.throttled.load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+/// Cancel an operation listed by list_active_operations. Fetches stop after the
+/// current page and return what they collected; tails and watches stop immediately.
+#[tauri::command]
+pub async fn cancel_operation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    operation_id: String,
+) -> Result<(), String> {
+    let cancel = {
+        let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry
+            .iter()
+            .find(|(id, _)| self::operation_id(**id) == operation_id)
+            .map(|(_, entry)| entry.cancel.clone())
+    };
+    let Some(cancel) = cancel else {
+        // Finished in the meantime
+        return Ok(());
+    };
+    log::info!("Cancelling operation {}", operation_id);
+    stop(app, state, cancel).await
+}
+
+/// Stop an operation through whatever owns it
+async fn stop(app: AppHandle, state: State<'_, AppState>, cancel: CancelBy) -> Result<(), String> {
+    match cancel {
+        CancelBy::Flag(flag) => flag.store(true, Ordering::SeqCst),
+        CancelBy::Tail(tail_id) => tail::stop_tail(app, state, tail_id).await?,
+        CancelBy::KinesisTail(tail_id) => kinesis::stop_kinesis_tail(state, tail_id).await?,
+        CancelBy::Watch(watch_id) => watch::cancel_watch(state, watch_id).await?,
+        CancelBy::LiveTail => crate::stop_live_tail(state).await?,
+    }
+    Ok(())
+}
+
+/// Payload of operation-cancelled
+#[derive(Debug, Clone, Serialize)]
+struct OperationCancelled {
+    operation_id: String,
+    kind: String,
+    target: String,
+    reason: String,
+}
+
+/// Cancel every in-flight operation because the connection it runs on is going
+/// away, emitting operation-cancelled with `reason` for each. Then wait up to
+/// DRAIN_TIMEOUT for fetches and queries to return, since they hold the client.
+pub async fn cancel_all(app: &AppHandle, state: State<'_, AppState>, reason: &str) {
+    let entries: Vec<(u64, &'static str, String, CancelBy)> = REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(id, e)| (*id, e.kind, e.target.clone(), e.cancel.clone()))
+        .collect();
+    if entries.is_empty() {
+        return;
+    }
+    log::info!("Cancelling {} operations: {}", entries.len(), reason);
+    let mut draining = Vec::new();
+    for (id, kind, target, how) in entries {
+        if matches!(how, CancelBy::Flag(_)) {
+            draining.push(id);
+        }
+        if let Err(e) = stop(app.clone(), state.clone(), how).await {
+            log::warn!("Failed to cancel {} {}: {}", kind, target, e);
+        }
+        let _ = app.emit_tagged(
+            "operation-cancelled",
+            OperationCancelled {
+                operation_id: operation_id(id),
+                kind: kind.to_string(),
+                target,
+                reason: reason.to_string(),
+            },
+        );
+    }
+
+    let deadline = std::time::Instant::now() + DRAIN_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        let pending = {
+            let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+            draining.iter().any(|id| registry.contains_key(id))
+        };
+        if !pending {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    log::warn!("Operations still running after cancellation: {}", reason);
+}
@@ -0,0 +1,107 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// FilterLogEvents page size bounds (10,000 is the API maximum and default)
+const MAX_PAGE_LIMIT: i32 = 10_000;
+const MIN_PAGE_LIMIT: i32 = 500;
+
+/// Pages slower than this get smaller, so progress and cancellation stay responsive
+const SLOW_PAGE: Duration = Duration::from_secs(5);
+
+/// Full pages faster than this may grow again
+const FAST_PAGE: Duration = Duration::from_millis(1_500);
+
+/// Inter-page delay after the first throttle; doubles on each further throttle
+const MIN_THROTTLE_DELAY_MS: u64 = 200;
+const MAX_THROTTLE_DELAY_MS: u64 = 5_000;
+
+/// Unthrottled pages after which the delay is halved
+const CALM_PAGES_BEFORE_SPEEDUP: u32 = 5;
+
+/// Delays below this are dropped entirely
+const MIN_DELAY_MS: u64 = 50;
+
+/// Page size and delay the last fetch ended with. FilterLogEvents limits are per
+/// account and region, so the next fetch starts from what was learned.
+static LEARNED: Mutex<(i32, u64)> = Mutex::new((MAX_PAGE_LIMIT, 0));
+
+/// Page size and inter-page delay of one paginated fetch, tuned from observed page
+/// latency and throttling: slow pages shrink, fast full pages grow, throttling
+/// adds a delay that decays once requests go through again
+#[derive(Debug)]
+pub struct PageTuner {
+    limit: i32,
+    delay_ms: u64,
+    calm_pages: u32,
+}
+
+impl Default for PageTuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PageTuner {
+    pub fn new() -> Self {
+        let (limit, delay_ms) = *LEARNED.lock().unwrap_or_else(|e| e.into_inner());
+        Self {
+            limit,
+            delay_ms,
+            calm_pages: 0,
+        }
+    }
+
+    /// Events to request per page
+    pub fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    /// Wait the current inter-page delay
+    pub async fn pace(&self) {
+        if self.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+        }
+    }
+
+    /// AWS throttled a request
+    pub fn throttled(&mut self) {
+        self.delay_ms = (self.delay_ms * 2).clamp(MIN_THROTTLE_DELAY_MS, MAX_THROTTLE_DELAY_MS);
+        self.calm_pages = 0;
+        log::debug!(
+            "FilterLogEvents throttled, pacing pages {}ms apart",
+            self.delay_ms
+        );
+        self.remember();
+    }
+
+    /// A page of `events` events came back after `latency`
+    pub fn observe(&mut self, latency: Duration, events: usize) {
+        self.calm_pages += 1;
+        if self.delay_ms > 0 && self.calm_pages >= CALM_PAGES_BEFORE_SPEEDUP {
+            self.delay_ms /= 2;
+            if self.delay_ms < MIN_DELAY_MS {
+                self.delay_ms = 0;
+            }
+            self.calm_pages = 0;
+        }
+        if latency > SLOW_PAGE && self.limit > MIN_PAGE_LIMIT {
+            self.limit = (self.limit / 2).max(MIN_PAGE_LIMIT);
+            log::debug!(
+                "FilterLogEvents page took {}ms, page size now {}",
+                latency.as_millis(),
+                self.limit
+            );
+        } else if latency < FAST_PAGE
+            && events >= self.limit as usize
+            && self.limit < MAX_PAGE_LIMIT
+        {
+            // Bound by the page size rather than the 1 MB response cap
+            self.limit = (self.limit * 2).min(MAX_PAGE_LIMIT);
+        }
+        self.remember();
+    }
+
+    fn remember(&self) {
+        *LEARNED.lock().unwrap_or_else(|e| e.into_inner()) = (self.limit, self.delay_ms);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{columns, filter_all_events, preview, redaction, session, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// Cap on events fetched into a pane
+const DEFAULT_PANE_MAX_EVENTS: usize = 50_000;
+
+/// Events returned with a pane view
+const DEFAULT_PANE_WINDOW: usize = 500;
+
+/// One of the two result sets of a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pane {
+    Primary,
+    Comparison,
+}
+
+impl Pane {
+    fn as_str(self) -> &'static str {
+        match self {
+            Pane::Primary => "primary",
+            Pane::Comparison => "comparison",
+        }
+    }
+}
+
+/// Result store id holding a pane's events, so window/goto/zoom commands work per pane
+pub fn pane_session_id(session_id: &str, pane: Pane) -> String {
+    format!("{}:{}", session_id, pane.as_str())
+}
+
+/// What a pane shows and where its cursor is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaneState {
+    pub pane: Pane,
+    /// Result store id of the pane (usable with get_session_window, goto_event, ...)
+    pub result_session_id: String,
+    pub log_group_name: String,
+    pub filter_pattern: Option<String>,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    /// Selected event
+    #[serde(default)]
+    pub cursor_event_id: Option<String>,
+    /// First visible row
+    #[serde(default)]
+    pub scroll_offset: usize,
+    pub event_count: usize,
+    pub truncated: bool,
+}
+
+/// A pane's state plus the events around its scroll position
+#[derive(Debug, Clone, Serialize)]
+pub struct PaneView {
+    pub state: PaneState,
+    pub events: Vec<LogEvent>,
+}
+
+async fn pane_view(
+    app: &AppHandle,
+    state: &AppState,
+    pane_state: PaneState,
+    window: usize,
+) -> PaneView {
+    let mut events = state
+        .sessions
+        .lock()
+        .await
+        .get(&pane_state.result_session_id)
+        .map(|s| s.window(pane_state.scroll_offset, window))
+        .unwrap_or_default();
+    redaction::mask_for_display(app, state, Some(&pane_state.result_session_id), &mut events).await;
+    columns::apply(app, &mut events);
+    preview::truncate(app, &mut events);
+    PaneView {
+        state: pane_state,
+        events,
+    }
+}
+
+/// Fetch a log group into one pane of a session, replacing what the pane showed.
+/// Each pane keeps its own filter, time range and cursor.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_into_pane(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    pane: Pane,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    filter_pattern: Option<String>,
+    max_count: Option<usize>,
+) -> Result<PaneView, String> {
+    let client = state.logs_client().await?;
+    let (mut events, truncated) = filter_all_events(
+        &client,
+        &log_group_name,
+        start_time,
+        end_time,
+        filter_pattern.as_deref(),
+        max_count.unwrap_or(DEFAULT_PANE_MAX_EVENTS),
+    )
+    .await?;
+
+    let result_session_id = pane_session_id(&session_id, pane);
+    session::tag_source(&mut events, &log_group_name);
+    let event_count = events.len();
+    {
+        let mut sessions = state.sessions.lock().await;
+        let result = sessions.get_or_create(&result_session_id);
+        result.clear();
+        result.fetches.clear();
+        result.replace_source(&log_group_name, events);
+        result.fetches.insert(
+            log_group_name.clone(),
+            session::FetchContext {
+                start_time,
+                end_time,
+                filter_pattern: filter_pattern.clone(),
+                truncated,
+                complete_ranges: Vec::new(),
+            },
+        );
+    }
+
+    let pane_state = PaneState {
+        pane,
+        result_session_id,
+        log_group_name,
+        filter_pattern,
+        start_time,
+        end_time,
+        cursor_event_id: None,
+        scroll_offset: 0,
+        event_count,
+        truncated,
+    };
+    state
+        .panes
+        .lock()
+        .await
+        .entry(session_id)
+        .or_default()
+        .insert(pane, pane_state.clone());
+    Ok(pane_view(&app, &state, pane_state, DEFAULT_PANE_WINDOW).await)
+}
+
+/// Move a pane's cursor and/or scroll position; returns the events now visible
+#[tauri::command]
+pub async fn set_pane_cursor(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    pane: Pane,
+    cursor_event_id: Option<String>,
+    scroll_offset: Option<usize>,
+    window: Option<usize>,
+) -> Result<PaneView, String> {
+    let pane_state = {
+        let mut panes = state.panes.lock().await;
+        let pane_state = panes
+            .get_mut(&session_id)
+            .and_then(|p| p.get_mut(&pane))
+            .ok_or_else(|| format!("Pane {} is empty", pane.as_str()))?;
+        if cursor_event_id.is_some() {
+            pane_state.cursor_event_id = cursor_event_id;
+        }
+        if let Some(offset) = scroll_offset {
+            pane_state.scroll_offset = offset.min(pane_state.event_count.saturating_sub(1));
+        }
+        pane_state.clone()
+    };
+    Ok(pane_view(
+        &app,
+        &state,
+        pane_state,
+        window.unwrap_or(DEFAULT_PANE_WINDOW),
+    )
+    .await)
+}
+
+/// Both panes of a session (an empty pane is absent)
+#[tauri::command]
+pub async fn get_panes(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<PaneState>, String> {
+    let mut panes: Vec<PaneState> = state
+        .panes
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|p| p.values().cloned().collect())
+        .unwrap_or_default();
+    panes.sort_by_key(|p| p.pane != Pane::Primary);
+    Ok(panes)
+}
+
+/// Exchange the primary and comparison panes (results, filters and cursors)
+#[tauri::command]
+pub async fn swap_panes(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<PaneState>, String> {
+    let primary_id = pane_session_id(&session_id, Pane::Primary);
+    let comparison_id = pane_session_id(&session_id, Pane::Comparison);
+    {
+        let mut sessions = state.sessions.lock().await;
+        let primary = sessions.remove(&primary_id);
+        let comparison = sessions.remove(&comparison_id);
+        if let Some(result) = comparison {
+            *sessions.get_or_create(&primary_id) = result;
+        }
+        if let Some(result) = primary {
+            *sessions.get_or_create(&comparison_id) = result;
+        }
+    }
+
+    let mut all_panes = state.panes.lock().await;
+    let panes = all_panes.entry(session_id).or_default();
+    let swapped: HashMap<Pane, PaneState> = panes
+        .drain()
+        .map(|(pane, mut pane_state)| {
+            let other = match pane {
+                Pane::Primary => Pane::Comparison,
+                Pane::Comparison => Pane::Primary,
+            };
+            pane_state.pane = other;
+            pane_state.result_session_id = if other == Pane::Primary {
+                primary_id.clone()
+            } else {
+                comparison_id.clone()
+            };
+            (other, pane_state)
+        })
+        .collect();
+    *panes = swapped;
+    let mut result: Vec<PaneState> = panes.values().cloned().collect();
+    result.sort_by_key(|p| p.pane != Pane::Primary);
+    Ok(result)
+}
+
+/// Close one pane and free its results
+#[tauri::command]
+pub async fn close_pane(
+    state: State<'_, AppState>,
+    session_id: String,
+    pane: Pane,
+) -> Result<(), String> {
+    state
+        .sessions
+        .lock()
+        .await
+        .remove(&pane_session_id(&session_id, pane));
+    if let Some(panes) = state.panes.lock().await.get_mut(&session_id) {
+        panes.remove(&pane);
+    }
+    Ok(())
+}
@@ -0,0 +1,871 @@
+use crate::connections::EnvironmentEmitter;
+use crate::redaction::{ProfileRedaction, RedactionRule, Redactor};
+use crate::{redaction, storage, AppState, LogEvent};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding per-group pipelines
+const PIPELINES_FILE: &str = "pipelines.json";
+
+/// Serializes read-modify-write of the pipelines file
+static PIPELINES_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// JSON fields checked for an explicit level before falling back to keywords
+const LEVEL_FIELDS: [&str; 6] = [
+    "level",
+    "severity",
+    "levelname",
+    "log_level",
+    "loglevel",
+    "logLevel",
+];
+
+/// Lines continuing the previous event (indented, Java/Python stack frames)
+const DEFAULT_CONTINUATION_PATTERN: &str = r"^(\s+\S|at |Caused by:|\.\.\. \d+ more)";
+
+/// CSI and OSC terminal escape sequences
+const ANSI_PATTERN: &str = r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07";
+
+/// `key=value` / `key="quoted value"` pairs
+const LOGFMT_PAIR_PATTERN: &str = r#"([A-Za-z_][\w.\-]*)=("(?:[^"\\]|\\.)*"|\S*)"#;
+
+/// Lambda runtime lines: platform START/END/REPORT/INIT_START and the
+/// "timestamp<TAB>request id<TAB>LEVEL<TAB>message" application format
+const LAMBDA_PATTERN: &str = r"^(START RequestId: |END RequestId: |REPORT RequestId: |INIT_START |INIT_REPORT |\S+\t[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\t)";
+
+/// Common/combined access log: host ident user [time] "METHOD path proto" status bytes
+const ACCESS_LOG_PATTERN: &str = r#"^\S+ \S+ \S+ \[[^\]]+\] "[A-Z]+ \S+[^"]*" \d{3} (\d+|-)"#;
+
+/// Events sampled to fingerprint a group's format
+const FINGERPRINT_SAMPLE: usize = 300;
+
+/// Share of sampled events a format needs to be chosen over plain text
+const FINGERPRINT_MIN_SHARE: f64 = 0.5;
+
+/// Pull a named field out of an event, from parsed JSON or the raw message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldExtractor {
+    pub name: String,
+    /// Dot-separated path into the parsed JSON ("request.id")
+    #[serde(default)]
+    pub json_path: Option<String>,
+    /// Regex over the message; the first capture group (or whole match) is the value
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+/// Keywords that put an event at a level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityRule {
+    pub level: String,
+    pub keywords: Vec<String>,
+}
+
+/// One processing step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PipelineStage {
+    StripAnsi,
+    ParseJson,
+    ExtractFields {
+        extractors: Vec<FieldExtractor>,
+    },
+    ClassifySeverity {
+        /// Checked in order; empty uses the built-in error/warn/info/debug/trace rules
+        #[serde(default)]
+        rules: Vec<SeverityRule>,
+    },
+    /// Extract every `key=value` pair of logfmt lines into fields
+    ParseLogfmt,
+    StitchMultiline {
+        /// Regex matching continuation lines (defaults to indented lines and stack frames)
+        #[serde(default)]
+        continuation_pattern: Option<String>,
+    },
+    /// Mask sensitive values (same rules as display-time redaction)
+    Redact {
+        #[serde(default = "default_true")]
+        use_builtin_rules: bool,
+        #[serde(default)]
+        rules: Vec<RedactionRule>,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl PipelineStage {
+    fn label(&self) -> &'static str {
+        match self {
+            PipelineStage::StripAnsi => "strip_ansi",
+            PipelineStage::ParseJson => "parse_json",
+            PipelineStage::ParseLogfmt => "parse_logfmt",
+            PipelineStage::ExtractFields { .. } => "extract_fields",
+            PipelineStage::ClassifySeverity { .. } => "classify_severity",
+            PipelineStage::StitchMultiline { .. } => "stitch_multiline",
+            PipelineStage::Redact { .. } => "redact",
+        }
+    }
+}
+
+/// Pipeline used for log groups starting with `log_group_prefix`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub log_group_prefix: String,
+    pub stages: Vec<PipelineStage>,
+    /// Set when the pipeline was chosen by format fingerprinting rather than the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_format: Option<LogFormat>,
+}
+
+/// Line formats recognized by fingerprinting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    Json,
+    Logfmt,
+    LambdaPlatform,
+    AccessLog,
+    Plain,
+}
+
+/// Outcome of fingerprinting a group, emitted as log-format-detected
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatDetection {
+    pub log_group_name: String,
+    pub format: LogFormat,
+    /// Share of sampled events matching the format (0-1)
+    pub confidence: f64,
+    pub sampled: usize,
+    /// Pipeline selected for the group
+    pub stages: Vec<PipelineStage>,
+}
+
+/// An event as it moves through the pipeline
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessedEvent {
+    pub timestamp: i64,
+    pub message: String,
+    pub log_stream_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+    pub fields: BTreeMap<String, String>,
+    pub level: Option<String>,
+}
+
+impl From<&LogEvent> for ProcessedEvent {
+    fn from(event: &LogEvent) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            message: event.message.clone(),
+            log_stream_name: event.log_stream_name.clone(),
+            json: None,
+            fields: BTreeMap::new(),
+            level: None,
+        }
+    }
+}
+
+/// Events after one stage of a preview
+#[derive(Debug, Clone, Serialize)]
+pub struct StagePreview {
+    pub stage: String,
+    pub events: Vec<ProcessedEvent>,
+}
+
+/// The built-in pipeline: strip ANSI -> parse JSON -> extract fields (none configured)
+/// -> classify severity -> stitch multilines
+pub fn default_stages() -> Vec<PipelineStage> {
+    vec![
+        PipelineStage::StripAnsi,
+        PipelineStage::ParseJson,
+        PipelineStage::ExtractFields {
+            extractors: Vec::new(),
+        },
+        PipelineStage::ClassifySeverity { rules: Vec::new() },
+        PipelineStage::StitchMultiline {
+            continuation_pattern: None,
+        },
+    ]
+}
+
+fn default_severity_rules() -> Vec<SeverityRule> {
+    let rule = |level: &str, keywords: &[&str]| SeverityRule {
+        level: level.to_string(),
+        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+    };
+    vec![
+        rule(
+            "error",
+            &["error", "err", "fatal", "critical", "panic", "exception"],
+        ),
+        rule("warn", &["warn", "warning"]),
+        rule("info", &["info", "notice"]),
+        rule("debug", &["debug"]),
+        rule("trace", &["trace"]),
+    ]
+}
+
+/// The `key=value` pairs of a logfmt line (quotes removed)
+fn logfmt_pairs(pairs: &Regex, line: &str) -> Vec<(String, String)> {
+    pairs
+        .captures_iter(line)
+        .map(|c| {
+            let value = &c[2];
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| v.replace("\\\"", "\""))
+                .unwrap_or_else(|| value.to_string());
+            (c[1].to_string(), value)
+        })
+        .collect()
+}
+
+/// Level and keyword regex of each severity rule (the built-in rules when empty)
+fn severity_regexes(rules: &[SeverityRule]) -> Result<Vec<(String, Regex)>, String> {
+    let rules = if rules.is_empty() {
+        default_severity_rules()
+    } else {
+        rules.to_vec()
+    };
+    rules
+        .iter()
+        .filter(|r| !r.keywords.is_empty())
+        .map(|r| {
+            let alternatives: Vec<String> = r.keywords.iter().map(|k| regex::escape(k)).collect();
+            Ok((
+                r.level.clone(),
+                compile(&format!(r"(?i)\b({})\b", alternatives.join("|")))?,
+            ))
+        })
+        .collect()
+}
+
+/// Text severity keywords are matched against, and the JSON level field it came
+/// from. An explicit JSON level wins over keywords in the message.
+fn severity_text(event: &ProcessedEvent) -> (Option<&'static str>, &str) {
+    let json_level = event.json.as_ref().and_then(|json| {
+        LEVEL_FIELDS
+            .iter()
+            .find_map(|f| json.get(*f).and_then(|v| v.as_str()).map(|v| (*f, v)))
+    });
+    match json_level {
+        Some((field, value)) => (Some(field), value),
+        None => (None, &event.message),
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid pattern {:?}: {}", pattern, e))
+}
+
+pub(crate) fn json_lookup<'a>(
+    json: &'a serde_json::Value,
+    path: &str,
+) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(json, |value, key| match value {
+        serde_json::Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(key),
+    })
+}
+
+pub(crate) fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Run one stage over a batch of events
+pub fn apply_stage(
+    stage: &PipelineStage,
+    mut events: Vec<ProcessedEvent>,
+) -> Result<Vec<ProcessedEvent>, String> {
+    match stage {
+        PipelineStage::StripAnsi => {
+            let ansi = compile(ANSI_PATTERN)?;
+            for event in &mut events {
+                if event.message.contains('\x1b') {
+                    event.message = ansi.replace_all(&event.message, "").into_owned();
+                }
+            }
+        }
+        PipelineStage::ParseJson => {
+            for event in &mut events {
+                let trimmed = event.message.trim();
+                if trimmed.starts_with('{') && trimmed.ends_with('}') {
+                    event.json = serde_json::from_str(trimmed).ok();
+                }
+            }
+        }
+        PipelineStage::ParseLogfmt => {
+            let pairs = compile(LOGFMT_PAIR_PATTERN)?;
+            for event in &mut events {
+                let found = logfmt_pairs(&pairs, &event.message);
+                // A lone "a=b" in prose isn't logfmt
+                if found.len() >= 2 {
+                    event.fields.extend(found);
+                }
+            }
+        }
+        PipelineStage::ExtractFields { extractors } => {
+            let compiled: Vec<(&FieldExtractor, Option<Regex>)> = extractors
+                .iter()
+                .map(|x| Ok((x, x.pattern.as_deref().map(compile).transpose()?)))
+                .collect::<Result<_, String>>()?;
+            for event in &mut events {
+                for (extractor, regex) in &compiled {
+                    let from_json = extractor.json_path.as_deref().and_then(|path| {
+                        event
+                            .json
+                            .as_ref()
+                            .and_then(|json| json_lookup(json, path))
+                            .map(value_to_string)
+                    });
+                    let value = from_json.or_else(|| {
+                        regex.as_ref().and_then(|re| {
+                            re.captures(&event.message).and_then(|c| {
+                                c.get(1)
+                                    .or_else(|| c.get(0))
+                                    .map(|m| m.as_str().to_string())
+                            })
+                        })
+                    });
+                    if let Some(value) = value {
+                        event.fields.insert(extractor.name.clone(), value);
+                    }
+                }
+            }
+        }
+        PipelineStage::ClassifySeverity { rules } => {
+            let keyword_res = severity_regexes(rules)?;
+            for event in &mut events {
+                let (_, text) = severity_text(event);
+                event.level = keyword_res
+                    .iter()
+                    .find(|(_, re)| re.is_match(text))
+                    .map(|(level, _)| level.clone());
+            }
+        }
+        PipelineStage::StitchMultiline {
+            continuation_pattern,
+        } => {
+            let continuation = compile(
+                continuation_pattern
+                    .as_deref()
+                    .unwrap_or(DEFAULT_CONTINUATION_PATTERN),
+            )?;
+            let mut stitched: Vec<ProcessedEvent> = Vec::with_capacity(events.len());
+            for event in events {
+                if let Some(previous) = stitched.last_mut() {
+                    if previous.log_stream_name == event.log_stream_name
+                        && event.json.is_none()
+                        && continuation.is_match(&event.message)
+                    {
+                        previous.message.push('\n');
+                        previous.message.push_str(&event.message);
+                        continue;
+                    }
+                }
+                stitched.push(event);
+            }
+            return Ok(stitched);
+        }
+        PipelineStage::Redact {
+            use_builtin_rules,
+            rules,
+        } => {
+            let redactor = Redactor::new(&ProfileRedaction {
+                enabled: true,
+                use_builtin_rules: *use_builtin_rules,
+                rules: rules.clone(),
+            })?;
+            for event in &mut events {
+                event.message = redactor.redact(&event.message);
+                // Keep already-parsed JSON consistent with the masked text
+                if event.json.is_some() {
+                    event.json = serde_json::from_str(event.message.trim()).ok();
+                }
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Format of one line, or None for plain text
+fn line_format(line: &str, logfmt: &Regex, lambda: &Regex, access: &Regex) -> Option<LogFormat> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{')
+        && trimmed.ends_with('}')
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return Some(LogFormat::Json);
+    }
+    if lambda.is_match(line) {
+        return Some(LogFormat::LambdaPlatform);
+    }
+    if access.is_match(trimmed) {
+        return Some(LogFormat::AccessLog);
+    }
+    // Mostly pairs: at least two, covering most of the line
+    let pairs: usize = logfmt.find_iter(trimmed).map(|m| m.len()).sum();
+    if logfmt.find_iter(trimmed).count() >= 2 && pairs * 10 >= trimmed.len() * 7 {
+        return Some(LogFormat::Logfmt);
+    }
+    None
+}
+
+/// Most common line format among `events` and its share; plain text unless one
+/// format covers at least FINGERPRINT_MIN_SHARE of them
+pub fn fingerprint(events: &[LogEvent]) -> Result<(LogFormat, f64), String> {
+    let (logfmt, lambda, access) = (
+        compile(LOGFMT_PAIR_PATTERN)?,
+        compile(LAMBDA_PATTERN)?,
+        compile(ACCESS_LOG_PATTERN)?,
+    );
+    if events.is_empty() {
+        return Ok((LogFormat::Plain, 0.0));
+    }
+    let mut counts: HashMap<LogFormat, usize> = HashMap::new();
+    for event in events {
+        if let Some(format) = line_format(&event.message, &logfmt, &lambda, &access) {
+            *counts.entry(format).or_default() += 1;
+        }
+    }
+    let best = counts.into_iter().max_by_key(|(_, count)| *count);
+    Ok(match best {
+        Some((format, count)) => {
+            let share = count as f64 / events.len() as f64;
+            if share >= FINGERPRINT_MIN_SHARE {
+                (format, share)
+            } else {
+                (LogFormat::Plain, 1.0 - share)
+            }
+        }
+        None => (LogFormat::Plain, 1.0),
+    })
+}
+
+/// Pipeline suited to a detected format
+pub fn stages_for_format(format: LogFormat) -> Vec<PipelineStage> {
+    let extract = |fields: &[(&str, &str)]| PipelineStage::ExtractFields {
+        extractors: fields
+            .iter()
+            .map(|(name, pattern)| FieldExtractor {
+                name: name.to_string(),
+                json_path: None,
+                pattern: Some(pattern.to_string()),
+            })
+            .collect(),
+    };
+    let classify = PipelineStage::ClassifySeverity { rules: Vec::new() };
+    match format {
+        LogFormat::Json | LogFormat::Plain => default_stages(),
+        LogFormat::Logfmt => vec![
+            PipelineStage::StripAnsi,
+            PipelineStage::ParseLogfmt,
+            classify,
+        ],
+        LogFormat::LambdaPlatform => vec![
+            PipelineStage::StripAnsi,
+            PipelineStage::ParseJson,
+            extract(&[
+                (
+                    "request_id",
+                    r"(?:RequestId: |^\S+\t)([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})",
+                ),
+                ("duration_ms", r"\tDuration: ([\d.]+) ms"),
+                ("billed_duration_ms", r"Billed Duration: (\d+) ms"),
+                ("max_memory_used_mb", r"Max Memory Used: (\d+) MB"),
+                ("init_duration_ms", r"Init Duration: ([\d.]+) ms"),
+            ]),
+            classify,
+            PipelineStage::StitchMultiline {
+                continuation_pattern: None,
+            },
+        ],
+        LogFormat::AccessLog => vec![
+            PipelineStage::StripAnsi,
+            extract(&[
+                ("client_ip", r"^(\S+)"),
+                ("method", r#""([A-Z]+) "#),
+                ("path", r#""[A-Z]+ (\S+)"#),
+                ("status", r#"" (\d{3}) "#),
+                ("bytes", r#"" \d{3} (\d+)"#),
+            ]),
+            classify,
+        ],
+    }
+}
+
+/// On the first open of a group with no configured pipeline, fingerprint a sample
+/// of its events, save the matching pipeline for the group and emit
+/// log-format-detected so the user can override it
+pub fn detect_on_first_open(app: &AppHandle, log_group: &str, events: &[LogEvent]) {
+    if events.is_empty()
+        || load_pipelines(app)
+            .iter()
+            .any(|p| log_group.starts_with(&p.log_group_prefix))
+    {
+        return;
+    }
+    let app = app.clone();
+    let log_group = log_group.to_string();
+    let step = events.len().div_ceil(FINGERPRINT_SAMPLE).max(1);
+    let sample: Vec<LogEvent> = events.iter().step_by(step).cloned().collect();
+    tauri::async_runtime::spawn_blocking(move || {
+        let (format, confidence) = match fingerprint(&sample) {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Format fingerprinting failed for {}: {}", log_group, e);
+                return;
+            }
+        };
+        let stages = stages_for_format(format);
+        {
+            let _guard = PIPELINES_FILE_LOCK
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let mut pipelines = load_pipelines(&app);
+            if pipelines
+                .iter()
+                .any(|p| log_group.starts_with(&p.log_group_prefix))
+            {
+                // Configured while sampling
+                return;
+            }
+            pipelines.push(PipelineConfig {
+                log_group_prefix: log_group.clone(),
+                stages: stages.clone(),
+                detected_format: Some(format),
+            });
+            pipelines.sort_by(|a, b| a.log_group_prefix.cmp(&b.log_group_prefix));
+            if let Err(e) = storage::write_json(&app, PIPELINES_FILE, &pipelines) {
+                log::warn!("Failed to save detected pipeline: {}", e);
+                return;
+            }
+        }
+        log::info!(
+            "Detected {:?} format for {} ({:.0}% of {} events)",
+            format,
+            log_group,
+            confidence * 100.0,
+            sample.len()
+        );
+        app.emit_tagged(
+            "log-format-detected",
+            FormatDetection {
+                log_group_name: log_group,
+                format,
+                confidence,
+                sampled: sample.len(),
+                stages,
+            },
+        )
+        .ok();
+    });
+}
+
+fn load_pipelines(app: &AppHandle) -> Vec<PipelineConfig> {
+    storage::read_json(app, PIPELINES_FILE)
+}
+
+/// Pipeline configured for a log group (longest matching prefix)
+fn pipeline_config_for_group(app: &AppHandle, log_group: &str) -> Option<PipelineConfig> {
+    load_pipelines(app)
+        .into_iter()
+        .filter(|p| log_group.starts_with(&p.log_group_prefix))
+        .max_by_key(|p| p.log_group_prefix.len())
+}
+
+/// Stages configured for a log group (longest matching prefix), or the default pipeline
+pub fn pipeline_for_group(app: &AppHandle, log_group: &str) -> Vec<PipelineStage> {
+    pipeline_config_for_group(app, log_group)
+        .map(|p| p.stages)
+        .unwrap_or_else(default_stages)
+}
+
+/// List configured per-group pipelines
+#[tauri::command]
+pub fn list_pipelines(app: AppHandle) -> Vec<PipelineConfig> {
+    load_pipelines(&app)
+}
+
+/// Save the pipeline for a log group prefix, replacing any existing one. Patterns
+/// are compiled first so mistakes are reported on save.
+#[tauri::command]
+pub fn save_pipeline(app: AppHandle, mut config: PipelineConfig) -> Result<(), String> {
+    for stage in &config.stages {
+        apply_stage(stage, Vec::new())?;
+    }
+    // Saving (even an unchanged detected pipeline) makes it the user's choice
+    config.detected_format = None;
+    let _guard = PIPELINES_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut pipelines = load_pipelines(&app);
+    pipelines.retain(|p| p.log_group_prefix != config.log_group_prefix);
+    pipelines.push(config);
+    pipelines.sort_by(|a, b| a.log_group_prefix.cmp(&b.log_group_prefix));
+    storage::write_json(&app, PIPELINES_FILE, &pipelines)
+}
+
+/// Remove the pipeline for a log group prefix
+#[tauri::command]
+pub fn delete_pipeline(app: AppHandle, log_group_prefix: String) -> Result<(), String> {
+    let _guard = PIPELINES_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut pipelines = load_pipelines(&app);
+    pipelines.retain(|p| p.log_group_prefix != log_group_prefix);
+    storage::write_json(&app, PIPELINES_FILE, &pipelines)
+}
+
+/// Run sample lines through a pipeline and return the events after every stage.
+/// Uses `stages` when given, otherwise the pipeline configured for `log_group`.
+#[tauri::command]
+pub fn preview_pipeline(
+    app: AppHandle,
+    sample: Vec<String>,
+    log_group: Option<String>,
+    stages: Option<Vec<PipelineStage>>,
+) -> Result<Vec<StagePreview>, String> {
+    let stages = stages.unwrap_or_else(|| match &log_group {
+        Some(group) => pipeline_for_group(&app, group),
+        None => default_stages(),
+    });
+
+    let mut events: Vec<ProcessedEvent> = sample
+        .into_iter()
+        .enumerate()
+        .map(|(i, message)| ProcessedEvent {
+            timestamp: i as i64,
+            message,
+            log_stream_name: None,
+            json: None,
+            fields: BTreeMap::new(),
+            level: None,
+        })
+        .collect();
+
+    let mut previews = vec![StagePreview {
+        stage: "input".to_string(),
+        events: events.clone(),
+    }];
+    for stage in &stages {
+        events = apply_stage(stage, events)?;
+        previews.push(StagePreview {
+            stage: stage.label().to_string(),
+            events: events.clone(),
+        });
+    }
+    Ok(previews)
+}
+
+/// What one stage did to the explained event
+#[derive(Debug, Clone, Serialize)]
+pub struct StageExplanation {
+    pub stage: String,
+    /// Whether the stage changed the event (message, JSON, fields or level)
+    pub changed: bool,
+    /// Human-readable account of the decisions the stage made
+    pub notes: Vec<String>,
+    /// Fields added or overwritten by the stage
+    pub fields: BTreeMap<String, String>,
+    pub event: ProcessedEvent,
+}
+
+/// How an event was parsed, stage by stage
+#[derive(Debug, Clone, Serialize)]
+pub struct EventExplanation {
+    pub event_id: String,
+    pub log_group_name: Option<String>,
+    /// Where the stages came from: "prefix <p>", "detected <format> for <p>" or "default"
+    pub pipeline: String,
+    pub stages: Vec<StageExplanation>,
+    pub level: Option<String>,
+    /// Why the final level was (or wasn't) assigned
+    pub severity_reason: String,
+}
+
+/// Notes on a stage's decisions for one event (before and after the stage)
+fn stage_notes(
+    stage: &PipelineStage,
+    before: &ProcessedEvent,
+    after: &ProcessedEvent,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let mut notes = Vec::new();
+    let mut severity_reason = None;
+    match stage {
+        PipelineStage::StripAnsi => notes.push(if before.message != after.message {
+            "Removed terminal escape sequences".to_string()
+        } else {
+            "No escape sequences found".to_string()
+        }),
+        PipelineStage::ParseJson => notes.push(match (&before.json, &after.json) {
+            (_, Some(_)) => "Message parsed as JSON".to_string(),
+            _ if before.message.trim().starts_with('{') => {
+                "Message looks like JSON but did not parse".to_string()
+            }
+            _ => "Message is not a JSON object".to_string(),
+        }),
+        PipelineStage::ParseLogfmt => {
+            let pairs = logfmt_pairs(&compile(LOGFMT_PAIR_PATTERN)?, &before.message).len();
+            notes.push(match pairs {
+                0 => "No key=value pairs found".to_string(),
+                1 => "Only one key=value pair; not treated as logfmt".to_string(),
+                n => format!("Extracted {} key=value pairs", n),
+            });
+        }
+        PipelineStage::ExtractFields { extractors } => {
+            for extractor in extractors {
+                let from_json = extractor.json_path.as_deref().and_then(|path| {
+                    before
+                        .json
+                        .as_ref()
+                        .and_then(|json| json_lookup(json, path))
+                        .map(|_| path)
+                });
+                let note = match (from_json, &extractor.pattern) {
+                    (Some(path), _) => format!("{}: taken from JSON path {}", extractor.name, path),
+                    (None, Some(pattern)) if compile(pattern)?.is_match(&before.message) => {
+                        format!("{}: pattern {:?} matched", extractor.name, pattern)
+                    }
+                    (None, Some(pattern)) => {
+                        format!("{}: pattern {:?} did not match", extractor.name, pattern)
+                    }
+                    (None, None) => match &extractor.json_path {
+                        Some(path) => format!("{}: JSON path {} not present", extractor.name, path),
+                        None => format!("{}: no JSON path or pattern configured", extractor.name),
+                    },
+                };
+                notes.push(note);
+            }
+        }
+        PipelineStage::ClassifySeverity { rules } => {
+            let (field, text) = severity_text(before);
+            let source = match field {
+                Some(field) => format!("JSON field \"{}\" ({:?})", field, text),
+                None => "the message text".to_string(),
+            };
+            let matched = severity_regexes(rules)?
+                .into_iter()
+                .find_map(|(level, re)| re.find(text).map(|m| (level, m.as_str().to_string())));
+            let reason = match matched {
+                Some((level, keyword)) => format!(
+                    "{} because keyword {:?} matched in {}{}",
+                    level,
+                    keyword,
+                    source,
+                    if rules.is_empty() {
+                        " (built-in rules)"
+                    } else {
+                        ""
+                    }
+                ),
+                None => format!("No severity keyword matched in {}", source),
+            };
+            notes.push(reason.clone());
+            severity_reason = Some(reason);
+        }
+        PipelineStage::StitchMultiline { .. } => notes.push(
+            "Joins continuation lines to the previous event of the stream; has no effect on a single event"
+                .to_string(),
+        ),
+        PipelineStage::Redact { .. } => notes.push(if before.message != after.message {
+            "Masked sensitive values".to_string()
+        } else {
+            "Nothing to mask".to_string()
+        }),
+    }
+    Ok((notes, severity_reason))
+}
+
+/// Explain how a session event is parsed: the pipeline used for its log group, what
+/// each stage did, which extractors matched and why its severity was assigned
+#[tauri::command]
+pub async fn explain_event(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_id: String,
+) -> Result<EventExplanation, String> {
+    let mut event = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .and_then(|s| {
+            s.events()
+                .into_iter()
+                .find(|e| e.event_id.as_deref() == Some(&event_id))
+        })
+        .ok_or_else(|| format!("Event {} not found", event_id))?;
+    // Every stage's output is shown, so the pipeline runs on the masked message
+    redaction::mask_for_display(
+        &app,
+        &state,
+        Some(&session_id),
+        std::slice::from_mut(&mut event),
+    )
+    .await;
+
+    let config = event
+        .source
+        .as_deref()
+        .and_then(|group| pipeline_config_for_group(&app, group));
+    let pipeline = match &config {
+        Some(PipelineConfig {
+            log_group_prefix,
+            detected_format: Some(format),
+            ..
+        }) => format!("detected {:?} for {}", format, log_group_prefix),
+        Some(config) => format!("prefix {}", config.log_group_prefix),
+        None => "default".to_string(),
+    };
+    let stages = config.map(|c| c.stages).unwrap_or_else(default_stages);
+
+    let mut current = ProcessedEvent::from(&event);
+    let mut explained = Vec::new();
+    let mut severity_reason = "No classify_severity stage in the pipeline".to_string();
+    for stage in &stages {
+        let after = apply_stage(stage, vec![current.clone()])?
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| current.clone());
+        let (notes, reason) = stage_notes(stage, &current, &after)?;
+        if let Some(reason) = reason {
+            severity_reason = reason;
+        }
+        let fields: BTreeMap<String, String> = after
+            .fields
+            .iter()
+            .filter(|(k, v)| current.fields.get(*k) != Some(*v))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        explained.push(StageExplanation {
+            stage: stage.label().to_string(),
+            changed: after.message != current.message
+                || after.json != current.json
+                || after.level != current.level
+                || !fields.is_empty(),
+            notes,
+            fields,
+            event: after.clone(),
+        });
+        current = after;
+    }
+
+    Ok(EventExplanation {
+        event_id,
+        log_group_name: event.source,
+        pipeline,
+        stages: explained,
+        level: current.level,
+        severity_reason,
+    })
+}
@@ -0,0 +1,152 @@
+use crate::{catalog, connectivity, regions, storage, LogGroup};
+use aws_config::BehaviorVersion;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// File in the app config directory holding the prefetch settings
+const PREFETCH_FILE: &str = "catalog-prefetch.json";
+
+/// Wait after launch before prefetching, so startup work goes first
+const STARTUP_DELAY: Duration = Duration::from_secs(10);
+
+/// Profiles listed at once
+const MAX_CONCURRENT_PREFETCHES: usize = 3;
+
+/// Prefetched catalogs older than this are listed again
+const CATALOG_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Log group catalogs of pinned profiles, keyed by profile
+static CATALOGS: Mutex<Option<HashMap<String, PrefetchedCatalog>>> = Mutex::new(None);
+
+struct PrefetchedCatalog {
+    fetched_at: Instant,
+    groups: Vec<LogGroup>,
+}
+
+/// Pinned profiles whose log group catalogs are warmed at launch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrefetchSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub profiles: Vec<String>,
+}
+
+/// Outcome of prefetching one profile's catalog
+#[derive(Debug, Clone, Serialize)]
+pub struct PrefetchResult {
+    pub profile: String,
+    pub group_count: usize,
+    pub error: Option<String>,
+}
+
+/// The prefetched catalog of a profile in its default region, if still fresh
+pub fn cached(profile: &str) -> Option<Vec<LogGroup>> {
+    let catalogs = CATALOGS.lock().unwrap_or_else(|e| e.into_inner());
+    catalogs
+        .as_ref()?
+        .get(profile)
+        .filter(|c| c.fetched_at.elapsed() < CATALOG_TTL)
+        .map(|c| c.groups.clone())
+}
+
+/// List a profile's log groups in its default region with a client of its own,
+/// leaving the active connection untouched
+async fn prefetch_profile(app: &AppHandle, profile: &str) -> Result<usize, String> {
+    crate::validate_profile_name(profile)?;
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .load()
+        .await;
+    let signing_region = regions::signing_region_for(app, Some(profile));
+    let client = regions::logs_client(&config, signing_region.as_deref());
+    let groups = catalog::describe_all_log_groups(&client).await?;
+    let count = groups.len();
+    CATALOGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            profile.to_string(),
+            PrefetchedCatalog {
+                fetched_at: Instant::now(),
+                groups,
+            },
+        );
+    Ok(count)
+}
+
+/// Prefetch the catalogs of `profiles`, a few at a time
+async fn prefetch_all(app: &AppHandle, profiles: Vec<String>) -> Vec<PrefetchResult> {
+    let permits = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_PREFETCHES));
+    let mut tasks = tokio::task::JoinSet::new();
+    for profile in profiles {
+        let app = app.clone();
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let result = prefetch_profile(&app, &profile).await;
+            match &result {
+                Ok(count) => log::debug!("Prefetched {} log groups of {}", count, profile),
+                Err(e) => log::info!("Prefetching log groups of {} failed: {}", profile, e),
+            }
+            PrefetchResult {
+                group_count: *result.as_ref().unwrap_or(&0),
+                error: result.err(),
+                profile,
+            }
+        });
+    }
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(result) = result {
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Warm the catalogs of the pinned profiles shortly after launch, when enabled,
+/// so list_log_groups answers without a listing delay after switching to one
+pub async fn run_startup_prefetch(app: AppHandle) {
+    let settings: PrefetchSettings = storage::read_json(&app, PREFETCH_FILE);
+    if !settings.enabled || settings.profiles.is_empty() {
+        return;
+    }
+    tokio::time::sleep(STARTUP_DELAY).await;
+    if connectivity::ensure_online().is_err() {
+        return;
+    }
+    let results = prefetch_all(&app, settings.profiles).await;
+    log::info!(
+        "Prefetched log group catalogs of {}/{} pinned profiles",
+        results.iter().filter(|r| r.error.is_none()).count(),
+        results.len()
+    );
+}
+
+/// Catalog prefetch settings
+#[tauri::command]
+pub fn get_prefetch_settings(app: AppHandle) -> PrefetchSettings {
+    storage::read_json(&app, PREFETCH_FILE)
+}
+
+/// Save catalog prefetch settings; takes effect at the next launch
+#[tauri::command]
+pub fn set_prefetch_settings(app: AppHandle, settings: PrefetchSettings) -> Result<(), String> {
+    for profile in &settings.profiles {
+        crate::validate_profile_name(profile)?;
+    }
+    storage::write_json(&app, PREFETCH_FILE, &settings)
+}
+
+/// Prefetch the pinned profiles' catalogs now (e.g. at the start of an incident)
+#[tauri::command]
+pub async fn prefetch_catalogs(app: AppHandle) -> Result<Vec<PrefetchResult>, String> {
+    connectivity::ensure_online()?;
+    let settings: PrefetchSettings = storage::read_json(&app, PREFETCH_FILE);
+    Ok(prefetch_all(&app, settings.profiles).await)
+}
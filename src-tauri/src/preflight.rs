@@ -0,0 +1,190 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{check_credentials_valid, is_sso_session_expired, open_sso_login_url, storage};
+use chrono::{Datelike, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// File in the app config directory holding the pre-flight schedule
+const PREFLIGHT_FILE: &str = "credential-preflight.json";
+
+/// How often the scheduler checks whether the pre-flight time has passed
+const SCHEDULER_INTERVAL_SECS: u64 = 30;
+
+/// How long to wait for a triggered SSO login to complete before moving on
+const LOGIN_WAIT_SECS: u64 = 120;
+
+const LOGIN_POLL_SECS: u64 = 5;
+
+/// Serializes read-modify-write of the pre-flight file
+static PREFLIGHT_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn default_trigger_login() -> bool {
+    true
+}
+
+/// When and for which profiles credentials are checked ahead of the workday
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreflightSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Local time of day, "HH:MM" (e.g. "08:55")
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Favorite profiles to validate, in order
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// Skip Saturdays and Sundays
+    #[serde(default)]
+    pub weekdays_only: bool,
+    /// Start the SSO sign-in for expired profiles
+    #[serde(default = "default_trigger_login")]
+    pub trigger_sso_login: bool,
+    /// Local date (YYYY-MM-DD) of the last scheduled run
+    #[serde(default)]
+    pub last_run_date: Option<String>,
+}
+
+/// Outcome for one profile; also the items of the credential-preflight payload
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightResult {
+    pub profile: String,
+    /// "valid", "refreshed" (after a triggered login), "expired" or "error"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("{} is not a valid HH:MM time", time))
+}
+
+/// Poll until the profile's credentials work or the wait runs out
+async fn wait_for_login(profile: &String) -> bool {
+    for _ in 0..LOGIN_WAIT_SECS / LOGIN_POLL_SECS {
+        tokio::time::sleep(Duration::from_secs(LOGIN_POLL_SECS)).await;
+        if check_credentials_valid(Some(profile)).await.is_ok() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Validate each profile in order, starting SSO login for expired ones. Waits for
+/// each login before checking the next profile, so profiles sharing an SSO session
+/// only prompt once.
+async fn run_preflight(app: &AppHandle, settings: &PreflightSettings) -> Vec<PreflightResult> {
+    let mut results = Vec::new();
+    for profile in &settings.profiles {
+        let (status, message) = match check_credentials_valid(Some(profile)).await {
+            Ok(()) => ("valid", None),
+            Err(e) if is_sso_session_expired(&e) && settings.trigger_sso_login => {
+                log::info!("Pre-flight: {} expired, starting SSO login", profile);
+                match open_sso_login_url(app.clone(), Some(profile)).await {
+                    Ok(()) if wait_for_login(profile).await => ("refreshed", None),
+                    Ok(()) => ("expired", Some("SSO login was not completed".to_string())),
+                    Err(e) => ("expired", Some(e)),
+                }
+            }
+            Err(e) if is_sso_session_expired(&e) => ("expired", None),
+            Err(e) => ("error", Some(e)),
+        };
+        results.push(PreflightResult {
+            profile: profile.clone(),
+            status: status.to_string(),
+            message,
+        });
+    }
+    app.emit_tagged("credential-preflight", &results).ok();
+    results
+}
+
+/// Whether the scheduled run is due now (time passed, not yet run today)
+fn is_due(settings: &PreflightSettings) -> bool {
+    let Some(time) = settings.time.as_deref().and_then(|t| parse_time(t).ok()) else {
+        return false;
+    };
+    let now = chrono::Local::now();
+    if settings.weekdays_only && matches!(now.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    let today = now.date_naive().to_string();
+    settings.enabled
+        && !settings.profiles.is_empty()
+        && now.time() >= time
+        && settings.last_run_date.as_deref() != Some(today.as_str())
+}
+
+/// Background scheduler running the credential pre-flight once a day at the
+/// configured time
+pub async fn run_preflight_scheduler(app: AppHandle) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(SCHEDULER_INTERVAL_SECS)).await;
+        let settings: PreflightSettings = storage::read_json(&app, PREFLIGHT_FILE);
+        if !is_due(&settings) {
+            continue;
+        }
+        // Record the run first so a slow login doesn't trigger it again
+        {
+            let _guard = PREFLIGHT_FILE_LOCK
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let mut current: PreflightSettings = storage::read_json(&app, PREFLIGHT_FILE);
+            current.last_run_date = Some(chrono::Local::now().date_naive().to_string());
+            if let Err(e) = storage::write_json(&app, PREFLIGHT_FILE, &current) {
+                log::warn!("Failed to record credential pre-flight run: {}", e);
+            }
+        }
+        log::info!(
+            "Running credential pre-flight for {} profiles",
+            settings.profiles.len()
+        );
+        run_preflight(&app, &settings).await;
+    }
+}
+
+/// The credential pre-flight schedule
+#[tauri::command]
+pub fn get_preflight_settings(app: AppHandle) -> PreflightSettings {
+    storage::read_json(&app, PREFLIGHT_FILE)
+}
+
+/// Configure the daily credential pre-flight (time, favorite profiles)
+#[tauri::command]
+pub fn set_preflight_settings(app: AppHandle, settings: PreflightSettings) -> Result<(), String> {
+    if let Some(time) = &settings.time {
+        parse_time(time)?;
+    }
+    for profile in &settings.profiles {
+        crate::validate_profile_name(profile)?;
+    }
+    if settings.enabled && settings.time.is_none() {
+        return Err("A pre-flight time is required".to_string());
+    }
+    let _guard = PREFLIGHT_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let previous: PreflightSettings = storage::read_json(&app, PREFLIGHT_FILE);
+    let settings = PreflightSettings {
+        last_run_date: previous.last_run_date,
+        ..settings
+    };
+    storage::write_json(&app, PREFLIGHT_FILE, &settings)
+}
+
+/// Run the pre-flight now for the configured profiles (or `profiles` if given)
+#[tauri::command]
+pub async fn run_credential_preflight(
+    app: AppHandle,
+    profiles: Option<Vec<String>>,
+) -> Result<Vec<PreflightResult>, String> {
+    let mut settings: PreflightSettings = storage::read_json(&app, PREFLIGHT_FILE);
+    if let Some(profiles) = profiles {
+        for profile in &profiles {
+            crate::validate_profile_name(profile)?;
+        }
+        settings.profiles = profiles;
+    }
+    Ok(run_preflight(&app, &settings).await)
+}
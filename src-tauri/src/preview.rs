@@ -0,0 +1,88 @@
+use crate::{redaction, storage, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding the message preview preference
+const PREVIEW_FILE: &str = "message-preview.json";
+
+/// Shortest preview allowed; anything less hides what the event is about
+const MIN_PREVIEW_LENGTH: usize = 80;
+
+/// How much of each message list views receive
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreviewSettings {
+    /// Maximum message length in bytes sent per event (full messages when None)
+    #[serde(default)]
+    pub max_message_length: Option<usize>,
+}
+
+fn load_settings(app: &AppHandle) -> PreviewSettings {
+    storage::read_json(app, PREVIEW_FILE)
+}
+
+/// Shorten messages longer than the preview preference, recording the full length
+/// in `full_length` so the view can offer the whole body via get_full_message.
+/// Runs after masking and computed columns, which need the whole message.
+pub fn truncate(app: &AppHandle, events: &mut [LogEvent]) {
+    let Some(max) = load_settings(app).max_message_length else {
+        return;
+    };
+    for event in events.iter_mut() {
+        if event.message.len() <= max {
+            continue;
+        }
+        let mut cut = max;
+        while !event.message.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        event.full_length = Some(event.message.len());
+        event.message.truncate(cut);
+        event.message.shrink_to_fit();
+    }
+}
+
+/// The message preview preference
+#[tauri::command]
+pub fn get_message_preview_settings(app: AppHandle) -> PreviewSettings {
+    load_settings(&app)
+}
+
+/// Set the maximum message length returned per event by fetches and list windows
+/// (None for full messages). Applies to the next fetch or window.
+#[tauri::command]
+pub fn set_message_preview_settings(
+    app: AppHandle,
+    settings: PreviewSettings,
+) -> Result<(), String> {
+    if settings
+        .max_message_length
+        .is_some_and(|max| max < MIN_PREVIEW_LENGTH)
+    {
+        return Err(format!(
+            "The message preview must be at least {} characters",
+            MIN_PREVIEW_LENGTH
+        ));
+    }
+    storage::write_json(&app, PREVIEW_FILE, &settings)
+}
+
+/// The full (masked) message of a session event whose preview was shortened
+#[tauri::command]
+pub async fn get_full_message(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_id: String,
+) -> Result<String, String> {
+    let event = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .and_then(|s| s.event(&event_id))
+        .ok_or_else(|| format!("Event {} is not in session {}", event_id, session_id))?;
+    let mut events = [event];
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    let [event] = events;
+    Ok(event.message)
+}
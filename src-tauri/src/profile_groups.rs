@@ -0,0 +1,119 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// File in the app config directory holding profile groups and labels
+const PROFILE_GROUPS_FILE: &str = "profile-groups.json";
+
+/// A labeled group of profiles in the profile picker, e.g. "Prod"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileGroup {
+    pub name: String,
+    /// Member profiles in picker order
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// `*` wildcards (e.g. "*-prod-admin") adding matching profiles not listed in
+    /// any group, sorted by name after the listed ones
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+/// Profile groups in picker order, and display labels for machine-named profiles
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileGroupSettings {
+    #[serde(default)]
+    pub groups: Vec<ProfileGroup>,
+    /// Profile name -> label shown instead of it
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+/// A profile as listed by list_aws_profiles
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileEntry {
+    pub name: String,
+    pub label: Option<String>,
+    /// Group the profile is in (None for ungrouped profiles, listed last)
+    pub group: Option<String>,
+}
+
+fn load_settings(app: &AppHandle) -> ProfileGroupSettings {
+    storage::read_json(app, PROFILE_GROUPS_FILE)
+}
+
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let regex = format!(
+        "^{}$",
+        pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*")
+    );
+    regex::Regex::new(&regex).is_ok_and(|re| re.is_match(name))
+}
+
+/// Order `profiles` (sorted by name) by group: each group's listed profiles in
+/// order, then its pattern matches, then ungrouped profiles
+pub fn arrange(app: &AppHandle, profiles: Vec<String>) -> Vec<ProfileEntry> {
+    let settings = load_settings(app);
+    let entry = |name: &str, group: Option<&str>| ProfileEntry {
+        name: name.to_string(),
+        label: settings.labels.get(name).cloned(),
+        group: group.map(str::to_string),
+    };
+    // Explicit membership wins over another group's pattern
+    let mut membership: HashMap<&str, &str> = HashMap::new();
+    for group in &settings.groups {
+        for name in group.profiles.iter().filter(|p| profiles.contains(p)) {
+            membership.entry(name).or_insert(&group.name);
+        }
+    }
+    let mut remaining: Vec<&String> = profiles
+        .iter()
+        .filter(|p| !membership.contains_key(p.as_str()))
+        .collect();
+
+    let mut arranged = Vec::with_capacity(profiles.len());
+    for group in &settings.groups {
+        for name in &group.profiles {
+            if membership.get(name.as_str()) == Some(&group.name.as_str()) {
+                membership.remove(name.as_str());
+                arranged.push(entry(name, Some(&group.name)));
+            }
+        }
+        let (matched, rest): (Vec<&String>, Vec<&String>) = remaining
+            .into_iter()
+            .partition(|name| group.patterns.iter().any(|p| matches_pattern(p, name)));
+        remaining = rest;
+        arranged.extend(
+            matched
+                .into_iter()
+                .map(|name| entry(name, Some(&group.name))),
+        );
+    }
+    arranged.extend(remaining.into_iter().map(|name| entry(name, None)));
+    arranged
+}
+
+/// Profile groups and labels
+#[tauri::command]
+pub fn get_profile_groups(app: AppHandle) -> ProfileGroupSettings {
+    load_settings(&app)
+}
+
+/// Save profile groups (in picker order) and labels
+#[tauri::command]
+pub fn save_profile_groups(app: AppHandle, settings: ProfileGroupSettings) -> Result<(), String> {
+    let mut names = std::collections::HashSet::new();
+    for group in &settings.groups {
+        if group.name.trim().is_empty() {
+            return Err("Profile groups need a name".to_string());
+        }
+        if !names.insert(group.name.as_str()) {
+            return Err(format!("There are two profile groups named {}", group.name));
+        }
+    }
+    storage::write_json(&app, PROFILE_GROUPS_FILE, &settings)
+}
@@ -0,0 +1,314 @@
+use crate::{storage, AppState, LogEvent};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+/// File in the app config directory holding redaction settings
+const REDACTION_FILE: &str = "redaction.json";
+
+/// Settings key used when no named profile is active
+const DEFAULT_PROFILE_KEY: &str = "default";
+
+/// Default replacement text for masked values
+const DEFAULT_REPLACEMENT: &str = "[REDACTED]";
+
+/// How long a reveal confirmation token stays valid
+const REVEAL_CHALLENGE_SECS: u64 = 60;
+
+/// Built-in rules for common customer data and credentials
+const BUILTIN_RULES: [(&str, &str, &str); 5] = [
+    (
+        "email",
+        r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+        "[EMAIL]",
+    ),
+    (
+        "jwt",
+        r"eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+",
+        "[JWT]",
+    ),
+    (
+        "bearer_token",
+        r"(?i)(bearer\s+)[A-Za-z0-9._~+/=-]+",
+        "${1}[REDACTED]",
+    ),
+    (
+        "aws_access_key",
+        r"\b(?:AKIA|ASIA)[A-Z0-9]{16}\b",
+        "[AWS_KEY]",
+    ),
+    (
+        "secret_assignment",
+        r#"(?i)((?:password|passwd|secret|token|api[_-]?key)"?\s*[:=]\s*"?)[^\s",}]+"#,
+        "${1}[REDACTED]",
+    ),
+];
+
+/// A user-defined masking rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionRule {
+    pub name: String,
+    /// Regex matched against the message
+    pub pattern: String,
+    /// Replacement text; may reference capture groups (${1})
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Display-time masking settings for one AWS profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileRedaction {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub use_builtin_rules: bool,
+    #[serde(default)]
+    pub rules: Vec<RedactionRule>,
+}
+
+/// Redaction settings keyed by profile name ("default" when no profile is set)
+pub type RedactionSettings = HashMap<String, ProfileRedaction>;
+
+/// Which sessions show unmasked data, and outstanding reveal confirmations
+#[derive(Debug, Default)]
+pub struct RevealState {
+    revealed: HashSet<String>,
+    challenges: HashMap<String, (String, Instant)>,
+}
+
+/// Compiled masking rules
+pub struct Redactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Redactor {
+    pub fn new(config: &ProfileRedaction) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        if config.use_builtin_rules {
+            for (_, pattern, replacement) in BUILTIN_RULES {
+                let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+                rules.push((regex, replacement.to_string()));
+            }
+        }
+        for rule in &config.rules {
+            let regex = Regex::new(&rule.pattern)
+                .map_err(|e| format!("Invalid redaction rule {}: {}", rule.name, e))?;
+            rules.push((
+                regex,
+                rule.replacement
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_REPLACEMENT.to_string()),
+            ));
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |acc, (regex, replacement)| {
+                regex.replace_all(&acc, replacement.as_str()).into_owned()
+            })
+    }
+
+    pub fn apply(&self, events: &mut [LogEvent]) {
+        for event in events {
+            event.message = self.redact(&event.message);
+        }
+    }
+}
+
+fn profile_key(profile: Option<&str>) -> String {
+    profile.unwrap_or(DEFAULT_PROFILE_KEY).to_string()
+}
+
+/// Compiled redactor of each profile key (None when masking is off for it), kept
+/// until the profile's settings are saved again
+pub type RedactorCache = HashMap<String, Option<Arc<Redactor>>>;
+
+/// The redactor for a profile, or None when masking is off for it
+pub fn redactor_for_profile(app: &AppHandle, profile: Option<&str>) -> Option<Arc<Redactor>> {
+    let key = profile_key(profile);
+    let state = app.try_state::<AppState>();
+    if let Some(state) = &state {
+        let cache = state.redactors.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return cached.clone();
+        }
+    }
+    let settings: RedactionSettings = storage::read_json(app, REDACTION_FILE);
+    let redactor = settings
+        .get(&key)
+        .filter(|c| c.enabled)
+        .and_then(|config| {
+            Redactor::new(config)
+                .map_err(|e| log::warn!("Redaction disabled: {}", e))
+                .ok()
+        })
+        .map(Arc::new);
+    if let Some(state) = &state {
+        state
+            .redactors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, redactor.clone());
+    }
+    redactor
+}
+
+/// Whether a session (result session or tail id) has been revealed
+pub async fn is_revealed(state: &AppState, session_id: Option<&str>) -> bool {
+    match session_id {
+        Some(id) => state.reveal.lock().await.revealed.contains(id),
+        None => false,
+    }
+}
+
+/// Mask events for display unless masking is off for the current profile or the
+/// session was explicitly revealed
+pub async fn mask_for_display(
+    app: &AppHandle,
+    state: &AppState,
+    session_id: Option<&str>,
+    events: &mut [LogEvent],
+) {
+    if is_revealed(state, session_id).await {
+        return;
+    }
+    let profile = state.current_profile.lock().await.clone();
+    if let Some(redactor) = redactor_for_profile(app, profile.as_deref()) {
+        redactor.apply(events);
+    }
+}
+
+/// Mask the field values of Logs Insights result rows like event messages.
+/// `@ptr` is left alone, as it is an opaque handle for fetching the record.
+pub async fn mask_rows_for_display(
+    app: &AppHandle,
+    state: &AppState,
+    session_id: Option<&str>,
+    rows: &mut [BTreeMap<String, String>],
+) {
+    if is_revealed(state, session_id).await {
+        return;
+    }
+    let profile = state.current_profile.lock().await.clone();
+    if let Some(redactor) = redactor_for_profile(app, profile.as_deref()) {
+        for (field, value) in rows.iter_mut().flat_map(|row| row.iter_mut()) {
+            if field != "@ptr" {
+                *value = redactor.redact(value);
+            }
+        }
+    }
+}
+
+/// Masking settings for the current profile
+#[tauri::command]
+pub async fn get_redaction_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ProfileRedaction, String> {
+    let profile = state.current_profile.lock().await.clone();
+    let mut settings: RedactionSettings = storage::read_json(&app, REDACTION_FILE);
+    Ok(settings
+        .remove(&profile_key(profile.as_deref()))
+        .unwrap_or_default())
+}
+
+/// Save masking settings for a profile (the current one when `profile` is None)
+#[tauri::command]
+pub async fn save_redaction_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile: Option<String>,
+    config: ProfileRedaction,
+) -> Result<(), String> {
+    Redactor::new(&config)?;
+    let profile = match profile {
+        Some(p) => Some(p),
+        None => state.current_profile.lock().await.clone(),
+    };
+    let key = profile_key(profile.as_deref());
+    let mut settings: RedactionSettings = storage::read_json(&app, REDACTION_FILE);
+    settings.insert(key.clone(), config);
+    storage::write_json(&app, REDACTION_FILE, &settings)?;
+    state
+        .redactors
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&key);
+    Ok(())
+}
+
+/// First step of revealing a session: returns a short-lived token the frontend
+/// passes to confirm_reveal after the user confirms
+#[tauri::command]
+pub async fn request_reveal(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    let token = format!(
+        "{:016x}",
+        std::collections::hash_map::RandomState::new()
+            .hash_one((&session_id, std::time::SystemTime::now()))
+    );
+    state
+        .reveal
+        .lock()
+        .await
+        .challenges
+        .insert(session_id, (token.clone(), Instant::now()));
+    Ok(token)
+}
+
+/// Second step of revealing a session: show unmasked data for it from now on
+#[tauri::command]
+pub async fn confirm_reveal(
+    state: State<'_, AppState>,
+    session_id: String,
+    token: String,
+) -> Result<(), String> {
+    let mut reveal = state.reveal.lock().await;
+    match reveal.challenges.remove(&session_id) {
+        Some((expected, issued))
+            if expected == token
+                && issued.elapsed() < Duration::from_secs(REVEAL_CHALLENGE_SECS) =>
+        {
+            log::info!("Sensitive data revealed for session {}", session_id);
+            reveal.revealed.insert(session_id);
+            Ok(())
+        }
+        _ => Err("Reveal confirmation expired or invalid; please confirm again".to_string()),
+    }
+}
+
+/// Mask a session again
+#[tauri::command]
+pub async fn hide_revealed(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    state.reveal.lock().await.revealed.remove(&session_id);
+    Ok(())
+}
+
+/// Whether a session currently shows unmasked data
+#[tauri::command]
+pub async fn is_session_revealed(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<bool, String> {
+    Ok(is_revealed(&state, Some(&session_id)).await)
+}
+
+/// Mask a batch for a running task that only holds an AppHandle (e.g. tails)
+pub async fn mask_with_handle(app: &AppHandle, session_id: &str, events: &mut [LogEvent]) {
+    if let Some(state) = app.try_state::<AppState>() {
+        mask_for_display(app, &state, Some(session_id), events).await;
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{redaction, AppState};
+use regex::RegexBuilder;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, State};
+
+/// Events tested when no sample size is given
+const DEFAULT_SAMPLE_SIZE: usize = 5_000;
+
+/// Largest sample tested
+const MAX_SAMPLE_SIZE: usize = 100_000;
+
+/// Matching events returned as examples
+const MAX_EXAMPLES: usize = 20;
+
+/// Most frequent values kept per capture group
+const MAX_TOP_VALUES: usize = 10;
+
+/// Longest example text returned
+const MAX_EXAMPLE_CHARS: usize = 300;
+
+/// Compiled size limit, so a pathological pattern fails instead of stalling
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// One matching event
+#[derive(Debug, Clone, Serialize)]
+pub struct RegexMatchExample {
+    pub event_id: Option<String>,
+    pub timestamp: i64,
+    /// Text of the first match
+    pub matched: String,
+    /// Capture group (name, or its number for unnamed groups) -> value
+    pub captures: BTreeMap<String, Option<String>>,
+}
+
+/// Frequency of one captured value
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureValue {
+    pub value: String,
+    pub count: usize,
+}
+
+/// How a candidate regex fares against loaded events
+#[derive(Debug, Clone, Serialize)]
+pub struct RegexTestResult {
+    pub sampled: usize,
+    /// Sampled events with at least one match
+    pub matched_events: usize,
+    /// Matches across the sampled events
+    pub total_matches: usize,
+    /// Names of the capture groups (numbers for unnamed ones), in pattern order
+    pub capture_groups: Vec<String>,
+    pub examples: Vec<RegexMatchExample>,
+    /// Capture group -> its most frequent values
+    pub top_values: BTreeMap<String, Vec<CaptureValue>>,
+}
+
+fn clip(text: &str) -> String {
+    text.chars().take(MAX_EXAMPLE_CHARS).collect()
+}
+
+/// Run a candidate regex (Rust regex syntax) against an evenly spread sample of a
+/// session's events, masked as displayed, returning match counts, capture
+/// examples and the most frequent captured values, so highlight and extractor
+/// rules can be checked before they are saved
+#[tauri::command]
+pub async fn test_regex(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    pattern: String,
+    sample_size: Option<usize>,
+    case_insensitive: Option<bool>,
+) -> Result<RegexTestResult, String> {
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive.unwrap_or(false))
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("Invalid pattern: {}", e))?;
+    let sample_size = sample_size
+        .unwrap_or(DEFAULT_SAMPLE_SIZE)
+        .clamp(1, MAX_SAMPLE_SIZE);
+
+    let mut sample = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let events = session.events();
+        let step = events.len().div_ceil(sample_size).max(1);
+        events.into_iter().step_by(step).collect::<Vec<_>>()
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut sample).await;
+
+    let capture_groups: Vec<String> = regex
+        .capture_names()
+        .enumerate()
+        .skip(1)
+        .map(|(i, name)| name.map_or_else(|| i.to_string(), str::to_string))
+        .collect();
+    let mut value_counts: Vec<HashMap<String, usize>> = vec![HashMap::new(); capture_groups.len()];
+    let mut matched_events = 0;
+    let mut total_matches = 0;
+    let mut examples = Vec::new();
+    for event in &sample {
+        let mut first = true;
+        for captures in regex.captures_iter(&event.message) {
+            total_matches += 1;
+            for (i, group) in captures.iter().skip(1).enumerate() {
+                if let Some(group) = group {
+                    *value_counts[i].entry(clip(group.as_str())).or_default() += 1;
+                }
+            }
+            if first {
+                first = false;
+                matched_events += 1;
+                if examples.len() < MAX_EXAMPLES {
+                    examples.push(RegexMatchExample {
+                        event_id: event.event_id.clone(),
+                        timestamp: event.timestamp,
+                        matched: clip(captures.get(0).map_or("", |m| m.as_str())),
+                        captures: capture_groups
+                            .iter()
+                            .zip(captures.iter().skip(1))
+                            .map(|(name, group)| (name.clone(), group.map(|g| clip(g.as_str()))))
+                            .collect(),
+                    });
+                }
+            }
+        }
+    }
+
+    let top_values = capture_groups
+        .iter()
+        .zip(value_counts)
+        .map(|(name, counts)| {
+            let mut values: Vec<CaptureValue> = counts
+                .into_iter()
+                .map(|(value, count)| CaptureValue { value, count })
+                .collect();
+            values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+            values.truncate(MAX_TOP_VALUES);
+            (name.clone(), values)
+        })
+        .collect();
+    Ok(RegexTestResult {
+        sampled: sample.len(),
+        matched_events,
+        total_matches,
+        capture_groups,
+        examples,
+        top_values,
+    })
+}
@@ -0,0 +1,304 @@
+use crate::{daily_summaries, storage, workspace, AppState};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+/// File in the app config directory mapping profiles to a SigV4 signing region
+const SIGNING_REGIONS_FILE: &str = "signing-regions.json";
+
+/// Serializes read-modify-write of the signing regions file
+static SIGNING_REGIONS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Key used for the default credential chain (no profile)
+const DEFAULT_PROFILE_KEY: &str = "default";
+
+/// SDK configs of the connected profile for the other regions its pinned groups
+/// live in, keyed by profile then region, so switching there skips config loading
+static WARM_CONFIGS: Mutex<Option<HashMap<String, HashMap<String, aws_config::SdkConfig>>>> =
+    Mutex::new(None);
+
+/// Regions enabled by default in every commercial account
+pub const DEFAULT_REGIONS: [&str; 17] = [
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-south-1",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ca-central-1",
+    "eu-central-1",
+    "eu-north-1",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "sa-east-1",
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+];
+
+/// Key of a connected client: (profile, region), no profile being the default chain
+pub type ClientKey = (Option<String>, String);
+
+/// A connected CloudWatch Logs client with the config it was built from
+#[derive(Clone)]
+pub struct RegionalClient {
+    pub client: CloudWatchClient,
+    pub config: aws_config::SdkConfig,
+    pub signing_region: Option<String>,
+}
+
+/// A region to offer in the region switcher
+#[derive(Debug, Clone, Serialize)]
+pub struct RegionInfo {
+    pub region: String,
+    pub opt_in: bool,
+    /// The region the app is connected to
+    pub current: bool,
+    /// A client of the connected profile is already open there (switching is instant)
+    pub connected: bool,
+}
+
+/// Regions disabled by default that an account has to opt in to
+pub const OPT_IN_REGIONS: [&str; 16] = [
+    "af-south-1",
+    "ap-east-1",
+    "ap-east-2",
+    "ap-south-2",
+    "ap-southeast-3",
+    "ap-southeast-4",
+    "ap-southeast-5",
+    "ap-southeast-7",
+    "ca-west-1",
+    "eu-central-2",
+    "eu-south-1",
+    "eu-south-2",
+    "il-central-1",
+    "me-central-1",
+    "me-south-1",
+    "mx-central-1",
+];
+
+pub fn is_opt_in(region: &str) -> bool {
+    OPT_IN_REGIONS.contains(&region)
+}
+
+/// The CloudWatch Logs endpoint of a region
+fn logs_endpoint(region: &str) -> String {
+    let suffix = if region.starts_with("cn-") {
+        "amazonaws.com.cn"
+    } else {
+        "amazonaws.com"
+    };
+    format!("https://logs.{}.{}", region, suffix)
+}
+
+/// Build the CloudWatch Logs client for a config. With `signing_region`, requests
+/// still go to the config region's endpoint but are signed for `signing_region`
+/// (for gateways and partitions where the two differ).
+pub fn logs_client(
+    config: &aws_config::SdkConfig,
+    signing_region: Option<&str>,
+) -> CloudWatchClient {
+    let endpoint_region = config.region().map(|r| r.to_string());
+    match (signing_region, endpoint_region) {
+        (Some(signing), Some(endpoint)) if signing != endpoint => {
+            let logs_config = aws_sdk_cloudwatchlogs::config::Builder::from(config)
+                .region(aws_config::Region::new(signing.to_string()))
+                .endpoint_url(logs_endpoint(&endpoint))
+                .build();
+            CloudWatchClient::from_conf(logs_config)
+        }
+        _ => CloudWatchClient::new(config),
+    }
+}
+
+fn load_signing_regions(app: &AppHandle) -> BTreeMap<String, String> {
+    storage::read_json(app, SIGNING_REGIONS_FILE)
+}
+
+/// Signing region override configured for a profile
+pub fn signing_region_for(app: &AppHandle, profile: Option<&str>) -> Option<String> {
+    load_signing_regions(app).remove(profile.unwrap_or(DEFAULT_PROFILE_KEY))
+}
+
+/// Region field of an ARN (arn:partition:service:region:account:resource)
+fn arn_region(arn: &str) -> Option<&str> {
+    arn.strip_prefix("arn:")?
+        .split(':')
+        .nth(2)
+        .filter(|r| !r.is_empty())
+}
+
+/// Regions of a profile's pinned groups: its workspaces' regions and the regions
+/// of log groups pinned by ARN (in workspaces and daily summaries)
+fn pinned_regions(app: &AppHandle, profile: Option<&str>) -> BTreeSet<String> {
+    let mut regions = BTreeSet::new();
+    for workspace in workspace::load_workspaces(app) {
+        if workspace
+            .profile
+            .as_deref()
+            .is_some_and(|p| Some(p) != profile)
+        {
+            continue;
+        }
+        regions.extend(workspace.region);
+        regions.extend(
+            workspace
+                .log_groups
+                .iter()
+                .filter_map(|g| arn_region(g))
+                .map(str::to_string),
+        );
+    }
+    regions.extend(
+        daily_summaries::pinned_groups(app)
+            .iter()
+            .filter(|g| g.profile.as_deref() == profile)
+            .filter_map(|g| arn_region(&g.log_group_name))
+            .map(str::to_string),
+    );
+    regions
+}
+
+/// After connecting, derive configs for the other regions the profile's pinned
+/// groups live in. Deriving shares the loaded credentials provider, so it costs
+/// no I/O; the first switch to one of those regions then skips config resolution.
+pub fn prewarm(app: &AppHandle, profile: Option<&str>, config: &aws_config::SdkConfig) {
+    let home = config.region().map(|r| r.to_string());
+    let configs: HashMap<String, aws_config::SdkConfig> = pinned_regions(app, profile)
+        .into_iter()
+        .filter(|region| Some(region) != home.as_ref())
+        .map(|region| {
+            let regional = config
+                .to_builder()
+                .region(aws_config::Region::new(region.clone()))
+                .build();
+            (region, regional)
+        })
+        .collect();
+    if !configs.is_empty() {
+        log::debug!(
+            "Prepared configs for {} pinned regions of {}",
+            configs.len(),
+            profile.unwrap_or(DEFAULT_PROFILE_KEY)
+        );
+    }
+    WARM_CONFIGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(profile.unwrap_or(DEFAULT_PROFILE_KEY).to_string(), configs);
+}
+
+/// A prepared config of `profile` for `region`, if prewarm made one
+pub fn warm_config(profile: Option<&str>, region: &str) -> Option<aws_config::SdkConfig> {
+    WARM_CONFIGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()?
+        .get(profile.unwrap_or(DEFAULT_PROFILE_KEY))?
+        .get(region)
+        .cloned()
+}
+
+/// Guidance when a request failed because the region is an opt-in region the
+/// account (or the credentials' STS token) isn't enabled for
+pub fn opt_in_hint(region: Option<&str>, error: &str) -> Option<String> {
+    let region = region.filter(|r| is_opt_in(r))?;
+    let error = error.to_lowercase();
+    let token_rejected = error.contains("unrecognizedclient")
+        || error.contains("security token included in the request is invalid")
+        || error.contains("invalidclienttokenid")
+        || error.contains("signature");
+    token_rejected.then(|| {
+        format!(
+            "{} is an opt-in region. Enable it for the account (Account settings > AWS Regions), \
+             and make sure credentials come from a regional STS endpoint; if a proxy or gateway \
+             expects another signing region, set a signing region override for this profile.",
+            region
+        )
+    })
+}
+
+/// Regions to switch between: the default and opt-in commercial regions plus any
+/// other region the connected profile already has a client in
+#[tauri::command]
+pub async fn list_regions(state: State<'_, AppState>) -> Result<Vec<RegionInfo>, String> {
+    let profile = state.current_profile.lock().await.clone();
+    let current = state
+        .config
+        .lock()
+        .await
+        .as_ref()
+        .and_then(|c| c.region().map(|r| r.to_string()));
+    let connected: BTreeSet<String> = state
+        .clients
+        .lock()
+        .await
+        .keys()
+        .filter(|(p, _)| *p == profile)
+        .map(|(_, region)| region.clone())
+        .collect();
+
+    let mut names: BTreeSet<String> = DEFAULT_REGIONS
+        .iter()
+        .chain(OPT_IN_REGIONS.iter())
+        .map(|r| r.to_string())
+        .collect();
+    names.extend(connected.iter().cloned());
+    names.extend(current.clone());
+    Ok(names
+        .into_iter()
+        .map(|region| RegionInfo {
+            opt_in: is_opt_in(&region),
+            current: current.as_ref() == Some(&region),
+            connected: connected.contains(&region),
+            region,
+        })
+        .collect())
+}
+
+/// The SigV4 signing region override of a profile (None for the default chain)
+#[tauri::command]
+pub fn get_signing_region(app: AppHandle, profile: Option<String>) -> Option<String> {
+    signing_region_for(&app, profile.as_deref())
+}
+
+/// Sign a profile's CloudWatch Logs requests for `signing_region` instead of the
+/// connected region, or remove the override with None. Applies on the next connect.
+#[tauri::command]
+pub fn set_signing_region(
+    app: AppHandle,
+    profile: Option<String>,
+    signing_region: Option<String>,
+) -> Result<(), String> {
+    if let Some(profile) = &profile {
+        crate::validate_profile_name(profile)?;
+    }
+    let signing_region = signing_region
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    if let Some(region) = &signing_region {
+        let valid = region
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && region.split('-').count() >= 3;
+        if !valid {
+            return Err(format!("{} is not a valid region name", region));
+        }
+    }
+    let key = profile.unwrap_or_else(|| DEFAULT_PROFILE_KEY.to_string());
+    let _guard = SIGNING_REGIONS_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut regions = load_signing_regions(&app);
+    match signing_region {
+        Some(region) => regions.insert(key, region),
+        None => regions.remove(&key),
+    };
+    storage::write_json(&app, SIGNING_REGIONS_FILE, &regions)
+}
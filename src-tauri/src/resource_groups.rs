@@ -0,0 +1,352 @@
+use crate::catalog::{describe_all_log_groups, log_group_name_from_arn};
+use crate::workspace::{self, WorkspaceConfig};
+use crate::{humanize_aws_error, AppState};
+use aws_sdk_resourcegroups::Client as ResourceGroupsClient;
+use aws_sdk_resourcegroupstagging::types::TagFilter;
+use aws_sdk_resourcegroupstagging::Client as TaggingClient;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::{AppHandle, State};
+
+/// A resource group defined in AWS Resource Groups
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceGroupSummary {
+    pub name: String,
+    pub arn: Option<String>,
+    pub description: Option<String>,
+}
+
+/// One tag condition of a Tag Editor style query; no value matches any value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCondition {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// Where the set of resources comes from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceSelection {
+    /// A saved group in AWS Resource Groups (tag or CloudFormation based)
+    Group { name: String },
+    /// An ad-hoc tag query; all conditions must match (e.g. team=checkout)
+    Tags { tags: Vec<TagCondition> },
+}
+
+/// How a log group was reached from a resource in the selection
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedLogGroup {
+    pub log_group_name: String,
+    pub resource_arn: String,
+    /// "log-group" when the resource is the log group itself, otherwise the
+    /// service whose naming convention produced it (e.g. "lambda")
+    pub via: String,
+}
+
+/// Log groups assembled from a resource selection
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceGroupLogGroups {
+    pub log_groups: Vec<ResolvedLogGroup>,
+    /// Resources in the selection with no known log group convention
+    pub unmapped_resources: Vec<String>,
+    /// Log groups the resources would write to that don't exist (yet)
+    pub missing_log_groups: Vec<String>,
+    /// Resources AWS Resource Groups couldn't evaluate
+    pub query_errors: Vec<String>,
+}
+
+/// Log group (or log group prefix) a resource writes to by convention
+enum LogTarget {
+    Exact(String),
+    Prefix(String),
+}
+
+/// Map a resource ARN to its log group(s) and the service convention used
+fn log_target(arn: &str) -> Option<(LogTarget, &'static str)> {
+    if let Some(name) = log_group_name_from_arn(arn) {
+        return Some((LogTarget::Exact(name), "log-group"));
+    }
+    let mut parts = arn.splitn(6, ':');
+    let (_, _, service, _, _) = (
+        parts.next()?,
+        parts.next()?,
+        parts.next()?,
+        parts.next()?,
+        parts.next()?,
+    );
+    let resource = parts.next()?;
+    let named = |prefix: &str| resource.strip_prefix(prefix).filter(|n| !n.is_empty());
+    match service {
+        "lambda" => named("function:").map(|n| {
+            let n = n.split(':').next().unwrap_or(n);
+            (LogTarget::Exact(format!("/aws/lambda/{}", n)), "lambda")
+        }),
+        "codebuild" => named("project/").map(|n| {
+            (
+                LogTarget::Exact(format!("/aws/codebuild/{}", n)),
+                "codebuild",
+            )
+        }),
+        "rds" => named("db:")
+            .map(|n| {
+                (
+                    LogTarget::Prefix(format!("/aws/rds/instance/{}/", n)),
+                    "rds",
+                )
+            })
+            .or_else(|| {
+                named("cluster:")
+                    .map(|n| (LogTarget::Prefix(format!("/aws/rds/cluster/{}/", n)), "rds"))
+            }),
+        "ecs" => named("cluster/").map(|n| {
+            (
+                LogTarget::Exact(format!("/aws/ecs/containerinsights/{}/performance", n)),
+                "ecs",
+            )
+        }),
+        "eks" => {
+            named("cluster/").map(|n| (LogTarget::Exact(format!("/aws/eks/{}/cluster", n)), "eks"))
+        }
+        "apigateway" => named("/restapis/").map(|n| {
+            let id = n.split('/').next().unwrap_or(n);
+            (
+                LogTarget::Prefix(format!("API-Gateway-Execution-Logs_{}/", id)),
+                "apigateway",
+            )
+        }),
+        "states" => named("stateMachine:").map(|n| {
+            (
+                LogTarget::Prefix(format!("/aws/vendedlogs/states/{}", n)),
+                "states",
+            )
+        }),
+        _ => None,
+    }
+}
+
+/// Resource ARNs in a saved resource group, plus any query errors
+async fn group_resource_arns(
+    config: &aws_config::SdkConfig,
+    group_name: &str,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let client = ResourceGroupsClient::new(config);
+    let mut arns = Vec::new();
+    let mut errors = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .list_group_resources()
+            .group(group_name)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        arns.extend(
+            response
+                .resources()
+                .iter()
+                .filter_map(|r| r.identifier()?.resource_arn())
+                .map(|a| a.to_string()),
+        );
+        errors.extend(
+            response
+                .query_errors()
+                .iter()
+                .filter_map(|e| e.message())
+                .map(|m| m.to_string()),
+        );
+        next_token = response.next_token.filter(|t| !t.is_empty());
+        if next_token.is_none() {
+            return Ok((arns, errors));
+        }
+    }
+}
+
+/// Resource ARNs of every resource carrying all the tags, via the tagging API
+async fn tagged_resource_arns(
+    config: &aws_config::SdkConfig,
+    tags: &[TagCondition],
+) -> Result<Vec<String>, String> {
+    let client = TaggingClient::new(config);
+    let filters: Vec<TagFilter> = tags
+        .iter()
+        .map(|t| {
+            TagFilter::builder()
+                .key(t.key.trim())
+                .set_values(t.value.as_ref().map(|v| vec![v.clone()]))
+                .build()
+        })
+        .collect();
+
+    let mut arns = Vec::new();
+    let mut pagination_token: Option<String> = None;
+    loop {
+        let response = client
+            .get_resources()
+            .set_tag_filters(Some(filters.clone()))
+            .set_pagination_token(pagination_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        arns.extend(
+            response
+                .resource_tag_mapping_list()
+                .iter()
+                .filter_map(|m| m.resource_arn())
+                .map(|a| a.to_string()),
+        );
+        pagination_token = response.pagination_token.filter(|t| !t.is_empty());
+        if pagination_token.is_none() {
+            return Ok(arns);
+        }
+    }
+}
+
+async fn resolve(
+    state: &AppState,
+    selection: &ResourceSelection,
+) -> Result<ResourceGroupLogGroups, String> {
+    let config = state.sdk_config().await?;
+    let client = state.logs_client().await?;
+
+    let (arns, query_errors) = match selection {
+        ResourceSelection::Group { name } => {
+            if name.trim().is_empty() {
+                return Err("Resource group name cannot be empty".to_string());
+            }
+            group_resource_arns(&config, name.trim()).await?
+        }
+        ResourceSelection::Tags { tags } => {
+            if tags.is_empty() || tags.iter().any(|t| t.key.trim().is_empty()) {
+                return Err("Tag query needs at least one tag key".to_string());
+            }
+            (tagged_resource_arns(&config, tags).await?, Vec::new())
+        }
+    };
+
+    let existing: BTreeSet<String> = describe_all_log_groups(&client)
+        .await?
+        .into_iter()
+        .map(|g| g.name)
+        .collect();
+
+    let mut found: BTreeMap<String, ResolvedLogGroup> = BTreeMap::new();
+    let mut unmapped_resources = Vec::new();
+    let mut missing_log_groups = BTreeSet::new();
+    for arn in arns {
+        let Some((target, via)) = log_target(&arn) else {
+            unmapped_resources.push(arn);
+            continue;
+        };
+        let names: Vec<&String> = match &target {
+            LogTarget::Exact(name) => existing.get(name).into_iter().collect(),
+            LogTarget::Prefix(prefix) => existing
+                .range(prefix.clone()..)
+                .take_while(|n| n.starts_with(prefix.as_str()))
+                .collect(),
+        };
+        if names.is_empty() {
+            missing_log_groups.insert(match target {
+                LogTarget::Exact(name) => name,
+                LogTarget::Prefix(prefix) => format!("{}*", prefix),
+            });
+            continue;
+        }
+        for name in names {
+            found
+                .entry(name.clone())
+                .or_insert_with(|| ResolvedLogGroup {
+                    log_group_name: name.clone(),
+                    resource_arn: arn.clone(),
+                    via: via.to_string(),
+                });
+        }
+    }
+    unmapped_resources.sort();
+
+    Ok(ResourceGroupLogGroups {
+        log_groups: found.into_values().collect(),
+        unmapped_resources,
+        missing_log_groups: missing_log_groups.into_iter().collect(),
+        query_errors,
+    })
+}
+
+/// List the resource groups defined in AWS Resource Groups
+#[tauri::command]
+pub async fn list_resource_groups(
+    state: State<'_, AppState>,
+) -> Result<Vec<ResourceGroupSummary>, String> {
+    let config = state.sdk_config().await?;
+    let client = ResourceGroupsClient::new(&config);
+    let mut groups = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let response = client
+            .list_groups()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+        for group in response.group_identifiers() {
+            let Some(name) = group.group_name() else {
+                continue;
+            };
+            groups.push(ResourceGroupSummary {
+                name: name.to_string(),
+                arn: group.group_arn().map(|a| a.to_string()),
+                description: group.description().map(|d| d.to_string()),
+            });
+        }
+        next_token = response.next_token.filter(|t| !t.is_empty());
+        if next_token.is_none() {
+            break;
+        }
+    }
+    groups.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(groups)
+}
+
+/// Log groups of the resources in a resource group or tag query, found directly
+/// (log group resources) or by the owning service's naming convention
+#[tauri::command]
+pub async fn resolve_resource_group_log_groups(
+    state: State<'_, AppState>,
+    selection: ResourceSelection,
+) -> Result<ResourceGroupLogGroups, String> {
+    resolve(&state, &selection).await
+}
+
+/// Save the log groups of a resource group or tag query as a workspace for the
+/// connected profile and region, replacing any workspace with the same name
+#[tauri::command]
+pub async fn create_workspace_from_resource_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    workspace_name: String,
+    selection: ResourceSelection,
+) -> Result<WorkspaceConfig, String> {
+    let resolved = resolve(&state, &selection).await?;
+    if resolved.log_groups.is_empty() {
+        return Err("No existing log groups belong to the selected resources".to_string());
+    }
+
+    let config = WorkspaceConfig {
+        name: workspace_name.trim().to_string(),
+        profile: state.current_profile.lock().await.clone(),
+        region: state.sdk_config().await?.region().map(|r| r.to_string()),
+        log_groups: resolved
+            .log_groups
+            .into_iter()
+            .map(|g| g.log_group_name)
+            .collect(),
+        filter_pattern: None,
+        time_range: None,
+        fetch_limits: None,
+        alert_thresholds: Vec::new(),
+        shared: false,
+    };
+    workspace::save_workspace(app, config.clone())?;
+    Ok(config)
+}
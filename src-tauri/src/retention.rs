@@ -0,0 +1,193 @@
+use crate::connections::EnvironmentEmitter;
+use crate::{export, humanize_aws_error, AppState};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+const DAY_MS: i64 = 86_400_000;
+
+/// Warn about loaded data that ages out of retention within this many days
+const DEFAULT_WARNING_DAYS: i64 = 3;
+
+/// How long a group's retention setting is reused before describing it again
+const RETENTION_CACHE_TTL: Duration = Duration::from_secs(600);
+
+type RetentionCache = HashMap<String, (Option<i32>, Instant)>;
+
+static RETENTION_CACHE: Mutex<Option<RetentionCache>> = Mutex::new(None);
+
+/// Loaded data of a log group that CloudWatch will delete soon
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionWarning {
+    pub log_group_name: String,
+    pub retention_days: i32,
+    /// Events older than this are already gone (now - retention)
+    pub horizon: i64,
+    /// Loaded events from `at_risk_from` to `at_risk_until` expire within the
+    /// warning window; export this range to keep them
+    pub at_risk_from: i64,
+    pub at_risk_until: i64,
+    /// Days until the oldest loaded event expires (0 when it already has)
+    pub days_left: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// A group's retention in days (None for never expire), cached for a few minutes
+async fn retention_days(
+    client: &CloudWatchClient,
+    log_group_name: &str,
+) -> Result<Option<i32>, String> {
+    let cached = RETENTION_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(log_group_name).copied())
+        .filter(|(_, at)| at.elapsed() < RETENTION_CACHE_TTL);
+    if let Some((days, _)) = cached {
+        return Ok(days);
+    }
+    let response = client
+        .describe_log_groups()
+        .log_group_name_prefix(log_group_name)
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+    let days = response
+        .log_groups()
+        .iter()
+        .find(|g| g.log_group_name() == Some(log_group_name))
+        .and_then(|g| g.retention_in_days());
+    RETENTION_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(log_group_name.to_string(), (days, Instant::now()));
+    Ok(days)
+}
+
+/// The warning for loaded data reaching back to `oldest` (up to `newest`), or None
+/// when none of it expires within `warning_days`
+fn assess(
+    log_group_name: &str,
+    retention_days: Option<i32>,
+    oldest: i64,
+    newest: i64,
+    warning_days: i64,
+) -> Option<RetentionWarning> {
+    let retention_days = retention_days?;
+    let horizon = chrono::Utc::now().timestamp_millis() - i64::from(retention_days) * DAY_MS;
+    let warn_before = horizon + warning_days * DAY_MS;
+    if oldest >= warn_before {
+        return None;
+    }
+    Some(RetentionWarning {
+        log_group_name: log_group_name.to_string(),
+        retention_days,
+        horizon,
+        at_risk_from: oldest.max(horizon),
+        at_risk_until: newest.min(warn_before),
+        days_left: ((oldest - horizon) / DAY_MS).max(0),
+        fetch_id: None,
+        session_id: None,
+    })
+}
+
+/// After a fetch, emit `retention-warning` in the background when the oldest
+/// loaded events are within a few days of the group's retention horizon
+pub fn warn_if_expiring(
+    app: &AppHandle,
+    log_group_name: &str,
+    range: Option<(i64, i64)>,
+    fetch_id: u32,
+    session_id: Option<String>,
+) {
+    let Some((oldest, newest)) = range else {
+        return;
+    };
+    let app = app.clone();
+    let log_group_name = log_group_name.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let Ok(client) = state.logs_client().await else {
+            return;
+        };
+        let days = match retention_days(&client, &log_group_name).await {
+            Ok(days) => days,
+            Err(e) => {
+                log::debug!("Retention check for {} failed: {}", log_group_name, e);
+                return;
+            }
+        };
+        if let Some(mut warning) =
+            assess(&log_group_name, days, oldest, newest, DEFAULT_WARNING_DAYS)
+        {
+            warning.fetch_id = Some(fetch_id);
+            warning.session_id = session_id;
+            app.emit_tagged("retention-warning", warning).ok();
+        }
+    });
+}
+
+/// Whether loaded data from `start_time` to `end_time` is within `warning_days`
+/// (default 3) of ageing out of the group's retention
+#[tauri::command]
+pub async fn check_retention_horizon(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: i64,
+    end_time: i64,
+    warning_days: Option<i64>,
+) -> Result<Option<RetentionWarning>, String> {
+    let client = state.logs_client().await?;
+    let days = retention_days(&client, &log_group_name).await?;
+    Ok(assess(
+        &log_group_name,
+        days,
+        start_time,
+        end_time,
+        warning_days.unwrap_or(DEFAULT_WARNING_DAYS),
+    ))
+}
+
+/// One-click export of a session's events from `log_group_name` that expire
+/// within the warning window, as a standalone HTML file at `path` or the default
+/// export path
+#[tauri::command]
+pub async fn export_expiring_events(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    log_group_name: String,
+    path: Option<String>,
+    warning_days: Option<i64>,
+) -> Result<export::ExportedFile, String> {
+    let client = state.logs_client().await?;
+    let days = retention_days(&client, &log_group_name)
+        .await?
+        .ok_or_else(|| format!("{} never expires its events", log_group_name))?;
+    let warn_before = chrono::Utc::now().timestamp_millis() - i64::from(days) * DAY_MS
+        + warning_days.unwrap_or(DEFAULT_WARNING_DAYS) * DAY_MS;
+    let event_ids: Vec<String> = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|s| s.events())
+        .ok_or_else(|| format!("Session {} not found", session_id))?
+        .into_iter()
+        .filter(|e| e.source.as_deref() == Some(log_group_name.as_str()))
+        .filter(|e| e.timestamp < warn_before)
+        .filter_map(|e| e.event_id)
+        .collect();
+    if event_ids.is_empty() {
+        return Err("No loaded events are close to expiring".to_string());
+    }
+    let title = format!("{} (expiring within retention)", log_group_name);
+    export::export_html(app, state, session_id, Some(event_ids), path, Some(title)).await
+}
@@ -0,0 +1,500 @@
+use crate::{storage, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tauri::{AppHandle, State};
+
+/// File in the app config directory holding discovered schemas per log group
+const SCHEMAS_FILE: &str = "schemas.json";
+
+/// Events sampled from each fetch to learn fields from
+const SAMPLE_SIZE: usize = 500;
+
+/// Deepest nested object path recorded ("a.b.c.d.e.f")
+const MAX_DEPTH: usize = 6;
+
+/// Cap on distinct fields remembered per log group
+const MAX_FIELDS_PER_GROUP: usize = 500;
+
+/// Serializes read-modify-write of the schemas file
+static SCHEMAS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Unit of a field's values, inferred from its name and values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldUnit {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Bytes,
+    Kilobytes,
+    Megabytes,
+    Count,
+    Percent,
+    Boolean,
+}
+
+impl FieldUnit {
+    /// Factor converting a value to the unit aggregations report in
+    /// (milliseconds for durations, bytes for sizes)
+    fn to_base(self) -> f64 {
+        match self {
+            FieldUnit::Nanoseconds => 1e-6,
+            FieldUnit::Microseconds => 1e-3,
+            FieldUnit::Seconds => 1000.0,
+            FieldUnit::Kilobytes => 1024.0,
+            FieldUnit::Megabytes => 1024.0 * 1024.0,
+            _ => 1.0,
+        }
+    }
+
+    fn is_duration(self) -> bool {
+        matches!(
+            self,
+            FieldUnit::Nanoseconds
+                | FieldUnit::Microseconds
+                | FieldUnit::Milliseconds
+                | FieldUnit::Seconds
+        )
+    }
+
+    fn is_size(self) -> bool {
+        matches!(
+            self,
+            FieldUnit::Bytes | FieldUnit::Kilobytes | FieldUnit::Megabytes
+        )
+    }
+}
+
+/// What is known about one field of a log group
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldInfo {
+    /// JSON types seen for the field: string, number, boolean, object, array, null
+    pub types: BTreeSet<String>,
+    pub occurrences: u64,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// Inferred unit, used to format aggregations (None when unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<FieldUnit>,
+}
+
+/// Fields discovered in a log group's JSON events over time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupSchema {
+    /// Field path ("request.id") -> info
+    pub fields: BTreeMap<String, FieldInfo>,
+    /// JSON events examined so far
+    pub sampled_events: u64,
+    pub updated_at: i64,
+}
+
+fn json_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Object(_) => "object",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Null => "null",
+    }
+}
+
+/// Unit suffixes accepted on string values ("250ms", "1.5 s", "12KB")
+const VALUE_SUFFIXES: [(&str, FieldUnit); 9] = [
+    ("ns", FieldUnit::Nanoseconds),
+    ("us", FieldUnit::Microseconds),
+    ("µs", FieldUnit::Microseconds),
+    ("ms", FieldUnit::Milliseconds),
+    ("s", FieldUnit::Seconds),
+    ("kb", FieldUnit::Kilobytes),
+    ("mb", FieldUnit::Megabytes),
+    ("b", FieldUnit::Bytes),
+    ("%", FieldUnit::Percent),
+];
+
+/// A string value with a unit suffix, as (number, unit)
+fn parse_suffixed(value: &str) -> Option<(f64, FieldUnit)> {
+    let lower = value.trim().to_lowercase();
+    VALUE_SUFFIXES.iter().find_map(|(suffix, unit)| {
+        let number = lower.strip_suffix(suffix)?.trim_end();
+        number.parse::<f64>().ok().map(|n| (n, *unit))
+    })
+}
+
+/// Words of a field name's last path segment, lowercased
+/// ("response.durationMs" -> ["duration", "ms"])
+fn name_words(path: &str) -> Vec<String> {
+    let name = path.rsplit('.').next().unwrap_or(path);
+    let mut words: Vec<String> = Vec::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c == '_' || c == '-' || c == ' ' {
+            words.push(String::new());
+            previous_lower = false;
+            continue;
+        }
+        if (c.is_uppercase() && previous_lower) || words.is_empty() {
+            words.push(String::new());
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        if let Some(word) = words.last_mut() {
+            word.extend(c.to_lowercase());
+        }
+    }
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+/// Unit implied by a field name ("durationMs", "response_bytes", "retry_count")
+fn unit_from_name(path: &str) -> Option<FieldUnit> {
+    let words = name_words(path);
+    let last = words.last()?.as_str();
+    let unit = match last {
+        "ns" | "nanos" | "nanoseconds" => FieldUnit::Nanoseconds,
+        "us" | "micros" | "microseconds" => FieldUnit::Microseconds,
+        "ms" | "millis" | "milliseconds" => FieldUnit::Milliseconds,
+        "s" | "sec" | "secs" | "seconds" => FieldUnit::Seconds,
+        "kb" | "kib" => FieldUnit::Kilobytes,
+        "mb" | "mib" => FieldUnit::Megabytes,
+        "bytes" | "size" | "length" => FieldUnit::Bytes,
+        "percent" | "pct" => FieldUnit::Percent,
+        "count" | "total" | "retries" | "attempts" => FieldUnit::Count,
+        // Unsuffixed durations are milliseconds in most AWS and APM logs
+        "duration" | "latency" | "elapsed" => FieldUnit::Milliseconds,
+        _ if words[0] == "num" => FieldUnit::Count,
+        _ => return None,
+    };
+    Some(unit)
+}
+
+/// Unit of one field occurrence, from its value first, then its name
+fn infer_unit(path: &str, value: &serde_json::Value) -> Option<FieldUnit> {
+    match value {
+        serde_json::Value::Bool(_) => Some(FieldUnit::Boolean),
+        serde_json::Value::String(s) if s == "true" || s == "false" => Some(FieldUnit::Boolean),
+        serde_json::Value::String(s) => parse_suffixed(s)
+            .map(|(_, unit)| unit)
+            .or_else(|| s.parse::<f64>().ok().and_then(|_| unit_from_name(path))),
+        serde_json::Value::Number(_) => unit_from_name(path),
+        _ => None,
+    }
+}
+
+/// Collect (path, type, unit) for every field of a JSON object, descending into
+/// nested objects but not arrays
+fn collect_fields(
+    value: &serde_json::Value,
+    prefix: &str,
+    depth: usize,
+    out: &mut Vec<(String, &'static str, Option<FieldUnit>)>,
+) {
+    let serde_json::Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        out.push((path.clone(), json_type(child), infer_unit(&path, child)));
+        if depth + 1 < MAX_DEPTH {
+            collect_fields(child, &path, depth + 1, out);
+        }
+    }
+}
+
+/// Merge the fields of a batch of events into a group schema
+fn learn(schema: &mut GroupSchema, events: &[LogEvent]) {
+    let now = chrono::Utc::now().timestamp_millis();
+    for event in events {
+        let trimmed = event.message.trim();
+        if !trimmed.starts_with('{') {
+            continue;
+        }
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(trimmed) else {
+            continue;
+        };
+        schema.sampled_events += 1;
+
+        let mut fields = Vec::new();
+        collect_fields(&json, "", 0, &mut fields);
+        for (path, kind, unit) in fields {
+            if !schema.fields.contains_key(&path) && schema.fields.len() >= MAX_FIELDS_PER_GROUP {
+                continue;
+            }
+            let info = schema.fields.entry(path).or_insert_with(|| FieldInfo {
+                first_seen: now,
+                ..FieldInfo::default()
+            });
+            info.types.insert(kind.to_string());
+            if info.unit.is_none() {
+                info.unit = unit;
+            }
+            info.occurrences += 1;
+            info.last_seen = now;
+        }
+    }
+    schema.updated_at = now;
+}
+
+/// Evenly spaced sample of at most SAMPLE_SIZE events
+fn sample(events: &[LogEvent]) -> Vec<LogEvent> {
+    let step = events.len().div_ceil(SAMPLE_SIZE).max(1);
+    events.iter().step_by(step).cloned().collect()
+}
+
+/// Learn fields from fetched events in the background so later sessions on the
+/// same group get autocomplete and column choices without re-sampling
+pub fn record_sample(app: &AppHandle, log_group: &str, events: &[LogEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let log_group = log_group.to_string();
+    let events = sample(events);
+    tauri::async_runtime::spawn_blocking(move || {
+        let _guard = SCHEMAS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut schemas: HashMap<String, GroupSchema> = storage::read_json(&app, SCHEMAS_FILE);
+        let schema = schemas.entry(log_group).or_default();
+        let before = schema.fields.len();
+        learn(schema, &events);
+        if schema.sampled_events == 0 && before == 0 {
+            // Not a JSON log group; nothing worth persisting
+            return;
+        }
+        if let Err(e) = storage::write_json(&app, SCHEMAS_FILE, &schemas) {
+            log::warn!("Failed to persist field schemas: {}", e);
+        }
+    });
+}
+
+/// Fields discovered so far for a log group (None if it was never sampled)
+#[tauri::command]
+pub fn get_group_schema(app: AppHandle, log_group_name: String) -> Option<GroupSchema> {
+    let mut schemas: HashMap<String, GroupSchema> = storage::read_json(&app, SCHEMAS_FILE);
+    schemas.remove(&log_group_name)
+}
+
+/// Forget the discovered fields of a log group
+#[tauri::command]
+pub fn clear_group_schema(app: AppHandle, log_group_name: String) -> Result<(), String> {
+    let _guard = SCHEMAS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut schemas: HashMap<String, GroupSchema> = storage::read_json(&app, SCHEMAS_FILE);
+    if schemas.remove(&log_group_name).is_some() {
+        storage::write_json(&app, SCHEMAS_FILE, &schemas)?;
+    }
+    Ok(())
+}
+
+/// Statistics of a numeric or boolean field over a session, in the field's base
+/// unit (milliseconds for durations, bytes for sizes)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FieldAggregate {
+    pub field: String,
+    pub unit: Option<FieldUnit>,
+    /// Events with a usable value
+    pub count: usize,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+    /// For booleans
+    pub true_count: usize,
+    pub false_count: usize,
+    /// The statistics formatted for display ("1.2 s", "3.4 MiB"), by name
+    pub formatted: BTreeMap<String, String>,
+}
+
+fn lookup<'a>(json: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(json, |value, key| value.get(key))
+}
+
+/// A value in the field's base unit
+fn numeric_value(value: &serde_json::Value, unit: Option<FieldUnit>) -> Option<f64> {
+    let (number, unit) = match value {
+        serde_json::Value::Number(n) => (n.as_f64()?, unit),
+        serde_json::Value::String(s) => match parse_suffixed(s) {
+            Some((n, suffix_unit)) => (n, Some(suffix_unit)),
+            None => (s.trim().parse().ok()?, unit),
+        },
+        _ => return None,
+    };
+    Some(number * unit.map_or(1.0, FieldUnit::to_base))
+}
+
+/// Human-readable value in the base unit of `unit`
+fn format_value(value: f64, unit: Option<FieldUnit>) -> String {
+    match unit {
+        Some(u) if u.is_duration() => {
+            if value.abs() >= 60_000.0 {
+                format!("{:.1} min", value / 60_000.0)
+            } else if value.abs() >= 1000.0 {
+                format!("{:.2} s", value / 1000.0)
+            } else {
+                format!("{:.1} ms", value)
+            }
+        }
+        Some(u) if u.is_size() => {
+            let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+            let mut scaled = value;
+            let mut index = 0;
+            while scaled.abs() >= 1024.0 && index < units.len() - 1 {
+                scaled /= 1024.0;
+                index += 1;
+            }
+            if index == 0 {
+                format!("{:.0} B", scaled)
+            } else {
+                format!("{:.1} {}", scaled, units[index])
+            }
+        }
+        Some(FieldUnit::Percent) => format!("{:.1}%", value),
+        Some(FieldUnit::Count) => format!("{:.0}", value),
+        _ => format!("{}", (value * 1000.0).round() / 1000.0),
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Values of a numeric JSON field across events, sorted, in the field's base unit
+/// (the unit recorded in the group schema, or inferred from the first value)
+pub fn numeric_field_values(
+    app: &AppHandle,
+    log_group_name: &str,
+    events: &[LogEvent],
+    field: &str,
+) -> Vec<f64> {
+    let schemas: HashMap<String, GroupSchema> = storage::read_json(app, SCHEMAS_FILE);
+    let mut unit = schemas
+        .get(log_group_name)
+        .and_then(|s| s.fields.get(field)?.unit);
+    let mut values: Vec<f64> = events
+        .iter()
+        .filter_map(|event| {
+            let trimmed = event.message.trim();
+            if !trimmed.starts_with('{') {
+                return None;
+            }
+            let json = serde_json::from_str::<serde_json::Value>(trimmed).ok()?;
+            let value = lookup(&json, field)?;
+            if unit.is_none() {
+                unit = infer_unit(field, value);
+            }
+            numeric_value(value, unit)
+        })
+        .collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    values
+}
+
+/// Aggregate a JSON field ("response.latencyMs") across a session's events, using
+/// the unit recorded in the group schema (or inferred from the values) so results
+/// come back normalized and formatted, e.g. percentiles in ms or humanized bytes
+#[tauri::command]
+pub async fn aggregate_field(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    field: String,
+    log_group_name: Option<String>,
+) -> Result<FieldAggregate, String> {
+    let events = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|s| s.events())
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let mut unit = log_group_name.and_then(|group| {
+        let schemas: HashMap<String, GroupSchema> = storage::read_json(&app, SCHEMAS_FILE);
+        schemas.get(&group)?.fields.get(&field)?.unit
+    });
+
+    let mut aggregate = FieldAggregate {
+        field: field.clone(),
+        ..FieldAggregate::default()
+    };
+    let mut values: Vec<f64> = Vec::new();
+    for event in &events {
+        let trimmed = event.message.trim();
+        if !trimmed.starts_with('{') {
+            continue;
+        }
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(trimmed) else {
+            continue;
+        };
+        let Some(value) = lookup(&json, &field) else {
+            continue;
+        };
+        if unit.is_none() {
+            unit = infer_unit(&field, value);
+        }
+        match (value, unit) {
+            (serde_json::Value::Bool(b), _) => {
+                if *b {
+                    aggregate.true_count += 1;
+                } else {
+                    aggregate.false_count += 1;
+                }
+            }
+            (serde_json::Value::String(s), Some(FieldUnit::Boolean)) => match s.as_str() {
+                "true" => aggregate.true_count += 1,
+                "false" => aggregate.false_count += 1,
+                _ => {}
+            },
+            _ => values.extend(numeric_value(value, unit)),
+        }
+    }
+
+    // Reported values are in the base unit: ms for durations, bytes for sizes
+    aggregate.unit = unit.map(|u| {
+        if u.is_duration() {
+            FieldUnit::Milliseconds
+        } else if u.is_size() {
+            FieldUnit::Bytes
+        } else {
+            u
+        }
+    });
+    if values.is_empty() {
+        aggregate.count = aggregate.true_count + aggregate.false_count;
+        return Ok(aggregate);
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    aggregate.count = values.len();
+    aggregate.sum = values.iter().sum();
+    aggregate.min = values.first().copied();
+    aggregate.max = values.last().copied();
+    aggregate.mean = Some(aggregate.sum / values.len() as f64);
+    aggregate.p50 = percentile(&values, 50.0);
+    aggregate.p90 = percentile(&values, 90.0);
+    aggregate.p99 = percentile(&values, 99.0);
+    for (name, value) in [
+        ("sum", Some(aggregate.sum)),
+        ("min", aggregate.min),
+        ("max", aggregate.max),
+        ("mean", aggregate.mean),
+        ("p50", aggregate.p50),
+        ("p90", aggregate.p90),
+        ("p99", aggregate.p99),
+    ] {
+        if let Some(value) = value {
+            aggregate
+                .formatted
+                .insert(name.to_string(), format_value(value, aggregate.unit));
+        }
+    }
+    Ok(aggregate)
+}
@@ -0,0 +1,335 @@
+use crate::connections::EnvironmentEmitter;
+use crate::session::SessionStore;
+use crate::{filter_all_events, storage, AppState, LogEvent};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, INT};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// File in the app config directory holding saved scripts
+const SCRIPTS_FILE: &str = "scripts.json";
+
+/// Directory (inside the app config directory) scripts may append files to
+const SCRIPT_OUTPUT_DIR: &str = "script-output";
+
+/// Maximum events a single `query()` call returns
+const MAX_QUERY_EVENTS: usize = 10_000;
+
+/// Longest look-back of a `query()` call: CloudWatch's longest retention (10 years)
+const MAX_QUERY_MINUTES: INT = 3653 * 24 * 60;
+
+/// Wall-clock limit for one script run
+const MAX_RUN_SECS: u64 = 120;
+
+/// How often the scheduler checks for scripts that are due
+const SCHEDULER_TICK_SECS: u64 = 60;
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A saved Rhai script, optionally run on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptDefinition {
+    pub name: String,
+    pub source: String,
+    /// Run every N minutes in the background (None = only on demand)
+    #[serde(default)]
+    pub interval_minutes: Option<u32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Outcome of one script run
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptRunResult {
+    pub name: String,
+    /// The script's final value, rendered as text
+    pub result: Option<String>,
+    pub error: Option<String>,
+    /// Lines written with print()/debug()
+    pub output: Vec<String>,
+    pub duration_ms: u64,
+}
+
+fn load_scripts(app: &AppHandle) -> Vec<ScriptDefinition> {
+    let mut scripts: Vec<ScriptDefinition> = storage::read_json(app, SCRIPTS_FILE);
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    scripts
+}
+
+fn event_to_map(event: &LogEvent) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("timestamp".into(), Dynamic::from(event.timestamp));
+    map.insert("message".into(), Dynamic::from(event.message.clone()));
+    map.insert(
+        "log_stream_name".into(),
+        event
+            .log_stream_name
+            .clone()
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT),
+    );
+    map.insert(
+        "source".into(),
+        event
+            .source
+            .clone()
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT),
+    );
+    Dynamic::from_map(map)
+}
+
+/// Resolve a script output file name inside the sandboxed output directory
+fn output_path(app: &AppHandle, file_name: &str) -> Result<std::path::PathBuf, String> {
+    if file_name.is_empty()
+        || file_name.contains(['/', '\\'])
+        || file_name.starts_with('.')
+        || file_name.contains("..")
+    {
+        return Err(format!(
+            "Invalid output file name {:?}: scripts can only write plain file names",
+            file_name
+        ));
+    }
+    let dir = storage::config_dir(app)?.join(SCRIPT_OUTPUT_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir.join(file_name))
+}
+
+/// Build a sandboxed engine. Scripts get no filesystem or network access beyond:
+/// - `query(log_group, filter_pattern, minutes)` -> array of events
+/// - `session_events(session_id)` -> array of events in a result session
+/// - `append_to_file(file_name, text)` -> appends a line under the script output dir
+/// - `now()` -> current time in epoch milliseconds
+/// - `print()` / `debug()` -> script output
+fn build_engine(
+    app: AppHandle,
+    client: Option<CloudWatchClient>,
+    sessions: Arc<Mutex<SessionStore>>,
+    runtime: tokio::runtime::Handle,
+    script_name: String,
+    output: Arc<std::sync::Mutex<Vec<String>>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(50_000_000);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10 * 1024 * 1024);
+    engine.set_max_array_size(MAX_QUERY_EVENTS * 10);
+    engine.set_max_map_size(10_000);
+    engine.disable_symbol("eval");
+
+    let started = Instant::now();
+    engine.on_progress(move |_| {
+        (started.elapsed() > Duration::from_secs(MAX_RUN_SECS))
+            .then(|| Dynamic::from(format!("Script exceeded {}s time limit", MAX_RUN_SECS)))
+    });
+
+    let print_output = output.clone();
+    let print_app = app.clone();
+    let print_name = script_name.clone();
+    engine.on_print(move |line| {
+        print_output
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(line.to_string());
+        print_app
+            .emit_tagged(
+                "script-output",
+                serde_json::json!({ "name": print_name, "line": line }),
+            )
+            .ok();
+    });
+    let debug_output = output;
+    engine.on_debug(move |line, _, _| {
+        debug_output
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(line.to_string());
+    });
+
+    engine.register_fn("now", || -> INT { chrono::Utc::now().timestamp_millis() });
+
+    let query_runtime = runtime.clone();
+    engine.register_fn(
+        "query",
+        move |log_group: &str,
+              filter_pattern: &str,
+              minutes: INT|
+              -> Result<Array, Box<EvalAltResult>> {
+            let client = client.as_ref().ok_or("AWS client not initialized")?;
+            let end = chrono::Utc::now().timestamp_millis();
+            let start = end - minutes.clamp(1, MAX_QUERY_MINUTES).saturating_mul(60_000);
+            let (events, _) = query_runtime
+                .block_on(filter_all_events(
+                    client,
+                    log_group,
+                    Some(start),
+                    Some(end),
+                    Some(filter_pattern),
+                    MAX_QUERY_EVENTS,
+                ))
+                .map_err(|e| -> Box<EvalAltResult> { e.into() })?;
+            Ok(events.iter().map(event_to_map).collect())
+        },
+    );
+
+    engine.register_fn("session_events", move |session_id: &str| -> Array {
+        runtime
+            .block_on(sessions.lock())
+            .get(session_id)
+            .map(|session| session.events().iter().map(event_to_map).collect())
+            .unwrap_or_default()
+    });
+
+    engine.register_fn(
+        "append_to_file",
+        move |file_name: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+            let path = output_path(&app, file_name)?;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            writeln!(file, "{}", text)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(())
+        },
+    );
+
+    engine
+}
+
+/// Run a script on a blocking thread with the sandboxed API
+async fn run_script_source(app: &AppHandle, name: String, source: String) -> ScriptRunResult {
+    let Some(state) = app.try_state::<AppState>() else {
+        return ScriptRunResult {
+            name,
+            result: None,
+            error: Some("App state unavailable".to_string()),
+            output: Vec::new(),
+            duration_ms: 0,
+        };
+    };
+    let client = state.logs_client().await.ok();
+    let sessions = state.sessions.clone();
+    let output = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let engine = build_engine(
+        app.clone(),
+        client,
+        sessions,
+        tokio::runtime::Handle::current(),
+        name.clone(),
+        output.clone(),
+    );
+
+    let started = Instant::now();
+    let outcome = tokio::task::spawn_blocking(move || engine.eval::<Dynamic>(&source))
+        .await
+        .map_err(|e| format!("Script task failed: {}", e))
+        .and_then(|r| r.map_err(|e| e.to_string()));
+    let output = std::mem::take(&mut *output.lock().unwrap_or_else(|e| e.into_inner()));
+
+    let (result, error) = match outcome {
+        Ok(value) if value.is_unit() => (None, None),
+        Ok(value) => (Some(value.to_string()), None),
+        Err(e) => (None, Some(e)),
+    };
+    ScriptRunResult {
+        name,
+        result,
+        error,
+        output,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Background scheduler running enabled scripts that have an interval
+pub async fn run_script_scheduler(app: AppHandle) {
+    let mut last_run: HashMap<String, Instant> = HashMap::new();
+    loop {
+        tokio::time::sleep(Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+        for script in load_scripts(&app) {
+            let Some(interval) = script.interval_minutes.filter(|m| *m > 0) else {
+                continue;
+            };
+            if !script.enabled {
+                continue;
+            }
+            let due = last_run
+                .get(&script.name)
+                .is_none_or(|at| at.elapsed() >= Duration::from_secs(u64::from(interval) * 60));
+            if !due {
+                continue;
+            }
+            last_run.insert(script.name.clone(), Instant::now());
+
+            let result = run_script_source(&app, script.name, script.source).await;
+            if let Some(error) = &result.error {
+                log::warn!("Scheduled script {} failed: {}", result.name, error);
+            }
+            app.emit_tagged("script-run-completed", &result).ok();
+        }
+    }
+}
+
+/// Save a script, replacing any existing script with the same name. The script is
+/// compiled first so syntax errors are reported on save.
+#[tauri::command]
+pub fn save_script(app: AppHandle, script: ScriptDefinition) -> Result<(), String> {
+    if script.name.trim().is_empty() {
+        return Err("Script name cannot be empty".to_string());
+    }
+    Engine::new()
+        .compile(&script.source)
+        .map_err(|e| format!("Script does not compile: {}", e))?;
+
+    let mut scripts = load_scripts(&app);
+    scripts.retain(|s| s.name != script.name);
+    scripts.push(script);
+    storage::write_json(&app, SCRIPTS_FILE, &scripts)
+}
+
+/// List saved scripts
+#[tauri::command]
+pub fn list_scripts(app: AppHandle) -> Vec<ScriptDefinition> {
+    load_scripts(&app)
+}
+
+/// Delete a saved script by name
+#[tauri::command]
+pub fn delete_script(app: AppHandle, name: String) -> Result<(), String> {
+    let mut scripts = load_scripts(&app);
+    let before = scripts.len();
+    scripts.retain(|s| s.name != name);
+    if scripts.len() == before {
+        return Err(format!("Script {} not found", name));
+    }
+    storage::write_json(&app, SCRIPTS_FILE, &scripts)
+}
+
+/// Run a saved script by name, or ad-hoc `source` when given
+#[tauri::command]
+pub async fn run_script(
+    app: AppHandle,
+    name: String,
+    source: Option<String>,
+) -> Result<ScriptRunResult, String> {
+    let source = match source {
+        Some(source) => source,
+        None => load_scripts(&app)
+            .into_iter()
+            .find(|s| s.name == name)
+            .map(|s| s.source)
+            .ok_or_else(|| format!("Script {} not found", name))?,
+    };
+    Ok(run_script_source(&app, name, source).await)
+}
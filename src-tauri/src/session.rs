@@ -0,0 +1,1036 @@
+use crate::{columns, preview, redaction, storage, AppState, LogEvent};
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// Sessions whose messages total more than this are stored compressed
+const COMPRESS_THRESHOLD_BYTES: usize = 32 * 1024 * 1024;
+
+/// Events per compressed message block (the unit decompressed on window access)
+const BLOCK_EVENTS: usize = 1_000;
+
+/// zstd level: fast, still 5-10x on verbose JSON
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// File in the app config directory holding each log group's sort order
+const SORT_ORDERS_FILE: &str = "sort-orders.json";
+
+/// Serializes read-modify-write of the sort orders file
+static SORT_ORDERS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Display order of a timeline; storage is always oldest-first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    /// Newest first
+    Descending,
+}
+
+/// The persisted sort order of a log group (ascending unless set)
+pub fn sort_order_for(app: &AppHandle, log_group_name: &str) -> SortOrder {
+    let orders: HashMap<String, SortOrder> = storage::read_json(app, SORT_ORDERS_FILE);
+    orders.get(log_group_name).copied().unwrap_or_default()
+}
+
+/// The messages of BLOCK_EVENTS consecutive events, concatenated and compressed
+#[derive(Debug)]
+struct MessageBlock {
+    data: Vec<u8>,
+    lengths: Vec<u32>,
+}
+
+impl MessageBlock {
+    fn compress(events: &mut [LogEvent]) -> Result<Self, String> {
+        let mut raw = Vec::new();
+        let mut lengths = Vec::with_capacity(events.len());
+        for event in events.iter() {
+            raw.extend_from_slice(event.message.as_bytes());
+            lengths.push(event.message.len() as u32);
+        }
+        let data = zstd::encode_all(raw.as_slice(), COMPRESSION_LEVEL)
+            .map_err(|e| format!("Failed to compress messages: {}", e))?;
+        for event in events.iter_mut() {
+            event.message = String::new();
+        }
+        Ok(Self { data, lengths })
+    }
+
+    fn messages(&self) -> Vec<String> {
+        let raw = zstd::decode_all(self.data.as_slice()).unwrap_or_else(|e| {
+            log::error!("Failed to decompress messages: {}", e);
+            Vec::new()
+        });
+        let mut offset = 0;
+        self.lengths
+            .iter()
+            .map(|&len| {
+                let end = (offset + len as usize).min(raw.len());
+                let message = String::from_utf8_lossy(&raw[offset.min(end)..end]).into_owned();
+                offset = end;
+                message
+            })
+            .collect()
+    }
+}
+
+/// A timeline of log events held in the backend, merged from one or more sources
+/// (CloudWatch log groups, imported local files) and kept sorted by timestamp.
+/// Large timelines keep message bodies in compressed blocks and decompress only the
+/// blocks a caller reads.
+#[derive(Debug, Default)]
+pub struct ResultSession {
+    /// Event metadata; messages are empty while `blocks` is non-empty
+    events: Vec<LogEvent>,
+    blocks: Vec<MessageBlock>,
+    /// How each CloudWatch source was fetched, keyed by log group name
+    pub fetches: HashMap<String, FetchContext>,
+    /// Time ranges zoomed into, outermost first
+    pub zoom_stack: Vec<(i64, i64)>,
+    /// Order of windows and offsets handed to the UI
+    pub order: SortOrder,
+    /// Event ids selected for export, share and report actions. Kept by id, so
+    /// selections survive re-fetches, filter changes and clearing the events.
+    pub selected: BTreeSet<String>,
+    /// Opened from an archive: the events are frozen and never replaced or cleared
+    pub read_only: bool,
+    /// Stored indices of the events of each severity level, ascending. Built on
+    /// the first severity lookup and dropped whenever the events change.
+    severity_index: Option<HashMap<String, Vec<usize>>>,
+}
+
+/// Parameters and completeness of a log group fetch merged into a session
+#[derive(Debug, Clone, Default)]
+pub struct FetchContext {
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub filter_pattern: Option<String>,
+    /// The fetch stopped at its count or size limit
+    pub truncated: bool,
+    /// Narrower ranges re-fetched completely while zooming
+    pub complete_ranges: Vec<(i64, i64)>,
+}
+
+impl FetchContext {
+    /// Whether the stored events hold everything in `start..=end`
+    pub fn covers(&self, start: i64, end: i64) -> bool {
+        !self.truncated
+            || self
+                .complete_ranges
+                .iter()
+                .any(|&(s, e)| s <= start && end <= e)
+    }
+}
+
+impl ResultSession {
+    /// Replace all events from `source` with `events`, keeping the timeline sorted.
+    /// Re-fetching or re-importing the same source never duplicates events.
+    pub fn replace_source(&mut self, source: &str, mut events: Vec<LogEvent>) {
+        if self.refuse_change() {
+            return;
+        }
+        events.iter_mut().for_each(LogEvent::ensure_event_id);
+        self.decompress();
+        self.severity_index = None;
+        self.events.retain(|e| e.source.as_deref() != Some(source));
+        self.events.extend(events);
+        // Stable sort keeps the original order of events sharing a timestamp
+        self.events.sort_by_key(|e| e.timestamp);
+        self.compress_if_large();
+    }
+
+    /// Replace the events from `source` whose timestamps fall in `start..=end`,
+    /// keeping the rest of that source (used when zooming re-fetches a window)
+    pub fn replace_source_range(
+        &mut self,
+        source: &str,
+        start: i64,
+        end: i64,
+        mut events: Vec<LogEvent>,
+    ) {
+        if self.refuse_change() {
+            return;
+        }
+        events.iter_mut().for_each(LogEvent::ensure_event_id);
+        self.decompress();
+        self.severity_index = None;
+        self.events.retain(|e| {
+            e.source.as_deref() != Some(source) || e.timestamp < start || e.timestamp > end
+        });
+        self.events.extend(events);
+        self.events.sort_by_key(|e| e.timestamp);
+        self.compress_if_large();
+    }
+
+    /// Replace everything with archived `events` and freeze the session
+    pub fn load_frozen(&mut self, mut events: Vec<LogEvent>) {
+        events.sort_by_key(|e| e.timestamp);
+        self.events = events;
+        self.blocks = Vec::new();
+        self.fetches.clear();
+        self.zoom_stack.clear();
+        self.severity_index = None;
+        self.compress_if_large();
+        self.read_only = true;
+    }
+
+    /// Events with timestamps in `start..=end`, in display order
+    pub fn range(&self, start: i64, end: i64) -> Vec<LogEvent> {
+        let first = self.events.partition_point(|e| e.timestamp < start);
+        let last = self.events.partition_point(|e| e.timestamp <= end);
+        let mut events = self.stored_window(first, last.saturating_sub(first));
+        if self.order == SortOrder::Descending {
+            events.reverse();
+        }
+        events
+    }
+
+    /// Display index of the event with `event_id` in the timeline
+    pub fn position_of(&self, event_id: &str) -> Option<usize> {
+        let index = self
+            .events
+            .iter()
+            .position(|e| e.event_id.as_deref() == Some(event_id))?;
+        Some(match self.order {
+            SortOrder::Ascending => index,
+            SortOrder::Descending => self.events.len() - 1 - index,
+        })
+    }
+
+    /// The stored event with `event_id`, message decompressed
+    pub fn event(&self, event_id: &str) -> Option<LogEvent> {
+        let index = self
+            .events
+            .iter()
+            .position(|e| e.event_id.as_deref() == Some(event_id))?;
+        self.stored_window(index, 1).pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        !self.blocks.is_empty()
+    }
+
+    /// Every event oldest-first, with messages decompressed
+    pub fn events(&self) -> Vec<LogEvent> {
+        self.stored_window(0, self.events.len())
+    }
+
+    /// Every event in display order
+    pub fn display_events(&self) -> Vec<LogEvent> {
+        self.window(0, self.events.len())
+    }
+
+    /// Events `offset..offset + limit` counted in display order, so paging a
+    /// newest-first session starts from the most recent events
+    pub fn window(&self, offset: usize, limit: usize) -> Vec<LogEvent> {
+        match self.order {
+            SortOrder::Ascending => self.stored_window(offset, limit),
+            SortOrder::Descending => {
+                let end = self.events.len().saturating_sub(offset);
+                let start = end.saturating_sub(limit);
+                let mut events = self.stored_window(start, end - start);
+                events.reverse();
+                events
+            }
+        }
+    }
+
+    /// Stored (oldest-first) events `offset..offset + limit`, decompressing only
+    /// the blocks they span
+    fn stored_window(&self, offset: usize, limit: usize) -> Vec<LogEvent> {
+        let end = offset.saturating_add(limit).min(self.events.len());
+        if offset >= end {
+            return Vec::new();
+        }
+        let mut events = self.events[offset..end].to_vec();
+        if self.blocks.is_empty() {
+            return events;
+        }
+        for block_index in offset / BLOCK_EVENTS..=(end - 1) / BLOCK_EVENTS {
+            let block_start = block_index * BLOCK_EVENTS;
+            for (i, message) in self.blocks[block_index].messages().into_iter().enumerate() {
+                let index = block_start + i;
+                if (offset..end).contains(&index) {
+                    events[index - offset].message = message;
+                }
+            }
+        }
+        events
+    }
+
+    /// Distinct sources merged into the timeline, sorted
+    pub fn sources(&self) -> Vec<String> {
+        let mut sources: Vec<String> = self
+            .events
+            .iter()
+            .filter_map(|e| e.source.clone())
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    /// Selected ids in display order, then selected ids no longer held
+    pub fn selection(&self) -> Selection {
+        let mut event_ids: Vec<String> = self
+            .events
+            .iter()
+            .filter_map(|e| e.event_id.as_ref())
+            .filter(|id| self.selected.contains(*id))
+            .cloned()
+            .collect();
+        if self.order == SortOrder::Descending {
+            event_ids.reverse();
+        }
+        let present = event_ids.len();
+        let held: BTreeSet<&String> = event_ids.iter().collect();
+        let missing: Vec<String> = self
+            .selected
+            .iter()
+            .filter(|id| !held.contains(id))
+            .cloned()
+            .collect();
+        event_ids.extend(missing);
+        Selection { event_ids, present }
+    }
+
+    /// Display offset of the nearest event of `severity` after `from_offset`
+    /// (before it when `backward`), by binary search of the severity index
+    pub fn next_by_severity(
+        &mut self,
+        from_offset: usize,
+        severity: &str,
+        backward: bool,
+    ) -> Option<usize> {
+        let len = self.events.len();
+        let descending = self.order == SortOrder::Descending;
+        let positions = self
+            .severity_index()
+            .get(&severity.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        // Newest-first offsets run against storage order
+        let from = if descending {
+            len.checked_sub(from_offset + 1)?
+        } else {
+            from_offset
+        };
+        let stored = if backward != descending {
+            let before = positions.partition_point(|&p| p < from);
+            positions.get(before.checked_sub(1)?).copied()?
+        } else {
+            let after = positions.partition_point(|&p| p <= from);
+            positions.get(after).copied()?
+        };
+        Some(if descending { len - 1 - stored } else { stored })
+    }
+
+    fn severity_index(&mut self) -> &HashMap<String, Vec<usize>> {
+        if self.severity_index.is_none() {
+            let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+            // A block at a time, so compressed sessions are never fully decompressed
+            for start in (0..self.events.len()).step_by(BLOCK_EVENTS) {
+                let chunk = self.stored_window(start, BLOCK_EVENTS);
+                for (i, event) in crate::export::classify(&chunk).into_iter().enumerate() {
+                    if let Some(level) = event.level {
+                        index.entry(level).or_default().push(start + i);
+                    }
+                }
+            }
+            self.severity_index = Some(index);
+        }
+        self.severity_index.get_or_insert_with(HashMap::new)
+    }
+
+    fn refuse_change(&self) -> bool {
+        if self.read_only {
+            log::warn!("Ignoring a change to a read-only (archived) session");
+        }
+        self.read_only
+    }
+
+    /// Drop all events and release their memory
+    pub fn clear(&mut self) {
+        if self.refuse_change() {
+            return;
+        }
+        self.events = Vec::new();
+        self.blocks = Vec::new();
+        self.severity_index = None;
+        self.zoom_stack.clear();
+    }
+
+    /// Approximate memory held by the timeline, including unused vector capacity
+    /// and compressed blocks
+    pub fn memory_bytes(&self) -> usize {
+        let unused = self.events.capacity() - self.events.len();
+        self.events
+            .iter()
+            .map(LogEvent::memory_bytes)
+            .sum::<usize>()
+            + unused * std::mem::size_of::<LogEvent>()
+            + self
+                .blocks
+                .iter()
+                .map(|b| b.data.capacity() + b.lengths.capacity() * 4)
+                .sum::<usize>()
+    }
+
+    fn decompress(&mut self) {
+        if self.blocks.is_empty() {
+            return;
+        }
+        let blocks = std::mem::take(&mut self.blocks);
+        for (block, chunk) in blocks.iter().zip(self.events.chunks_mut(BLOCK_EVENTS)) {
+            for (event, message) in chunk.iter_mut().zip(block.messages()) {
+                event.message = message;
+            }
+        }
+    }
+
+    fn compress_if_large(&mut self) {
+        let message_bytes: usize = self.events.iter().map(|e| e.message.len()).sum();
+        if message_bytes < COMPRESS_THRESHOLD_BYTES {
+            return;
+        }
+        let mut blocks = Vec::with_capacity(self.events.len().div_ceil(BLOCK_EVENTS));
+        for chunk in self.events.chunks_mut(BLOCK_EVENTS) {
+            match MessageBlock::compress(chunk) {
+                Ok(block) => blocks.push(block),
+                Err(e) => {
+                    // Keep the session usable uncompressed
+                    log::warn!("{}", e);
+                    self.blocks = blocks;
+                    self.decompress();
+                    return;
+                }
+            }
+        }
+        let compressed: usize = blocks.iter().map(|b| b.data.len()).sum();
+        log::info!(
+            "Compressed {} messages: {} -> {} bytes",
+            self.events.len(),
+            message_bytes,
+            compressed
+        );
+        self.blocks = blocks;
+    }
+}
+
+/// All result sessions, keyed by a frontend-chosen session id
+#[derive(Debug, Default)]
+pub struct SessionStore {
+    sessions: HashMap<String, ResultSession>,
+}
+
+impl SessionStore {
+    pub fn get(&self, session_id: &str) -> Option<&ResultSession> {
+        self.sessions.get(session_id)
+    }
+
+    pub fn get_mut(&mut self, session_id: &str) -> Option<&mut ResultSession> {
+        self.sessions.get_mut(session_id)
+    }
+
+    pub fn get_or_create(&mut self, session_id: &str) -> &mut ResultSession {
+        self.sessions.entry(session_id.to_string()).or_default()
+    }
+
+    pub fn remove(&mut self, session_id: &str) -> Option<ResultSession> {
+        self.sessions.remove(session_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ResultSession)> {
+        self.sessions.iter()
+    }
+}
+
+/// Tag events with their source before merging them into a session
+pub fn tag_source(events: &mut [LogEvent], source: &str) {
+    for event in events {
+        event.source = Some(source.to_string());
+    }
+}
+
+/// Field names checked for a timestamp in JSON-formatted lines
+const JSON_TIMESTAMP_FIELDS: [&str; 5] = ["timestamp", "@timestamp", "time", "ts", "date"];
+
+/// Parse a timestamp string (RFC 3339 or "YYYY-MM-DD HH:MM:SS[.fff]", assumed UTC)
+/// into epoch milliseconds
+fn parse_timestamp_str(value: &str) -> Option<i64> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.timestamp_millis());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(dt.and_utc().timestamp_millis());
+        }
+    }
+    None
+}
+
+/// Extract the timestamp from a single line of an imported file.
+/// Supports JSON lines with a timestamp field and lines starting with an ISO 8601 timestamp.
+fn parse_line_timestamp(line: &str) -> Option<i64> {
+    if line.starts_with('{') {
+        if let Ok(serde_json::Value::Object(obj)) = serde_json::from_str::<serde_json::Value>(line)
+        {
+            for field in JSON_TIMESTAMP_FIELDS {
+                match obj.get(field) {
+                    Some(serde_json::Value::Number(n)) => {
+                        // Treat small values as epoch seconds
+                        let value = n.as_f64()?;
+                        return Some(if value < 1e11 {
+                            (value * 1000.0) as i64
+                        } else {
+                            value as i64
+                        });
+                    }
+                    Some(serde_json::Value::String(s)) => {
+                        if let Some(ts) = parse_timestamp_str(s) {
+                            return Some(ts);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        return None;
+    }
+
+    // Leading timestamp: "2024-05-01T12:00:00.123Z ..." or "2024-05-01 12:00:00,123 ..."
+    let mut parts = line.splitn(3, ' ');
+    let first = parts.next()?.trim_start_matches('[').trim_end_matches(']');
+    if let Some(ts) = parse_timestamp_str(first) {
+        return Some(ts);
+    }
+    let second = parts.next()?.trim_end_matches(']').replace(',', ".");
+    parse_timestamp_str(&format!("{} {}", first, second))
+}
+
+/// Read a local log file into events tagged with `source`.
+/// Lines without a recognizable timestamp are treated as continuations of the
+/// previous event (e.g. stack traces); leading lines fall back to the file's mtime.
+pub fn read_log_file(path: &Path, source: &str) -> Result<Vec<LogEvent>, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let fallback_ts = file
+        .metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    let mut events: Vec<LogEvent> = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_line_timestamp(&line) {
+            Some(timestamp) => events.push(LogEvent {
+                timestamp,
+                message: line,
+                log_stream_name: None,
+                event_id: Some(format!("{}:{}", source, index + 1)),
+                source: Some(source.to_string()),
+                columns: None,
+                full_length: None,
+            }),
+            None => match events.last_mut() {
+                Some(previous) => {
+                    previous.message.push('\n');
+                    previous.message.push_str(&line);
+                }
+                None => events.push(LogEvent {
+                    timestamp: fallback_ts,
+                    message: line,
+                    log_stream_name: None,
+                    event_id: Some(format!("{}:{}", source, index + 1)),
+                    source: Some(source.to_string()),
+                    columns: None,
+                    full_length: None,
+                }),
+            },
+        }
+    }
+
+    Ok(events)
+}
+
+/// Import a local log file into a session, merging it with any CloudWatch results
+/// already fetched into the same session. Returns the merged timeline.
+#[tauri::command]
+pub async fn import_log_file(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    path: String,
+    source: Option<String>,
+) -> Result<Vec<LogEvent>, String> {
+    let path = Path::new(&path);
+    let source = source.unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "imported".to_string())
+    });
+
+    let events = read_log_file(path, &source)?;
+    log::info!(
+        "Imported {} events from {} into session {}",
+        events.len(),
+        path.display(),
+        session_id
+    );
+
+    let mut merged = {
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions.get_or_create(&session_id);
+        session.replace_source(&source, events);
+        session.display_events()
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut merged).await;
+    columns::apply(&app, &mut merged);
+    preview::truncate(&app, &mut merged);
+    Ok(merged)
+}
+
+/// Get the merged timeline of a session
+#[tauri::command]
+pub async fn get_session_events(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<LogEvent>, String> {
+    let mut events = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|s| s.display_events())
+        .unwrap_or_default();
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(events)
+}
+
+/// A window of a session's timeline, for virtualized views of large sessions.
+/// Only the compressed blocks covering the window are decompressed.
+#[tauri::command]
+pub async fn get_session_window(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<LogEvent>, String> {
+    let mut events = state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|s| s.window(offset, limit))
+        .unwrap_or_default();
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(events)
+}
+
+/// List the distinct sources merged into a session
+#[tauri::command]
+pub async fn list_session_sources(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<String>, String> {
+    let sessions = state.sessions.lock().await;
+    Ok(sessions
+        .get(&session_id)
+        .map(|s| s.sources())
+        .unwrap_or_default())
+}
+
+/// Remove a single source (e.g. an imported file) from a session
+#[tauri::command]
+pub async fn remove_session_source(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    source: String,
+) -> Result<Vec<LogEvent>, String> {
+    let mut events = {
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions.get_or_create(&session_id);
+        session.replace_source(&source, Vec::new());
+        session.fetches.remove(&source);
+        session.display_events()
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(events)
+}
+
+/// Drop a session and all of its events
+#[tauri::command]
+pub async fn close_session(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    state.sessions.lock().await.remove(&session_id);
+    Ok(())
+}
+
+/// Memory held by one result session or tail buffer
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMemoryUsage {
+    pub session_id: String,
+    /// "result" for fetch/import sessions, "tail" for tail ring buffers
+    pub kind: String,
+    pub event_count: usize,
+    pub bytes: usize,
+}
+
+/// Backend memory report: per-session estimates plus the whole process
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryUsageReport {
+    pub sessions: Vec<SessionMemoryUsage>,
+    pub total_bytes: usize,
+    /// Resident memory of the app process, if the platform reports it
+    pub process_rss_bytes: Option<usize>,
+}
+
+/// Estimated memory held by each result session and tail buffer, largest first
+#[tauri::command]
+pub async fn get_session_memory_usage(
+    state: State<'_, AppState>,
+) -> Result<MemoryUsageReport, String> {
+    let mut sessions: Vec<SessionMemoryUsage> = state
+        .sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(id, session)| SessionMemoryUsage {
+            session_id: id.clone(),
+            kind: "result".to_string(),
+            event_count: session.len(),
+            bytes: session.memory_bytes(),
+        })
+        .collect();
+
+    let buffers: Vec<(String, _)> = state
+        .tails
+        .lock()
+        .await
+        .iter()
+        .map(|(id, tail)| (id.clone(), tail.buffer.clone()))
+        .collect();
+    for (id, buffer) in buffers {
+        let buffer = buffer.lock().await;
+        sessions.push(SessionMemoryUsage {
+            session_id: id,
+            kind: "tail".to_string(),
+            event_count: buffer.len(),
+            bytes: buffer.memory_bytes(),
+        });
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.bytes));
+    Ok(MemoryUsageReport {
+        total_bytes: sessions.iter().map(|s| s.bytes).sum(),
+        sessions,
+        process_rss_bytes: memory_stats::memory_stats().map(|m| m.physical_mem),
+    })
+}
+
+/// Drop the events held for a session (or a running tail's buffer) and release
+/// their memory, keeping the session itself open. Returns the bytes freed.
+#[tauri::command]
+pub async fn clear_session_results(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<usize, String> {
+    let mut freed = 0;
+    if let Some(session) = state.sessions.lock().await.get_mut(&session_id) {
+        freed += session.memory_bytes();
+        session.clear();
+    }
+    let buffer = state
+        .tails
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|tail| tail.buffer.clone());
+    if let Some(buffer) = buffer {
+        let mut buffer = buffer.lock().await;
+        freed += buffer.memory_bytes();
+        buffer.clear();
+    }
+    log::info!("Cleared session {} ({} bytes)", session_id, freed);
+    Ok(freed)
+}
+
+/// Events re-fetched on each side of a timestamp when goto_event can't find an
+/// event in the store
+const GOTO_REFETCH_WINDOW_MS: i64 = 60_000;
+
+/// Which way next_event_by_severity searches, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchDirection {
+    Next,
+    Previous,
+}
+
+/// Where goto_event found an event
+#[derive(Debug, Clone, Serialize)]
+pub struct EventLocation {
+    /// Index of the event in the session timeline, in display order
+    pub offset: usize,
+    pub total: usize,
+    /// Events around the target, starting at `window_offset`
+    pub window: Vec<LogEvent>,
+    pub window_offset: usize,
+    /// The event was not in the store and a narrow window was re-fetched
+    pub refetched: bool,
+}
+
+/// Locate an event in a session by id (deep links, bookmarks, report references).
+/// If it isn't held any more, re-fetches a narrow window around `timestamp` from
+/// `log_group_name` (or every CloudWatch source of the session) and looks again.
+#[tauri::command]
+pub async fn goto_event(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_id: String,
+    timestamp: Option<i64>,
+    log_group_name: Option<String>,
+    context: Option<usize>,
+) -> Result<EventLocation, String> {
+    let context = context.unwrap_or(50);
+    let mut refetched = false;
+    let mut found = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&session_id)
+            .and_then(|s| s.position_of(&event_id))
+    };
+
+    if found.is_none() {
+        let timestamp = timestamp.ok_or_else(|| {
+            "Event is no longer loaded; its timestamp is needed to fetch it again".to_string()
+        })?;
+        // Re-fetched with the pattern the source was fetched with, so a filtered
+        // source never gets unfiltered events spliced in
+        let sources: Vec<(String, Option<String>)> = {
+            let sessions = state.sessions.lock().await;
+            let fetches = sessions.get(&session_id).map(|s| &s.fetches);
+            match log_group_name {
+                Some(group) => {
+                    let filter_pattern = fetches
+                        .and_then(|f| f.get(&group))
+                        .and_then(|ctx| ctx.filter_pattern.clone());
+                    vec![(group, filter_pattern)]
+                }
+                None => fetches
+                    .map(|f| {
+                        f.iter()
+                            .map(|(source, ctx)| (source.clone(), ctx.filter_pattern.clone()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            }
+        };
+        let client = state.logs_client().await?;
+        let (start, end) = (
+            timestamp - GOTO_REFETCH_WINDOW_MS,
+            timestamp + GOTO_REFETCH_WINDOW_MS,
+        );
+        for (source, filter_pattern) in sources {
+            let (mut events, _) = crate::filter_all_events(
+                &client,
+                &source,
+                Some(start),
+                Some(end),
+                filter_pattern.as_deref(),
+                10_000,
+            )
+            .await?;
+            tag_source(&mut events, &source);
+            let mut sessions = state.sessions.lock().await;
+            let session = sessions.get_or_create(&session_id);
+            session.replace_source_range(&source, start, end, events);
+            found = session.position_of(&event_id);
+            refetched = true;
+            if found.is_some() {
+                break;
+            }
+        }
+    }
+
+    let offset = found.ok_or_else(|| format!("Event {} not found", event_id))?;
+    let (mut window, window_offset, total) = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let window_offset = offset.saturating_sub(context);
+        (
+            session.window(window_offset, context * 2 + 1),
+            window_offset,
+            session.len(),
+        )
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(EventLocation {
+        offset,
+        total,
+        window,
+        window_offset,
+        refetched,
+    })
+}
+
+/// Jump from `from_offset` to the next event of a severity ("error", "warn", ...)
+/// in `direction`, using the session's severity index so keyboard navigation
+/// doesn't scan loaded events. None when there is no such event that way.
+#[tauri::command]
+pub async fn next_event_by_severity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    from_offset: usize,
+    severity: String,
+    direction: SearchDirection,
+    context: Option<usize>,
+) -> Result<Option<EventLocation>, String> {
+    let context = context.unwrap_or(50);
+    let (mut window, offset, window_offset, total) = {
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let Some(offset) = session.next_by_severity(
+            from_offset,
+            &severity,
+            direction == SearchDirection::Previous,
+        ) else {
+            return Ok(None);
+        };
+        let window_offset = offset.saturating_sub(context);
+        (
+            session.window(window_offset, context * 2 + 1),
+            offset,
+            window_offset,
+            session.len(),
+        )
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut window).await;
+    columns::apply(&app, &mut window);
+    preview::truncate(&app, &mut window);
+    Ok(Some(EventLocation {
+        offset,
+        total,
+        window,
+        window_offset,
+        refetched: false,
+    }))
+}
+
+/// The persisted sort order of a log group
+#[tauri::command]
+pub fn get_sort_order(app: AppHandle, log_group_name: String) -> SortOrder {
+    sort_order_for(&app, &log_group_name)
+}
+
+/// Persist a log group's sort order (used by its next fetches) and, with
+/// `session_id`, apply it to that session's windows and offsets right away
+#[tauri::command]
+pub async fn set_sort_order(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    order: SortOrder,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    {
+        let _guard = SORT_ORDERS_FILE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut orders: HashMap<String, SortOrder> = storage::read_json(&app, SORT_ORDERS_FILE);
+        match order {
+            SortOrder::Ascending => orders.remove(&log_group_name),
+            SortOrder::Descending => orders.insert(log_group_name, order),
+        };
+        storage::write_json(&app, SORT_ORDERS_FILE, &orders)?;
+    }
+    if let Some(session_id) = session_id {
+        if let Some(session) = state.sessions.lock().await.get_mut(&session_id) {
+            session.order = order;
+        }
+    }
+    Ok(())
+}
+
+/// A session's selected events
+#[derive(Debug, Clone, Serialize)]
+pub struct Selection {
+    pub event_ids: Vec<String>,
+    /// How many of the selected events are in the session right now
+    pub present: usize,
+}
+
+/// Change a session's selection: `clear` first, then add `select` and remove
+/// `deselect`. Returns the resulting selection.
+#[tauri::command]
+pub async fn update_selection(
+    state: State<'_, AppState>,
+    session_id: String,
+    select: Vec<String>,
+    deselect: Vec<String>,
+    clear: Option<bool>,
+) -> Result<Selection, String> {
+    let mut sessions = state.sessions.lock().await;
+    let session = sessions.get_or_create(&session_id);
+    if clear.unwrap_or(false) {
+        session.selected.clear();
+    }
+    session.selected.extend(select);
+    for id in &deselect {
+        session.selected.remove(id);
+    }
+    Ok(session.selection())
+}
+
+/// A session's selected event ids, for passing to exports and reports
+#[tauri::command]
+pub async fn get_selection(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Selection, String> {
+    Ok(state
+        .sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(ResultSession::selection)
+        .unwrap_or(Selection {
+            event_ids: Vec::new(),
+            present: 0,
+        }))
+}
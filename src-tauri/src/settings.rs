@@ -0,0 +1,180 @@
+use crate::storage::{self, SharedConfigSettings};
+use crate::workspace::{self, WorkspaceConfig};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// Marker identifying a Loggy settings bundle
+const BUNDLE_FORMAT: &str = "loggy-settings";
+
+/// Current bundle layout version; bumped on incompatible changes
+const BUNDLE_VERSION: u32 = 1;
+
+/// Key fragments that mark a value as a secret; such keys are never exported
+const SECRET_KEY_FRAGMENTS: [&str; 6] = [
+    "secret",
+    "password",
+    "token",
+    "credential",
+    "accesskey",
+    "privatekey",
+];
+
+/// Everything needed to reproduce a Loggy setup on another machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: i64,
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// Frontend-owned settings (preferences, saved views, highlight rules, extractors)
+    #[serde(default)]
+    pub frontend: serde_json::Value,
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceConfig>,
+}
+
+/// Summary returned by import_settings
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedSettings {
+    /// Frontend-owned settings for the frontend stores to apply
+    pub frontend: serde_json::Value,
+    pub workspaces_imported: usize,
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase().replace(['_', '-'], "");
+    SECRET_KEY_FRAGMENTS.iter().any(|f| key.contains(f))
+}
+
+/// Remove every secret-looking key from a JSON value, recursively
+fn strip_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|key, _| !is_secret_key(key));
+            map.values_mut().for_each(strip_secrets);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_secrets),
+        _ => {}
+    }
+}
+
+/// Write all settings to a single JSON bundle. `frontend_settings` carries the
+/// settings owned by the frontend stores; workspaces are added from the backend.
+#[tauri::command]
+pub fn export_settings(
+    app: AppHandle,
+    path: String,
+    frontend_settings: serde_json::Value,
+) -> Result<usize, String> {
+    let mut frontend = frontend_settings;
+    strip_secrets(&mut frontend);
+
+    let bundle = SettingsBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        app_version: Some(app.package_info().version.to_string()),
+        frontend,
+        workspaces: workspace::load_local_workspaces(&app),
+    };
+    let contents = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    log::info!(
+        "Exported settings to {} ({} workspaces)",
+        path,
+        bundle.workspaces.len()
+    );
+    Ok(bundle.workspaces.len())
+}
+
+/// Read a settings bundle, merge its workspaces into the local ones (same-name
+/// workspaces are replaced), and return the frontend settings to apply
+#[tauri::command]
+pub fn import_settings(app: AppHandle, path: String) -> Result<ImportedSettings, String> {
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut bundle: SettingsBundle = serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is not a Loggy settings file: {}", path, e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!("{} is not a Loggy settings file", path));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Settings file version {} is newer than this version of Loggy supports",
+            bundle.version
+        ));
+    }
+    for config in bundle.workspaces.iter_mut() {
+        config.validate()?;
+        config.shared = false;
+    }
+    strip_secrets(&mut bundle.frontend);
+
+    let imported = bundle.workspaces.len();
+    if imported > 0 {
+        let mut workspaces = workspace::load_local_workspaces(&app);
+        workspaces.retain(|w| !bundle.workspaces.iter().any(|i| i.name == w.name));
+        workspaces.extend(bundle.workspaces);
+        workspace::store_workspaces(&app, &workspaces)?;
+    }
+
+    log::info!("Imported settings from {} ({} workspaces)", path, imported);
+    Ok(ImportedSettings {
+        frontend: bundle.frontend,
+        workspaces_imported: imported,
+    })
+}
+
+/// Frontend-owned collections that can be curated in the shared config directory
+const SHARED_COLLECTIONS: [&str; 2] = ["saved-queries", "parser-profiles"];
+
+/// The team-shared config directory, if one is configured
+#[tauri::command]
+pub fn get_shared_config_dir(app: AppHandle) -> Option<String> {
+    storage::shared_config_settings(&app)
+        .shared_dir
+        .map(|dir| dir.display().to_string())
+}
+
+/// Point Loggy at a read-only shared config directory (or clear it with None).
+/// Its workspaces.json, saved-queries.json and parser-profiles.json are merged
+/// with local configuration.
+#[tauri::command]
+pub fn set_shared_config_dir(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    let shared_dir = match path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => {
+            let dir = PathBuf::from(&path);
+            if !dir.is_dir() {
+                return Err(format!("{} is not a directory", path));
+            }
+            Some(dir)
+        }
+        None => None,
+    };
+    log::info!("Shared config directory set to {:?}", shared_dir);
+    storage::store_shared_config_settings(&app, &SharedConfigSettings { shared_dir })
+}
+
+/// Entries of a shared frontend collection ("saved-queries" or "parser-profiles"),
+/// each marked with `"shared": true` for the frontend to merge with its local entries
+#[tauri::command]
+pub fn load_shared_collection(
+    app: AppHandle,
+    collection: String,
+) -> Result<Vec<serde_json::Value>, String> {
+    if !SHARED_COLLECTIONS.contains(&collection.as_str()) {
+        return Err(format!("Unknown shared collection {}", collection));
+    }
+    let mut entries: Vec<serde_json::Value> =
+        storage::read_shared_json(&app, &format!("{}.json", collection));
+    for entry in entries.iter_mut() {
+        if let serde_json::Value::Object(map) = entry {
+            map.insert("shared".to_string(), serde_json::Value::Bool(true));
+        }
+    }
+    Ok(entries)
+}
@@ -0,0 +1,355 @@
+use crate::redaction::{self, ProfileRedaction, Redactor};
+use crate::{audit, storage, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use tokio::io::AsyncWriteExt;
+
+/// File in the app config directory holding the share preferences
+const SHARE_SETTINGS_FILE: &str = "share.json";
+
+/// File in the app config directory listing published pastes
+const SHARES_FILE: &str = "shares.json";
+
+/// Largest selection that can be shared; bigger ones belong in an export
+const MAX_SHARED_EVENTS: usize = 1000;
+const MAX_SHARED_BYTES: usize = 1024 * 1024;
+
+/// How long the paste command may take before it is killed
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often expired gists are looked for
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+static SHARES_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Where shared selections are published
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareProvider {
+    /// A secret GitHub gist created with the `gh` CLI, deleted once it expires
+    #[default]
+    Gist,
+    /// `paste_command`, which reads the text on stdin and prints the URL
+    Command,
+}
+
+/// Share preferences
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShareSettings {
+    #[serde(default)]
+    pub provider: ShareProvider,
+    /// Program and arguments for the Command provider, e.g. ["pastebinit", "-"]
+    #[serde(default)]
+    pub paste_command: Option<Vec<String>>,
+    /// Expiry applied when share_selection isn't given one (None keeps pastes)
+    #[serde(default)]
+    pub default_expiry_hours: Option<u32>,
+}
+
+/// A selection published by share_selection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedPaste {
+    pub url: String,
+    pub provider: ShareProvider,
+    /// Gist id, used to delete it on expiry
+    #[serde(default)]
+    pub gist_id: Option<String>,
+    pub title: String,
+    pub event_count: usize,
+    /// Unix millis
+    pub created_at: i64,
+    /// Unix millis after which the gist is deleted (not enforced for the Command
+    /// provider, whose service handles its own expiry)
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+}
+
+fn load_settings(app: &AppHandle) -> ShareSettings {
+    storage::read_json(app, SHARE_SETTINGS_FILE)
+}
+
+fn update_shares<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Vec<SharedPaste>) -> T,
+) -> Result<T, String> {
+    let _guard = SHARES_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut shares: Vec<SharedPaste> = storage::read_json(app, SHARES_FILE);
+    let result = f(&mut shares);
+    storage::write_json(app, SHARES_FILE, &shares)?;
+    Ok(result)
+}
+
+/// The profile's masking rules, or the built-in rules when masking is off for it:
+/// shared text always leaves the machine redacted, whatever the session shows
+fn share_redactor(app: &AppHandle, profile: Option<&str>) -> Result<Arc<Redactor>, String> {
+    match redaction::redactor_for_profile(app, profile) {
+        Some(redactor) => Ok(redactor),
+        None => Redactor::new(&ProfileRedaction {
+            enabled: true,
+            use_builtin_rules: true,
+            rules: Vec::new(),
+        })
+        .map(Arc::new),
+    }
+}
+
+fn format_line(event: &LogEvent) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(event.timestamp)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string())
+        .unwrap_or_else(|| event.timestamp.to_string());
+    let stream = event.log_stream_name.as_deref().unwrap_or("-");
+    format!("{}  {}  {}", time, stream, event.message.trim_end())
+}
+
+/// Run `program args` with `input` on stdin and return its stdout
+async fn run_with_input(program: &str, args: &[String], input: &str) -> Result<String, String> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+    let output = tokio::time::timeout(PUBLISH_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            format!(
+                "{} timed out after {} seconds",
+                program,
+                PUBLISH_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("{} failed: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The first URL a paste tool printed
+fn parse_url(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .find(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .map(str::to_string)
+}
+
+/// Publish `text`, returning the URL and the gist id for the Gist provider
+async fn publish(
+    settings: &ShareSettings,
+    title: &str,
+    text: &str,
+) -> Result<(String, Option<String>), String> {
+    match settings.provider {
+        ShareProvider::Gist => {
+            // gh gists are secret unless --public is passed
+            let args = [
+                "gist".to_string(),
+                "create".to_string(),
+                "--filename".to_string(),
+                "events.log".to_string(),
+                "--desc".to_string(),
+                title.to_string(),
+                "-".to_string(),
+            ];
+            let output = run_with_input("gh", &args, text).await?;
+            let url =
+                parse_url(&output).ok_or_else(|| "gh did not print the gist URL".to_string())?;
+            let gist_id = url.rsplit('/').next().map(str::to_string);
+            Ok((url, gist_id))
+        }
+        ShareProvider::Command => {
+            let (program, args) = settings
+                .paste_command
+                .as_deref()
+                .and_then(|argv| argv.split_first())
+                .ok_or_else(|| "No paste command is configured".to_string())?;
+            let output = run_with_input(program, args, text).await?;
+            let url =
+                parse_url(&output).ok_or_else(|| format!("{} did not print a URL", program))?;
+            Ok((url, None))
+        }
+    }
+}
+
+async fn delete_gist(gist_id: &str) -> Result<(), String> {
+    let args = [
+        "gist".to_string(),
+        "delete".to_string(),
+        gist_id.to_string(),
+        "--yes".to_string(),
+    ];
+    run_with_input("gh", &args, "").await.map(|_| ())
+}
+
+/// Share preferences
+#[tauri::command]
+pub fn get_share_settings(app: AppHandle) -> ShareSettings {
+    load_settings(&app)
+}
+
+/// Save share preferences; the command provider needs a paste command
+#[tauri::command]
+pub fn set_share_settings(app: AppHandle, settings: ShareSettings) -> Result<(), String> {
+    if settings.provider == ShareProvider::Command
+        && settings.paste_command.as_ref().is_none_or(|c| c.is_empty())
+    {
+        return Err("Set the paste command to use the command provider".to_string());
+    }
+    storage::write_json(&app, SHARE_SETTINGS_FILE, &settings)
+}
+
+/// Publish events of a session (the session's selection when `event_ids` is None)
+/// as one redacted text paste and return it with its URL, so a handful of
+/// relevant lines can be shared without attaching an export. Messages are masked
+/// with the profile's rules (built-in rules when masking is off) even when the
+/// session was revealed. Gists are secret and deleted after `expires_in_hours`.
+#[tauri::command]
+pub async fn share_selection(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    event_ids: Option<Vec<String>>,
+    title: Option<String>,
+    expires_in_hours: Option<u32>,
+) -> Result<SharedPaste, String> {
+    let mut events = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let ids = event_ids.unwrap_or_else(|| session.selection().event_ids);
+        ids.iter()
+            .filter_map(|id| session.event(id))
+            .collect::<Vec<LogEvent>>()
+    };
+    if events.is_empty() {
+        return Err("Select the events to share".to_string());
+    }
+    if events.len() > MAX_SHARED_EVENTS {
+        return Err(format!(
+            "At most {} events can be shared; export larger selections",
+            MAX_SHARED_EVENTS
+        ));
+    }
+
+    let profile = state.current_profile.lock().await.clone();
+    share_redactor(&app, profile.as_deref())?.apply(&mut events);
+    let text: String = events.iter().map(|e| format_line(e) + "\n").collect();
+    if text.len() > MAX_SHARED_BYTES {
+        return Err(format!(
+            "The selection is larger than {} KB; export it instead",
+            MAX_SHARED_BYTES / 1024
+        ));
+    }
+
+    let settings = load_settings(&app);
+    let title = title.unwrap_or_else(|| format!("{} log events", events.len()));
+    let expiry_hours = expires_in_hours.or(settings.default_expiry_hours);
+    let result = publish(&settings, &title, &text).await;
+    audit::record(
+        &app,
+        &state,
+        "share_selection",
+        serde_json::json!({
+            "provider": settings.provider,
+            "event_count": events.len(),
+            "expires_in_hours": expiry_hours,
+        }),
+        &result,
+    )
+    .await;
+    let (url, gist_id) = result?;
+
+    let created_at = chrono::Utc::now().timestamp_millis();
+    let share = SharedPaste {
+        url,
+        provider: settings.provider,
+        gist_id,
+        title,
+        event_count: events.len(),
+        created_at,
+        expires_at: expiry_hours.map(|h| created_at + i64::from(h) * 3_600_000),
+    };
+    update_shares(&app, |shares| shares.push(share.clone()))?;
+    Ok(share)
+}
+
+/// Published pastes, newest first
+#[tauri::command]
+pub fn list_shares(app: AppHandle) -> Vec<SharedPaste> {
+    let mut shares: Vec<SharedPaste> = storage::read_json(&app, SHARES_FILE);
+    shares.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    shares
+}
+
+/// Delete a paste now: the gist itself for the Gist provider, otherwise just the
+/// record (the paste service is not contacted)
+#[tauri::command]
+pub async fn delete_share(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<(), String> {
+    let share = list_shares(app.clone())
+        .into_iter()
+        .find(|s| s.url == url)
+        .ok_or_else(|| format!("No share with URL {}", url))?;
+    let result = match &share.gist_id {
+        Some(id) if share.provider == ShareProvider::Gist => delete_gist(id).await,
+        _ => Ok(()),
+    };
+    audit::record(
+        &app,
+        &state,
+        "delete_share",
+        serde_json::json!({ "url": url }),
+        &result,
+    )
+    .await;
+    result?;
+    update_shares(&app, |shares| shares.retain(|s| s.url != url))
+}
+
+/// Delete expired gists at startup and then hourly. Failures (gh missing or
+/// signed out) are logged and retried on the next pass.
+pub async fn run_expiry_sweeper(app: AppHandle) {
+    loop {
+        let now = chrono::Utc::now().timestamp_millis();
+        let expired: Vec<SharedPaste> = storage::read_json::<Vec<SharedPaste>>(&app, SHARES_FILE)
+            .into_iter()
+            .filter(|s| s.provider == ShareProvider::Gist && s.expires_at.is_some_and(|e| e <= now))
+            .collect();
+        for share in expired {
+            let Some(id) = &share.gist_id else {
+                continue;
+            };
+            match delete_gist(id).await {
+                Ok(()) => {
+                    log::info!("Deleted expired gist {}", share.url);
+                    if let Err(e) =
+                        update_shares(&app, |shares| shares.retain(|s| s.url != share.url))
+                    {
+                        log::warn!("Failed to update {}: {}", SHARES_FILE, e);
+                    }
+                }
+                Err(e) => log::warn!("Failed to delete expired gist {}: {}", share.url, e),
+            }
+        }
+        tokio::time::sleep(EXPIRY_CHECK_INTERVAL).await;
+    }
+}
@@ -0,0 +1,361 @@
+use crate::connections::EnvironmentEmitter;
+use crate::operations::Operation;
+use crate::{pacing, send_filter_with_retry, AppState, LogEvent, LogsProgress, PageError};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::JoinSet;
+
+/// Shortest time slice worth a request stream of its own
+const MIN_SLICE_MS: i64 = 15 * 60 * 1000;
+
+/// Most slices a range is split into
+const MAX_SLICES: i64 = 8;
+
+/// Slices fetched at once; each paces itself when FilterLogEvents throttles
+const MAX_CONCURRENT_SLICES: usize = 4;
+
+/// Cancellation of one fetch. Slices check it between pages and the fetch
+/// aborts its in-flight requests as soon as it is cancelled.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// A fetch's cancel token, registered in AppState under its fetch_id until dropped
+pub struct FetchRegistration<'a> {
+    state: &'a AppState,
+    fetch_id: u32,
+    pub token: CancelToken,
+}
+
+impl Drop for FetchRegistration<'_> {
+    fn drop(&mut self) {
+        let mut tokens = self
+            .state
+            .fetch_tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // A newer fetch reusing the id keeps its own token
+        if tokens
+            .get(&self.fetch_id)
+            .is_some_and(|t| Arc::ptr_eq(&t.cancelled, &self.token.cancelled))
+        {
+            tokens.remove(&self.fetch_id);
+        }
+    }
+}
+
+/// Register a new cancel token for `fetch_id`
+pub fn register(state: &AppState, fetch_id: u32) -> FetchRegistration<'_> {
+    let token = CancelToken::default();
+    state
+        .fetch_tokens
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(fetch_id, token.clone());
+    FetchRegistration {
+        state,
+        fetch_id,
+        token,
+    }
+}
+
+/// Cancel the fetch registered as `fetch_id`, or every registered fetch. Returns
+/// how many were cancelled.
+pub fn cancel(state: &AppState, fetch_id: Option<u32>) -> usize {
+    let tokens = state.fetch_tokens.lock().unwrap_or_else(|e| e.into_inner());
+    let mut cancelled = 0;
+    for (id, token) in tokens.iter() {
+        if fetch_id.is_none_or(|f| f == *id) {
+            token.cancel();
+            cancelled += 1;
+        }
+    }
+    cancelled
+}
+
+/// Number of slices `start..=end` is fetched in; 1 means a serial fetch
+pub fn slice_count(start: i64, end: i64) -> usize {
+    ((end - start + 1) / MIN_SLICE_MS).clamp(1, MAX_SLICES) as usize
+}
+
+/// Outcome of a sliced fetch
+#[derive(Default)]
+pub struct SlicedFetch {
+    /// Oldest first
+    pub events: Vec<LogEvent>,
+    pub size_bytes: usize,
+    /// "count" or "size" when a limit stopped the fetch before the end of the range
+    pub truncated: Option<&'static str>,
+    /// The error that cut the fetch short; `events` are everything before it
+    pub error: Option<PageError>,
+    pub cancelled: bool,
+}
+
+/// One slice, fetched until its pages run out, a limit or an error
+struct Slice {
+    index: usize,
+    events: Vec<LogEvent>,
+    size_bytes: usize,
+    /// Every page of the slice was fetched
+    complete: bool,
+    error: Option<PageError>,
+}
+
+/// What every slice task shares
+struct SliceContext {
+    app: AppHandle,
+    client: CloudWatchClient,
+    op: Arc<Operation>,
+    token: CancelToken,
+    fetch_id: u32,
+    log_group_name: String,
+    filter_pattern: Option<String>,
+    max_events: usize,
+    max_bytes: usize,
+    /// Events and bytes fetched by all slices so far, for progress events
+    fetched_events: AtomicUsize,
+    fetched_bytes: AtomicUsize,
+}
+
+async fn fetch_slice(context: Arc<SliceContext>, index: usize, start: i64, end: i64) -> Slice {
+    let mut slice = Slice {
+        index,
+        events: Vec::new(),
+        size_bytes: 0,
+        complete: false,
+        error: None,
+    };
+    let mut tuner = pacing::PageTuner::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        if context.token.is_cancelled() || context.op.is_cancelled() {
+            return slice;
+        }
+        let request = context
+            .client
+            .filter_log_events()
+            .log_group_name(&context.log_group_name)
+            .start_time(start)
+            .end_time(end)
+            .set_filter_pattern(context.filter_pattern.clone())
+            .set_next_token(next_token);
+        match send_filter_with_retry(request, &context.op, &mut tuner).await {
+            Ok(response) => {
+                let events: Vec<LogEvent> = response
+                    .events
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(LogEvent::from)
+                    .collect();
+                context.op.page(events.len());
+                let size: usize = events.iter().map(|e| e.message.len()).sum();
+                let progress = LogsProgress {
+                    fetch_id: context.fetch_id,
+                    count: context
+                        .fetched_events
+                        .fetch_add(events.len(), Ordering::Relaxed)
+                        + events.len(),
+                    size_bytes: context.fetched_bytes.fetch_add(size, Ordering::Relaxed) + size,
+                };
+                context.app.emit_tagged("logs-progress", progress).ok();
+                slice.size_bytes += size;
+                slice.events.extend(events);
+
+                next_token = response.next_token;
+                if next_token.is_none() {
+                    slice.complete = true;
+                    return slice;
+                }
+                // No earlier slice can need more than the whole fetch's limits
+                if slice.events.len() >= context.max_events || slice.size_bytes >= context.max_bytes
+                {
+                    return slice;
+                }
+            }
+            Err(error) => {
+                slice.error = Some(error);
+                return slice;
+            }
+        }
+    }
+}
+
+/// Walk the finished slices in time order the way they are merged. True once
+/// the result can't change: every slice is in, or the merged prefix already hit
+/// a limit, an error or a cancelled slice, so later slices are not needed.
+fn settled(slices: &[Option<Slice>], max_events: usize, max_bytes: usize) -> bool {
+    let (mut events, mut bytes) = (0, 0);
+    for slot in slices {
+        let Some(slice) = slot else {
+            return false;
+        };
+        events += slice.events.len();
+        bytes += slice.size_bytes;
+        if slice.error.is_some() || !slice.complete || events >= max_events || bytes >= max_bytes {
+            return true;
+        }
+    }
+    true
+}
+
+/// Fetch `start..=end` of a log group as consecutive time slices in parallel
+/// (bounded, each slice pacing itself under throttling) and merge them in time
+/// order. Slices past a limit reached by the earlier ones are abandoned, and
+/// cancelling `token` aborts the requests in flight.
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_sliced(
+    app: &AppHandle,
+    client: &CloudWatchClient,
+    op: &Arc<Operation>,
+    token: &CancelToken,
+    fetch_id: u32,
+    log_group_name: &str,
+    start: i64,
+    end: i64,
+    filter_pattern: Option<&str>,
+    max_events: usize,
+    max_bytes: usize,
+) -> SlicedFetch {
+    let count = slice_count(start, end);
+    let width = (end - start + 1) / count as i64;
+    let context = Arc::new(SliceContext {
+        app: app.clone(),
+        client: client.clone(),
+        op: op.clone(),
+        token: token.clone(),
+        fetch_id,
+        log_group_name: log_group_name.to_string(),
+        filter_pattern: filter_pattern.filter(|p| !p.is_empty()).map(str::to_string),
+        max_events,
+        max_bytes,
+        fetched_events: AtomicUsize::new(0),
+        fetched_bytes: AtomicUsize::new(0),
+    });
+    log::info!(
+        "Fetching {} in {} parallel slices of {} minutes",
+        log_group_name,
+        count,
+        width / 60_000
+    );
+
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_SLICES));
+    let mut tasks = JoinSet::new();
+    // Slice index of each task, so a task that fails still fills its slot
+    let mut task_slices = HashMap::new();
+    for index in 0..count {
+        let slice_start = start + index as i64 * width;
+        let slice_end = if index + 1 == count {
+            end
+        } else {
+            slice_start + width - 1
+        };
+        let context = context.clone();
+        let permits = permits.clone();
+        let task = tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            fetch_slice(context, index, slice_start, slice_end).await
+        });
+        task_slices.insert(task.id(), index);
+    }
+
+    let mut slices: Vec<Option<Slice>> = (0..count).map(|_| None).collect();
+    let mut cancelled = false;
+    loop {
+        tokio::select! {
+            _ = token.cancelled() => {
+                cancelled = true;
+                break;
+            }
+            joined = tasks.join_next() => {
+                let slice = match joined {
+                    Some(Ok(slice)) => slice,
+                    // A failed task ends the result at its slice, like a failed page
+                    Some(Err(e)) => {
+                        log::warn!("Fetch slice task failed: {}", e);
+                        let Some(&index) = task_slices.get(&e.id()) else {
+                            continue;
+                        };
+                        Slice {
+                            index,
+                            events: Vec::new(),
+                            size_bytes: 0,
+                            complete: false,
+                            error: Some(PageError {
+                                message: format!("Fetching a time slice failed: {}", e),
+                                group_missing: false,
+                            }),
+                        }
+                    }
+                    None => break,
+                };
+                let index = slice.index;
+                slices[index] = Some(slice);
+                if settled(&slices, max_events, max_bytes) {
+                    break;
+                }
+            }
+        }
+    }
+    // Abandoned slices stop mid-request
+    tasks.abort_all();
+
+    let mut result = SlicedFetch {
+        cancelled: cancelled || op.is_cancelled(),
+        ..SlicedFetch::default()
+    };
+    for (index, slot) in slices.into_iter().enumerate() {
+        let Some(mut slice) = slot else {
+            break;
+        };
+        // Slices cover consecutive ranges, so sorting each one orders the whole
+        slice.events.sort_by_key(|e| e.timestamp);
+        result.size_bytes += slice.size_bytes;
+        result.events.extend(slice.events);
+        if slice.error.is_some() {
+            result.error = slice.error;
+            break;
+        }
+        let more = !slice.complete || index + 1 < count;
+        if result.events.len() >= max_events && (more || result.events.len() > max_events) {
+            result.events.truncate(max_events);
+            result.truncated = Some("count");
+            break;
+        }
+        if result.size_bytes >= max_bytes && more {
+            result.truncated = Some("size");
+            break;
+        }
+        if !slice.complete {
+            break;
+        }
+    }
+    result
+}
@@ -0,0 +1,486 @@
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_ssooidc::operation::create_token::CreateTokenError;
+use aws_sdk_ssooidc::Client as OidcClient;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Client name registered with IAM Identity Center for the device flow
+const OIDC_CLIENT_NAME: &str = "loggy";
+
+/// Scope registered for `[sso-session]` profiles; it is what grants refresh tokens
+const REGISTRATION_SCOPE: &str = "sso:account:access";
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+const REFRESH_TOKEN_GRANT: &str = "refresh_token";
+
+/// A client registration this close to expiry is replaced rather than reused
+const REGISTRATION_MARGIN_SECS: i64 = 15 * 60;
+
+/// Sections of ~/.aws/config by header ("default", "profile dev",
+/// "sso-session corp"), each a map of keys to values
+fn config_sections() -> HashMap<String, HashMap<String, String>> {
+    let Some(contents) = crate::get_aws_config_path().and_then(|p| std::fs::read_to_string(p).ok())
+    else {
+        return HashMap::new();
+    };
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            let header = line[1..line.len() - 1].trim().to_string();
+            sections.entry(header.clone()).or_default();
+            current = Some(header);
+            continue;
+        }
+        let (Some(section), Some((key, value))) = (&current, line.split_once('=')) else {
+            continue;
+        };
+        let value = value.trim();
+        if !value.is_empty() {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.to_string());
+        }
+    }
+    sections
+}
+
+fn profile_header(profile: &str) -> String {
+    if profile == "default" {
+        "default".to_string()
+    } else {
+        format!("profile {}", profile)
+    }
+}
+
+/// The SSO portal a profile signs in through
+#[derive(Debug, Clone, Serialize)]
+pub struct SsoTarget {
+    pub profile: String,
+    /// `[sso-session NAME]` the profile references (None for legacy profiles that
+    /// set sso_start_url directly)
+    pub session_name: Option<String>,
+    pub start_url: String,
+    pub region: Option<String>,
+    /// Expiry of the cached portal token for this session (RFC 3339)
+    pub token_expires_at: Option<String>,
+    /// The cached token exists and hasn't expired
+    pub token_valid: bool,
+}
+
+impl SsoTarget {
+    /// Identity of the portal session: profiles sharing it share one token
+    pub fn session_key(&self) -> String {
+        self.session_name
+            .clone()
+            .unwrap_or_else(|| self.start_url.clone())
+    }
+
+    /// The token cache file the CLI and SDK use: ~/.aws/sso/cache/<sha1>.json of
+    /// the session name, or of the start URL for legacy profiles
+    fn cache_path(&self) -> Option<PathBuf> {
+        let digest = Sha1::digest(self.session_key().as_bytes());
+        let name: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        dirs::home_dir().map(|h| {
+            h.join(".aws")
+                .join("sso")
+                .join("cache")
+                .join(format!("{}.json", name))
+        })
+    }
+
+    fn with_token_state(mut self) -> Self {
+        let expires_at = read_cached_token(&self).map(|t| t.expires_at);
+        self.token_valid = expires_at
+            .as_deref()
+            .and_then(|e| chrono::DateTime::parse_from_rfc3339(e).ok())
+            .is_some_and(|e| e > chrono::Utc::now());
+        self.token_expires_at = expires_at;
+        self
+    }
+}
+
+/// An SSO token cache file, in the layout the AWS CLI and SDKs share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedToken {
+    #[serde(default)]
+    start_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+    access_token: String,
+    /// RFC 3339
+    expires_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    registration_expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+}
+
+fn read_cached_token(target: &SsoTarget) -> Option<CachedToken> {
+    let contents = std::fs::read_to_string(target.cache_path()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cached_token(target: &SsoTarget, token: &CachedToken) -> Result<(), String> {
+    let path = target
+        .cache_path()
+        .ok_or("Could not determine the home directory for the SSO token cache")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(token).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // The token grants access to every account of the portal
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+fn rfc3339_in(secs: i64) -> String {
+    (chrono::Utc::now() + chrono::Duration::seconds(secs))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// An OIDC client registration, reused until it nears expiry
+struct Registration {
+    client_id: String,
+    client_secret: String,
+    /// RFC 3339
+    expires_at: String,
+}
+
+impl Registration {
+    fn from_cache(token: &CachedToken) -> Option<Registration> {
+        let expires_at = token.registration_expires_at.clone()?;
+        let usable = chrono::DateTime::parse_from_rfc3339(&expires_at).is_ok_and(|e| {
+            e > chrono::Utc::now() + chrono::Duration::seconds(REGISTRATION_MARGIN_SECS)
+        });
+        usable
+            .then(|| Registration {
+                client_id: token.client_id.clone().unwrap_or_default(),
+                client_secret: token.client_secret.clone().unwrap_or_default(),
+                expires_at,
+            })
+            .filter(|r| !r.client_id.is_empty() && !r.client_secret.is_empty())
+    }
+
+    async fn register(client: &OidcClient, target: &SsoTarget) -> Result<Registration, String> {
+        let response = client
+            .register_client()
+            .client_name(OIDC_CLIENT_NAME)
+            .client_type("public")
+            .set_scopes(
+                target
+                    .session_name
+                    .as_ref()
+                    .map(|_| vec![REGISTRATION_SCOPE.to_string()]),
+            )
+            .send()
+            .await
+            .map_err(|e| crate::humanize_aws_error(&format!("{}", e)))?;
+        let expires_at = chrono::DateTime::from_timestamp(response.client_secret_expires_at(), 0)
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        Ok(Registration {
+            client_id: response.client_id().unwrap_or_default().to_string(),
+            client_secret: response.client_secret().unwrap_or_default().to_string(),
+            expires_at,
+        })
+    }
+}
+
+/// Cache a newly issued token so the SDK credential provider (and the AWS CLI)
+/// picks it up
+fn store_token(
+    target: &SsoTarget,
+    registration: &Registration,
+    access_token: Option<&str>,
+    expires_in: i32,
+    refresh_token: Option<&str>,
+) -> Result<(), String> {
+    let access_token = access_token.ok_or("SSO returned no access token")?;
+    write_cached_token(
+        target,
+        &CachedToken {
+            start_url: Some(target.start_url.clone()),
+            region: target.region.clone(),
+            access_token: access_token.to_string(),
+            expires_at: rfc3339_in(i64::from(expires_in)),
+            client_id: Some(registration.client_id.clone()),
+            client_secret: Some(registration.client_secret.clone()),
+            registration_expires_at: Some(registration.expires_at.clone()),
+            refresh_token: refresh_token.map(str::to_string),
+        },
+    )
+}
+
+/// The code and page of a device authorization the user approves in the browser
+#[derive(Debug, Clone, Serialize)]
+pub struct SsoDeviceCode {
+    pub profile: String,
+    pub start_url: String,
+    pub user_code: String,
+    /// Verification page with the code filled in
+    pub verification_uri: String,
+    pub expires_at: String,
+}
+
+/// A device authorization waiting for the user's approval
+pub struct DeviceAuthorization {
+    client: OidcClient,
+    target: SsoTarget,
+    registration: Registration,
+    device_code: String,
+    interval: Duration,
+    deadline: Instant,
+    pub code: SsoDeviceCode,
+}
+
+impl DeviceAuthorization {
+    /// Poll CreateToken at the interval SSO asks for until the user approves
+    /// (caching the token), denies, or the code expires
+    pub async fn wait(mut self) -> Result<(), String> {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            if Instant::now() >= self.deadline {
+                return Err("The SSO sign-in code expired before it was approved".to_string());
+            }
+            let result = self
+                .client
+                .create_token()
+                .client_id(&self.registration.client_id)
+                .client_secret(&self.registration.client_secret)
+                .grant_type(DEVICE_CODE_GRANT)
+                .device_code(&self.device_code)
+                .send()
+                .await;
+            match result {
+                Ok(token) => {
+                    return store_token(
+                        &self.target,
+                        &self.registration,
+                        token.access_token(),
+                        token.expires_in(),
+                        token.refresh_token(),
+                    );
+                }
+                Err(e) => match e.as_service_error() {
+                    Some(CreateTokenError::AuthorizationPendingException(_)) => {}
+                    Some(CreateTokenError::SlowDownException(_)) => {
+                        self.interval += Duration::from_secs(5);
+                    }
+                    Some(CreateTokenError::AccessDeniedException(_)) => {
+                        return Err("The SSO sign-in was denied".to_string());
+                    }
+                    Some(CreateTokenError::ExpiredTokenException(_)) => {
+                        return Err(
+                            "The SSO sign-in code expired before it was approved".to_string()
+                        );
+                    }
+                    _ => return Err(crate::humanize_aws_error(&format!("{}", e))),
+                },
+            }
+        }
+    }
+}
+
+/// How signing in to a portal got started
+pub enum SsoLogin {
+    /// The cached refresh token was exchanged for a new token; nothing to approve
+    Refreshed,
+    /// The user has to approve the code in the browser
+    Pending(Box<DeviceAuthorization>),
+}
+
+/// Start signing in to a profile's SSO portal with the OAuth device flow, as
+/// `aws sso login` does: refresh the cached token when the session allows it,
+/// otherwise register (or reuse the cached registration) and start a device
+/// authorization.
+pub async fn start_login(target: &SsoTarget) -> Result<SsoLogin, String> {
+    let region = target.region.clone().ok_or_else(|| {
+        format!(
+            "The {} profile has no sso_region in ~/.aws/config",
+            target.profile
+        )
+    })?;
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .region(Region::new(region))
+        .no_credentials()
+        .load()
+        .await;
+    let client = OidcClient::new(&config);
+
+    let cached = read_cached_token(target);
+    let registration = match cached.as_ref().and_then(Registration::from_cache) {
+        Some(registration) => registration,
+        None => Registration::register(&client, target).await?,
+    };
+
+    if let Some(refresh_token) = cached.and_then(|t| t.refresh_token) {
+        match client
+            .create_token()
+            .client_id(&registration.client_id)
+            .client_secret(&registration.client_secret)
+            .grant_type(REFRESH_TOKEN_GRANT)
+            .refresh_token(refresh_token)
+            .send()
+            .await
+        {
+            Ok(token) => {
+                store_token(
+                    target,
+                    &registration,
+                    token.access_token(),
+                    token.expires_in(),
+                    token.refresh_token(),
+                )?;
+                return Ok(SsoLogin::Refreshed);
+            }
+            // Refresh tokens expire with the portal session; sign in again
+            Err(e) => log::debug!("SSO token refresh failed: {}", e),
+        }
+    }
+
+    let response = client
+        .start_device_authorization()
+        .client_id(&registration.client_id)
+        .client_secret(&registration.client_secret)
+        .start_url(&target.start_url)
+        .send()
+        .await
+        .map_err(|e| crate::humanize_aws_error(&format!("{}", e)))?;
+    let device_code = response
+        .device_code()
+        .ok_or("SSO returned no device code")?
+        .to_string();
+    let verification_uri = response
+        .verification_uri_complete()
+        .or(response.verification_uri())
+        .ok_or("SSO returned no verification page")?
+        .to_string();
+    let expires_in = i64::from(response.expires_in());
+    Ok(SsoLogin::Pending(Box::new(DeviceAuthorization {
+        code: SsoDeviceCode {
+            profile: target.profile.clone(),
+            start_url: target.start_url.clone(),
+            user_code: response.user_code().unwrap_or_default().to_string(),
+            verification_uri,
+            expires_at: rfc3339_in(expires_in),
+        },
+        client,
+        target: target.clone(),
+        registration,
+        device_code,
+        interval: Duration::from_secs(response.interval().max(1) as u64),
+        deadline: Instant::now() + Duration::from_secs(expires_in.max(0) as u64),
+    })))
+}
+
+fn resolve_in(
+    sections: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Option<SsoTarget> {
+    let section = sections.get(&profile_header(profile))?;
+    let target = match section.get("sso_session") {
+        Some(session_name) => {
+            let session = sections.get(&format!("sso-session {}", session_name))?;
+            SsoTarget {
+                profile: profile.to_string(),
+                session_name: Some(session_name.clone()),
+                start_url: session.get("sso_start_url")?.clone(),
+                region: session.get("sso_region").cloned(),
+                token_expires_at: None,
+                token_valid: false,
+            }
+        }
+        None => SsoTarget {
+            profile: profile.to_string(),
+            session_name: None,
+            start_url: section.get("sso_start_url")?.clone(),
+            region: section.get("sso_region").cloned(),
+            token_expires_at: None,
+            token_valid: false,
+        },
+    };
+    Some(target.with_token_state())
+}
+
+/// The SSO portal of a profile (AWS_PROFILE or "default" when None), following
+/// `sso_session` to its `[sso-session]` section. None for non-SSO profiles.
+pub fn resolve(profile: Option<&str>) -> Option<SsoTarget> {
+    let env_profile = std::env::var("AWS_PROFILE").ok();
+    let profile = profile.or(env_profile.as_deref()).unwrap_or("default");
+    resolve_in(&config_sections(), profile)
+}
+
+/// Which SSO portal (and session) signing in for a profile will open, and whether
+/// its cached token is still valid, so the UI can say so before launching a login
+#[tauri::command]
+pub fn get_sso_login_target(profile: Option<String>) -> Option<SsoTarget> {
+    resolve(profile.as_deref())
+}
+
+/// One SSO portal session and the profiles that sign in through it
+#[derive(Debug, Clone, Serialize)]
+pub struct SsoSessionInfo {
+    pub session_key: String,
+    pub session_name: Option<String>,
+    pub start_url: String,
+    pub region: Option<String>,
+    pub profiles: Vec<String>,
+    pub token_expires_at: Option<String>,
+    pub token_valid: bool,
+}
+
+/// Every SSO portal session configured in ~/.aws/config with its profiles, for
+/// organizations with several identity centers (e.g. prod and sandbox)
+#[tauri::command]
+pub fn list_sso_sessions() -> Vec<SsoSessionInfo> {
+    let sections = config_sections();
+    let mut sessions: BTreeMap<String, SsoSessionInfo> = BTreeMap::new();
+    for header in sections.keys() {
+        let profile = match header.strip_prefix("profile ") {
+            Some(name) => name.trim(),
+            None if header == "default" => "default",
+            None => continue,
+        };
+        let Some(target) = resolve_in(&sections, profile) else {
+            continue;
+        };
+        sessions
+            .entry(target.session_key())
+            .or_insert_with(|| SsoSessionInfo {
+                session_key: target.session_key(),
+                session_name: target.session_name.clone(),
+                start_url: target.start_url.clone(),
+                region: target.region.clone(),
+                profiles: Vec::new(),
+                token_expires_at: target.token_expires_at.clone(),
+                token_valid: target.token_valid,
+            })
+            .profiles
+            .push(profile.to_string());
+    }
+    let mut sessions: Vec<SsoSessionInfo> = sessions.into_values().collect();
+    sessions.iter_mut().for_each(|s| s.profiles.sort());
+    sessions
+}
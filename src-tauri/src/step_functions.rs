@@ -0,0 +1,209 @@
+use crate::{filter_all_events, AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+/// Max events scanned when building execution timelines from CloudWatch
+const MAX_TIMELINE_EVENTS: usize = 50_000;
+
+/// A Step Functions execution history event as written to CloudWatch Logs
+/// when logging is enabled on a state machine
+#[derive(Debug, Clone, Deserialize)]
+struct ExecutionHistoryEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    execution_arn: String,
+    #[serde(default)]
+    event_timestamp: Option<String>,
+    #[serde(default)]
+    details: serde_json::Value,
+}
+
+/// One state visited during an execution
+#[derive(Debug, Clone, Serialize)]
+pub struct StateTransition {
+    pub name: String,
+    /// Task, Choice, Pass, Wait, Parallel, Map, ...
+    pub state_type: String,
+    /// "Running", "Succeeded", or "Failed"
+    pub status: String,
+    pub entered_at: i64,
+    pub exited_at: Option<i64>,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+    pub cause: Option<String>,
+}
+
+/// Structured timeline of a single execution
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionTimeline {
+    pub execution_arn: String,
+    /// "Running", "Succeeded", "Failed", "TimedOut", or "Aborted"
+    pub status: String,
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+    pub cause: Option<String>,
+    pub states: Vec<StateTransition>,
+}
+
+impl ExecutionTimeline {
+    fn new(execution_arn: &str) -> Self {
+        Self {
+            execution_arn: execution_arn.to_string(),
+            status: "Running".to_string(),
+            started_at: None,
+            ended_at: None,
+            duration_ms: None,
+            error: None,
+            cause: None,
+            states: Vec::new(),
+        }
+    }
+
+    /// The most recently entered state that hasn't exited yet
+    fn open_state(&mut self) -> Option<&mut StateTransition> {
+        self.states.iter_mut().rev().find(|s| s.exited_at.is_none())
+    }
+
+    fn apply(&mut self, event: &ExecutionHistoryEvent, timestamp: i64) {
+        let detail = |key: &str| {
+            event
+                .details
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let event_type = event.event_type.as_str();
+
+        if event_type == "ExecutionStarted" {
+            self.started_at = Some(timestamp);
+        } else if let Some(state_type) = event_type.strip_suffix("StateEntered") {
+            self.states.push(StateTransition {
+                name: detail("name").unwrap_or_default(),
+                state_type: state_type.to_string(),
+                status: "Running".to_string(),
+                entered_at: timestamp,
+                exited_at: None,
+                duration_ms: None,
+                error: None,
+                cause: None,
+            });
+        } else if event_type.ends_with("StateExited") {
+            let name = detail("name");
+            if let Some(state) = self
+                .states
+                .iter_mut()
+                .rev()
+                .find(|s| s.exited_at.is_none() && Some(&s.name) == name.as_ref())
+            {
+                state.exited_at = Some(timestamp);
+                state.duration_ms = Some(timestamp - state.entered_at);
+                if state.status == "Running" {
+                    state.status = "Succeeded".to_string();
+                }
+            }
+        } else if let Some(terminal) = event_type.strip_prefix("Execution") {
+            if matches!(terminal, "Succeeded" | "Failed" | "TimedOut" | "Aborted") {
+                self.status = terminal.to_string();
+                self.ended_at = Some(timestamp);
+                self.duration_ms = self.started_at.map(|start| timestamp - start);
+                self.error = detail("error");
+                self.cause = detail("cause");
+            }
+        } else if event_type.ends_with("Failed") || event_type.ends_with("TimedOut") {
+            // Task/Lambda/Activity failures belong to the state currently running
+            let (error, cause) = (detail("error"), detail("cause"));
+            if let Some(state) = self.open_state() {
+                state.status = "Failed".to_string();
+                state.error = error;
+                state.cause = cause;
+            }
+        }
+    }
+}
+
+/// Build execution timelines from raw log events, one per execution ARN,
+/// optionally restricted to a single execution
+pub fn build_timelines(events: &[LogEvent], execution_arn: Option<&str>) -> Vec<ExecutionTimeline> {
+    let mut parsed: Vec<(i64, ExecutionHistoryEvent)> = events
+        .iter()
+        .filter_map(|e| {
+            let history: ExecutionHistoryEvent = serde_json::from_str(&e.message).ok()?;
+            if execution_arn.is_some_and(|arn| arn != history.execution_arn) {
+                return None;
+            }
+            let timestamp = history
+                .event_timestamp
+                .as_deref()
+                .and_then(|t| t.parse().ok())
+                .unwrap_or(e.timestamp);
+            Some((timestamp, history))
+        })
+        .collect();
+    parsed.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut timelines: HashMap<String, ExecutionTimeline> = HashMap::new();
+    for (timestamp, event) in &parsed {
+        timelines
+            .entry(event.execution_arn.clone())
+            .or_insert_with(|| ExecutionTimeline::new(&event.execution_arn))
+            .apply(event, *timestamp);
+    }
+
+    let mut timelines: Vec<ExecutionTimeline> = timelines.into_values().collect();
+    timelines.sort_by_key(|t| std::cmp::Reverse(t.started_at));
+    timelines
+}
+
+/// Fetch Step Functions execution history from a log group and return structured
+/// execution timelines, optionally filtered to a single execution ARN
+#[tauri::command]
+pub async fn get_execution_timelines(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    execution_arn: Option<String>,
+) -> Result<Vec<ExecutionTimeline>, String> {
+    let client = state.logs_client().await?;
+
+    // Push the execution filter down to CloudWatch when possible
+    let pattern = execution_arn
+        .as_ref()
+        .map(|arn| format!("{{ $.execution_arn = \"{}\" }}", arn));
+
+    let (events, truncated) = filter_all_events(
+        &client,
+        &log_group_name,
+        start_time,
+        end_time,
+        pattern.as_deref(),
+        MAX_TIMELINE_EVENTS,
+    )
+    .await?;
+    if truncated {
+        log::warn!(
+            "Execution timeline scan for {} truncated at {} events",
+            log_group_name,
+            MAX_TIMELINE_EVENTS
+        );
+    }
+
+    Ok(build_timelines(&events, execution_arn.as_deref()))
+}
+
+/// Build execution timelines from events already loaded into a session
+#[tauri::command]
+pub async fn get_session_execution_timelines(
+    state: State<'_, AppState>,
+    session_id: String,
+    execution_arn: Option<String>,
+) -> Result<Vec<ExecutionTimeline>, String> {
+    let sessions = state.sessions.lock().await;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    Ok(build_timelines(&session.events(), execution_arn.as_deref()))
+}
@@ -0,0 +1,83 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Directory holding the backend's persisted JSON files (workspaces, alert state, ...)
+pub fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Could not determine app config directory: {}", e))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Local file recording the team-shared configuration directory, if any
+const SHARED_CONFIG_FILE: &str = "shared-config.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SharedConfigSettings {
+    /// Read-only directory (e.g. a git checkout) whose config is merged with local config
+    #[serde(default)]
+    pub shared_dir: Option<PathBuf>,
+}
+
+pub fn shared_config_settings(app: &AppHandle) -> SharedConfigSettings {
+    read_json(app, SHARED_CONFIG_FILE)
+}
+
+pub fn store_shared_config_settings(
+    app: &AppHandle,
+    settings: &SharedConfigSettings,
+) -> Result<(), String> {
+    write_json(app, SHARED_CONFIG_FILE, settings)
+}
+
+/// Read a JSON file from the shared configuration directory. Missing directory or
+/// file yields the default value; the shared directory is never written to.
+pub fn read_shared_json<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let Some(dir) = shared_config_settings(app).shared_dir else {
+        return T::default();
+    };
+    let path = dir.join(file_name);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable shared {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Read a JSON file from the config directory, returning the default value if it
+/// doesn't exist yet. A corrupt file is logged and treated as missing.
+pub fn read_json<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let path = match config_dir(app) {
+        Ok(dir) => dir.join(file_name),
+        Err(e) => {
+            log::warn!("{}", e);
+            return T::default();
+        }
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Write a JSON file to the config directory atomically (write to temp file, then rename)
+pub fn write_json<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<(), String> {
+    let path = config_dir(app)?.join(file_name);
+    let tmp_path = path.with_extension("json.tmp");
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", file_name, e))?;
+    std::fs::write(&tmp_path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
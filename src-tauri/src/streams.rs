@@ -0,0 +1,176 @@
+use crate::{budget, columns, humanize_aws_error, preview, redaction, AppState, LogEvent};
+use aws_sdk_cloudwatchlogs::types::OrderBy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// Streams per DescribeLogStreams page unless the caller asks for fewer
+const DEFAULT_STREAM_PAGE_SIZE: i32 = 50;
+
+/// Events per GetLogEvents page unless the caller asks for fewer
+const DEFAULT_EVENT_PAGE_SIZE: i32 = 1000;
+
+/// How log streams are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamOrder {
+    #[default]
+    LastEventTime,
+    Name,
+}
+
+/// One log stream of a group
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStreamInfo {
+    pub name: String,
+    pub arn: Option<String>,
+    pub creation_time: Option<i64>,
+    pub first_event_timestamp: Option<i64>,
+    /// Updated lazily by CloudWatch (usually within an hour of the event)
+    pub last_event_timestamp: Option<i64>,
+    pub last_ingestion_time: Option<i64>,
+}
+
+/// A page of log streams
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStreamPage {
+    pub streams: Vec<LogStreamInfo>,
+    /// Pass back to list the next page; None on the last page
+    pub next_token: Option<String>,
+}
+
+/// A page of one stream's events, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamEventsPage {
+    pub events: Vec<LogEvent>,
+    /// Continues towards newer events
+    pub next_forward_token: Option<String>,
+    /// Continues towards older events
+    pub next_backward_token: Option<String>,
+    /// The page reached the end of the stream in the direction read; the same
+    /// token polls for newer events later
+    pub at_end: bool,
+}
+
+/// List the log streams of a group, most recently written first by default.
+/// `prefix` only works with name ordering (a DescribeLogStreams restriction).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn list_log_streams(
+    state: State<'_, AppState>,
+    log_group_name: String,
+    order_by: Option<StreamOrder>,
+    prefix: Option<String>,
+    descending: Option<bool>,
+    next_token: Option<String>,
+    limit: Option<i32>,
+) -> Result<LogStreamPage, String> {
+    let order_by = order_by.unwrap_or_default();
+    let prefix = prefix.filter(|p| !p.is_empty());
+    if prefix.is_some() && order_by == StreamOrder::LastEventTime {
+        return Err("A stream name prefix can only be used when ordering by name".to_string());
+    }
+    let client = state.logs_client().await?;
+    let response = client
+        .describe_log_streams()
+        .log_group_name(&log_group_name)
+        .order_by(match order_by {
+            StreamOrder::LastEventTime => OrderBy::LastEventTime,
+            StreamOrder::Name => OrderBy::LogStreamName,
+        })
+        .descending(descending.unwrap_or(order_by == StreamOrder::LastEventTime))
+        .set_log_stream_name_prefix(prefix)
+        .set_next_token(next_token)
+        .limit(limit.unwrap_or(DEFAULT_STREAM_PAGE_SIZE).clamp(1, 50))
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+    Ok(LogStreamPage {
+        streams: response
+            .log_streams()
+            .iter()
+            .filter_map(|stream| {
+                Some(LogStreamInfo {
+                    name: stream.log_stream_name()?.to_string(),
+                    arn: stream.arn().map(str::to_string),
+                    creation_time: stream.creation_time(),
+                    first_event_timestamp: stream.first_event_timestamp(),
+                    last_event_timestamp: stream.last_event_timestamp(),
+                    last_ingestion_time: stream.last_ingestion_time(),
+                })
+            })
+            .collect(),
+        next_token: response.next_token,
+    })
+}
+
+/// Read one page of a single stream with GetLogEvents, in stream order. Without a
+/// token, reading starts at the oldest event in the range (`start_from_head`,
+/// the default) or the newest; pass a returned token to page on in either direction.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_stream_events(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    log_group_name: String,
+    log_stream_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    start_from_head: Option<bool>,
+    next_token: Option<String>,
+    limit: Option<i32>,
+) -> Result<StreamEventsPage, String> {
+    let client = state.logs_client().await?;
+    let start_from_head = start_from_head.unwrap_or(true);
+    let response = client
+        .get_log_events()
+        .log_group_name(&log_group_name)
+        .log_stream_name(&log_stream_name)
+        .set_start_time(start_time)
+        .set_end_time(end_time)
+        .start_from_head(start_from_head)
+        .set_next_token(next_token.clone())
+        .limit(limit.unwrap_or(DEFAULT_EVENT_PAGE_SIZE).clamp(1, 10_000))
+        .send()
+        .await
+        .map_err(|e| humanize_aws_error(&format!("{}", e)))?;
+
+    let mut events: Vec<LogEvent> = response
+        .events
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| {
+            let mut event = LogEvent {
+                timestamp: e.timestamp.unwrap_or(0),
+                message: e.message.unwrap_or_default(),
+                log_stream_name: Some(log_stream_name.clone()),
+                event_id: None,
+                source: Some(log_group_name.clone()),
+                columns: None,
+                full_length: None,
+            };
+            event.ensure_event_id();
+            event
+        })
+        .collect();
+    let size: usize = events.iter().map(|e| e.message.len()).sum();
+    budget::record(&app, &state, size as u64, 0).await;
+
+    // GetLogEvents hands back the token it was given once there is nothing more
+    let returned = if start_from_head {
+        &response.next_forward_token
+    } else {
+        &response.next_backward_token
+    };
+    let at_end = events.is_empty() || (next_token.is_some() && *returned == next_token);
+
+    redaction::mask_for_display(&app, &state, None, &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(StreamEventsPage {
+        events,
+        next_forward_token: response.next_forward_token,
+        next_backward_token: response.next_backward_token,
+        at_end,
+    })
+}
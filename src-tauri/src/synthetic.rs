@@ -0,0 +1,312 @@
+use crate::pipeline::LogFormat;
+use crate::{AppState, LogEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+const DEFAULT_EVENT_COUNT: usize = 5_000;
+const MAX_EVENT_COUNT: usize = 2_000_000;
+const DEFAULT_RATE_PER_SEC: f64 = 5.0;
+const DEFAULT_ERROR_RATE: f64 = 0.02;
+const DEFAULT_SOURCE: &str = "/aws/lambda/demo-orders-api";
+
+/// A window of the generated range with a raised error rate
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorBurst {
+    /// Start of the burst, milliseconds after the first generated event
+    pub offset_ms: i64,
+    pub duration_ms: i64,
+    /// Share of events (0-1) that are errors during the burst
+    pub error_rate: f64,
+}
+
+/// What inject_synthetic_events generates
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyntheticSpec {
+    pub session_id: String,
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// Events per second of simulated time; sets the spacing of timestamps
+    #[serde(default)]
+    pub rate_per_sec: Option<f64>,
+    /// Timestamp of the last event (defaults to now)
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    /// Line formats to mix, picked per event (defaults to JSON and plain text)
+    #[serde(default)]
+    pub formats: Vec<LogFormat>,
+    /// Source names the events are spread across
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Baseline share of error events (0-1)
+    #[serde(default)]
+    pub error_rate: Option<f64>,
+    #[serde(default)]
+    pub bursts: Vec<ErrorBurst>,
+    /// Same seed, same events (for reproducible screenshots)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// What was injected
+#[derive(Debug, Clone, Serialize)]
+pub struct SyntheticSummary {
+    pub events: usize,
+    pub errors: usize,
+    /// Events per source
+    pub sources: BTreeMap<String, usize>,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// xorshift64*: small, dependency-free and deterministic per seed
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+const PATHS: [&str; 6] = [
+    "/orders",
+    "/orders/{id}",
+    "/cart",
+    "/checkout",
+    "/users/{id}",
+    "/health",
+];
+
+const ERRORS: [&str; 5] = [
+    "Task timed out after 30.03 seconds",
+    "ConditionalCheckFailedException: The conditional request failed",
+    "TypeError: Cannot read properties of undefined (reading 'items')",
+    "connect ECONNREFUSED 10.0.12.7:5432",
+    "ThrottlingException: Rate exceeded",
+];
+
+const INFO_MESSAGES: [&str; 4] = [
+    "request completed",
+    "cache miss, loading from table",
+    "published order event",
+    "validated payload",
+];
+
+fn render(format: LogFormat, rng: &mut Rng, timestamp: i64, error: bool) -> String {
+    let request_id = format!(
+        "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+        rng.next() as u32,
+        rng.next() as u16,
+        rng.next() % 0x1000,
+        rng.next() % 0x1000,
+        rng.next() % 0x1_0000_0000_0000
+    );
+    let level = if error {
+        "error"
+    } else {
+        *rng.pick(&["info", "info", "info", "debug", "warn"])
+    };
+    let text = if error {
+        rng.pick(&ERRORS).to_string()
+    } else {
+        rng.pick(&INFO_MESSAGES).to_string()
+    };
+    let path = rng
+        .pick(&PATHS)
+        .replace("{id}", &(1000 + rng.below(9000)).to_string());
+    let status = if error {
+        *rng.pick(&[500, 502, 503, 504])
+    } else {
+        *rng.pick(&[200, 200, 200, 201, 204, 404])
+    };
+    let duration_ms = if error {
+        800 + rng.below(29_000)
+    } else {
+        5 + rng.below(400)
+    };
+    let time = chrono::DateTime::from_timestamp_millis(timestamp).unwrap_or_default();
+    match format {
+        LogFormat::Json => serde_json::json!({
+            "level": level,
+            "requestId": request_id,
+            "msg": text,
+            "http": { "method": "GET", "path": path, "status": status },
+            "duration_ms": duration_ms,
+        })
+        .to_string(),
+        LogFormat::Logfmt => format!(
+            "time={} level={} request_id={} path={} status={} duration_ms={} msg=\"{}\"",
+            time.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            level,
+            request_id,
+            path,
+            status,
+            duration_ms,
+            text.replace('"', "'")
+        ),
+        LogFormat::LambdaPlatform => {
+            if error || rng.below(4) > 0 {
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    time.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+                    request_id,
+                    level.to_uppercase(),
+                    text
+                )
+            } else {
+                format!(
+                    "REPORT RequestId: {}\tDuration: {}.{:02} ms\tBilled Duration: {} ms\tMemory Size: 512 MB\tMax Memory Used: {} MB",
+                    request_id,
+                    duration_ms,
+                    rng.below(100),
+                    duration_ms + 1,
+                    80 + rng.below(120)
+                )
+            }
+        }
+        LogFormat::AccessLog => format!(
+            "10.0.{}.{} - - [{}] \"GET {} HTTP/1.1\" {} {}",
+            rng.below(255),
+            rng.below(255),
+            time.format("%d/%b/%Y:%H:%M:%S %z"),
+            path,
+            status,
+            200 + rng.below(20_000)
+        ),
+        LogFormat::Plain => {
+            let mut line = format!(
+                "{} [{}] {} ({}ms)",
+                level.to_uppercase(),
+                &request_id[..8],
+                text,
+                duration_ms
+            );
+            if error && rng.chance(0.5) {
+                line.push_str("\n    at handler (/var/task/index.js:42:13)\n    at process.processTicksAndRejections (node:internal/process/task_queues:95:5)");
+            }
+            line
+        }
+    }
+}
+
+/// Generate a realistic event stream into a session without AWS, for demos,
+/// screenshots and frontend work: formats are mixed per event, timestamps follow
+/// `rate_per_sec` with jitter, and `bursts` raise the error rate for a while.
+/// Each source replaces earlier events of the same name. Dev builds only.
+#[tauri::command]
+pub async fn inject_synthetic_events(
+    state: State<'_, AppState>,
+    spec: SyntheticSpec,
+) -> Result<SyntheticSummary, String> {
+    if !cfg!(debug_assertions) && std::env::var_os("LOGGY_DEV").is_none() {
+        return Err("Synthetic events are only available in development builds".to_string());
+    }
+    let count = spec
+        .count
+        .unwrap_or(DEFAULT_EVENT_COUNT)
+        .clamp(1, MAX_EVENT_COUNT);
+    let rate = spec
+        .rate_per_sec
+        .filter(|r| *r > 0.0)
+        .unwrap_or(DEFAULT_RATE_PER_SEC);
+    let interval_ms = 1000.0 / rate;
+    let end_time = spec
+        .end_time
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let start_time = end_time - (interval_ms * (count - 1) as f64) as i64;
+    let formats = if spec.formats.is_empty() {
+        vec![LogFormat::Json, LogFormat::Plain]
+    } else {
+        spec.formats.clone()
+    };
+    let sources = if spec.sources.is_empty() {
+        vec![DEFAULT_SOURCE.to_string()]
+    } else {
+        spec.sources.clone()
+    };
+    let baseline = spec
+        .error_rate
+        .unwrap_or(DEFAULT_ERROR_RATE)
+        .clamp(0.0, 1.0);
+    let mut rng = Rng::new(
+        spec.seed
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(1) as u64),
+    );
+
+    let mut by_source: BTreeMap<String, Vec<LogEvent>> = BTreeMap::new();
+    let mut errors = 0;
+    for i in 0..count {
+        let jitter = (rng.next() % (interval_ms.max(1.0) as u64)) as i64 / 2;
+        let timestamp = (start_time + (interval_ms * i as f64) as i64 + jitter).min(end_time);
+        let offset = timestamp - start_time;
+        let error_rate = spec
+            .bursts
+            .iter()
+            .filter(|b| offset >= b.offset_ms && offset < b.offset_ms + b.duration_ms)
+            .map(|b| b.error_rate.clamp(0.0, 1.0))
+            .fold(baseline, f64::max);
+        let error = rng.chance(error_rate);
+        errors += usize::from(error);
+        let source = rng.pick(&sources).clone();
+        let format = *rng.pick(&formats);
+        let message = render(format, &mut rng, timestamp, error);
+        let log_stream_name = Some(format!(
+            "{}/[$LATEST]{:016x}",
+            chrono::DateTime::from_timestamp_millis(timestamp)
+                .unwrap_or_default()
+                .format("%Y/%m/%d"),
+            rng.next() % 4
+        ));
+        by_source.entry(source.clone()).or_default().push(LogEvent {
+            timestamp,
+            message,
+            log_stream_name,
+            event_id: None,
+            source: Some(source),
+            columns: None,
+            full_length: None,
+        });
+    }
+
+    let summary = SyntheticSummary {
+        events: count,
+        errors,
+        sources: by_source
+            .iter()
+            .map(|(s, e)| (s.clone(), e.len()))
+            .collect(),
+        start_time,
+        end_time,
+    };
+    let mut sessions = state.sessions.lock().await;
+    let target = sessions.get_or_create(&spec.session_id);
+    for (source, events) in by_source {
+        target.replace_source(&source, events);
+    }
+    log::info!(
+        "Injected {} synthetic events ({} errors) into session {}",
+        summary.events,
+        summary.errors,
+        spec.session_id
+    );
+    Ok(summary)
+}
@@ -0,0 +1,1255 @@
+use crate::connections::EnvironmentEmitter;
+use crate::i18n::{self, Message};
+use crate::operations::{CancelBy, Operation};
+use crate::tail_sink::{self, TailSink};
+use crate::{
+    catalog, connectivity, emit_group_unavailable, filter_all_events, filter_stream_events,
+    group_history, is_resource_not_found, is_sso_session_expired, normalize_log_group_identifier,
+    redaction, storage, AppState, LogEvent,
+};
+use aws_sdk_cloudwatchlogs::types::StartLiveTailResponseStream;
+use aws_sdk_cloudwatchlogs::Client as CloudWatchClient;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::{mpsc, Mutex};
+
+/// How often a single-stream source polls GetLogEvents when caught up
+const STREAM_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Wait after a throttled GetLogEvents call
+const STREAM_THROTTLE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// How often a source polls FilterLogEvents when StartLiveTail is unavailable
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Each poll re-reads this far back to pick up events ingested late
+const POLL_LOOKBACK_MS: i64 = 30_000;
+
+/// Maximum events read by a single poll
+const POLL_MAX_EVENTS: usize = 10_000;
+
+/// How often merged batches are emitted to the frontend
+const FLUSH_INTERVAL_MS: u64 = 500;
+
+/// Default maximum events a single source contributes per batch
+const DEFAULT_PER_SOURCE_CAP: usize = 1000;
+
+/// File in the app config directory holding active tail definitions
+const TAILS_FILE: &str = "tails.json";
+
+/// How often a running tail records its last seen timestamp
+const LAST_SEEN_PERSIST_SECS: u64 = 10;
+
+/// Maximum events fetched per source to fill the gap when resuming a tail
+const CATCH_UP_MAX_EVENTS: usize = 10_000;
+
+/// Serializes read-modify-write of the tails file across tail tasks
+static TAILS_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Tails paused because the SSO session expired, resumed on the next reconnect
+static EXPIRED_TAILS: std::sync::Mutex<BTreeSet<String>> = std::sync::Mutex::new(BTreeSet::new());
+
+/// Default number of events kept in a tail's ring buffer
+const DEFAULT_BUFFER_CAPACITY: usize = 50_000;
+
+/// Default events/second above which a source is sampled
+const DEFAULT_SAMPLING_THRESHOLD_PER_SEC: usize = 2000;
+
+/// Default sampling rate (keep 1 of every N non-error events) during a storm
+const DEFAULT_SAMPLING_RATE: u32 = 10;
+
+/// JSON fields checked for a log level when deciding whether an event is an error
+const LEVEL_FIELDS: [&str; 6] = [
+    "level",
+    "severity",
+    "levelname",
+    "log_level",
+    "loglevel",
+    "logLevel",
+];
+
+/// Level keywords that are never sampled away
+const ERROR_KEYWORDS: [&str; 6] = ["error", "err", "fatal", "critical", "crit", "panic"];
+
+fn default_sampling_threshold() -> usize {
+    DEFAULT_SAMPLING_THRESHOLD_PER_SEC
+}
+
+fn default_sampling_rate() -> u32 {
+    DEFAULT_SAMPLING_RATE
+}
+
+/// Protective sampling applied to a source producing more than `threshold_per_sec`
+/// events per second: errors are always kept, everything else is sampled 1:`rate`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SamplingOptions {
+    #[serde(default = "default_sampling_threshold")]
+    pub threshold_per_sec: usize,
+    #[serde(default = "default_sampling_rate")]
+    pub rate: u32,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        Self {
+            threshold_per_sec: DEFAULT_SAMPLING_THRESHOLD_PER_SEC,
+            rate: DEFAULT_SAMPLING_RATE,
+        }
+    }
+}
+
+/// One log group to follow in a tail session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailSource {
+    pub log_group: String,
+    /// Follow only this stream, reading forward with GetLogEvents instead of
+    /// StartLiveTail: exact stream order, no session limit, no filter pattern
+    #[serde(default)]
+    pub log_stream: Option<String>,
+}
+
+/// Per-tail options (all optional, defaults applied when missing)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TailOptions {
+    /// Maximum events a single source contributes per batch
+    #[serde(default)]
+    pub per_source_cap: Option<usize>,
+    /// Protective sampling during log storms
+    #[serde(default)]
+    pub sampling: Option<SamplingOptions>,
+    /// Number of events kept in the backend ring buffer
+    #[serde(default)]
+    pub buffer_capacity: Option<usize>,
+    /// Mirror of the tail's events to a file, named pipe or local TCP port
+    #[serde(default)]
+    pub sink: Option<TailSink>,
+}
+
+/// What is needed to restart a tail after an app restart or crash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailDefinition {
+    pub tail_id: String,
+    pub sources: Vec<TailSource>,
+    #[serde(default)]
+    pub filter_pattern: Option<String>,
+    #[serde(default)]
+    pub options: TailOptions,
+    /// Profile the tail was started with
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Timestamp of the newest event delivered, used to fill the gap on resume
+    #[serde(default)]
+    pub last_seen: Option<i64>,
+}
+
+/// Outcome of resuming a tail
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeSummary {
+    /// Events fetched to fill the gap since the tail last ran
+    pub catch_up_events: usize,
+    /// True when the gap had more events than were fetched
+    pub truncated: bool,
+}
+
+/// Apply a change to the persisted tail definitions
+fn update_tail_definitions(app: &AppHandle, change: impl FnOnce(&mut Vec<TailDefinition>)) {
+    let _guard = TAILS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut definitions: Vec<TailDefinition> = storage::read_json(app, TAILS_FILE);
+    change(&mut definitions);
+    if let Err(e) = storage::write_json(app, TAILS_FILE, &definitions) {
+        log::warn!("Failed to persist tail definitions: {}", e);
+    }
+}
+
+fn remember_tail(app: &AppHandle, definition: &TailDefinition) {
+    update_tail_definitions(app, |definitions| {
+        definitions.retain(|d| d.tail_id != definition.tail_id);
+        definitions.push(definition.clone());
+    });
+}
+
+fn forget_tail(app: &AppHandle, tail_id: &str) {
+    update_tail_definitions(app, |definitions| {
+        definitions.retain(|d| d.tail_id != tail_id)
+    });
+}
+
+fn record_last_seen(app: &AppHandle, tail_id: &str, last_seen: i64) {
+    update_tail_definitions(app, |definitions| {
+        if let Some(definition) = definitions.iter_mut().find(|d| d.tail_id == tail_id) {
+            definition.last_seen = Some(last_seen);
+        }
+    });
+}
+
+/// Called with the events pushed out of a full ring buffer
+pub type EvictionCallback = Box<dyn FnMut(&[LogEvent]) + Send>;
+
+/// Bounded buffer of the most recent events of a tail session
+pub struct TailBuffer {
+    events: VecDeque<LogEvent>,
+    capacity: usize,
+    evicted_total: u64,
+    on_evict: Option<EvictionCallback>,
+}
+
+impl TailBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: VecDeque::with_capacity(capacity.min(DEFAULT_BUFFER_CAPACITY)),
+            capacity,
+            evicted_total: 0,
+            on_evict: None,
+        }
+    }
+
+    /// Register a callback invoked with each batch of evicted events
+    pub fn with_eviction_callback(mut self, callback: EvictionCallback) -> Self {
+        self.on_evict = Some(callback);
+        self
+    }
+
+    /// Append events, evicting the oldest ones once capacity is reached
+    pub fn push(&mut self, events: &[LogEvent]) {
+        self.events.extend(events.iter().cloned());
+        let excess = self.events.len().saturating_sub(self.capacity);
+        if excess == 0 {
+            return;
+        }
+        let evicted: Vec<LogEvent> = self.events.drain(..excess).collect();
+        self.evicted_total += evicted.len() as u64;
+        if let Some(callback) = self.on_evict.as_mut() {
+            callback(&evicted);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<LogEvent> {
+        self.events.iter().cloned().collect()
+    }
+
+    /// Timestamp of the newest buffered event
+    pub fn newest_timestamp(&self) -> Option<i64> {
+        self.events.iter().map(|e| e.timestamp).max()
+    }
+
+    pub fn evicted_total(&self) -> u64 {
+        self.evicted_total
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Approximate memory held by buffered events, including unused capacity
+    pub fn memory_bytes(&self) -> usize {
+        let unused = self.events.capacity() - self.events.len();
+        self.events
+            .iter()
+            .map(LogEvent::memory_bytes)
+            .sum::<usize>()
+            + unused * std::mem::size_of::<LogEvent>()
+    }
+
+    /// Drop buffered events and release their memory; capacity grows back as needed
+    pub fn clear(&mut self) {
+        self.events = VecDeque::new();
+    }
+}
+
+/// A running tail session tracked in AppState
+pub struct TailSession {
+    pub handle: tokio::task::JoinHandle<()>,
+    pub buffer: Arc<Mutex<TailBuffer>>,
+    /// Feeds the tail's output sink, if it has one
+    pub sink: Arc<Mutex<Option<tail_sink::SinkSender>>>,
+}
+
+/// Remove the tail's session, unless a newer tail under the same id replaced it
+async fn remove_own_session(
+    tails: &Mutex<HashMap<String, TailSession>>,
+    tail_id: &str,
+    buffer: &Arc<Mutex<TailBuffer>>,
+) {
+    let mut tails = tails.lock().await;
+    if tails
+        .get(tail_id)
+        .is_some_and(|s| Arc::ptr_eq(&s.buffer, buffer))
+    {
+        tails.remove(tail_id);
+    }
+}
+
+/// Payload for tail-evicted:{tail_id}
+#[derive(Debug, Clone, Serialize)]
+struct TailEvictedPayload {
+    tail_id: String,
+    evicted: usize,
+    evicted_total: u64,
+    /// Timestamp of the newest event that was evicted; older events are gone
+    evicted_through: Option<i64>,
+}
+
+/// Payload for tail-events:{tail_id}
+#[derive(Debug, Clone, Serialize)]
+struct TailBatchPayload {
+    tail_id: String,
+    /// Events from all sources, ordered by timestamp; each carries its `source`
+    events: Vec<LogEvent>,
+    /// Events dropped in this batch per source because it exceeded the per-source cap
+    dropped: BTreeMap<String, usize>,
+    /// Non-error events sampled away in this batch per source (log storm protection)
+    sampled_out: BTreeMap<String, usize>,
+    /// Sources currently being sampled
+    sampling: Vec<String>,
+}
+
+/// Payload for tail-error:{tail_id}
+#[derive(Debug, Clone, Serialize)]
+struct TailErrorPayload {
+    tail_id: String,
+    source: String,
+    message: String,
+}
+
+/// Payload for tail-polling:{tail_id}
+#[derive(Debug, Clone, Serialize)]
+struct TailPollingPayload {
+    tail_id: String,
+    source: String,
+    /// Why StartLiveTail couldn't be used
+    reason: String,
+}
+
+/// What a source reader reports back to the merger
+enum SourceMessage {
+    Events(String, Vec<LogEvent>),
+    Error(String, String),
+    /// StartLiveTail was refused; the source polls FilterLogEvents instead
+    Polling(String, String),
+    /// The credentials expired; the whole tail pauses until the next reconnect
+    Expired(String),
+    /// The log group was deleted; the source stops for good
+    Unavailable(String),
+    Ended(String),
+}
+
+fn source_failure(label: String, message: String) -> SourceMessage {
+    if is_resource_not_found(&message) {
+        SourceMessage::Unavailable(label)
+    } else if is_session_expired(&message) {
+        SourceMessage::Expired(label)
+    } else {
+        SourceMessage::Error(label, message)
+    }
+}
+
+/// Whether a source error means the SSO session or credentials expired. A plain
+/// AccessDenied is a permission problem of that source, not an expired session.
+fn is_session_expired(message: &str) -> bool {
+    message == i18n::tr(Message::SessionExpired)
+        || (is_sso_session_expired(message)
+            && !message.to_lowercase().contains("accessdeniedexception"))
+}
+
+/// Whether StartLiveTail was refused in a way polling can work around: the
+/// concurrent session limit, a missing logs:StartLiveTail permission, or an
+/// endpoint that doesn't offer it
+fn live_tail_unavailable(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("limitexceeded")
+        || lower.contains("unknownoperation")
+        || lower.contains("not supported")
+        || (lower.contains("startlivetail")
+            && (lower.contains("accessdenied") || lower.contains("not authorized")))
+}
+
+/// Follow one log group with StartLiveTail, forwarding every session update to the merger
+async fn tail_source(
+    client: CloudWatchClient,
+    source: TailSource,
+    filter_pattern: Option<String>,
+    tx: mpsc::UnboundedSender<SourceMessage>,
+) {
+    let label = source.log_group.clone();
+    let mut request = client
+        .start_live_tail()
+        .log_group_identifiers(normalize_log_group_identifier(&source.log_group));
+    if let Some(pattern) = filter_pattern.clone().filter(|p| !p.is_empty()) {
+        request = request.log_event_filter_pattern(pattern);
+    }
+
+    let mut stream = match request.send().await {
+        Ok(output) => output.response_stream,
+        Err(e) => {
+            let message = format!("{:?}", e);
+            if live_tail_unavailable(&message) {
+                log::warn!(
+                    "StartLiveTail unavailable for {}, polling instead: {}",
+                    label,
+                    message
+                );
+                if tx
+                    .send(SourceMessage::Polling(label.clone(), format!("{}", e)))
+                    .is_ok()
+                {
+                    poll_source(client, source.log_group, filter_pattern, tx).await;
+                }
+                return;
+            }
+            log::error!("Failed to start tail for {}: {}", label, message);
+            tx.send(source_failure(label, message)).ok();
+            return;
+        }
+    };
+
+    loop {
+        match stream.recv().await {
+            Ok(Some(StartLiveTailResponseStream::SessionUpdate(update))) => {
+                let events: Vec<LogEvent> = update
+                    .session_results
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| {
+                        let mut event = LogEvent {
+                            timestamp: e.timestamp.unwrap_or(0),
+                            message: e.message.unwrap_or_default(),
+                            log_stream_name: e.log_stream_name,
+                            event_id: None,
+                            source: Some(label.clone()),
+                            columns: None,
+                            full_length: None,
+                        };
+                        event.ensure_event_id();
+                        event
+                    })
+                    .collect();
+                if !events.is_empty()
+                    && tx
+                        .send(SourceMessage::Events(label.clone(), events))
+                        .is_err()
+                {
+                    // Merger is gone (tail stopped)
+                    return;
+                }
+            }
+            Ok(Some(StartLiveTailResponseStream::SessionStart(_))) => {
+                log::info!("Tail session started for {}", label);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                // Stream ended (3-hour session limit)
+                tx.send(SourceMessage::Ended(label)).ok();
+                return;
+            }
+            Err(e) => {
+                let message = format!("{:?}", e);
+                log::error!("Tail stream error for {}: {}", label, message);
+                tx.send(source_failure(label, message)).ok();
+                return;
+            }
+        }
+    }
+}
+
+/// Follow one log group by polling FilterLogEvents from now on. Each poll re-reads
+/// a short lookback for late-ingested events and skips the ids already delivered.
+async fn poll_source(
+    client: CloudWatchClient,
+    log_group: String,
+    filter_pattern: Option<String>,
+    tx: mpsc::UnboundedSender<SourceMessage>,
+) {
+    let label = log_group.clone();
+    let identifier = normalize_log_group_identifier(&log_group);
+    let log_group_name = catalog::log_group_name_from_arn(&identifier).unwrap_or(identifier);
+    let mut newest = chrono::Utc::now().timestamp_millis();
+    // Delivered event ids and their timestamps, kept for the lookback window
+    let mut delivered: HashMap<String, i64> = HashMap::new();
+    log::info!("Tail polling {}", label);
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let since = newest - POLL_LOOKBACK_MS;
+        let events = match filter_all_events(
+            &client,
+            &log_group_name,
+            Some(since),
+            None,
+            filter_pattern.as_deref(),
+            POLL_MAX_EVENTS,
+        )
+        .await
+        {
+            Ok((events, _)) => events,
+            Err(message) => {
+                if message.to_lowercase().contains("throttl") {
+                    tokio::time::sleep(STREAM_THROTTLE_BACKOFF).await;
+                    continue;
+                }
+                log::error!("Tail poll of {} failed: {}", label, message);
+                tx.send(source_failure(label, message)).ok();
+                return;
+            }
+        };
+
+        let events: Vec<LogEvent> = events
+            .into_iter()
+            .filter_map(|mut event| {
+                event.ensure_event_id();
+                let id = event.event_id.clone()?;
+                if delivered.insert(id, event.timestamp).is_some() {
+                    return None;
+                }
+                event.source = Some(label.clone());
+                Some(event)
+            })
+            .collect();
+        newest = newest.max(events.iter().map(|e| e.timestamp).max().unwrap_or(newest));
+        delivered.retain(|_, ts| *ts >= newest - POLL_LOOKBACK_MS);
+        if !events.is_empty()
+            && tx
+                .send(SourceMessage::Events(label.clone(), events))
+                .is_err()
+        {
+            // Merger is gone (tail stopped)
+            return;
+        }
+    }
+}
+
+/// Follow one log stream forward with GetLogEvents from now on, paging with the
+/// forward token. Unlike StartLiveTail (which samples and dedupes across streams)
+/// this delivers every event of the stream exactly once, in stream order.
+async fn tail_stream(
+    client: CloudWatchClient,
+    log_group: String,
+    log_stream: String,
+    tx: mpsc::UnboundedSender<SourceMessage>,
+) {
+    let label = log_group.clone();
+    let mut token: Option<String> = None;
+    let started_at = chrono::Utc::now().timestamp_millis();
+    log::info!("Tail started for stream {} of {}", log_stream, label);
+    loop {
+        let request = client
+            .get_log_events()
+            .log_group_name(&log_group)
+            .log_stream_name(&log_stream)
+            .start_from_head(true);
+        // The first call starts at the tail's start; later calls continue from the token
+        let request = match &token {
+            Some(token) => request.next_token(token),
+            None => request.start_time(started_at),
+        };
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let message = format!("{:?}", e);
+                if message.to_lowercase().contains("throttl") {
+                    tokio::time::sleep(STREAM_THROTTLE_BACKOFF).await;
+                    continue;
+                }
+                log::error!("Tail of stream {} failed: {}", log_stream, message);
+                tx.send(source_failure(label, message)).ok();
+                return;
+            }
+        };
+        let events: Vec<LogEvent> = response
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| {
+                let mut event = LogEvent {
+                    timestamp: e.timestamp.unwrap_or(0),
+                    message: e.message.unwrap_or_default(),
+                    log_stream_name: Some(log_stream.clone()),
+                    event_id: None,
+                    source: Some(label.clone()),
+                    columns: None,
+                    full_length: None,
+                };
+                event.ensure_event_id();
+                event
+            })
+            .collect();
+        // The same forward token comes back once the end of the stream is reached
+        let caught_up = events.is_empty() || response.next_forward_token == token;
+        if !events.is_empty()
+            && tx
+                .send(SourceMessage::Events(label.clone(), events))
+                .is_err()
+        {
+            // Merger is gone (tail stopped)
+            return;
+        }
+        if response.next_forward_token.is_some() {
+            token = response.next_forward_token;
+        }
+        if caught_up {
+            tokio::time::sleep(STREAM_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Whether an event looks like an error (JSON level field or a level keyword in the text)
+pub(crate) fn is_error_event(message: &str) -> bool {
+    let trimmed = message.trim();
+    if trimmed.starts_with('{') {
+        if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(trimmed) {
+            if let Some(level) = LEVEL_FIELDS
+                .iter()
+                .find_map(|f| map.get(*f).and_then(|v| v.as_str()))
+            {
+                return ERROR_KEYWORDS.contains(&level.to_lowercase().as_str());
+            }
+        }
+    }
+    trimmed
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .take(32)
+        .any(|word| {
+            matches!(
+                word,
+                "ERROR" | "FATAL" | "CRITICAL" | "PANIC" | "Error" | "Fatal"
+            ) || word.ends_with("Exception")
+        })
+}
+
+/// Per-source sampling bookkeeping, kept across batches so 1:N stays even
+#[derive(Default)]
+struct SourceSampler {
+    active: bool,
+    counter: u64,
+}
+
+/// Apply protective sampling to each source's pending events. A source is sampled
+/// while its rate over the last flush interval exceeds the threshold.
+fn sample_pending(
+    pending: &mut BTreeMap<String, Vec<LogEvent>>,
+    samplers: &mut BTreeMap<String, SourceSampler>,
+    options: SamplingOptions,
+    interval: Duration,
+    tail_id: &str,
+) -> BTreeMap<String, usize> {
+    let mut sampled_out = BTreeMap::new();
+    let rate = u64::from(options.rate.max(1));
+    // Sources that went quiet are no longer storming
+    for (source, sampler) in samplers.iter_mut() {
+        if sampler.active && !pending.contains_key(source) {
+            sampler.active = false;
+            log::info!("Tail {} stopped sampling {}", tail_id, source);
+        }
+    }
+    for (source, events) in pending.iter_mut() {
+        let per_sec = events.len() as f64 / interval.as_secs_f64();
+        let sampler = samplers.entry(source.clone()).or_default();
+        let storming = per_sec > options.threshold_per_sec as f64;
+        if storming != sampler.active {
+            sampler.active = storming;
+            if storming {
+                log::warn!(
+                    "Tail {} sampling {} at 1:{} ({:.0} events/s)",
+                    tail_id,
+                    source,
+                    rate,
+                    per_sec
+                );
+            } else {
+                log::info!("Tail {} stopped sampling {}", tail_id, source);
+            }
+        }
+        if !sampler.active {
+            continue;
+        }
+
+        let before = events.len();
+        events.retain(|e| {
+            if is_error_event(&e.message) {
+                return true;
+            }
+            sampler.counter += 1;
+            (sampler.counter - 1).is_multiple_of(rate)
+        });
+        let removed = before - events.len();
+        if removed > 0 {
+            sampled_out.insert(source.clone(), removed);
+        }
+    }
+    sampled_out
+}
+
+/// Merge the pending per-source buffers into one timestamp-ordered batch, keeping at
+/// most `per_source_cap` (newest) events from each source
+fn merge_pending(
+    pending: &mut BTreeMap<String, Vec<LogEvent>>,
+    per_source_cap: usize,
+) -> (Vec<LogEvent>, BTreeMap<String, usize>) {
+    let mut merged = Vec::new();
+    let mut dropped = BTreeMap::new();
+    for (source, mut events) in std::mem::take(pending) {
+        if events.len() > per_source_cap {
+            events.sort_by_key(|e| e.timestamp);
+            let excess = events.len() - per_source_cap;
+            events.drain(..excess);
+            dropped.insert(source, excess);
+        }
+        merged.extend(events);
+    }
+    merged.sort_by_key(|e| e.timestamp);
+    (merged, dropped)
+}
+
+/// Spawn the source readers and merger for a tail definition. `initial_events`
+/// (e.g. a resume catch-up) are delivered as the first batch.
+async fn spawn_tail(
+    app: AppHandle,
+    state: &AppState,
+    definition: TailDefinition,
+    initial_events: Vec<LogEvent>,
+) -> Result<(), String> {
+    let client = state.logs_client().await?;
+    let TailDefinition {
+        tail_id,
+        sources,
+        filter_pattern,
+        options,
+        profile,
+        ..
+    } = definition;
+    let per_source_cap = options
+        .per_source_cap
+        .unwrap_or(DEFAULT_PER_SOURCE_CAP)
+        .max(1);
+    let sampling = options.sampling.unwrap_or_default();
+    // Rates are only meaningful for groups followed in full
+    let full_groups = match filter_pattern.as_deref() {
+        Some(pattern) if !pattern.is_empty() => Vec::new(),
+        _ => sources
+            .iter()
+            .filter(|s| s.log_stream.is_none())
+            .map(|s| s.log_group.clone())
+            .collect(),
+    };
+    let mut history = group_history::LiveCounter::new(&app, profile, full_groups);
+
+    let evict_app = app.clone();
+    let evict_tail_id = tail_id.clone();
+    let mut evicted_total: u64 = 0;
+    let buffer = Arc::new(Mutex::new(
+        TailBuffer::new(options.buffer_capacity.unwrap_or(DEFAULT_BUFFER_CAPACITY))
+            .with_eviction_callback(Box::new(move |evicted: &[LogEvent]| {
+                evicted_total += evicted.len() as u64;
+                evict_app
+                    .emit_tagged(
+                        &format!("tail-evicted:{}", evict_tail_id),
+                        TailEvictedPayload {
+                            tail_id: evict_tail_id.clone(),
+                            evicted: evicted.len(),
+                            evicted_total,
+                            evicted_through: evicted.iter().map(|e| e.timestamp).max(),
+                        },
+                    )
+                    .ok();
+            })),
+    ));
+
+    let sink =
+        Arc::new(Mutex::new(options.sink.map(|sink| {
+            tail_sink::start(app.clone(), tail_id.clone(), sink)
+        })));
+
+    let task_tail_id = tail_id.clone();
+    let task_buffer = buffer.clone();
+    let task_sink = sink.clone();
+    let tails = state.tails.clone();
+    let target = sources
+        .iter()
+        .map(|s| s.log_group.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let op = Operation::begin("tail", target, CancelBy::Tail(tail_id.clone()));
+    // Held until the session is inserted, so a task ending early can't miss it
+    let mut running = state.tails.lock().await;
+    let handle = tokio::spawn(async move {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        // Dropping the JoinSet (when this task is aborted) aborts every source reader
+        let mut readers = tokio::task::JoinSet::new();
+        for source in sources {
+            match source.log_stream.clone() {
+                Some(log_stream) => readers.spawn(tail_stream(
+                    client.clone(),
+                    source.log_group,
+                    log_stream,
+                    tx.clone(),
+                )),
+                None => readers.spawn(tail_source(
+                    client.clone(),
+                    source,
+                    filter_pattern.clone(),
+                    tx.clone(),
+                )),
+            };
+        }
+        drop(tx);
+
+        let events_event = format!("tail-events:{}", task_tail_id);
+        let mut last_seen = initial_events.iter().map(|e| e.timestamp).max();
+        if !initial_events.is_empty() {
+            task_buffer.lock().await.push(&initial_events);
+            let mut initial_events = initial_events;
+            redaction::mask_with_handle(&app, &task_tail_id, &mut initial_events).await;
+            if let Some(sink) = task_sink.lock().await.as_ref() {
+                sink.send(initial_events.clone()).ok();
+            }
+            app.emit_tagged(
+                &events_event,
+                TailBatchPayload {
+                    tail_id: task_tail_id.clone(),
+                    events: initial_events,
+                    dropped: BTreeMap::new(),
+                    sampled_out: BTreeMap::new(),
+                    sampling: Vec::new(),
+                },
+            )
+            .ok();
+        }
+        let mut persisted_at = std::time::Instant::now();
+
+        let mut pending: BTreeMap<String, Vec<LogEvent>> = BTreeMap::new();
+        let mut samplers: BTreeMap<String, SourceSampler> = BTreeMap::new();
+        let flush_interval = Duration::from_millis(FLUSH_INTERVAL_MS);
+        let mut flush = tokio::time::interval(flush_interval);
+        let mut open = true;
+        // A source dropped by a network failure; the tail pauses instead of ending
+        let mut network_lost = false;
+        // The credentials expired; the tail pauses until the next reconnect
+        let mut session_expired = false;
+        while open {
+            tokio::select! {
+                message = rx.recv() => match message {
+                    Some(SourceMessage::Events(source, events)) => {
+                        op.page(events.len());
+                        history.observe(&source, &events);
+                        pending.entry(source).or_default().extend(events);
+                        continue;
+                    }
+                    Some(SourceMessage::Error(source, message)) => {
+                        if connectivity::is_network_error(&message) {
+                            log::warn!("Tail {} lost {} to a network error", task_tail_id, source);
+                            network_lost = true;
+                            continue;
+                        }
+                        app.emit_tagged(
+                            &format!("tail-error:{}", task_tail_id),
+                            TailErrorPayload { tail_id: task_tail_id.clone(), source, message },
+                        )
+                        .ok();
+                        continue;
+                    }
+                    Some(SourceMessage::Polling(source, reason)) => {
+                        app.emit_tagged(
+                            &format!("tail-polling:{}", task_tail_id),
+                            TailPollingPayload { tail_id: task_tail_id.clone(), source, reason },
+                        )
+                        .ok();
+                        continue;
+                    }
+                    Some(SourceMessage::Expired(source)) => {
+                        log::warn!("Tail {} stopped: session expired while reading {}", task_tail_id, source);
+                        session_expired = true;
+                        open = false;
+                    }
+                    Some(SourceMessage::Unavailable(source)) => {
+                        emit_group_unavailable(&app, &source, "tail", Some(&task_tail_id));
+                        continue;
+                    }
+                    Some(SourceMessage::Ended(source)) => {
+                        log::info!("Tail {} source {} ended", task_tail_id, source);
+                        continue;
+                    }
+                    // Every source reader has finished; flush what's left and stop
+                    None => open = false,
+                },
+                _ = flush.tick() => history.flush(&app),
+            }
+
+            if pending.values().all(|events| events.is_empty()) {
+                continue;
+            }
+            let sampled_out = sample_pending(
+                &mut pending,
+                &mut samplers,
+                sampling,
+                flush_interval,
+                &task_tail_id,
+            );
+            let (mut events, dropped) = merge_pending(&mut pending, per_source_cap);
+            task_buffer.lock().await.push(&events);
+            last_seen = last_seen.max(events.iter().map(|e| e.timestamp).max());
+            redaction::mask_with_handle(&app, &task_tail_id, &mut events).await;
+            if let Some(sink) = task_sink.lock().await.as_ref() {
+                sink.send(events.clone()).ok();
+            }
+            if let Some(ts) = last_seen {
+                if persisted_at.elapsed() >= Duration::from_secs(LAST_SEEN_PERSIST_SECS) {
+                    record_last_seen(&app, &task_tail_id, ts);
+                    persisted_at = std::time::Instant::now();
+                }
+            }
+            for (source, count) in &dropped {
+                log::warn!(
+                    "Tail {} dropped {} events from {}",
+                    task_tail_id,
+                    count,
+                    source
+                );
+            }
+            app.emit_tagged(
+                &events_event,
+                TailBatchPayload {
+                    tail_id: task_tail_id.clone(),
+                    events,
+                    dropped,
+                    sampled_out,
+                    sampling: samplers
+                        .iter()
+                        .filter(|(_, s)| s.active)
+                        .map(|(source, _)| source.clone())
+                        .collect(),
+                },
+            )
+            .ok();
+        }
+        if session_expired {
+            readers.abort_all();
+        }
+        while readers.join_next().await.is_some() {}
+
+        if session_expired {
+            // Keep the definition so the next reconnect resumes it with catch-up
+            if let Some(ts) = last_seen {
+                record_last_seen(&app, &task_tail_id, ts);
+            }
+            remove_own_session(&tails, &task_tail_id, &task_buffer).await;
+            EXPIRED_TAILS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(task_tail_id.clone());
+            app.emit_tagged(
+                &format!("tail-paused:{}", task_tail_id),
+                serde_json::json!({ "tail_id": task_tail_id, "reason": "session-expired" }),
+            )
+            .ok();
+            app.emit_tagged("aws-session-expired", ()).ok();
+            return;
+        }
+
+        if network_lost {
+            // Keep the definition so the connectivity watcher resumes it with catch-up
+            if let Some(ts) = last_seen {
+                record_last_seen(&app, &task_tail_id, ts);
+            }
+            remove_own_session(&tails, &task_tail_id, &task_buffer).await;
+            connectivity::tail_disconnected(&app, &task_tail_id);
+            return;
+        }
+
+        // The tail ended on its own (stream limit or errors); nothing to resume
+        forget_tail(&app, &task_tail_id);
+
+        app.emit_tagged(
+            &format!("tail-ended:{}", task_tail_id),
+            serde_json::json!({ "tail_id": task_tail_id }),
+        )
+        .ok();
+        remove_own_session(&tails, &task_tail_id, &task_buffer).await;
+    });
+
+    if let Some(previous) = running.insert(
+        tail_id,
+        TailSession {
+            handle,
+            buffer,
+            sink,
+        },
+    ) {
+        previous.handle.abort();
+    }
+    Ok(())
+}
+
+/// Start tailing one or more log groups as a single merged stream. Batches are
+/// emitted as `tail-events:{tail_id}`, source failures as `tail-error:{tail_id}`,
+/// and `tail-ended:{tail_id}` once every source has stopped. Sources flooding the
+/// tail are sampled (errors always kept) and every dropped event is counted in the batch.
+/// The most recent events are kept in a bounded ring buffer; evictions are reported
+/// as `tail-evicted:{tail_id}`. The definition is persisted so the tail can be
+/// resumed after a restart. Groups where StartLiveTail is refused (session limit,
+/// permissions) are polled with FilterLogEvents instead, reported as
+/// `tail-polling:{tail_id}`. If the SSO session expires mid-tail the tail pauses
+/// (`tail-paused:{tail_id}`) and resumes with catch-up after the next reconnect.
+#[tauri::command]
+pub async fn start_tail(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+    sources: Vec<TailSource>,
+    filter_pattern: Option<String>,
+    options: Option<TailOptions>,
+) -> Result<(), String> {
+    if sources.is_empty() {
+        return Err("Select at least one log group to tail".to_string());
+    }
+    if sources.iter().any(|s| s.log_stream.is_some())
+        && filter_pattern.as_deref().is_some_and(|p| !p.is_empty())
+    {
+        return Err("Filter patterns can't be applied when tailing a single stream".to_string());
+    }
+    let definition = TailDefinition {
+        tail_id,
+        sources,
+        filter_pattern,
+        options: options.unwrap_or_default(),
+        profile: state.current_profile.lock().await.clone(),
+        last_seen: None,
+    };
+    spawn_tail(app.clone(), &state, definition.clone(), Vec::new()).await?;
+    remember_tail(&app, &definition);
+    Ok(())
+}
+
+/// Tails that were running when the app last exited (or crashed) and can be resumed
+#[tauri::command]
+pub async fn list_resumable_tails(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<TailDefinition>, String> {
+    let definitions: Vec<TailDefinition> = storage::read_json(&app, TAILS_FILE);
+    let running = state.tails.lock().await;
+    Ok(definitions
+        .into_iter()
+        .filter(|d| !running.contains_key(&d.tail_id))
+        .collect())
+}
+
+/// Resume a persisted tail: fetch the events missed since it last ran, deliver them
+/// as the first batch, then continue live
+#[tauri::command]
+pub async fn resume_tail(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+) -> Result<ResumeSummary, String> {
+    resume(app, &state, &tail_id).await
+}
+
+/// Stop every running tail, keeping their definitions (with the newest timestamp
+/// delivered) so they can be resumed; returns the paused tail ids
+pub(crate) async fn pause_all(app: &AppHandle, state: &AppState) -> Vec<String> {
+    let paused: Vec<(String, TailSession)> = state.tails.lock().await.drain().collect();
+    let mut tail_ids = Vec::with_capacity(paused.len());
+    for (tail_id, session) in paused {
+        session.handle.abort();
+        if let Some(ts) = session.buffer.lock().await.newest_timestamp() {
+            record_last_seen(app, &tail_id, ts);
+        }
+        tail_ids.push(tail_id);
+    }
+    tail_ids
+}
+
+/// Restart a persisted tail after filling the gap since its last seen event
+pub(crate) async fn resume(
+    app: AppHandle,
+    state: &AppState,
+    tail_id: &str,
+) -> Result<ResumeSummary, String> {
+    let definitions: Vec<TailDefinition> = storage::read_json(&app, TAILS_FILE);
+    let definition = definitions
+        .into_iter()
+        .find(|d| d.tail_id == tail_id)
+        .ok_or_else(|| format!("No saved tail {}", tail_id))?;
+
+    let current_profile = state.current_profile.lock().await.clone();
+    if definition.profile.is_some() && definition.profile != current_profile {
+        return Err(format!(
+            "Tail {} was started with profile {}; switch to it to resume",
+            tail_id,
+            definition.profile.as_deref().unwrap_or_default()
+        ));
+    }
+
+    let mut catch_up: Vec<LogEvent> = Vec::new();
+    let mut truncated = false;
+    if let Some(last_seen) = definition.last_seen {
+        let client = state.logs_client().await?;
+        let now = chrono::Utc::now().timestamp_millis();
+        for source in &definition.sources {
+            let (mut events, source_truncated) = match &source.log_stream {
+                Some(log_stream) => {
+                    filter_stream_events(
+                        &client,
+                        &source.log_group,
+                        std::slice::from_ref(log_stream),
+                        Some(last_seen + 1),
+                        Some(now),
+                        None,
+                        CATCH_UP_MAX_EVENTS,
+                    )
+                    .await?
+                }
+                None => {
+                    filter_all_events(
+                        &client,
+                        &source.log_group,
+                        Some(last_seen + 1),
+                        Some(now),
+                        definition.filter_pattern.as_deref(),
+                        CATCH_UP_MAX_EVENTS,
+                    )
+                    .await?
+                }
+            };
+            truncated |= source_truncated;
+            crate::session::tag_source(&mut events, &source.log_group);
+            catch_up.extend(events);
+        }
+        catch_up.sort_by_key(|e| e.timestamp);
+    }
+
+    let summary = ResumeSummary {
+        catch_up_events: catch_up.len(),
+        truncated,
+    };
+    log::info!(
+        "Resuming tail {} with {} catch-up events{}",
+        tail_id,
+        summary.catch_up_events,
+        if truncated { " (truncated)" } else { "" }
+    );
+    spawn_tail(app, state, definition, catch_up).await?;
+    Ok(summary)
+}
+
+/// Resume the tails paused by an expired session, after a successful reconnect
+pub(crate) fn resume_expired(app: &AppHandle) {
+    let tail_ids = std::mem::take(&mut *EXPIRED_TAILS.lock().unwrap_or_else(|e| e.into_inner()));
+    if tail_ids.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        for tail_id in tail_ids {
+            match resume(app.clone(), &state, &tail_id).await {
+                Ok(summary) => {
+                    app.emit_tagged(&format!("tail-resumed:{}", tail_id), summary)
+                        .ok();
+                }
+                Err(e) => log::warn!("Failed to resume tail {}: {}", tail_id, e),
+            }
+        }
+    });
+}
+
+/// Drop a persisted tail instead of resuming it
+#[tauri::command]
+pub fn discard_resumable_tail(app: AppHandle, tail_id: String) {
+    forget_tail(&app, &tail_id);
+}
+
+/// Stop a tail started with start_tail
+#[tauri::command]
+pub async fn stop_tail(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+) -> Result<(), String> {
+    forget_tail(&app, &tail_id);
+    if let Some(session) = state.tails.lock().await.remove(&tail_id) {
+        session.handle.abort();
+        log::info!("Tail {} stopped", tail_id);
+    }
+    Ok(())
+}
+
+/// Start, replace or (with None) stop mirroring a running tail's events to an
+/// output sink, e.g. a local TCP port to pipe live logs into grep or jq. The sink
+/// is kept with the tail's definition, so a resumed tail mirrors again.
+#[tauri::command]
+pub async fn set_tail_sink(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+    sink: Option<TailSink>,
+) -> Result<(), String> {
+    let current = state
+        .tails
+        .lock()
+        .await
+        .get(&tail_id)
+        .map(|session| session.sink.clone())
+        .ok_or_else(|| format!("Tail {} is not running", tail_id))?;
+    // Replacing the sender drops the previous one, which stops its sink
+    *current.lock().await = sink
+        .clone()
+        .map(|sink| tail_sink::start(app.clone(), tail_id.clone(), sink));
+    update_tail_definitions(&app, |definitions| {
+        if let Some(definition) = definitions.iter_mut().find(|d| d.tail_id == tail_id) {
+            definition.options.sink = sink;
+        }
+    });
+    Ok(())
+}
+
+/// Write a tail's current ring buffer to a file as JSON lines, returning the number
+/// of events written
+#[tauri::command]
+pub async fn snapshot_tail_buffer(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tail_id: String,
+    path: String,
+) -> Result<usize, String> {
+    let buffer = state
+        .tails
+        .lock()
+        .await
+        .get(&tail_id)
+        .map(|session| session.buffer.clone())
+        .ok_or_else(|| format!("Tail {} is not running", tail_id))?;
+    let (mut events, evicted_total) = {
+        let buffer = buffer.lock().await;
+        (buffer.snapshot(), buffer.evicted_total())
+    };
+    // The buffer holds raw events; the file gets what the tail view shows
+    redaction::mask_for_display(&app, &state, Some(&tail_id), &mut events).await;
+
+    let file =
+        std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    for event in &events {
+        let line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        writeln!(writer, "{}", line).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    log::info!(
+        "Snapshot of tail {} written to {} ({} events, {} evicted earlier)",
+        tail_id,
+        path,
+        events.len(),
+        evicted_total
+    );
+    Ok(events.len())
+}
@@ -0,0 +1,182 @@
+use crate::connections::EnvironmentEmitter;
+use crate::LogEvent;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc};
+
+/// Batches a slow TCP client may fall behind before it misses some
+const TCP_CLIENT_BACKLOG: usize = 256;
+
+/// Where a tail mirrors its events besides the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SinkTarget {
+    /// A regular file, appended to or truncated when the sink starts
+    File {
+        path: String,
+        #[serde(default)]
+        append: bool,
+    },
+    /// An existing named pipe (`mkfifo`); writing starts once a reader opens it
+    Pipe { path: String },
+    /// A TCP port on 127.0.0.1; every client that connects (e.g. `nc localhost 9000`)
+    /// gets the events from then on
+    Tcp { port: u16 },
+}
+
+/// How mirrored events are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SinkFormat {
+    /// "timestamp source message" lines, for grep
+    #[default]
+    Text,
+    /// One JSON event per line, for jq
+    Json,
+}
+
+/// An output sink of a tail session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailSink {
+    pub target: SinkTarget,
+    #[serde(default)]
+    pub format: SinkFormat,
+}
+
+/// Sender feeding a running sink; dropping it stops the sink
+pub type SinkSender = mpsc::UnboundedSender<Vec<LogEvent>>;
+
+/// Payload of tail-sink-error:{tail_id}
+#[derive(Debug, Clone, Serialize)]
+struct SinkErrorPayload {
+    tail_id: String,
+    message: String,
+}
+
+fn render(events: &[LogEvent], format: SinkFormat) -> String {
+    let mut out = String::new();
+    for event in events {
+        match format {
+            SinkFormat::Text => {
+                let timestamp = chrono::DateTime::from_timestamp_millis(event.timestamp)
+                    .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    timestamp,
+                    event.source.as_deref().unwrap_or("-"),
+                    event.message.trim_end()
+                ));
+            }
+            SinkFormat::Json => {
+                if let Ok(line) = serde_json::to_string(event) {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+async fn write_to_file(
+    path: &str,
+    append: bool,
+    pipe: bool,
+    format: SinkFormat,
+    rx: &mut mpsc::UnboundedReceiver<Vec<LogEvent>>,
+) -> Result<(), String> {
+    // Opening a named pipe waits for a reader
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(!pipe)
+        .append(append && !pipe)
+        .truncate(!append && !pipe)
+        .open(path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    while let Some(events) = rx.recv().await {
+        file.write_all(render(&events, format).as_bytes())
+            .await
+            .and(file.flush().await)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+async fn serve_tcp(
+    port: u16,
+    format: SinkFormat,
+    rx: &mut mpsc::UnboundedReceiver<Vec<LogEvent>>,
+) -> Result<(), String> {
+    // Loopback only: live production logs are never exposed to the network
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to listen on 127.0.0.1:{}: {}", port, e))?;
+    let (clients, _) = broadcast::channel::<Arc<String>>(TCP_CLIENT_BACKLOG);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let Ok((mut stream, address)) = accepted else {
+                    continue;
+                };
+                log::info!("Tail sink client {} connected on port {}", address, port);
+                let mut batches = clients.subscribe();
+                tokio::spawn(async move {
+                    loop {
+                        match batches.recv().await {
+                            Ok(batch) => {
+                                if stream.write_all(batch.as_bytes()).await.is_err() {
+                                    return;
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                                log::warn!("Tail sink client {} missed {} batches", address, missed);
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return,
+                        }
+                    }
+                });
+            }
+            batch = rx.recv() => match batch {
+                // No connected client is not an error; the batch is just not mirrored
+                Some(events) => {
+                    clients.send(Arc::new(render(&events, format))).ok();
+                }
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Start mirroring a tail's batches to `sink`, returning the sender to feed it.
+/// The sink stops when the sender is dropped; failures are emitted as
+/// `tail-sink-error:{tail_id}` and stop the sink, not the tail.
+pub fn start(app: AppHandle, tail_id: String, sink: TailSink) -> SinkSender {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let result = match &sink.target {
+            SinkTarget::File { path, append } => {
+                write_to_file(path, *append, false, sink.format, &mut rx).await
+            }
+            SinkTarget::Pipe { path } => {
+                write_to_file(path, false, true, sink.format, &mut rx).await
+            }
+            SinkTarget::Tcp { port } => serve_tcp(*port, sink.format, &mut rx).await,
+        };
+        if let Err(message) = result {
+            log::warn!("Tail {} sink stopped: {}", tail_id, message);
+            app.emit_tagged(
+                &format!("tail-sink-error:{}", tail_id),
+                SinkErrorPayload {
+                    tail_id: tail_id.clone(),
+                    message,
+                },
+            )
+            .ok();
+        }
+    });
+    tx
+}
@@ -0,0 +1,201 @@
+use crate::connections::EnvironmentEmitter;
+use crate::i18n::{self, Message};
+use crate::operations::{CancelBy, Operation};
+use crate::{filter_all_events, redaction, AppState, LogEvent};
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+/// How often a watch re-runs its search
+const POLL_INTERVAL_SECS: u64 = 5;
+
+const DEFAULT_TIMEOUT_MINUTES: u64 = 60;
+const MAX_TIMEOUT_MINUTES: u64 = 24 * 60;
+
+/// A pending one-shot watch
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchInfo {
+    pub watch_id: String,
+    pub log_group_name: String,
+    pub pattern: String,
+    pub started_at: i64,
+    pub expires_at: i64,
+}
+
+/// A running watch tracked in AppState
+pub struct WatchSession {
+    pub info: WatchInfo,
+    pub handle: tokio::task::JoinHandle<()>,
+}
+
+/// Payload for watch-matched
+#[derive(Debug, Clone, Serialize)]
+struct WatchMatchedPayload {
+    watch_id: String,
+    log_group_name: String,
+    pattern: String,
+    event: LogEvent,
+}
+
+/// Payload for watch-expired and watch-error
+#[derive(Debug, Clone, Serialize)]
+struct WatchEndedPayload {
+    watch_id: String,
+    message: Option<String>,
+}
+
+/// Humanized errors that retrying will not fix
+fn is_permanent_error(message: &str) -> bool {
+    message == i18n::tr(Message::LogGroupNotFound) || crate::iam::is_access_denied_message(message)
+}
+
+async fn run_watch(app: AppHandle, info: WatchInfo, op: Operation) {
+    let mut interval = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        if chrono::Utc::now().timestamp_millis() >= info.expires_at {
+            log::info!("Watch {} expired without a match", info.watch_id);
+            app.emit_tagged(
+                "watch-expired",
+                WatchEndedPayload {
+                    watch_id: info.watch_id.clone(),
+                    message: None,
+                },
+            )
+            .ok();
+            break;
+        }
+        let client = match state.logs_client().await {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("Watch {} waiting for a client: {}", info.watch_id, e);
+                continue;
+            }
+        };
+
+        // Always search from the start: late-ingested events can carry timestamps
+        // older than the previous poll
+        match filter_all_events(
+            &client,
+            &info.log_group_name,
+            Some(info.started_at),
+            None,
+            Some(&info.pattern),
+            1,
+        )
+        .await
+        {
+            Ok((mut events, _)) if !events.is_empty() => {
+                op.page(events.len());
+                redaction::mask_for_display(&app, &state, Some(&info.watch_id), &mut events).await;
+                log::info!("Watch {} matched in {}", info.watch_id, info.log_group_name);
+                app.emit_tagged(
+                    "watch-matched",
+                    WatchMatchedPayload {
+                        watch_id: info.watch_id.clone(),
+                        log_group_name: info.log_group_name.clone(),
+                        pattern: info.pattern.clone(),
+                        event: events.remove(0),
+                    },
+                )
+                .ok();
+                break;
+            }
+            Ok(_) => op.page(0),
+            Err(e) if is_permanent_error(&e) => {
+                if e == i18n::tr(Message::LogGroupNotFound) {
+                    crate::emit_group_unavailable(&app, &info.log_group_name, "watch", None);
+                }
+                app.emit_tagged(
+                    "watch-error",
+                    WatchEndedPayload {
+                        watch_id: info.watch_id.clone(),
+                        message: Some(e),
+                    },
+                )
+                .ok();
+                break;
+            }
+            // Transient failures (throttling, network, expired session) just retry
+            Err(e) => log::warn!("Watch {} poll failed: {}", info.watch_id, e),
+        }
+    }
+
+    if let Some(state) = app.try_state::<AppState>() {
+        state.watches.lock().await.remove(&info.watch_id);
+    }
+}
+
+/// Watch a log group until an event matching `pattern` (CloudWatch filter syntax)
+/// appears once, then emit `watch-matched` and stop. Emits `watch-expired` if nothing
+/// matches within `timeout_minutes` (default 60).
+#[tauri::command]
+pub async fn watch_for(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    watch_id: String,
+    log_group_name: String,
+    pattern: String,
+    timeout_minutes: Option<u64>,
+) -> Result<WatchInfo, String> {
+    if pattern.trim().is_empty() {
+        return Err("A pattern to watch for is required".to_string());
+    }
+    state.logs_client().await?;
+    let timeout = timeout_minutes
+        .unwrap_or(DEFAULT_TIMEOUT_MINUTES)
+        .clamp(1, MAX_TIMEOUT_MINUTES);
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let info = WatchInfo {
+        watch_id: watch_id.clone(),
+        log_group_name,
+        pattern,
+        started_at,
+        expires_at: started_at + (timeout * 60_000) as i64,
+    };
+
+    let op = Operation::begin(
+        "watch",
+        format!("{} ({})", info.log_group_name, info.pattern),
+        CancelBy::Watch(watch_id.clone()),
+    );
+    let handle = tokio::spawn(run_watch(app, info.clone(), op));
+    let mut watches = state.watches.lock().await;
+    if let Some(previous) = watches.insert(
+        watch_id,
+        WatchSession {
+            info: info.clone(),
+            handle,
+        },
+    ) {
+        previous.handle.abort();
+    }
+    Ok(info)
+}
+
+/// Cancel a pending watch
+#[tauri::command]
+pub async fn cancel_watch(state: State<'_, AppState>, watch_id: String) -> Result<(), String> {
+    if let Some(watch) = state.watches.lock().await.remove(&watch_id) {
+        watch.handle.abort();
+        log::info!("Watch {} cancelled", watch_id);
+    }
+    Ok(())
+}
+
+/// Watches still waiting for a match
+#[tauri::command]
+pub async fn list_watches(state: State<'_, AppState>) -> Result<Vec<WatchInfo>, String> {
+    let mut watches: Vec<WatchInfo> = state
+        .watches
+        .lock()
+        .await
+        .values()
+        .map(|w| w.info.clone())
+        .collect();
+    watches.sort_by_key(|w| w.started_at);
+    Ok(watches)
+}
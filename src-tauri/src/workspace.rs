@@ -0,0 +1,288 @@
+use crate::alerts::AlertThreshold;
+use crate::diff::{self, SignatureChange};
+use crate::i18n::{self, Message};
+use crate::{storage, MenuState};
+use serde::{Deserialize, Serialize};
+use tauri::menu::MenuItemBuilder;
+use tauri::{AppHandle, Manager};
+
+/// File in the app config directory holding saved workspaces
+const WORKSPACES_FILE: &str = "workspaces.json";
+
+/// File in the app config directory listing recently opened workspaces, newest first
+const RECENT_WORKSPACES_FILE: &str = "recent-workspaces.json";
+
+/// Workspaces listed in the Recent Workspaces menu
+const MAX_RECENT_WORKSPACES: usize = 8;
+
+/// Menu item id prefix of a Recent Workspaces entry; the rest is the workspace name
+pub const RECENT_WORKSPACE_MENU_PREFIX: &str = "recent-workspace:";
+
+/// Guards read-modify-write of the recent workspaces file
+static RECENT_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Time range a workspace opens with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorkspaceTimeRange {
+    /// The last `duration_ms` up to now, e.g. the last hour
+    Relative { duration_ms: i64 },
+    /// A fixed range; no end means up to now
+    Absolute {
+        start_time: i64,
+        #[serde(default)]
+        end_time: Option<i64>,
+    },
+}
+
+/// Fetch limits a workspace opens with, as passed to fetch_logs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceFetchLimits {
+    #[serde(default)]
+    pub max_count: Option<i32>,
+    #[serde(default)]
+    pub max_size_mb: Option<i32>,
+}
+
+/// A saved set of log groups (and how to view them) that can be reopened later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    pub name: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub log_groups: Vec<String>,
+    #[serde(default)]
+    pub filter_pattern: Option<String>,
+    #[serde(default)]
+    pub time_range: Option<WorkspaceTimeRange>,
+    #[serde(default)]
+    pub fetch_limits: Option<WorkspaceFetchLimits>,
+    /// Error-rate and numeric field thresholds evaluated in the background by the
+    /// alert poller
+    #[serde(default)]
+    pub alert_thresholds: Vec<AlertThreshold>,
+    /// Loaded from the team-shared config directory (read-only)
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// Load the workspaces saved on this machine, sorted by name
+pub fn load_local_workspaces(app: &AppHandle) -> Vec<WorkspaceConfig> {
+    let mut workspaces: Vec<WorkspaceConfig> = storage::read_json(app, WORKSPACES_FILE);
+    workspaces.iter_mut().for_each(|w| w.shared = false);
+    workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+    workspaces
+}
+
+/// Load local workspaces merged with the team-shared ones, sorted by name.
+/// A local workspace overrides a shared one with the same name.
+pub fn load_workspaces(app: &AppHandle) -> Vec<WorkspaceConfig> {
+    let mut workspaces = load_local_workspaces(app);
+    let shared: Vec<WorkspaceConfig> = storage::read_shared_json(app, WORKSPACES_FILE);
+    for mut workspace in shared {
+        if workspaces.iter().any(|w| w.name == workspace.name) {
+            continue;
+        }
+        workspace.shared = true;
+        workspaces.push(workspace);
+    }
+    workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+    workspaces
+}
+
+pub fn store_workspaces(app: &AppHandle, workspaces: &[WorkspaceConfig]) -> Result<(), String> {
+    storage::write_json(app, WORKSPACES_FILE, &workspaces)
+}
+
+impl WorkspaceConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Workspace name cannot be empty".to_string());
+        }
+        for threshold in &self.alert_thresholds {
+            threshold.validate()?;
+        }
+        match &self.time_range {
+            Some(WorkspaceTimeRange::Relative { duration_ms }) if *duration_ms <= 0 => {
+                return Err("Workspace time range must be longer than zero".to_string());
+            }
+            Some(WorkspaceTimeRange::Absolute {
+                start_time,
+                end_time: Some(end_time),
+            }) if end_time < start_time => {
+                return Err("Workspace time range ends before it starts".to_string());
+            }
+            _ => {}
+        }
+        if let Some(limits) = &self.fetch_limits {
+            if limits.max_count.is_some_and(|c| c <= 0)
+                || limits.max_size_mb.is_some_and(|mb| mb <= 0)
+            {
+                return Err("Workspace fetch limits must be positive".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Save a workspace, replacing any existing workspace with the same name
+#[tauri::command]
+pub fn save_workspace(app: AppHandle, mut config: WorkspaceConfig) -> Result<(), String> {
+    config.validate()?;
+    // Saving a shared workspace creates a local copy that overrides it
+    config.shared = false;
+
+    let mut workspaces = load_local_workspaces(&app);
+    workspaces.retain(|w| w.name != config.name);
+    workspaces.push(config);
+    store_workspaces(&app, &workspaces)
+}
+
+/// List saved workspaces
+#[tauri::command]
+pub fn list_workspaces(app: AppHandle) -> Vec<WorkspaceConfig> {
+    load_workspaces(&app)
+}
+
+/// Names of the recently opened workspaces that still exist, newest first
+fn recent_workspaces(app: &AppHandle) -> Vec<String> {
+    let names: Vec<String> = storage::read_json(app, RECENT_WORKSPACES_FILE);
+    let workspaces = load_workspaces(app);
+    names
+        .into_iter()
+        .filter(|n| workspaces.iter().any(|w| w.name == *n))
+        .collect()
+}
+
+/// Move `name` to the front of the recent workspaces (or drop it when `opened` is
+/// false) and rebuild the menu
+fn update_recent(app: &AppHandle, name: &str, opened: bool) {
+    {
+        let _guard = RECENT_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut names = recent_workspaces(app);
+        names.retain(|n| n != name);
+        if opened {
+            names.insert(0, name.to_string());
+        }
+        names.truncate(MAX_RECENT_WORKSPACES);
+        if let Err(e) = storage::write_json(app, RECENT_WORKSPACES_FILE, &names) {
+            log::warn!("Failed to save recent workspaces: {}", e);
+        }
+    }
+    refresh_recent_menu(app);
+}
+
+/// Fill the File > Recent Workspaces submenu from the recent workspaces file
+pub fn refresh_recent_menu(app: &AppHandle) {
+    let Some(menu) = app.try_state::<MenuState>() else {
+        return;
+    };
+    let submenu = &menu.recent_workspaces;
+    let result = (|| -> tauri::Result<()> {
+        while submenu.remove_at(0)?.is_some() {}
+        let names = recent_workspaces(app);
+        if names.is_empty() {
+            let placeholder = MenuItemBuilder::new(i18n::tr(Message::MenuNoRecentWorkspaces))
+                .enabled(false)
+                .build(app)?;
+            return submenu.append(&placeholder);
+        }
+        for name in names {
+            let item = MenuItemBuilder::new(&name)
+                .id(format!("{}{}", RECENT_WORKSPACE_MENU_PREFIX, name))
+                .build(app)?;
+            submenu.append(&item)?;
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        log::warn!("Failed to update the Recent Workspaces menu: {}", e);
+    }
+}
+
+/// Open a saved workspace: returns its profile, region, log groups, filter, time
+/// range and fetch limits for the frontend to restore, and lists it first under
+/// File > Recent Workspaces (which emits `restore-workspace` with the name)
+#[tauri::command]
+pub fn load_workspace(app: AppHandle, name: String) -> Result<WorkspaceConfig, String> {
+    let workspace = load_workspaces(&app)
+        .into_iter()
+        .find(|w| w.name == name)
+        .ok_or_else(|| format!("Workspace {} not found", name))?;
+    update_recent(&app, &workspace.name, true);
+    Ok(workspace)
+}
+
+/// Delete a saved workspace by name
+#[tauri::command]
+pub fn delete_workspace(app: AppHandle, name: String) -> Result<(), String> {
+    let mut workspaces = load_local_workspaces(&app);
+    let before = workspaces.len();
+    workspaces.retain(|w| w.name != name);
+    if workspaces.len() == before {
+        if load_workspaces(&app).iter().any(|w| w.name == name) {
+            return Err(format!(
+                "Workspace {} comes from the shared config directory and is read-only",
+                name
+            ));
+        }
+        return Err(format!("Workspace {} not found", name));
+    }
+    store_workspaces(&app, &workspaces)?;
+    update_recent(&app, &name, false);
+    Ok(())
+}
+
+/// Health of one view (log group) of a workspace as of its last fetch
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewHealth {
+    pub log_group_name: String,
+    /// When the group was last fetched (None if never)
+    pub last_run: Option<i64>,
+    pub event_count: usize,
+    pub error_count: usize,
+    /// The most frequent error template that did not appear in the fetch before
+    pub top_new_error: Option<SignatureChange>,
+}
+
+/// One-glance health digest of a workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceSummary {
+    pub name: String,
+    pub views: Vec<ViewHealth>,
+    /// Errors across all views' last fetches
+    pub total_errors: usize,
+}
+
+/// Summarize each view of a workspace from its last fetch: when it ran, its error
+/// count and the top new error template since the fetch before. Reads recorded runs
+/// only, so it works offline.
+#[tauri::command]
+pub fn workspace_summary(app: AppHandle, name: String) -> Result<WorkspaceSummary, String> {
+    let workspace = load_workspaces(&app)
+        .into_iter()
+        .find(|w| w.name == name)
+        .ok_or_else(|| format!("Workspace {} not found", name))?;
+    let views: Vec<ViewHealth> = workspace
+        .log_groups
+        .iter()
+        .map(|group| {
+            let run = diff::last_group_run(&app, group);
+            ViewHealth {
+                log_group_name: group.clone(),
+                last_run: run.as_ref().map(|r| r.ran_at),
+                event_count: run.as_ref().map_or(0, |r| r.event_count),
+                error_count: run.as_ref().map_or(0, |r| r.error_count),
+                top_new_error: run.and_then(|r| r.new_errors.into_iter().next()),
+            }
+        })
+        .collect();
+    Ok(WorkspaceSummary {
+        name: workspace.name,
+        total_errors: views.iter().map(|v| v.error_count).sum(),
+        views,
+    })
+}
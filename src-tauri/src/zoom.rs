@@ -0,0 +1,161 @@
+use crate::{columns, filter_all_events, preview, redaction, session, AppState, LogEvent};
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+/// Cap on events re-fetched per source when zooming into a truncated fetch
+const ZOOM_MAX_EVENTS: usize = 50_000;
+
+/// A session viewed at one zoom level
+#[derive(Debug, Clone, Serialize)]
+pub struct ZoomResult {
+    /// Visible range; None at the outermost level (the whole session)
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    /// Number of zoom levels below the whole session
+    pub depth: usize,
+    pub events: Vec<LogEvent>,
+    /// Sources re-fetched for the narrower window because the stored results were
+    /// truncated
+    pub refetched_sources: Vec<String>,
+    /// Sources whose re-fetch failed; their stored (partial) events are shown instead
+    pub errors: Vec<String>,
+    /// Index of the cursor event in `events`, if it is still visible
+    pub cursor_index: Option<usize>,
+}
+
+fn cursor_index(events: &[LogEvent], cursor_event_id: Option<&str>) -> Option<usize> {
+    let cursor = cursor_event_id?;
+    events
+        .iter()
+        .position(|e| e.event_id.as_deref() == Some(cursor))
+}
+
+/// Re-fetch sources whose stored results don't fully cover `start..=end`, using the
+/// same filter pattern as their original fetch
+async fn refine_sources(
+    state: &AppState,
+    session_id: &str,
+    start: i64,
+    end: i64,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let stale: Vec<(String, Option<String>)> = {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        session
+            .fetches
+            .iter()
+            .filter(|(_, context)| !context.covers(start, end))
+            .map(|(source, context)| (source.clone(), context.filter_pattern.clone()))
+            .collect()
+    };
+    if stale.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let client = state.logs_client().await?;
+    let mut refetched = Vec::new();
+    let mut errors = Vec::new();
+    for (source, filter_pattern) in stale {
+        match filter_all_events(
+            &client,
+            &source,
+            Some(start),
+            Some(end),
+            filter_pattern.as_deref(),
+            ZOOM_MAX_EVENTS,
+        )
+        .await
+        {
+            Ok((mut events, truncated)) => {
+                session::tag_source(&mut events, &source);
+                let mut sessions = state.sessions.lock().await;
+                let session = sessions.get_or_create(session_id);
+                session.replace_source_range(&source, start, end, events);
+                if let Some(context) = session.fetches.get_mut(&source) {
+                    if !truncated {
+                        context.complete_ranges.push((start, end));
+                    }
+                }
+                refetched.push(source);
+            }
+            Err(e) => errors.push(format!("{}: {}", source, e)),
+        }
+    }
+    Ok((refetched, errors))
+}
+
+/// Zoom a session into `start..=end` (e.g. a histogram selection). Filters the stored
+/// results, re-fetching any log group whose stored results were truncated so the
+/// narrower window is complete. Pushes a zoom level that zoom_out returns from.
+#[tauri::command]
+pub async fn zoom_range(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    start: i64,
+    end: i64,
+    cursor_event_id: Option<String>,
+) -> Result<ZoomResult, String> {
+    if start >= end {
+        return Err("Zoom range start must be before its end".to_string());
+    }
+    let (refetched_sources, errors) = refine_sources(&state, &session_id, start, end).await?;
+
+    let (mut events, depth) = {
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions.get_or_create(&session_id);
+        if session.zoom_stack.last() != Some(&(start, end)) {
+            session.zoom_stack.push((start, end));
+        }
+        (session.range(start, end), session.zoom_stack.len())
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(ZoomResult {
+        start: Some(start),
+        end: Some(end),
+        depth,
+        cursor_index: cursor_index(&events, cursor_event_id.as_deref()),
+        events,
+        refetched_sources,
+        errors,
+    })
+}
+
+/// Return to the previous zoom level (the whole session once the stack is empty)
+#[tauri::command]
+pub async fn zoom_out(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    cursor_event_id: Option<String>,
+) -> Result<ZoomResult, String> {
+    let (range, mut events, depth) = {
+        let mut sessions = state.sessions.lock().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        session.zoom_stack.pop();
+        let range = session.zoom_stack.last().copied();
+        let events = match range {
+            Some((start, end)) => session.range(start, end),
+            None => session.display_events(),
+        };
+        (range, events, session.zoom_stack.len())
+    };
+    redaction::mask_for_display(&app, &state, Some(&session_id), &mut events).await;
+    columns::apply(&app, &mut events);
+    preview::truncate(&app, &mut events);
+    Ok(ZoomResult {
+        start: range.map(|r| r.0),
+        end: range.map(|r| r.1),
+        depth,
+        cursor_index: cursor_index(&events, cursor_event_id.as_deref()),
+        events,
+        refetched_sources: Vec::new(),
+        errors: Vec::new(),
+    })
+}
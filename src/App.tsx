@@ -14,6 +14,7 @@ import { useUpdateCheck } from "./hooks/useUpdateCheck";
 import { useSettingsStore, getLogLevelCssVars } from "./stores/settingsStore";
 import { useDemoStore } from "./demo/demoStore";
 import { useSystemTheme } from "./hooks/useSystemTheme";
+import type { ProfileEntry } from "./types";
 import "./App.css";
 
 interface ToastProps {
@@ -46,6 +47,30 @@ function Toast({ message, isDark, onDismiss }: ToastProps) {
   );
 }
 
+// Consecutive profiles of the same group, in the order the backend arranged them
+function groupProfiles(
+  profiles: ProfileEntry[],
+): [string | null, ProfileEntry[]][] {
+  const groups: [string | null, ProfileEntry[]][] = [];
+  for (const profile of profiles) {
+    const last = groups[groups.length - 1];
+    if (last && last[0] === profile.group) {
+      last[1].push(profile);
+    } else {
+      groups.push([profile.group, [profile]]);
+    }
+  }
+  return groups;
+}
+
+function renderProfileOption(profile: ProfileEntry) {
+  return (
+    <option key={profile.name} value={profile.name} title={profile.name}>
+      {profile.label ?? profile.name}
+    </option>
+  );
+}
+
 function App() {
   const {
     initializeAws,
@@ -74,8 +99,8 @@ function App() {
     sizeBytes: number;
     reason: string;
   } | null>(null);
-  const [availableProfiles, setAvailableProfiles] = useState<string[]>([
-    "default",
+  const [availableProfiles, setAvailableProfiles] = useState<ProfileEntry[]>([
+    { name: "default", label: null, group: null },
   ]);
   const [isChangingProfile, setIsChangingProfile] = useState(false);
 
@@ -107,7 +132,7 @@ function App() {
   useEffect(() => {
     initializeAws();
     // Load available profiles
-    invoke<string[]>("list_aws_profiles")
+    invoke<ProfileEntry[]>("list_aws_profiles")
       .then((profiles) => setAvailableProfiles(profiles))
       .catch((err) => console.error("Failed to load profiles:", err));
   }, [initializeAws]);
@@ -222,7 +247,7 @@ function App() {
       useDemoStore.getState().setDemoMode(enabled);
       store.resetState();
       // Refresh profiles (demo wrapper returns ["demo"], real returns AWS profiles)
-      invoke<string[]>("list_aws_profiles")
+      invoke<ProfileEntry[]>("list_aws_profiles")
         .then((profiles) => setAvailableProfiles(profiles))
         .catch((err) => console.error("Failed to load profiles:", err));
       store.initializeAws();
@@ -350,11 +375,15 @@ function App() {
                   : "text-yellow-400"
             } border`}
           >
-            {availableProfiles.map((profile) => (
-              <option key={profile} value={profile}>
-                {profile}
-              </option>
-            ))}
+            {groupProfiles(availableProfiles).map(([group, profiles]) =>
+              group ? (
+                <optgroup key={group} label={group}>
+                  {profiles.map(renderProfileOption)}
+                </optgroup>
+              ) : (
+                profiles.map(renderProfileOption)
+              ),
+            )}
           </select>
 
           {/* Region */}
@@ -13,7 +13,7 @@ function handleDemoCommand<T>(
   } else if (cmd === "list_log_groups") {
     return MOCK_LOG_GROUPS as T;
   } else if (cmd === "list_aws_profiles") {
-    return ["demo"] as T;
+    return [{ name: "demo", label: null, group: null }] as T;
   } else if (cmd === "fetch_logs") {
     const logGroupName = (args?.logGroupName as string) ?? "";
     const startTime = args?.startTime as number | undefined;
@@ -11,6 +11,12 @@ export interface LogGroup {
   stored_bytes: number | null;
 }
 
+export interface ProfileEntry {
+  name: string;
+  label: string | null;
+  group: string | null;
+}
+
 // Log level is now a dynamic string (level ID from settings, or "unknown" for unmatched)
 export type LogLevel = string;
 